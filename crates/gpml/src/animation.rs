@@ -0,0 +1,524 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use gpui::*;
+use crate::ast::{AttributeValue, GPMLElement, GPMLNode};
+use crate::error::*;
+use crate::style::Style;
+
+thread_local! {
+    static CURRENT_ANIMATIONS: RefCell<Option<Rc<GPMLAnimations>>> = const { RefCell::new(None) };
+}
+
+/// Timing function of an `<animation>`, set with its `easing` attribute
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// A spring with the `(damping, stiffness)`, written `spring(10, 100)`, it overshoots the
+    /// target when it is underdamped
+    Spring(f32, f32),
+}
+
+impl Easing {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim() {
+            "linear" => Some(Easing::Linear),
+            "ease-in" => Some(Easing::EaseIn),
+            "ease-out" => Some(Easing::EaseOut),
+            "ease-in-out" => Some(Easing::EaseInOut),
+            s => {
+                let args = s.strip_prefix("spring(")?.strip_suffix(')')?;
+                let (damping, stiffness) = args.split_once(',')?;
+                let damping = damping.trim().parse::<f32>().ok().filter(|v| *v >= 0.0)?;
+                let stiffness = stiffness.trim().parse::<f32>().ok().filter(|v| *v > 0.0)?;
+                Some(Easing::Spring(damping, stiffness))
+            }
+        }
+    }
+
+    /// The CSS `cubic-bezier(x1, y1, x2, y2)` control points of the easing, `None` for springs
+    pub fn control_points(&self) -> Option<(f32, f32, f32, f32)> {
+        match self {
+            Easing::Linear => Some((0.0, 0.0, 1.0, 1.0)),
+            Easing::EaseIn => Some((0.42, 0.0, 1.0, 1.0)),
+            Easing::EaseOut => Some((0.0, 0.0, 0.58, 1.0)),
+            Easing::EaseInOut => Some((0.42, 0.0, 0.58, 1.0)),
+            Easing::Spring(..) => None,
+        }
+    }
+
+    /// Map the linear progress of the animation (0.0..=1.0) to the eased progress
+    pub fn apply(&self, progress: f32) -> f32 {
+        match (self, self.control_points()) {
+            (Easing::Spring(damping, stiffness), _) => spring(*damping, *stiffness, progress),
+            (_, Some((x1, y1, x2, y2))) => cubic_bezier(x1, y1, x2, y2, progress),
+            (_, None) => progress,
+        }
+    }
+}
+
+/// Evaluate a spring of unit mass moving from 0.0 to 1.0 at `progress`.
+///
+/// The whole animation is one second of the spring's time, the value snaps to 1.0 at the end
+/// in case the spring hasn't settled yet.
+pub fn spring(damping: f32, stiffness: f32, progress: f32) -> f32 {
+    let t = progress.clamp(0.0, 1.0);
+    if t == 0.0 || t == 1.0 || stiffness <= 0.0 {
+        return t;
+    }
+
+    let omega = stiffness.sqrt();
+    let zeta = damping / (2.0 * omega);
+    if (zeta - 1.0).abs() < 1e-3 {
+        1.0 - (-omega * t).exp() * (1.0 + omega * t)
+    } else if zeta < 1.0 {
+        let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+        let decay = (-zeta * omega * t).exp();
+        1.0 - decay * ((omega_d * t).cos() + zeta * omega / omega_d * (omega_d * t).sin())
+    } else {
+        let root = (zeta * zeta - 1.0).sqrt();
+        let (r1, r2) = (-omega * (zeta - root), -omega * (zeta + root));
+        1.0 - (r2 * (r1 * t).exp() - r1 * (r2 * t).exp()) / (r2 - r1)
+    }
+}
+
+/// A numeric variable animated by [`crate::GPMLCanvas::animate_variable`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableAnimation {
+    pub from: f64,
+    pub to: f64,
+    pub started_at: Instant,
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl VariableAnimation {
+    pub fn new(from: f64, to: f64, duration: Duration, easing: Easing) -> Self {
+        Self { from, to, started_at: Instant::now(), duration, easing }
+    }
+
+    /// Linear progress (0.0..=1.0) of the animation at `now`
+    pub fn progress(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(self.started_at);
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    /// Value of the variable at `now`, it is exactly `to` once the animation is complete
+    pub fn value_at(&self, now: Instant) -> f64 {
+        let progress = self.progress(now);
+        if progress >= 1.0 {
+            return self.to;
+        }
+        self.from + (self.to - self.from) * self.easing.apply(progress) as f64
+    }
+
+    pub fn is_complete(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.0
+    }
+}
+
+/// Evaluate the CSS cubic bezier timing function defined by the control points at `progress`.
+///
+/// The curve is parametric, so the parameter `t` with `x(t) == progress` is found with
+/// Newton's method, falling back to bisection when the slope is too flat.
+pub fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, progress: f32) -> f32 {
+    fn sample(a1: f32, a2: f32, t: f32) -> f32 {
+        let u = 1.0 - t;
+        3.0 * u * u * t * a1 + 3.0 * u * t * t * a2 + t * t * t
+    }
+    fn slope(a1: f32, a2: f32, t: f32) -> f32 {
+        let u = 1.0 - t;
+        3.0 * u * u * a1 + 6.0 * u * t * (a2 - a1) + 3.0 * t * t * (1.0 - a2)
+    }
+
+    let progress = progress.clamp(0.0, 1.0);
+    if progress == 0.0 || progress == 1.0 {
+        return progress;
+    }
+
+    let mut t = progress;
+    for _ in 0..8 {
+        let error = sample(x1, x2, t) - progress;
+        if error.abs() < 1e-5 {
+            return sample(y1, y2, t);
+        }
+        let d = slope(x1, x2, t);
+        if d.abs() < 1e-6 {
+            break;
+        }
+        t = (t - error / d).clamp(0.0, 1.0);
+    }
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+    t = progress;
+    for _ in 0..32 {
+        let x = sample(x1, x2, t);
+        if (x - progress).abs() < 1e-5 {
+            break;
+        }
+        if x < progress { lo = t; } else { hi = t; }
+        t = (lo + hi) / 2.0;
+    }
+    sample(y1, y2, t)
+}
+
+/// Parse an animation duration: `300ms`, `1.5s` or a plain number of milliseconds
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (value, scale) = if let Some(ms) = s.strip_suffix("ms") {
+        (ms, 1.0)
+    } else if let Some(secs) = s.strip_suffix('s') {
+        (secs, 1000.0)
+    } else {
+        (s, 1.0)
+    };
+    let millis = value.trim().parse::<f64>().ok()? * scale;
+    (millis.is_finite() && millis >= 0.0).then(|| Duration::from_secs_f64(millis / 1000.0))
+}
+
+/// A `<keyframe at="50%">` of an animation, `at` is stored as a fraction (0.0..=1.0)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframe {
+    pub at: f32,
+    pub style: Style,
+}
+
+impl Keyframe {
+    /// Parse a keyframe, its style is the `style` attribute merged with the other attributes:
+    /// `<keyframe at="100%" opacity="1" style="width: 200px" />`
+    pub fn from_element(element: &GPMLElement) -> GPMLResult<Self> {
+        let at = element.get_attribute("at").map(|v| v.as_string()).unwrap_or_default();
+        let at = match at.trim() {
+            "from" => 0.0,
+            "to" => 1.0,
+            value => value
+                .strip_suffix('%')
+                .and_then(|v| v.trim().parse::<f32>().ok())
+                .filter(|v| (0.0..=100.0).contains(v))
+                .map(|v| v / 100.0)
+                .ok_or_else(|| GPMLError::InvalidAttributeValue {
+                    message: format!("Invalid keyframe offset '{}', expected a percentage", value),
+                })?,
+        };
+
+        let mut style = element
+            .get_attribute("style")
+            .map(|v| Style::from_inline(&v.as_string()))
+            .unwrap_or_default();
+        for (name, value) in &element.attributes {
+            if name != "at" && name != "style" {
+                style.props.insert(name.to_lowercase(), value.as_string());
+            }
+        }
+
+        Ok(Self { at, style })
+    }
+}
+
+/// An `<animation>` definition:
+///
+/// ```gpml
+/// <animation name="fadeIn" duration="300ms" easing="ease-out">
+///     <keyframe at="0%" opacity="0" />
+///     <keyframe at="100%" opacity="1" />
+/// </animation>
+/// ```
+///
+/// Elements play it with `animation="fadeIn"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationDef {
+    pub name: String,
+    pub duration: Duration,
+    pub easing: Easing,
+    /// Sorted by offset
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl AnimationDef {
+    pub fn from_element(element: &GPMLElement) -> GPMLResult<Self> {
+        let name = element
+            .get_attribute("name")
+            .map(|v| v.as_string())
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| GPMLError::InvalidAttributeValue {
+                message: "<animation> requires a name attribute".to_string(),
+            })?;
+
+        let duration = match element.get_attribute("duration") {
+            Some(value) => parse_duration(&value.as_string()).ok_or_else(|| GPMLError::InvalidAttributeValue {
+                message: format!("Invalid duration '{}' of animation '{}'", value.as_string(), name),
+            })?,
+            None => Duration::from_millis(300),
+        };
+
+        let easing = match element.get_attribute("easing") {
+            Some(value) => Easing::parse(&value.as_string()).ok_or_else(|| GPMLError::InvalidAttributeValue {
+                message: format!("Unknown easing '{}' of animation '{}'", value.as_string(), name),
+            })?,
+            None => Easing::default(),
+        };
+
+        let mut keyframes = element
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                GPMLNode::Element(child) if child.tag == "keyframe" => Some(Keyframe::from_element(child)),
+                _ => None,
+            })
+            .collect::<GPMLResult<Vec<_>>>()?;
+        keyframes.sort_by(|a, b| a.at.total_cmp(&b.at));
+
+        Ok(Self { name, duration, easing, keyframes })
+    }
+
+    /// The style at `progress` (0.0..=1.0) of the animation.
+    ///
+    /// Numeric props are interpolated between the surrounding keyframes, other props
+    /// (colors, keywords) keep the value of the previous keyframe.
+    pub fn style_at(&self, progress: f32) -> Style {
+        let Some(first) = self.keyframes.first() else {
+            return Style::default();
+        };
+        let next_ix = self.keyframes.iter().position(|frame| frame.at > progress);
+        let (from, to) = match next_ix {
+            None => return self.keyframes.last().unwrap_or(first).style.clone(),
+            Some(0) => return first.style.clone(),
+            Some(ix) => (&self.keyframes[ix - 1], &self.keyframes[ix]),
+        };
+
+        let t = (progress - from.at) / (to.at - from.at);
+        let mut props = to.style.props.clone();
+        for (name, from_value) in &from.style.props {
+            let value = match to.style.props.get(name) {
+                Some(to_value) => interpolate_value(from_value, to_value, t),
+                None => from_value.clone(),
+            };
+            props.insert(name.clone(), value);
+        }
+        Style { props }
+    }
+}
+
+fn interpolate_value(from: &str, to: &str, t: f32) -> String {
+    match (Style::parse_px(from), Style::parse_px(to)) {
+        (Some(a), Some(b)) => {
+            let value = a + (b - a) * t;
+            if from.trim().ends_with("px") || to.trim().ends_with("px") {
+                format!("{}px", value)
+            } else {
+                value.to_string()
+            }
+        }
+        _ => from.to_string(),
+    }
+}
+
+/// Collect the `<animation>` definitions of the tree by name, invalid ones are skipped with a warning
+pub fn collect_animations(root: &GPMLElement) -> HashMap<String, AnimationDef> {
+    fn walk(element: &GPMLElement, animations: &mut HashMap<String, AnimationDef>) {
+        if element.tag == "animation" {
+            match AnimationDef::from_element(element) {
+                Ok(def) => {
+                    animations.insert(def.name.clone(), def);
+                }
+                Err(e) => tracing::warn!("Skipping invalid animation: {}", e),
+            }
+            return;
+        }
+        for child in &element.children {
+            if let GPMLNode::Element(child) = child {
+                walk(child, animations);
+            }
+        }
+    }
+
+    let mut animations = HashMap::new();
+    walk(root, &mut animations);
+    animations
+}
+
+/// Animations available to a render pass.
+///
+/// Like [`crate::style::GPMLStylesheet`] it is installed for the duration of a render pass with
+/// [`GPMLAnimations::enter`]. The generation is part of the element id of each animation, so
+/// bumping it (the canvas does on every variable change) restarts the running animations.
+#[derive(Debug, Default)]
+pub struct GPMLAnimations {
+    pub animations: HashMap<String, Rc<AnimationDef>>,
+    pub generation: usize,
+    next_ix: Cell<usize>,
+}
+
+impl GPMLAnimations {
+    pub fn new(animations: HashMap<String, AnimationDef>, generation: usize) -> Self {
+        Self {
+            animations: animations.into_iter().map(|(name, def)| (name, Rc::new(def))).collect(),
+            generation,
+            next_ix: Cell::new(0),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.animations.is_empty()
+    }
+
+    /// Install these animations until the returned guard is dropped
+    pub fn enter(self: Rc<Self>) -> AnimationsGuard {
+        self.next_ix.set(0);
+        let previous = CURRENT_ANIMATIONS.with(|current| current.borrow_mut().replace(self));
+        AnimationsGuard { previous }
+    }
+
+    /// Wrap the rendered element in the animation named by its `animation` attribute, if any.
+    ///
+    /// The interpolated style is applied to a wrapper div, GPUI requests the animation frames
+    /// until the animation is done.
+    pub fn animate_current(element: &GPMLElement, rendered: AnyElement) -> AnyElement {
+        let Some(name) = element.get_attribute("animation").map(AttributeValue::as_string) else {
+            return rendered;
+        };
+
+        let animation = CURRENT_ANIMATIONS.with(|current| {
+            let current = current.borrow();
+            let animations = current.as_ref()?;
+            let def = animations.animations.get(&name)?.clone();
+            let ix = animations.next_ix.get();
+            animations.next_ix.set(ix + 1);
+            Some((def, animations.generation, ix))
+        });
+        let Some((def, generation, ix)) = animation else {
+            tracing::warn!("Unknown GPML animation: {}", name);
+            return rendered;
+        };
+
+        let easing = def.easing;
+        div()
+            .child(rendered)
+            .with_animation(
+                ElementId::Name(format!("gpml-animation-{}-{}-{}", name, generation, ix).into()),
+                Animation::new(def.duration).with_easing(move |delta| easing.apply(delta)),
+                move |this, delta| apply_animated_style(this, &def.style_at(delta)),
+            )
+            .into_any_element()
+    }
+}
+
+fn apply_animated_style(this: Div, style: &Style) -> Div {
+    let mut this = style.apply_text(style.apply_box(this));
+    if let Some(opacity) = style.get("opacity").and_then(|v| v.trim().parse::<f32>().ok()) {
+        this = this.opacity(opacity.clamp(0.0, 1.0));
+    }
+    this
+}
+
+/// Restores the previous [`GPMLAnimations`] when dropped
+pub struct AnimationsGuard {
+    previous: Option<Rc<GPMLAnimations>>,
+}
+
+impl Drop for AnimationsGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT_ANIMATIONS.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::GPMLParser;
+
+    fn parse_root(input: &str) -> GPMLElement {
+        match GPMLParser::parse_file(input).unwrap() {
+            GPMLNode::Document { root: Some(root), .. } => root,
+            other => panic!("expected a document with a root, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("300ms"), Some(Duration::from_millis(300)));
+        assert_eq!(parse_duration("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("200"), Some(Duration::from_millis(200)));
+        assert_eq!(parse_duration("fast"), None);
+        assert_eq!(parse_duration("-1s"), None);
+    }
+
+    #[test]
+    fn test_easing() {
+        assert_eq!(Easing::parse("ease-in"), Some(Easing::EaseIn));
+        assert_eq!(Easing::parse("bounce"), None);
+
+        for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+        assert!((Easing::Linear.apply(0.3) - 0.3).abs() < 1e-3);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-3);
+
+        assert_eq!(Easing::parse("spring(10, 100)"), Some(Easing::Spring(10.0, 100.0)));
+        assert_eq!(Easing::parse("spring(10)"), None);
+        assert_eq!(Easing::parse("spring(10, 0)"), None);
+        for easing in [Easing::Spring(5.0, 100.0), Easing::Spring(20.0, 100.0), Easing::Spring(40.0, 100.0)] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert!(easing.apply(0.1) > 0.0);
+        }
+        // Underdamped springs overshoot the target
+        assert!((1..10).any(|i| Easing::Spring(5.0, 100.0).apply(i as f32 / 10.0) > 1.0));
+        assert!((1..10).all(|i| Easing::Spring(40.0, 100.0).apply(i as f32 / 10.0) < 1.0));
+    }
+
+    #[test]
+    fn test_variable_animation() {
+        let animation = VariableAnimation::new(10.0, 20.0, Duration::from_millis(100), Easing::Linear);
+        let start = animation.started_at;
+        assert_eq!(animation.value_at(start), 10.0);
+        assert!((animation.value_at(start + Duration::from_millis(50)) - 15.0).abs() < 1e-3);
+        assert!(!animation.is_complete(start + Duration::from_millis(50)));
+        assert_eq!(animation.value_at(start + Duration::from_millis(150)), 20.0);
+        assert!(animation.is_complete(start + Duration::from_millis(100)));
+
+        let instant = VariableAnimation::new(0.0, 1.0, Duration::ZERO, Easing::EaseIn);
+        assert!(instant.is_complete(instant.started_at));
+        assert_eq!(instant.value_at(instant.started_at), 1.0);
+    }
+
+    #[test]
+    fn test_collect_animations() {
+        let root = parse_root(
+            r#"<root>
+                <animation name="fadeIn" duration="300ms" easing="ease-out">
+                    <keyframe at="100%" opacity="1" style="width: 200px" />
+                    <keyframe at="0%" opacity="0" style="width: 100px; color: red" />
+                </animation>
+                <animation duration="1s" />
+                <div animation="fadeIn">Hello</div>
+            </root>"#,
+        );
+
+        let animations = collect_animations(&root);
+        assert_eq!(animations.len(), 1);
+        let fade_in = &animations["fadeIn"];
+        assert_eq!(fade_in.duration, Duration::from_millis(300));
+        assert_eq!(fade_in.easing, Easing::EaseOut);
+        assert_eq!(fade_in.keyframes.len(), 2);
+        assert_eq!(fade_in.keyframes[0].at, 0.0);
+
+        let style = fade_in.style_at(0.5);
+        assert_eq!(style.get("opacity").map(|v| v.as_str()), Some("0.5"));
+        assert_eq!(style.get("width").map(|v| v.as_str()), Some("150px"));
+        assert_eq!(style.get("color").map(|v| v.as_str()), Some("red"));
+        assert_eq!(fade_in.style_at(1.0).get("opacity").map(|v| v.as_str()), Some("1"));
+    }
+}
@@ -6,6 +6,9 @@ use serde::{Deserialize, Serialize};
 pub enum GPMLNode {
     /// Root node containing all top-level declarations
     Document {
+        /// Path of the parent template from `extends "./Base.gpml"`
+        #[serde(default)]
+        extends: Option<String>,
         imports: Vec<Import>,
         components: Vec<ComponentDef>,
         root: Option<Element>,
@@ -29,12 +32,119 @@ pub struct Import {
     pub alias: String,
 }
 
-/// Component definition: def ComponentName(param1, param2) { ... }
+/// Component definition: def ComponentName(param1, param2: Number) { ... }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ComponentDef {
     pub name: String,
     pub parameters: Vec<String>,
+    /// Annotated parameter types, parameters without an annotation are [`ParamType::Any`]
+    #[serde(default)]
+    pub parameter_types: HashMap<String, ParamType>,
     pub body: Element,
+    /// Slots declared in the body with `<slot name="..." />`, keyed by name.
+    /// The unnamed slot is stored under [`DEFAULT_SLOT`]. The value is the `<slot>`
+    /// element when it carries fallback children.
+    #[serde(default)]
+    pub slots: HashMap<String, Option<Element>>,
+}
+
+/// Type annotation of a component parameter: `def Card(title: String)`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ParamType {
+    String,
+    Number,
+    Boolean,
+    #[default]
+    Any,
+}
+
+impl ParamType {
+    /// Parse a type annotation name, returns None for unknown types
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "String" => Some(ParamType::String),
+            "Number" => Some(ParamType::Number),
+            "Boolean" => Some(ParamType::Boolean),
+            "Any" => Some(ParamType::Any),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParamType::String => "String",
+            ParamType::Number => "Number",
+            ParamType::Boolean => "Boolean",
+            ParamType::Any => "Any",
+        }
+    }
+
+    /// Check whether an argument value is valid for this type.
+    ///
+    /// Literals are accepted when they parse as the expected type, and unresolved
+    /// expressions are always accepted since their value is not known yet.
+    pub fn accepts(&self, value: &AttributeValue) -> bool {
+        match (self, value) {
+            (ParamType::Any, _) | (_, AttributeValue::Expression(_)) => true,
+            (ParamType::String, AttributeValue::Array(_)) => false,
+            (ParamType::String, _) => true,
+            (ParamType::Number, value) => value.as_number().is_some(),
+            (ParamType::Boolean, value) => value.as_bool().is_some(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParamType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl ComponentDef {
+    /// Get the annotated type of a parameter
+    pub fn parameter_type(&self, name: &str) -> ParamType {
+        self.parameter_types.get(name).copied().unwrap_or_default()
+    }
+}
+
+/// Name of the slot that receives children without a `slot` attribute
+pub const DEFAULT_SLOT: &str = "default";
+
+/// Reserved tag of `<include src="./partial.gpml" />`, replaced by the root element of the
+/// included file when the document is loaded
+pub const INCLUDE_TAG: &str = "include";
+
+/// Reserved tag of `<each items="${list}" as="item" index="i">`, its children are repeated
+/// for each item of the array without a wrapper element
+pub const EACH_TAG: &str = "each";
+
+/// Reserved tag of `<portal target="overlay">`, its children are rendered in an overlay layer
+/// of the canvas instead of in place, out of the clipping of the parents
+pub const PORTAL_TAG: &str = "portal";
+
+impl ComponentDef {
+    /// Collect all `<slot>` declarations from a component body
+    pub fn collect_slots(body: &Element) -> HashMap<String, Option<Element>> {
+        fn walk(element: &Element, slots: &mut HashMap<String, Option<Element>>) {
+            if element.tag == "slot" {
+                let name = element
+                    .get_attribute("name")
+                    .map(|v| v.as_string())
+                    .unwrap_or_else(|| DEFAULT_SLOT.to_string());
+                let fallback = (!element.children.is_empty()).then(|| element.clone());
+                slots.insert(name, fallback);
+            }
+            for child in &element.children {
+                if let GPMLNode::Element(child) = child {
+                    walk(child, slots);
+                }
+            }
+        }
+
+        let mut slots = HashMap::new();
+        walk(body, &mut slots);
+        slots
+    }
 }
 
 /// XML-like element: <tag attr="value">children</tag>
@@ -44,6 +154,48 @@ pub struct Element {
     pub attributes: HashMap<String, AttributeValue>,
     pub children: Vec<GPMLNode>,
     pub self_closing: bool,
+    /// Conditional rendering directive from `gpml:if`, `gpml:else-if` or `gpml:else`
+    #[serde(default)]
+    pub conditions: Option<Condition>,
+    /// Namespace prefix of the tag, `ui` for `<ui:Button>`, the `tag` is `Button`
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+/// Conditional rendering directive attached to an element
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Condition {
+    /// `gpml:if="expr"`
+    If(String),
+    /// `gpml:else-if="expr"`, only valid directly after an `if` or `else-if` sibling
+    ElseIf(String),
+    /// `gpml:else`, only valid directly after an `if` or `else-if` sibling
+    Else,
+}
+
+impl Condition {
+    /// Build a condition from a reserved `gpml:` attribute, returns None for regular attributes
+    pub fn from_attribute(name: &str, value: &AttributeValue) -> Option<Self> {
+        let expr = || match value {
+            AttributeValue::Expression(expr) => expr.trim().to_string(),
+            AttributeValue::Literal(s) => {
+                let s = s.trim();
+                if s.starts_with("${") && s.ends_with('}') {
+                    s[2..s.len() - 1].trim().to_string()
+                } else {
+                    s.to_string()
+                }
+            }
+            other => other.as_string(),
+        };
+
+        match name {
+            "gpml:if" => Some(Condition::If(expr())),
+            "gpml:else-if" => Some(Condition::ElseIf(expr())),
+            "gpml:else" => Some(Condition::Else),
+            _ => None,
+        }
+    }
 }
 
 /// Attribute value which can be a literal or expression
@@ -57,6 +209,8 @@ pub enum AttributeValue {
     Number(f64),
     /// Boolean literal
     Boolean(bool),
+    /// Array literal: [item1, item2]
+    Array(Vec<AttributeValue>),
 }
 
 impl AttributeValue {
@@ -66,6 +220,28 @@ impl AttributeValue {
             AttributeValue::Expression(expr) => format!("${{{}}}", expr),
             AttributeValue::Number(n) => n.to_string(),
             AttributeValue::Boolean(b) => b.to_string(),
+            AttributeValue::Array(items) => format!(
+                "[{}]",
+                items.iter().map(|item| item.as_string()).collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+
+    /// Name of the value kind, used in type mismatch errors
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            AttributeValue::Literal(_) => "String",
+            AttributeValue::Expression(_) => "Expression",
+            AttributeValue::Number(_) => "Number",
+            AttributeValue::Boolean(_) => "Boolean",
+            AttributeValue::Array(_) => "Array",
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[AttributeValue]> {
+        match self {
+            AttributeValue::Array(items) => Some(items),
+            _ => None,
         }
     }
 
@@ -93,6 +269,8 @@ impl Element {
             attributes: HashMap::new(),
             children: Vec::new(),
             self_closing: false,
+            conditions: None,
+            namespace: None,
         }
     }
 
@@ -110,6 +288,30 @@ impl Element {
         self.attributes.get(name)
     }
 
+    /// The tag with its namespace prefix, like `ui:Button`
+    pub fn qualified_tag(&self) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}:{}", namespace, self.tag),
+            None => self.tag.clone(),
+        }
+    }
+
+    /// The namespaces declared with `xmlns:prefix="./path"` attributes, keyed by prefix.
+    /// The default namespace of `xmlns="./path"` is keyed by `None`.
+    pub fn namespace_declarations(&self) -> HashMap<Option<String>, String> {
+        self.attributes
+            .iter()
+            .filter_map(|(name, value)| {
+                let prefix = match name.strip_prefix("xmlns") {
+                    Some("") => None,
+                    Some(prefix) => Some(prefix.strip_prefix(':')?.to_string()),
+                    None => return None,
+                };
+                Some((prefix, value.as_string()))
+            })
+            .collect()
+    }
+
     pub fn get_text_content(&self) -> String {
         let mut content = String::new();
         for child in &self.children {
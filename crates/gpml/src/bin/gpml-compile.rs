@@ -0,0 +1,33 @@
+use gpml::{GPMLParser, GPMLSerializer};
+use std::io::{Read, Write};
+
+/// Compile GPML read from stdin into JSON UI written to stdout
+fn main() {
+    let mut input = String::new();
+    if let Err(e) = std::io::stdin().read_to_string(&mut input) {
+        eprintln!("Failed to read stdin: {}", e);
+        std::process::exit(1);
+    }
+
+    let document = match GPMLParser::parse_file(&input) {
+        Ok(document) => document,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match GPMLSerializer::to_json_string(&document) {
+        Ok(json) => {
+            let mut stdout = std::io::stdout().lock();
+            if let Err(e) = writeln!(stdout, "{}", json) {
+                eprintln!("Failed to write stdout: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,396 @@
+use crate::ast::*;
+use crate::canvas::GPMLCanvas;
+use crate::component::GPMLContext;
+use crate::error::GPMLResult;
+use crate::renderer::{apply_diff, DiffOp};
+use crate::script::collect_scripts;
+use gpui::{
+    div, AnyElement, App, ElementId, Entity, InteractiveElement, IntoElement, MouseButton, ParentElement, WeakEntity,
+    Window,
+};
+use gpui_component::input::InputState;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Attribute naming the runtime variable a form element is bound to
+pub const BIND_ATTRIBUTE: &str = "gpml:bind";
+
+/// Tags that support `gpml:bind`
+pub const BINDABLE_TAGS: &[&str] = &["input", "select", "textarea"];
+
+/// Attributes naming the handler mounted with [`GPMLCanvas::mount_event_handler`] that is called
+/// on the event, e.g. `on_click="cart.add"`
+pub const EVENT_ATTRIBUTES: &[&str] = &["on_click", "on_hover", "on_mouse_down", "on_mouse_up"];
+
+/// Handler mounted with [`GPMLCanvas::mount_event_handler`], called with the `value` attribute of
+/// the element that received the event
+pub type GPMLEventHandler = Arc<dyn Fn(&AttributeValue, &mut Window, &mut App)>;
+
+/// Mounted event handlers keyed by the name used in the event attributes
+pub type GPMLEventHandlers = HashMap<String, Vec<GPMLEventHandler>>;
+
+/// State of the canvas needed by bound form elements and event attributes, passed down
+/// through the element renderers while a GPML tree is being rendered.
+#[derive(Clone, Default)]
+pub struct BindingScope {
+    /// Canvas whose variables are updated when a bound element changes
+    pub canvas: Option<WeakEntity<GPMLCanvas>>,
+    /// Text input states for bound `<input>` and `<textarea>` elements, keyed by variable name
+    pub inputs: Rc<HashMap<String, Entity<InputState>>>,
+    /// Handlers mounted on the canvas for the event attributes
+    pub handlers: Rc<GPMLEventHandlers>,
+    /// Child indexes from the rendered root to the element being rendered
+    pub path: Vec<usize>,
+    /// Ids of the elements kept by the last hot reload
+    pub ids: Rc<ElementIds>,
+}
+
+impl BindingScope {
+    pub fn new(canvas: Option<WeakEntity<GPMLCanvas>>) -> Self {
+        Self {
+            canvas,
+            ..Default::default()
+        }
+    }
+
+    /// The scope of the child at `ix` of the element being rendered
+    pub fn child(&self, ix: usize) -> Self {
+        let mut scope = self.clone();
+        scope.path.push(ix);
+        scope
+    }
+
+    /// Element id of the element being rendered, stable across renders
+    pub fn element_id(&self, element: &Element) -> ElementId {
+        ElementId::Name(element_id_name(element, &self.ids, &self.path).into())
+    }
+}
+
+/// Name of the element id from the `id` attribute of the element, or else from its path
+fn element_id_name(element: &Element, ids: &ElementIds, path: &[usize]) -> String {
+    match element.get_attribute("id") {
+        Some(id) => format!("gpml-id-{}", id.as_string()),
+        None => ids.name(path),
+    }
+}
+
+/// Element ids of a rendered tree that was edited by hot reloads.
+///
+/// The elements kept from the previous tree keep the id of their previous path, so GPUI keeps
+/// their element state when siblings are inserted or removed before them. The other elements are
+/// named after their path, relative to their nearest renamed ancestor.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ElementIds {
+    names: HashMap<Vec<usize>, String>,
+    /// Number of applied edits, keeps the ids of inserted elements unique
+    generation: usize,
+}
+
+impl ElementIds {
+    /// Name of the element id of the node at `path`
+    pub fn name(&self, path: &[usize]) -> String {
+        for len in (0..=path.len()).rev() {
+            if let Some(name) = self.names.get(&path[..len]) {
+                return path[len..].iter().fold(name.clone(), |name, ix| format!("{}.{}", name, ix));
+            }
+        }
+        path_name(path)
+    }
+
+    /// The ids of the tree after applying the edits of [`GPMLRenderer::diff`](crate::renderer::GPMLRenderer::diff)
+    /// to the `old` tree, the nodes kept from the old tree keep their id and the inserted nodes get
+    /// new ids.
+    pub fn apply(&self, old: &GPMLNode, ops: &[DiffOp]) -> GPMLResult<Self> {
+        let mut ids = Self {
+            names: HashMap::new(),
+            generation: self.generation + 1,
+        };
+        apply_diff(old, ops, &mut |new_path, old_path| {
+            let name = match old_path {
+                Some(old_path) => self.name(old_path),
+                None => format!("{}@{}", path_name(new_path), ids.generation),
+            };
+            if ids.name(new_path) != name {
+                ids.names.insert(new_path.to_vec(), name);
+            }
+        })?;
+        Ok(ids)
+    }
+}
+
+fn path_name(path: &[usize]) -> String {
+    let path = path.iter().map(|ix| ix.to_string()).collect::<Vec<_>>();
+    format!("gpml-path-{}", path.join("."))
+}
+
+/// Get the variable name an element is bound to
+pub fn bound_variable(element: &Element) -> Option<String> {
+    element
+        .get_attribute(BIND_ATTRIBUTE)
+        .map(|v| v.as_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Collect `(variable, tag, initial value)` for all bound text inputs in a resolved tree
+pub fn collect_bound_inputs(element: &Element, out: &mut Vec<(String, String, String)>) {
+    if element.tag == "input" || element.tag == "textarea" {
+        if let Some(name) = bound_variable(element) {
+            let initial = element
+                .get_attribute("value")
+                .map(|v| v.as_string())
+                .unwrap_or_default();
+            out.push((name, element.tag.clone(), initial));
+        }
+    }
+    for child in &element.children {
+        if let GPMLNode::Element(child) = child {
+            collect_bound_inputs(child, out);
+        }
+    }
+}
+
+/// Write a new value to the bound variable of the canvas
+pub fn update_bound_variable(
+    canvas: &WeakEntity<GPMLCanvas>,
+    name: String,
+    value: AttributeValue,
+    cx: &mut App,
+) {
+    let result = canvas.update(cx, |canvas, cx| {
+        canvas.update_bound_variable(name, value);
+        cx.notify();
+    });
+    if let Err(e) = result {
+        tracing::warn!("Failed to update bound variable: {:?}", e);
+    }
+}
+
+/// Seed the `value` attribute of a bound element from the context variable it is bound to
+pub fn apply_bound_value(element: &mut Element, context: &GPMLContext) {
+    if !BINDABLE_TAGS.contains(&element.tag.as_str()) {
+        return;
+    }
+    if let Some(name) = bound_variable(element) {
+        if let Some(value) = context.get_variable(&name) {
+            element.attributes.insert("value".to_string(), value.clone());
+        }
+    }
+}
+
+/// Set the `value` attribute of the elements bound to `name` in a resolved tree
+pub fn set_bound_value(element: &mut Element, name: &str, value: &AttributeValue) {
+    if BINDABLE_TAGS.contains(&element.tag.as_str()) && bound_variable(element).as_deref() == Some(name) {
+        element.attributes.insert("value".to_string(), value.clone());
+    }
+    for child in &mut element.children {
+        if let GPMLNode::Element(child) = child {
+            set_bound_value(child, name, value);
+        }
+    }
+}
+
+/// Whether the tree reads the variable `name` outside of `gpml:bind`: in an attribute, an
+/// interpolated text, an expression or a condition. `<script>` blocks are not searched.
+pub fn reads_variable(element: &Element, name: &str) -> bool {
+    let in_attributes = element
+        .attributes
+        .iter()
+        .any(|(attribute, value)| attribute != BIND_ATTRIBUTE && contains_identifier(&value.as_string(), name));
+    let in_condition = match &element.conditions {
+        Some(Condition::If(expr)) | Some(Condition::ElseIf(expr)) => contains_identifier(expr, name),
+        _ => false,
+    };
+    in_attributes
+        || in_condition
+        || element.children.iter().any(|child| match child {
+            GPMLNode::Element(child) => child.tag != "script" && reads_variable(child, name),
+            GPMLNode::Text(text) => text.contains("${") && contains_identifier(text, name),
+            GPMLNode::Expression(expr) => contains_identifier(expr, name),
+            _ => false,
+        })
+}
+
+/// Whether a Rhai `<script>` block of the tree mentions the variable `name`, its derived
+/// variables are stale until the scripts run again
+pub fn scripts_read_variable(element: &Element, name: &str) -> bool {
+    collect_scripts(element).iter().any(|source| contains_identifier(source, name))
+}
+
+/// Whether `name` occurs in `text` as a whole identifier
+fn contains_identifier(text: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(name).any(|(ix, _)| {
+        !text[..ix].chars().next_back().is_some_and(is_ident)
+            && !text[ix + name.len()..].chars().next().is_some_and(is_ident)
+    })
+}
+
+/// The mounted handlers of each event attribute of an element, attributes naming no mounted
+/// handler are skipped
+fn element_event_handlers(element: &Element, handlers: &GPMLEventHandlers) -> Vec<(&'static str, Vec<GPMLEventHandler>)> {
+    EVENT_ATTRIBUTES
+        .iter()
+        .filter_map(|event| {
+            let name = element.get_attribute(event)?.as_string();
+            let mounted = handlers.get(&name).filter(|mounted| !mounted.is_empty())?;
+            Some((*event, mounted.clone()))
+        })
+        .collect()
+}
+
+/// Attach the handlers of the scope named by the event attributes of `element` to its rendered root.
+///
+/// Clicks and hovers need an element id, a root without one gets [`BindingScope::element_id`].
+pub fn attach_event_handlers<E: InteractiveElement>(element: &Element, scope: &BindingScope, mut rendered: E) -> E {
+    let events = element_event_handlers(element, &scope.handlers);
+    if events.is_empty() {
+        return rendered;
+    }
+
+    let value = element
+        .get_attribute("value")
+        .cloned()
+        .unwrap_or_else(|| AttributeValue::Literal(String::new()));
+    let interactivity = rendered.interactivity();
+    if interactivity.element_id.is_none() {
+        interactivity.element_id = Some(scope.element_id(element));
+    }
+    for (event, handlers) in events {
+        let value = value.clone();
+        let fire = move |window: &mut Window, cx: &mut App| {
+            for handler in &handlers {
+                handler(&value, window, cx);
+            }
+        };
+        match event {
+            "on_click" => interactivity.on_click(move |_, window, cx| fire(window, cx)),
+            "on_hover" => interactivity.on_hover(move |hovered, window, cx| {
+                if *hovered {
+                    fire(window, cx)
+                }
+            }),
+            "on_mouse_down" => interactivity.on_mouse_down(MouseButton::Left, move |_, window, cx| fire(window, cx)),
+            "on_mouse_up" => interactivity.on_mouse_up(MouseButton::Left, move |_, window, cx| fire(window, cx)),
+            _ => {}
+        }
+    }
+    rendered
+}
+
+/// Like [`attach_event_handlers`] for components that don't take mouse listeners, such as
+/// text inputs and labels. They are rendered in a div only when they have event handlers.
+pub fn attach_component_event_handlers(element: &Element, scope: &BindingScope, rendered: impl IntoElement) -> AnyElement {
+    if element_event_handlers(element, &scope.handlers).is_empty() {
+        return rendered.into_any_element();
+    }
+    attach_event_handlers(element, scope, div().child(rendered)).into_any_element()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::GPMLRenderer;
+
+    #[test]
+    fn test_element_event_handlers() {
+        let mut handlers = GPMLEventHandlers::new();
+        let handler: GPMLEventHandler = Arc::new(|_, _, _| {});
+        handlers.entry("cart.add".to_string()).or_default().push(handler.clone());
+        handlers.entry("cart.add".to_string()).or_default().push(handler);
+
+        let element = Element::new("button".to_string())
+            .with_attribute("on_click".to_string(), AttributeValue::Literal("cart.add".to_string()))
+            .with_attribute("on_hover".to_string(), AttributeValue::Literal("cart.remove".to_string()));
+        let events = element_event_handlers(&element, &handlers);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, "on_click");
+        assert_eq!(events[0].1.len(), 2);
+
+        let element = Element::new("div".to_string());
+        assert!(element_event_handlers(&element, &handlers).is_empty());
+    }
+
+    #[test]
+    fn test_element_id_name() {
+        let ids = ElementIds::default();
+        let element = Element::new("button".to_string());
+        assert_eq!(element_id_name(&element, &ids, &[]), "gpml-path-");
+        assert_eq!(element_id_name(&element, &ids, &[0, 2, 1]), "gpml-path-0.2.1");
+
+        let element = element.with_attribute("id".to_string(), AttributeValue::Literal("checkout".to_string()));
+        assert_eq!(element_id_name(&element, &ids, &[0, 2, 1]), "gpml-id-checkout");
+    }
+
+    #[test]
+    fn test_element_ids_apply() {
+        let item = |name: &str| {
+            let li = Element::new("li".to_string()).with_attribute("key".to_string(), AttributeValue::Literal(name.to_string()));
+            GPMLNode::Element(li.with_child(GPMLNode::Element(
+                Element::new("button".to_string()).with_child(GPMLNode::Text(name.to_string())),
+            )))
+        };
+        let list = |items: Vec<GPMLNode>| {
+            let mut list = Element::new("ul".to_string());
+            list.children = items;
+            GPMLNode::Element(list)
+        };
+
+        // Inserting an item keeps the ids of the items after it
+        let old = list(vec![item("a"), item("b")]);
+        let new = list(vec![item("x"), item("a"), item("b")]);
+        let ids = ElementIds::default().apply(&old, &GPMLRenderer::diff(&old, &new)).unwrap();
+        assert_eq!(ids.name(&[0]), "gpml-path-0@1");
+        assert_eq!(ids.name(&[0, 0]), "gpml-path-0@1.0");
+        assert_eq!(ids.name(&[1, 0]), "gpml-path-0.0");
+        assert_eq!(ids.name(&[2, 0]), "gpml-path-1.0");
+
+        // Moved items keep their ids across reloads
+        let newer = list(vec![item("b"), item("x"), item("a")]);
+        let ids = ids.apply(&new, &GPMLRenderer::diff(&new, &newer)).unwrap();
+        assert_eq!(ids.name(&[0, 0]), "gpml-path-1.0");
+        assert_eq!(ids.name(&[1, 0]), "gpml-path-0@1.0");
+        assert_eq!(ids.name(&[2, 0]), "gpml-path-0.0");
+    }
+
+    #[test]
+    fn test_reads_variable() {
+        let input = Element::new("input".to_string())
+            .with_attribute(BIND_ATTRIBUTE.to_string(), AttributeValue::Literal("query".to_string()));
+        let root = Element::new("div".to_string()).with_child(GPMLNode::Element(input.clone()));
+        assert!(!reads_variable(&root, "query"));
+
+        let text = Element::new("p".to_string()).with_child(GPMLNode::Text("${query}".to_string()));
+        assert!(reads_variable(&root.clone().with_child(GPMLNode::Element(text)), "query"));
+
+        let mut shown = Element::new("p".to_string());
+        shown.conditions = Some(Condition::If("query != \"\"".to_string()));
+        assert!(reads_variable(&root.clone().with_child(GPMLNode::Element(shown)), "query"));
+
+        let other = Element::new("p".to_string())
+            .with_attribute("title".to_string(), AttributeValue::Expression("query_count".to_string()));
+        assert!(!reads_variable(&root.with_child(GPMLNode::Element(other)), "query"));
+    }
+
+    #[test]
+    fn test_scripts_read_variable() {
+        let script = Element::new("script".to_string())
+            .with_child(GPMLNode::Text("let total = price * qty;".to_string()));
+        let root = Element::new("root".to_string()).with_child(GPMLNode::Element(script));
+        assert!(scripts_read_variable(&root, "qty"));
+        assert!(scripts_read_variable(&root, "price"));
+        assert!(!scripts_read_variable(&root, "qt"));
+        assert!(!scripts_read_variable(&Element::new("root".to_string()), "qty"));
+    }
+
+    #[test]
+    fn test_set_bound_value() {
+        let select = Element::new("select".to_string())
+            .with_attribute(BIND_ATTRIBUTE.to_string(), AttributeValue::Literal("size".to_string()))
+            .with_attribute("value".to_string(), AttributeValue::Literal("s".to_string()));
+        let mut root = Element::new("form".to_string()).with_child(GPMLNode::Element(select));
+        set_bound_value(&mut root, "size", &AttributeValue::Literal("m".to_string()));
+        let select = root.children[0].as_element().unwrap();
+        assert_eq!(select.get_attribute("value"), Some(&AttributeValue::Literal("m".to_string())));
+    }
+}
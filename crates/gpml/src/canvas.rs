@@ -2,13 +2,22 @@ use crate::ast::*;
 use crate::component::*;
 use crate::error::*;
 use crate::hot_reload::*;
-use crate::parser::GPMLParser;
-use crate::renderer::GPMLRenderer;
+use crate::parser::{ChangeSummary, GPMLParser, IncrementalParser};
+use crate::renderer::{DiffOp, GPMLRenderer};
 use crate::bundled_assets::GPMLFileSource;
+use crate::binding::{collect_bound_inputs, reads_variable, scripts_read_variable, set_bound_value, BindingScope, ElementIds, GPMLEventHandler, GPMLEventHandlers};
+use crate::style::GPMLStylesheet;
+use crate::animation::{collect_animations, Easing, GPMLAnimations, VariableAnimation};
+use crate::script::GPMLScriptEngine;
+use crate::portal::{group_portals, PortalScope};
+use gpui_component::input::{InputEvent, InputState};
 use gpui::*;
+use gpui::prelude::FluentBuilder as _;
 use gpui_component::*;
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 use notify::{RecommendedWatcher, Watcher};
 
 /// Main GPML canvas component that loads and renders GPML files dynamically
@@ -33,10 +42,52 @@ pub struct GPMLCanvas {
     file_watcher: Option<RecommendedWatcher>,
     /// Cached compiled root element (only recompiled when file changes)
     cached_root_element: Option<GPMLElement>,
+    /// Rules of the `<style>` blocks in the cached root element
+    cached_stylesheet: Rc<GPMLStylesheet>,
+    /// `<animation>` definitions of the cached root element
+    cached_animations: Rc<GPMLAnimations>,
+    /// Element ids of the cached root element after the edits of the last hot reload, reset when
+    /// the tree is compiled for another reason
+    element_ids: Rc<ElementIds>,
     /// Whether the cache is dirty and needs recompilation
     cache_dirty: bool,
+    /// Whether the next recompilation runs the `<script>` blocks, bound elements recompile without them
+    scripts_dirty: bool,
+    /// Tracks component checksums of the root file for incremental reloads
+    incremental_parser: IncrementalParser,
+    /// Input states backing `gpml:bind` text inputs, keyed by variable name
+    bound_inputs: HashMap<String, Entity<InputState>>,
+    /// Handlers mounted for the event attributes, keyed by handler name
+    event_handlers: Rc<GPMLEventHandlers>,
+    _binding_subscriptions: Vec<Subscription>,
+    /// Whether parameter type mismatches fail rendering instead of logging a warning
+    strict_types: bool,
+    /// Whether malformed markup is skipped instead of failing the load, for live previews
+    lenient_mode: bool,
+    /// Runs the `<script>` blocks, keeps the compiled scripts across reloads
+    script_engine: GPMLScriptEngine,
+    /// Path shown by `<router>` elements, kept across reloads
+    current_path: SharedString,
+    /// Fixed size of the rendered document, it fills the parent when `None`
+    size: Option<Size<Pixels>>,
+    /// Content of the `<portal>` elements of the current render pass, with their target layer
+    portal_elements: Vec<(SharedString, AnyElement)>,
+    /// Running [`GPMLCanvas::animate_variable`] animations, keyed by variable name
+    variable_animations: HashMap<SharedString, VariableAnimation>,
 }
 
+/// Events emitted by a [`GPMLCanvas`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GPMLCanvasEvent {
+    /// The canvas navigated to a new path, with [`GPMLCanvas::navigate`] or a `<link to="...">`
+    Navigated(SharedString),
+    /// The animation of a variable started with [`GPMLCanvas::animate_variable`] reached its
+    /// target value
+    AnimationComplete(SharedString),
+}
+
+impl EventEmitter<GPMLCanvasEvent> for GPMLCanvas {}
+
 impl GPMLCanvas {
     /// Create a new GPML canvas with the given root file
     pub fn new(root_path: impl AsRef<Path>) -> Self {
@@ -53,7 +104,22 @@ impl GPMLCanvas {
             runtime_vars: HashMap::new(),
             file_watcher: None,
             cached_root_element: None,
+            cached_stylesheet: Rc::default(),
+            cached_animations: Rc::default(),
+            element_ids: Rc::default(),
             cache_dirty: true,
+            scripts_dirty: true,
+            incremental_parser: IncrementalParser::new(),
+            bound_inputs: HashMap::new(),
+            event_handlers: Rc::default(),
+            _binding_subscriptions: Vec::new(),
+            strict_types: false,
+            lenient_mode: false,
+            script_engine: GPMLScriptEngine::new(),
+            current_path: SharedString::from("/"),
+            size: None,
+            portal_elements: Vec::new(),
+            variable_animations: HashMap::new(),
         }
     }
 
@@ -63,11 +129,78 @@ impl GPMLCanvas {
         self
     }
 
+    /// Fail rendering on component parameter type mismatches, by default they are
+    /// only logged as warnings
+    pub fn with_strict_types(mut self, strict: bool) -> Self {
+        self.strict_types = strict;
+        self.resolver.set_strict_types(strict);
+        self
+    }
+
+    /// Whether component parameter type mismatches fail rendering
+    pub fn strict_types(&self) -> bool {
+        self.strict_types
+    }
+
+    /// Skip malformed markup instead of failing the load, so a live preview keeps rendering
+    /// the valid elements while the user types incomplete markup.
+    ///
+    /// The skipped markup of the root file is available from [`GPMLCanvas::parse_warnings`].
+    pub fn with_lenient_mode(mut self, lenient: bool) -> Self {
+        self.lenient_mode = lenient;
+        self.resolver.set_lenient_mode(lenient);
+        self.incremental_parser.set_lenient(lenient);
+        self
+    }
+
+    /// Whether malformed markup is skipped instead of failing the load
+    pub fn lenient_mode(&self) -> bool {
+        self.lenient_mode
+    }
+
+    /// Malformed markup of the root file skipped by the last load in lenient mode
+    pub fn parse_warnings(&self) -> &[ParseWarning] {
+        self.incremental_parser.warnings()
+    }
+
+    /// Render the document at a fixed size in pixels instead of filling the parent,
+    /// the content outside of the size is clipped
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some(size(px(width), px(height)));
+        self
+    }
+
+    /// The fixed size of the rendered document, if any
+    pub fn size(&self) -> Option<Size<Pixels>> {
+        self.size
+    }
+
     /// Add a runtime variable
     pub fn add_variable(&mut self, name: String, value: AttributeValue) {
         self.runtime_vars.insert(name, value);
     }
 
+    /// Warm the component cache with the given files and their imports, without loading
+    /// them as the document of this canvas.
+    ///
+    /// Preloaded files are served from the cache when the canvas later loads or imports them.
+    /// To preload off the main thread, preload a [`ComponentResolver`] in a background task
+    /// with `cx.background_executor().spawn` and pass it to [`GPMLCanvas::with_preloaded`].
+    pub fn preload(&mut self, paths: &[PathBuf]) -> GPMLResult<()> {
+        self.resolver.preload(paths)
+    }
+
+    /// Like [`GPMLCanvas::preload`], calls `progress(loaded, total)` after each file
+    pub fn preload_with_progress(&mut self, paths: &[PathBuf], progress: impl Fn(usize, usize)) -> GPMLResult<()> {
+        self.resolver.preload_with_progress(paths, progress)
+    }
+
+    /// Use the documents cached by a resolver that preloaded them
+    pub fn with_preloaded(mut self, resolver: ComponentResolver) -> Self {
+        self.resolver.extend_cache(resolver);
+        self
+    }
+
     /// Load the GPML file and all its dependencies
     pub fn load(&mut self) -> GPMLResult<()> {
         tracing::info!("GPMLCanvas::load called for path: {:?}", self.root_path);
@@ -76,6 +209,7 @@ impl GPMLCanvas {
 
         // Invalidate cache when loading new content
         self.cache_dirty = true;
+        self.scripts_dirty = true;
         self.cached_root_element = None;
 
         match self.load_internal() {
@@ -115,6 +249,7 @@ impl GPMLCanvas {
             tracing::debug!("Adding runtime variable: {} = {:?}", name, value);
             context.variables.insert(name.clone(), value.clone());
         }
+        context.current_path = self.current_path.clone();
         
         self.context = Some(context);
         tracing::info!("Context loaded successfully");
@@ -140,18 +275,16 @@ impl GPMLCanvas {
         );
 
         tracing::info!("Parsing GPML document");
-        let document = GPMLParser::parse_file(&content)
+        let (document, _) = self.incremental_parser.parse(&content)
             .map_err(|e| {
-                tracing::error!("Parse error: {}", e);
-                GPMLError::ParseError { 
-                    message: e, 
-                    line: 0, 
-                    column: 0 
-                }
+                tracing::error!("{}", e);
+                e
             })?;
+        let document = self.resolver.resolve_extends(&self.root_path, document)?;
+        let document = self.resolver.resolve_includes(&self.root_path, document)?;
         
         tracing::info!("Document parsed successfully");
-        if let GPMLNode::Document { imports, components, root } = &document {
+        if let GPMLNode::Document { imports, components, root, .. } = &document {
             tracing::info!("Document structure - imports: {}, components: {}, has_root: {}", 
                 imports.len(), components.len(), root.is_some());
             if let Some(root_elem) = root {
@@ -191,9 +324,14 @@ impl GPMLCanvas {
         
         // Spawn a background task to watch for file changes with debouncing
         let (tx, rx) = smol::channel::bounded(10); // Smaller buffer to prevent flooding
-        let watched_file = absolute_path.clone();
+        // Watch the directory of the root file so edits to imported files reload too
+        let watched_dir = absolute_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| absolute_path.clone());
+        let filter_dir = watched_dir.clone();
         
-        tracing::info!("Creating file watcher for: {:?}", watched_file);
+        tracing::info!("Creating file watcher for: {:?}", watched_dir);
         
         let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
             tracing::debug!("File watcher event received: {:?}", res);
@@ -205,9 +343,9 @@ impl GPMLCanvas {
                     notify::EventKind::Modify(notify::event::ModifyKind::Any) => {
                         tracing::info!("File modification event detected: {:?}", event.kind);
                         for path in &event.paths {
-                            tracing::info!("Checking path: {:?} against watched file: {:?}", path, watched_file);
-                            // Only react to changes to our specific file
-                            if path == &watched_file && path.extension().and_then(|s| s.to_str()) == Some("gpml") {
+                            tracing::info!("Checking path: {:?} against watched directory: {:?}", path, filter_dir);
+                            // Only react to GPML files under the watched directory
+                            if path.starts_with(&filter_dir) && path.extension().and_then(|s| s.to_str()) == Some("gpml") {
                                 tracing::info!("GPML file change detected, sending to channel: {:?}", path);
                                 // Use try_send to avoid blocking - if channel is full, skip this event
                                 match tx.try_send(path.clone()) {
@@ -231,10 +369,9 @@ impl GPMLCanvas {
         )))?;
         
         use notify::Watcher;
-        // Only watch the specific file, not the directory
-        tracing::info!("Attempting to watch file: {:?}", absolute_path);
-        watcher.watch(&absolute_path, notify::RecursiveMode::NonRecursive).map_err(|e| {
-            tracing::error!("Failed to watch path {:?}: {}", absolute_path, e);
+        tracing::info!("Attempting to watch directory: {:?}", watched_dir);
+        watcher.watch(&watched_dir, notify::RecursiveMode::Recursive).map_err(|e| {
+            tracing::error!("Failed to watch path {:?}: {}", watched_dir, e);
             GPMLError::IoError(std::io::Error::new(
                 std::io::ErrorKind::Other,
                 format!("Failed to watch path: {}", e)
@@ -244,7 +381,7 @@ impl GPMLCanvas {
         // Store the watcher in the struct to keep it alive
         self.file_watcher = Some(watcher);
         
-        tracing::info!("File watcher started successfully for: {:?}", absolute_path);
+        tracing::info!("File watcher started successfully for: {:?}", watched_dir);
         
         cx.spawn(async move |this, mut cx| {
             tracing::info!("Hot reload background task started");
@@ -265,20 +402,39 @@ impl GPMLCanvas {
                 tracing::info!("GPML file changed (debounced): {:?}", changed_path);
                 
                 // Update the canvas on the main thread
+                let root_changed = changed_path == absolute_path;
                 let update_result = this.update(cx, |canvas, cx| {
                     tracing::info!("Updating canvas after file change");
-                    // Clear resolver cache for changed file
-                    canvas.resolver.remove_from_cache(&changed_path);
-                    
-                    // Reload the canvas
-                    if let Err(e) = canvas.load() {
-                        tracing::error!("Failed to reload after file change: {}", e);
+                    let previous = canvas.cached_root_element.clone();
+                    // Only re-parse and invalidate the components that changed in the root
+                    // file, an imported file may be cached under any path so reload all
+                    let result = if root_changed {
+                        canvas.reload_changed_only()
                     } else {
-                        tracing::info!("Successfully reloaded after file change");
+                        canvas.reload().map(|_| ChangeSummary {
+                            root_changed: true,
+                            ..Default::default()
+                        })
+                    };
+                    match result {
+                        Ok(summary) if summary.is_empty() => {
+                            tracing::info!("File content unchanged, skipping re-render");
+                        }
+                        Ok(_) => match canvas.apply_compiled_diff(previous) {
+                            // e.g. only a comment or an unused component changed
+                            Some(ops) if ops.is_empty() => {
+                                tracing::info!("Compiled tree unchanged, skipping re-render");
+                            }
+                            ops => {
+                                tracing::info!("Successfully reloaded after file change, {} tree edits", ops.map_or(0, |ops| ops.len()));
+                                cx.notify();
+                            }
+                        },
+                        Err(e) => {
+                            tracing::error!("Failed to reload after file change: {}", e);
+                            cx.notify();
+                        }
                     }
-                    
-                    // Notify for re-render
-                    cx.notify();
                 });
                 
                 if let Err(e) = update_result {
@@ -312,6 +468,110 @@ impl GPMLCanvas {
         Ok(false)
     }
 
+    /// Re-parse the root file and only invalidate the components whose source changed.
+    ///
+    /// Falls back to a full `load()` when nothing has been loaded yet. The returned
+    /// summary is empty when the file content is unchanged, in which case nothing
+    /// needs to be re-rendered.
+    pub fn reload_changed_only(&mut self) -> GPMLResult<ChangeSummary> {
+        if self.context.is_none() {
+            self.incremental_parser.reset();
+            self.load()?;
+            return Ok(ChangeSummary {
+                added: self.incremental_parser.components().keys().cloned().collect(),
+                root_changed: true,
+                ..Default::default()
+            });
+        }
+
+        let path_str = self.root_path.display().to_string();
+        let content = GPMLFileSource::load_file(&path_str).map_err(|_| GPMLError::FileNotFound {
+            path: path_str,
+        })?;
+
+        let (document, summary) = match self
+            .incremental_parser
+            .parse(&content)
+            .and_then(|(document, summary)| {
+                let document = self.resolver.resolve_extends(&self.root_path, document)?;
+                Ok((self.resolver.resolve_includes(&self.root_path, document)?, summary))
+            }) {
+            Ok(result) => result,
+            Err(e) => {
+                self.error = Some(format!("{}", e));
+                return Err(e);
+            }
+        };
+        self.error = None;
+
+        if summary.is_empty() {
+            tracing::debug!("No GPML changes detected");
+            return Ok(summary);
+        }
+
+        tracing::info!(
+            "GPML changes - changed: {:?}, added: {:?}, removed: {:?}, root changed: {}, imports changed: {}",
+            summary.changed, summary.added, summary.removed, summary.root_changed, summary.imports_changed
+        );
+
+        self.resolver.remove_components_from_cache(summary.invalidated());
+        self.resolver.remove_from_cache(&self.root_path);
+
+        if summary.imports_changed {
+            // Imported components are registered under their alias, rebuild them from the new imports
+            let mut context = match self.resolver.load_file(&self.root_path) {
+                Ok(context) => context,
+                Err(e) => {
+                    self.error = Some(format!("{}", e));
+                    return Err(e);
+                }
+            };
+            if let Some(previous) = self.context.take() {
+                context.variables = previous.variables;
+                context.canvas_handle = previous.canvas_handle;
+                context.animation_generation = previous.animation_generation;
+                context.current_path = previous.current_path;
+            }
+            self.context = Some(context);
+        }
+
+        if let Some(context) = self.context.as_mut() {
+            for name in &summary.removed {
+                context.components.remove(name);
+            }
+            for name in summary.changed.iter().chain(summary.added.iter()) {
+                if let Some(component) = self.incremental_parser.components().get(name) {
+                    context.add_component(component.clone());
+                }
+            }
+        }
+
+        self.current_document = Some(document);
+        self.cache_dirty = true;
+        self.scripts_dirty = true;
+        self.cached_root_element = None;
+
+        Ok(summary)
+    }
+
+    /// Compile the reloaded document, diff it against the previously compiled tree and apply the
+    /// edits to the element ids. The elements kept or moved from the previous tree keep their ids,
+    /// so only the inserted subtrees start with a fresh GPUI element state such as hover.
+    ///
+    /// Returns `None` when either tree is not available, e.g. after a compilation error.
+    fn apply_compiled_diff(&mut self, previous: Option<GPMLElement>) -> Option<Vec<DiffOp>> {
+        let ids = self.element_ids.clone();
+        let previous = GPMLNode::Element(previous?);
+        let current = GPMLNode::Element(self.get_compiled_root_element()?.clone());
+        let ops = GPMLRenderer::diff(&previous, &current);
+        tracing::debug!("GPML tree edits: {:?}", ops);
+        match ids.apply(&previous, &ops) {
+            Ok(ids) => self.element_ids = Rc::new(ids),
+            Err(e) => tracing::warn!("Failed to apply the GPML tree edits: {}", e),
+        }
+        Some(ops)
+    }
+
     /// Force reload the canvas
     pub fn reload(&mut self) -> GPMLResult<()> {
         self.resolver.clear_cache();
@@ -347,10 +607,31 @@ impl GPMLCanvas {
         // Only recompile if cache is dirty
         if self.cache_dirty {
             tracing::info!("Cache is dirty, recompiling root element");
+            self.element_ids = Rc::default();
+            if self.scripts_dirty {
+                if let Err(e) = self.run_scripts() {
+                    tracing::error!("Failed to run GPML scripts: {}", e);
+                    self.error = Some(format!("{}", e));
+                    return None;
+                }
+                self.scripts_dirty = false;
+            }
             if let (Some(root_element), Some(context)) = (self.get_root_element(), &self.context) {
+                if !context.is_element_visible(root_element) {
+                    tracing::info!("Root element hidden by gpml:if, caching empty element");
+                    self.cached_root_element = Some(GPMLElement::new("div".to_string()));
+                    self.cache_dirty = false;
+                    return self.cached_root_element.as_ref();
+                }
                 match resolve_element(root_element, context, &self.resolver) {
                     Ok(compiled_element) => {
                         tracing::info!("Successfully compiled root element, caching result");
+                        self.cached_stylesheet = Rc::new(GPMLStylesheet::from_element(&compiled_element));
+                        let animations = collect_animations(&compiled_element);
+                        if let Some(context) = self.context.as_mut() {
+                            self.cached_animations = Rc::new(GPMLAnimations::new(animations.clone(), context.animation_generation));
+                            context.animations = animations;
+                        }
                         self.cached_root_element = Some(compiled_element);
                         self.cache_dirty = false;
                     }
@@ -371,6 +652,17 @@ impl GPMLCanvas {
         self.cached_root_element.as_ref()
     }
 
+    /// Run the `<script>` blocks of the document, storing their variables in the context
+    fn run_scripts(&mut self) -> GPMLResult<()> {
+        let Some(GPMLNode::Document { root: Some(root), .. }) = &self.current_document else {
+            return Ok(());
+        };
+        let Some(context) = self.context.as_mut() else {
+            return Ok(());
+        };
+        self.script_engine.run_scripts(root, &mut context.variables)
+    }
+
     /// Load GPML from a string instead of a file
     pub fn load_from_string(&mut self, content: &str, base_path: Option<&Path>) -> GPMLResult<()> {
         self.is_loading = true;
@@ -378,6 +670,7 @@ impl GPMLCanvas {
 
         // Invalidate cache when loading new content
         self.cache_dirty = true;
+        self.scripts_dirty = true;
         self.cached_root_element = None;
 
         let base_path = base_path.unwrap_or_else(|| Path::new("."));
@@ -387,13 +680,13 @@ impl GPMLCanvas {
         for (name, value) in &self.runtime_vars {
             context.variables.insert(name.clone(), value.clone());
         }
+        context.current_path = self.current_path.clone();
 
-        let document = GPMLParser::parse_file(content)
-            .map_err(|e| GPMLError::ParseError { 
-                message: e, 
-                line: 0, 
-                column: 0 
-            })?;
+        let document = if self.lenient_mode {
+            self.incremental_parser.parse(content)?.0
+        } else {
+            GPMLParser::parse_file(content)?
+        };
 
         // Process imports and components from the document
         self.resolver.clear_cache();
@@ -416,18 +709,191 @@ impl GPMLCanvas {
         Ok(())
     }
 
+    /// Mount a handler called when an element whose event attribute names `event_name` receives
+    /// the event, e.g. `<button on_click="cart.add">` calls the handlers mounted as `"cart.add"`.
+    ///
+    /// Supported attributes are `on_click`, `on_hover`, `on_mouse_down` and `on_mouse_up`. Several
+    /// handlers can be mounted under the same name, they are called in the order they were mounted.
+    pub fn mount_event_handler(&mut self, event_name: &str, handler: GPMLEventHandler) {
+        Rc::make_mut(&mut self.event_handlers)
+            .entry(event_name.to_string())
+            .or_default()
+            .push(handler);
+    }
+
     /// Update a runtime variable and trigger re-render if canvas is loaded
     pub fn update_variable(&mut self, name: String, value: AttributeValue) -> bool {
         self.runtime_vars.insert(name.clone(), value.clone());
         
         if let Some(ref mut context) = self.context {
             context.variables.insert(name, value);
+            context.animation_generation += 1;
+            // Resolved values are baked into the cached element, recompile on next render
+            self.cache_dirty = true;
+            self.scripts_dirty = true;
             true
         } else {
             false
         }
     }
 
+    /// Store the value written by a `gpml:bind` element.
+    ///
+    /// Unlike [`GPMLCanvas::update_variable`] the `<script>` blocks only run again when one of them
+    /// mentions the variable, and the tree is only recompiled when the document or a script reads
+    /// the variable. Otherwise the bound elements of the compiled tree are updated in place, a text
+    /// input already shows the new value.
+    pub fn update_bound_variable(&mut self, name: String, value: AttributeValue) -> bool {
+        let scripted = self.get_root_element().is_some_and(|root| scripts_read_variable(root, &name));
+        let read = scripted
+            || self.get_root_element().is_some_and(|root| reads_variable(root, &name))
+            || self.context.as_ref().is_some_and(|context| {
+                context.components.values().any(|component| reads_variable(&component.body, &name))
+            });
+        self.runtime_vars.insert(name.clone(), value.clone());
+
+        let Some(context) = self.context.as_mut() else {
+            return false;
+        };
+        context.variables.insert(name.clone(), value.clone());
+        if scripted {
+            self.scripts_dirty = true;
+        }
+        match self.cached_root_element.as_mut() {
+            Some(root) if !read => set_bound_value(root, &name, &value),
+            _ => self.cache_dirty = true,
+        }
+        true
+    }
+
+    /// Interpolate the numeric variable `name` from `from` to `to` over the `duration`.
+    ///
+    /// The variable is updated on each frame until [`GPMLCanvasEvent::AnimationComplete`] is
+    /// emitted. Animations of different variables run concurrently, animating a variable again
+    /// replaces its running animation.
+    pub fn animate_variable(
+        &mut self,
+        name: &str,
+        from: AttributeValue,
+        to: AttributeValue,
+        duration: Duration,
+        easing: Easing,
+        cx: &mut Context<Self>,
+    ) -> GPMLResult<()> {
+        let (AttributeValue::Number(from), AttributeValue::Number(to)) = (&from, &to) else {
+            return Err(GPMLError::TypeError {
+                message: format!(
+                    "Can't animate variable '{}' from {} to {}, only numbers are interpolated",
+                    name,
+                    from.type_name(),
+                    to.type_name()
+                ),
+            });
+        };
+
+        let animation = VariableAnimation::new(*from, *to, duration, easing);
+        self.set_animated_variable(name, animation.value_at(animation.started_at));
+        self.variable_animations.insert(SharedString::from(name.to_string()), animation);
+        cx.notify();
+        Ok(())
+    }
+
+    /// Store the current value of a variable animation, unlike [`GPMLCanvas::update_variable`]
+    /// this doesn't restart the `<animation>`s of the elements on each frame.
+    fn set_animated_variable(&mut self, name: &str, value: f64) {
+        let value = AttributeValue::Number(value);
+        self.runtime_vars.insert(name.to_string(), value.clone());
+        if let Some(context) = self.context.as_mut() {
+            context.variables.insert(name.to_string(), value);
+            // Resolved values are baked into the cached element, recompile on next render
+            self.cache_dirty = true;
+            self.scripts_dirty = true;
+        }
+    }
+
+    /// Advance the variable animations to the current frame, and request the next frame while
+    /// some are still running.
+    fn tick_variable_animations(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.variable_animations.is_empty() {
+            return;
+        }
+
+        let now = Instant::now();
+        let animations = self
+            .variable_animations
+            .iter()
+            .map(|(name, animation)| (name.clone(), animation.value_at(now), animation.is_complete(now)))
+            .collect::<Vec<_>>();
+        for (name, value, complete) in animations {
+            self.set_animated_variable(&name, value);
+            if complete {
+                self.variable_animations.remove(&name);
+                cx.emit(GPMLCanvasEvent::AnimationComplete(name));
+            }
+        }
+
+        if !self.variable_animations.is_empty() {
+            window.request_animation_frame();
+        }
+    }
+
+    /// Get the path shown by `<router>` elements
+    pub fn current_path(&self) -> &SharedString {
+        &self.current_path
+    }
+
+    /// Show the `<route>` matching the path in all `<router>` elements
+    pub fn navigate(&mut self, path: &str, cx: &mut Context<Self>) {
+        let path = SharedString::from(path.to_string());
+        if path == self.current_path {
+            return;
+        }
+
+        self.current_path = path.clone();
+        if let Some(context) = self.context.as_mut() {
+            context.current_path = path.clone();
+            // Routes are selected while compiling, recompile on next render
+            self.cache_dirty = true;
+            self.scripts_dirty = true;
+        }
+        cx.emit(GPMLCanvasEvent::Navigated(path));
+        cx.notify();
+    }
+
+    /// Create input states for `gpml:bind` text inputs that don't have one yet.
+    ///
+    /// Each state writes its text back to the bound variable on change.
+    fn ensure_bound_inputs(
+        &mut self,
+        bound: Vec<(String, String, String)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        for (name, tag, initial) in bound {
+            if self.bound_inputs.contains_key(&name) {
+                continue;
+            }
+
+            let state = cx.new(|cx| {
+                let state = InputState::new(window, cx);
+                let state = if tag == "textarea" { state.multi_line() } else { state };
+                state.default_value(initial)
+            });
+
+            let variable = name.clone();
+            let subscription = cx.subscribe(&state, move |canvas, state, event: &InputEvent, cx| {
+                if let InputEvent::Change = event {
+                    let value = state.read(cx).value().to_string();
+                    canvas.update_bound_variable(variable.clone(), AttributeValue::Literal(value));
+                    cx.notify();
+                }
+            });
+
+            self._binding_subscriptions.push(subscription);
+            self.bound_inputs.insert(name, state);
+        }
+    }
+
     /// Get current runtime variables
     pub fn get_variables(&self) -> &HashMap<String, AttributeValue> {
         &self.runtime_vars
@@ -464,14 +930,41 @@ impl Render for GPMLCanvas {
             return self.render_error_state(error, window, cx);
         }
 
+        self.tick_variable_animations(window, cx);
+
+        // Make the canvas, bound input states and mounted event handlers available to the elements
+        if let Some(context) = self.context.as_mut() {
+            context.canvas_handle = Some(cx.weak_entity());
+        }
+        let mut bound = Vec::new();
+        if let Some(compiled_element) = self.get_compiled_root_element() {
+            collect_bound_inputs(compiled_element, &mut bound);
+        }
+        self.ensure_bound_inputs(bound, window, cx);
+        let scope = BindingScope {
+            canvas: Some(cx.weak_entity()),
+            inputs: Rc::new(self.bound_inputs.clone()),
+            handlers: self.event_handlers.clone(),
+            ids: self.element_ids.clone(),
+            ..Default::default()
+        };
+        let _stylesheet = self.cached_stylesheet.clone().enter();
+        let _animations = self.cached_animations.clone().enter();
+
         // Use the cached compiled element instead of re-resolving on every render
         if let Some(compiled_element) = self.get_compiled_root_element() {
             tracing::info!("Rendering cached compiled GPML element: tag={}, children={}",
                 compiled_element.tag, compiled_element.children.len());
-            match GPMLRenderer::render_resolved_element_direct(compiled_element, cx) {
+            let portal_scope = PortalScope::enter();
+            match GPMLRenderer::render_resolved_element_direct(compiled_element, &scope, cx) {
                 Ok(element) => {
                     tracing::info!("Successfully rendered cached GPML element");
-                    element
+                    self.portal_elements = portal_scope.finish();
+                    let element = match self.size {
+                        Some(size) => div().w(size.width).h(size.height).overflow_hidden().child(element).into_any_element(),
+                        None => element,
+                    };
+                    self.render_portal_layers(element)
                 },
                 Err(e) => {
                     tracing::error!("GPML render error: {}", e);
@@ -490,6 +983,27 @@ impl Render for GPMLCanvas {
 }
 
 impl GPMLCanvas {
+    /// Render the `<portal>` content in overlay layers above the document, one layer per target
+    fn render_portal_layers(&mut self, element: AnyElement) -> AnyElement {
+        if self.portal_elements.is_empty() {
+            return element;
+        }
+
+        // The layers have no mouse handlers, so the events still reach the document below
+        let layers = group_portals(std::mem::take(&mut self.portal_elements))
+            .into_iter()
+            .map(|(_, elements)| div().absolute().top_0().left_0().size_full().children(elements));
+        div()
+            .relative()
+            .map(|this| match self.size {
+                Some(size) => this.w(size.width).h(size.height),
+                None => this.size_full(),
+            })
+            .child(element)
+            .children(layers)
+            .into_any_element()
+    }
+
     fn render_loading_state(&self, _window: &mut Window, cx: &mut Context<Self>) -> AnyElement {
         v_flex()
             .items_center()
@@ -511,6 +1025,12 @@ impl GPMLCanvas {
     }
 
     fn render_error_state(&self, error: &String, _window: &mut Window, cx: &mut Context<Self>) -> AnyElement {
+        // Parse errors carry a source snippet after the first line, render it monospaced
+        // so the caret lines up with the offending column
+        let mut lines = error.lines();
+        let headline = lines.next().unwrap_or_default().to_string();
+        let snippet: Vec<String> = lines.map(|line| line.to_string()).collect();
+
         v_flex()
             .items_center()
             .justify_center()
@@ -535,8 +1055,22 @@ impl GPMLCanvas {
                     .text_color(cx.theme().muted_foreground)
                     //TODO:.text_wrap()
                     .max_w(px(600.0))
-                    .child(error.clone())
+                    .child(headline)
             )
+            .when(!snippet.is_empty(), |this| {
+                this.child(
+                    v_flex()
+                        .p_2()
+                        .rounded_md()
+                        .bg(cx.theme().muted)
+                        .font_family("monospace")
+                        .text_size(px(13.0))
+                        .text_color(cx.theme().foreground)
+                        .children(snippet.into_iter().map(|line| {
+                            div().whitespace_nowrap().child(line)
+                        }))
+                )
+            })
             .child(
                 button::Button::new("reload-button")
                     .child("Reload")
@@ -605,3 +1139,32 @@ where
     
     canvas
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_bound_variable_runs_scripts() {
+        let vars = HashMap::from([("price".to_string(), AttributeValue::Number(2.0))]);
+        let mut canvas = GPMLCanvas::new("test.gpml").with_variables(vars);
+        canvas
+            .load_from_string(
+                r#"<root>
+    <script>let total = price * qty;</script>
+    <input gpml:bind="qty" />
+    <text>Total: ${total}</text>
+</root>"#,
+                None,
+            )
+            .unwrap();
+        canvas.update_bound_variable("qty".to_string(), AttributeValue::Number(1.0));
+        assert!(canvas.get_compiled_root_element().is_some());
+        assert_eq!(canvas.context.as_ref().unwrap().get_variable("total"), Some(&AttributeValue::Number(2.0)));
+
+        canvas.update_bound_variable("qty".to_string(), AttributeValue::Number(3.0));
+        assert!(canvas.scripts_dirty);
+        assert!(canvas.get_compiled_root_element().is_some());
+        assert_eq!(canvas.context.as_ref().unwrap().get_variable("total"), Some(&AttributeValue::Number(6.0)));
+    }
+}
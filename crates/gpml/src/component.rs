@@ -1,7 +1,12 @@
 use crate::ast::*;
+use crate::animation::AnimationDef;
 use crate::error::*;
 use crate::parser::GPMLParser;
 use crate::bundled_assets::GPMLFileSource;
+use crate::binding::apply_bound_value;
+use crate::canvas::GPMLCanvas;
+use crate::router::filter_routes;
+use gpui::{SharedString, WeakEntity};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -14,6 +19,14 @@ pub struct GPMLContext {
     pub variables: HashMap<String, AttributeValue>,
     /// Base path for resolving imports
     pub base_path: PathBuf,
+    /// Canvas owning this context, used by `gpml:bind` to write variables back
+    pub canvas_handle: Option<WeakEntity<GPMLCanvas>>,
+    /// `<animation>` definitions of the compiled document, by name
+    pub animations: HashMap<String, AnimationDef>,
+    /// Bumped on every variable change, restarts the running animations
+    pub animation_generation: usize,
+    /// Path shown by `<router>` elements, changed with [`GPMLCanvas::navigate`]
+    pub current_path: SharedString,
 }
 
 impl GPMLContext {
@@ -22,6 +35,10 @@ impl GPMLContext {
             components: HashMap::new(),
             variables: HashMap::new(),
             base_path: base_path.as_ref().to_path_buf(),
+            canvas_handle: None,
+            animations: HashMap::new(),
+            animation_generation: 0,
+            current_path: SharedString::from("/"),
         }
     }
 
@@ -55,6 +72,65 @@ impl GPMLContext {
         }
     }
 
+    /// Evaluate a conditional expression such as `show`, `!hidden` or `mode == "dark"`
+    pub fn evaluate_condition(&self, expr: &str) -> bool {
+        let expr = expr.trim();
+        let expr = if expr.starts_with("${") && expr.ends_with('}') {
+            expr[2..expr.len() - 1].trim()
+        } else {
+            expr
+        };
+
+        if let Some((left, right)) = expr.split_once("!=") {
+            return self.resolve_operand(left).map(|v| v.as_string())
+                != self.resolve_operand(right).map(|v| v.as_string());
+        }
+        if let Some((left, right)) = expr.split_once("==") {
+            return self.resolve_operand(left).map(|v| v.as_string())
+                == self.resolve_operand(right).map(|v| v.as_string());
+        }
+        if let Some(negated) = expr.strip_prefix('!') {
+            return !self.evaluate_condition(negated);
+        }
+
+        match self.resolve_operand(expr) {
+            Some(AttributeValue::Boolean(b)) => b,
+            Some(AttributeValue::Number(n)) => n != 0.0,
+            Some(AttributeValue::Literal(s)) => !s.is_empty() && s != "false",
+            Some(AttributeValue::Array(items)) => !items.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Resolve a condition operand to a variable value or a literal
+    fn resolve_operand(&self, operand: &str) -> Option<AttributeValue> {
+        let operand = operand.trim();
+        if let Some(value) = self.get_variable(operand) {
+            return Some(value.clone());
+        }
+
+        if operand.len() >= 2
+            && ((operand.starts_with('"') && operand.ends_with('"'))
+                || (operand.starts_with('\'') && operand.ends_with('\'')))
+        {
+            return Some(AttributeValue::Literal(operand[1..operand.len() - 1].to_string()));
+        }
+
+        match operand {
+            "true" => Some(AttributeValue::Boolean(true)),
+            "false" => Some(AttributeValue::Boolean(false)),
+            _ => operand.parse::<f64>().ok().map(AttributeValue::Number),
+        }
+    }
+
+    /// Check whether a standalone element passes its own `gpml:if` condition
+    pub fn is_element_visible(&self, element: &Element) -> bool {
+        match &element.conditions {
+            Some(Condition::If(expr)) => self.evaluate_condition(expr),
+            _ => true,
+        }
+    }
+
     pub fn interpolate_attribute(&self, value: &AttributeValue) -> AttributeValue {
         match value {
             AttributeValue::Expression(expr) => {
@@ -73,6 +149,28 @@ impl GPMLContext {
 pub struct ComponentResolver {
     cache: HashMap<PathBuf, GPMLNode>,
     loading: Vec<PathBuf>, // Track files currently being loaded to detect circular deps
+    /// Fail instantiation on parameter type mismatches instead of logging a warning
+    strict_types: bool,
+    /// Parse files with [`GPMLParser::parse_file_lenient`]
+    lenient_mode: bool,
+    /// Directory of the parsed documents kept across sessions, see [`ComponentResolver::with_disk_cache`]
+    disk_cache_dir: Option<PathBuf>,
+}
+
+/// Extension of the parsed documents in the disk cache
+const DISK_CACHE_EXTENSION: &str = "gpmlcache";
+
+/// Mixed into the disk cache keys, so the documents cached by another version of the parser
+/// and AST are not read back
+const DISK_CACHE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Key of the parsed `content` in the disk cache
+fn disk_cache_key(content: &str) -> blake3::Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(DISK_CACHE_VERSION.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(content.as_bytes());
+    hasher.finalize()
 }
 
 impl ComponentResolver {
@@ -80,9 +178,44 @@ impl ComponentResolver {
         Self {
             cache: HashMap::new(),
             loading: Vec::new(),
+            strict_types: false,
+            lenient_mode: false,
+            disk_cache_dir: None,
         }
     }
 
+    /// Keep the parsed documents in `dir` across sessions, keyed by the Blake3 hash of the
+    /// file content and the crate version, so unchanged files are not parsed again on the next startup.
+    ///
+    /// Only the parse result is cached, `extends` and `<include>` are resolved on every load.
+    /// The cache is not used in lenient mode.
+    pub fn with_disk_cache(mut self, dir: &Path) -> Self {
+        self.disk_cache_dir = Some(dir.to_path_buf());
+        self
+    }
+
+    /// Remove the cached documents of [`ComponentResolver::with_disk_cache`] from `dir`
+    pub fn clear_disk_cache(dir: &Path) -> GPMLResult<()> {
+        if !dir.exists() {
+            return Ok(());
+        }
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == DISK_CACHE_EXTENSION) {
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_strict_types(&mut self, strict: bool) {
+        self.strict_types = strict;
+    }
+
+    pub fn set_lenient_mode(&mut self, lenient: bool) {
+        self.lenient_mode = lenient;
+    }
+
     /// Load and parse a GPML file with all its dependencies
     pub fn load_file(&mut self, path: impl AsRef<Path>) -> GPMLResult<GPMLContext> {
         let path = path.as_ref();
@@ -141,12 +274,17 @@ impl ComponentResolver {
             path: path_str,
         })?;
 
-        let document = GPMLParser::parse_file(&content)
-            .map_err(|e| GPMLError::ParseError { 
-                message: e, 
-                line: 0, 
-                column: 0 
-            })?;
+        let document = if self.lenient_mode {
+            let (document, warnings) = GPMLParser::parse_file_lenient(&content);
+            for warning in warnings {
+                tracing::warn!("{}: skipped malformed markup at byte {}: {}", path.display(), warning.byte_offset, warning.message);
+            }
+            document
+        } else {
+            self.parse_with_disk_cache(&content)?
+        };
+        let document = self.resolve_extends(path, document)?;
+        let document = self.resolve_includes(path, document)?;
 
         // Cache the result
         self.cache.insert(path.to_path_buf(), document.clone());
@@ -157,8 +295,202 @@ impl ComponentResolver {
         Ok(document)
     }
 
+    /// Parse the content, or read the parsed document from the disk cache when the content
+    /// hasn't changed. A corrupted cache entry is parsed again and overwritten.
+    fn parse_with_disk_cache(&self, content: &str) -> GPMLResult<GPMLNode> {
+        let Some(dir) = self.disk_cache_dir.as_ref() else {
+            return GPMLParser::parse_file(content);
+        };
+
+        let hash = disk_cache_key(content);
+        let cache_path = dir.join(format!("{}.{}", hash.to_hex(), DISK_CACHE_EXTENSION));
+        if let Ok(cached) = std::fs::read(&cache_path) {
+            match serde_json::from_slice::<GPMLNode>(&cached) {
+                Ok(document) => return Ok(document),
+                Err(err) => tracing::warn!("Ignoring corrupted GPML cache file {:?}: {}", cache_path, err),
+            }
+        }
+
+        let document = GPMLParser::parse_file(content)?;
+        let written = std::fs::create_dir_all(dir)
+            .map_err(|err| err.to_string())
+            .and_then(|_| serde_json::to_vec(&document).map_err(|err| err.to_string()))
+            .and_then(|json| std::fs::write(&cache_path, json).map_err(|err| err.to_string()));
+        if let Err(err) = written {
+            tracing::warn!("Failed to write GPML cache file {:?}: {}", cache_path, err);
+        }
+        Ok(document)
+    }
+
+    /// Merge a document that `extends` a parent template into the parent.
+    ///
+    /// The result has the parent's root with every `<block name="...">` replaced by the
+    /// child's block of the same name, and the components of both files (the child's
+    /// win on conflicts). Documents without `extends` are returned unchanged.
+    pub fn resolve_extends(&mut self, path: &Path, document: GPMLNode) -> GPMLResult<GPMLNode> {
+        let GPMLNode::Document { extends: Some(parent), imports, components, root } = document else {
+            return Ok(document);
+        };
+
+        let current_file = path.display().to_string();
+        let parent_path = GPMLFileSource::resolve_component_import(&current_file, &parent)
+            .map(PathBuf::from)
+            .map_err(|_| GPMLError::FileNotFound {
+                path: format!("Unable to resolve template: {}", parent),
+            })?;
+        tracing::debug!("{:?} extends {:?}", path, parent_path);
+
+        // Keep the child marked as loading while the parent chain is loaded, so that
+        // a template extending one of its children is reported as circular
+        let marked = !self.loading.iter().any(|p| p == path);
+        if marked {
+            self.loading.push(path.to_path_buf());
+        }
+        let parent_document = self.load_document(&parent_path).and_then(|parent_document| {
+            // Resolve the parent's imports relative to the parent file
+            let mut parent_context =
+                GPMLContext::new(parent_path.parent().unwrap_or(Path::new(".")));
+            self.process_document(&parent_document, &mut parent_context)?;
+            Ok((parent_document, parent_context))
+        });
+        if marked {
+            self.loading.retain(|p| p != path);
+        }
+        let (parent_document, parent_context) = parent_document?;
+
+        let GPMLNode::Document { root: parent_root, .. } = parent_document else {
+            return Err(GPMLError::ImportError {
+                message: format!("Template {} is not a GPML document", parent),
+            });
+        };
+
+        let mut blocks = HashMap::new();
+        if let Some(root) = &root {
+            collect_blocks(root, &mut blocks);
+        }
+        let root = parent_root.map(|mut parent_root| {
+            override_blocks(&mut parent_root, &blocks);
+            parent_root
+        });
+
+        let mut merged_components: Vec<ComponentDef> =
+            parent_context.components.into_values().collect();
+        merged_components.retain(|c| !components.iter().any(|own| own.name == c.name));
+        merged_components.extend(components);
+
+        Ok(GPMLNode::Document {
+            extends: None,
+            imports,
+            components: merged_components,
+            root,
+        })
+    }
+
+    /// Replace every `<include src="..." />` of a document with the root element of the included file.
+    ///
+    /// The components of the included files are added to the document (its own win on conflicts).
+    /// Includes leading back to a file being loaded are reported as a circular dependency.
+    pub fn resolve_includes(&mut self, path: &Path, document: GPMLNode) -> GPMLResult<GPMLNode> {
+        let GPMLNode::Document { extends, imports, mut components, mut root } = document else {
+            return Ok(document);
+        };
+
+        let marked = !self.loading.iter().any(|p| p == path);
+        if marked {
+            self.loading.push(path.to_path_buf());
+        }
+        let included_components = self.expand_document_includes(path, root.as_mut(), &mut components);
+        if marked {
+            self.loading.retain(|p| p != path);
+        }
+        let mut included_components = included_components?;
+
+        included_components.retain(|c| !components.iter().any(|own| own.name == c.name));
+        components.extend(included_components);
+
+        Ok(GPMLNode::Document { extends, imports, components, root })
+    }
+
+    /// Expand the includes of the root and component bodies, returns the included components
+    fn expand_document_includes(
+        &mut self,
+        path: &Path,
+        root: Option<&mut Element>,
+        components: &mut [ComponentDef],
+    ) -> GPMLResult<Vec<ComponentDef>> {
+        let mut included_components = Vec::new();
+        if let Some(root) = root {
+            self.expand_includes(path, root, &mut included_components)?;
+        }
+        for component in components.iter_mut() {
+            self.expand_includes(path, &mut component.body, &mut included_components)?;
+            component.slots = ComponentDef::collect_slots(&component.body);
+        }
+        Ok(included_components)
+    }
+
+    fn expand_includes(
+        &mut self,
+        path: &Path,
+        element: &mut Element,
+        components: &mut Vec<ComponentDef>,
+    ) -> GPMLResult<()> {
+        if element.tag == INCLUDE_TAG {
+            let mut included = self.load_include(path, element, components)?;
+            // A condition on the <include> applies to the included root
+            if element.conditions.is_some() {
+                included.conditions = element.conditions.take();
+            }
+            *element = included;
+            return Ok(());
+        }
+
+        for child in element.children.iter_mut() {
+            if let GPMLNode::Element(child) = child {
+                self.expand_includes(path, child, components)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Load the root element of the file referenced by an `<include>`, its includes are already expanded
+    fn load_include(
+        &mut self,
+        path: &Path,
+        include: &Element,
+        components: &mut Vec<ComponentDef>,
+    ) -> GPMLResult<Element> {
+        let src = include
+            .get_attribute("src")
+            .map(|v| v.as_string())
+            .ok_or_else(|| GPMLError::InvalidAttributeValue {
+                message: "<include> requires a src attribute".to_string(),
+            })?;
+
+        let current_file = path.display().to_string();
+        let include_path = GPMLFileSource::resolve_component_import(&current_file, &src)
+            .map(PathBuf::from)
+            .map_err(|_| GPMLError::FileNotFound {
+                path: format!("Unable to resolve include: {}", src),
+            })?;
+        tracing::debug!("{:?} includes {:?}", path, include_path);
+
+        let document = self.load_document(&include_path)?;
+        // Resolve the included file's imports relative to the included file
+        let mut include_context = GPMLContext::new(include_path.parent().unwrap_or(Path::new(".")));
+        self.process_document(&document, &mut include_context)?;
+        components.extend(include_context.components.into_values());
+
+        match document {
+            GPMLNode::Document { root: Some(root), .. } => Ok(root),
+            _ => Err(GPMLError::ImportError {
+                message: format!("Included file {} has no root element", src),
+            }),
+        }
+    }
+
     fn process_document(&mut self, document: &GPMLNode, context: &mut GPMLContext) -> GPMLResult<()> {
-        if let GPMLNode::Document { imports, components, .. } = document {
+        if let GPMLNode::Document { imports, components, root, .. } = document {
             // Process imports first
             for import in imports {
                 self.process_import(import, context)?;
@@ -168,11 +500,83 @@ impl ComponentResolver {
             for component in components {
                 context.add_component(component.clone());
             }
+
+            if let Some(root) = root {
+                self.process_namespaces(root, components, context)?;
+            }
         }
 
         Ok(())
     }
 
+    /// Load the components of the namespaced tags, like `<ui:Button>` with
+    /// `xmlns:ui="./components"` declared on the root element.
+    ///
+    /// A namespace maps to a `.gpml` file defining the components, or to a directory with a
+    /// `<Name>.gpml` file per component. The components and imports of the file are added to the
+    /// context under the namespace prefix, see [`ComponentResolver::process_namespace_document`].
+    /// Unqualified tags that are not components are looked up in the default namespace of
+    /// `xmlns="./path"`, and are kept as regular elements when it doesn't have them.
+    fn process_namespaces(&mut self, root: &Element, components: &[ComponentDef], context: &mut GPMLContext) -> GPMLResult<()> {
+        let declarations = root.namespace_declarations();
+        let mut tags = Vec::new();
+        collect_tags(root, &mut tags);
+        for component in components {
+            collect_tags(&component.body, &mut tags);
+        }
+
+        for (namespace, name) in tags {
+            let qualified_tag = match &namespace {
+                Some(namespace) => format!("{}:{}", namespace, name),
+                None => name.clone(),
+            };
+            if context.get_component(&qualified_tag).is_some() {
+                continue;
+            }
+
+            let Some(namespace_path) = declarations.get(&namespace) else {
+                if let Some(namespace) = namespace {
+                    return Err(GPMLError::ImportError {
+                        message: format!("Undeclared namespace '{}' of <{}>, declare it with xmlns:{}=\"...\" on the root element", namespace, qualified_tag, namespace),
+                    });
+                }
+                continue;
+            };
+
+            let path = namespace_component_path(&context.base_path, namespace_path, &name);
+            if namespace.is_none() && !GPMLFileSource::file_exists(&path.display().to_string()) {
+                continue;
+            }
+            self.process_namespace_document(&path, namespace.as_deref(), context)?;
+            if namespace.is_some() && context.get_component(&qualified_tag).is_none() {
+                return Err(GPMLError::ComponentNotFound { name: format!("{} in {}", qualified_tag, path.display()) });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Process the imports and components of a namespace file in a context of their own, and add
+    /// them to `context` under the `namespace` prefix.
+    ///
+    /// The tags of the components referring to each other are prefixed as well, so a component
+    /// can use the helpers defined next to it. Components already in `context` are kept.
+    fn process_namespace_document(&mut self, path: &Path, namespace: Option<&str>, context: &mut GPMLContext) -> GPMLResult<()> {
+        let document = self.load_document(path)?;
+        let mut namespace_context = GPMLContext::new(path.parent().unwrap_or(Path::new(".")));
+        self.process_document(&document, &mut namespace_context)?;
+
+        let names = namespace_context.components.keys().cloned().collect::<Vec<_>>();
+        for (_, mut component) in namespace_context.components {
+            if let Some(namespace) = namespace {
+                qualify_tags(&mut component.body, namespace, &names);
+                component.name = format!("{}:{}", namespace, component.name);
+            }
+            context.components.entry(component.name.clone()).or_insert(component);
+        }
+        Ok(())
+    }
+
     fn process_import(&mut self, import: &Import, context: &mut GPMLContext) -> GPMLResult<()> {
         tracing::info!("Processing import: {} as {}", import.path, import.alias);
 
@@ -224,6 +628,21 @@ impl ComponentResolver {
         component_def: &ComponentDef,
         args: &HashMap<String, AttributeValue>,
         context: &GPMLContext,
+    ) -> GPMLResult<Element> {
+        self.instantiate_component_with_slots(component_def, args, &HashMap::new(), context)
+    }
+
+    /// Instantiate a component with given parameters and slot content.
+    ///
+    /// Slot content must already be resolved in the caller's context, it is injected
+    /// as-is in place of the matching `<slot>` placeholders. Placeholders without content
+    /// fall back to their own children.
+    pub fn instantiate_component_with_slots(
+        &self,
+        component_def: &ComponentDef,
+        args: &HashMap<String, AttributeValue>,
+        slots: &HashMap<String, Vec<GPMLNode>>,
+        context: &GPMLContext,
     ) -> GPMLResult<Element> {
         // Validate parameter count
         if args.len() != component_def.parameters.len() {
@@ -233,6 +652,8 @@ impl ComponentResolver {
             });
         }
 
+        self.check_parameter_types(component_def, args)?;
+
         // Create new context with parameter bindings
         let mut instance_context = context.clone();
         for (_param, arg_name) in component_def.parameters.iter().enumerate() {
@@ -245,36 +666,121 @@ impl ComponentResolver {
         let mut instance_body = component_def.body.clone();
         self.interpolate_element(&mut instance_body, &instance_context)?;
 
+        for name in slots.keys() {
+            if !component_def.slots.contains_key(name) {
+                tracing::warn!("Component '{}' has no slot named '{}'", component_def.name, name);
+            }
+        }
+        fill_slots(&mut instance_body, slots);
+
         Ok(instance_body)
     }
 
+    /// Validate the arguments against the parameter type annotations.
+    ///
+    /// Mismatches are errors in strict mode, otherwise they are only logged.
+    fn check_parameter_types(
+        &self,
+        component_def: &ComponentDef,
+        args: &HashMap<String, AttributeValue>,
+    ) -> GPMLResult<()> {
+        for param in &component_def.parameters {
+            let expected = component_def.parameter_type(param);
+            let Some(value) = args.get(param) else {
+                continue;
+            };
+            if expected.accepts(value) {
+                continue;
+            }
+
+            let error = GPMLError::TypeMismatch {
+                param: param.clone(),
+                expected,
+                got: value.type_name().to_string(),
+            };
+            if self.strict_types {
+                return Err(error);
+            }
+            tracing::warn!("Component '{}': {}", component_def.name, error);
+        }
+        Ok(())
+    }
+
     fn interpolate_element(&self, element: &mut Element, context: &GPMLContext) -> GPMLResult<()> {
         // Interpolate attributes
         for (_, value) in element.attributes.iter_mut() {
             *value = context.interpolate_attribute(value);
         }
+        apply_bound_value(element, context);
 
-        // Interpolate children
-        for child in element.children.iter_mut() {
+        // Drop children whose conditional branch was not taken
+        element.children = filter_conditional_children(&element.children, context);
+
+        // Interpolate children, expanding `gpml:for` loops with the loop variable bound
+        let mut children = Vec::with_capacity(element.children.len());
+        for child in element.children.drain(..) {
             match child {
-                GPMLNode::Element(child_element) => {
-                    self.interpolate_element(child_element, context)?;
+                GPMLNode::Element(mut child_element) => {
+                    if let Some(iterations) = expand_each(&child_element, context) {
+                        for (mut each, item_context) in iterations {
+                            self.interpolate_element(&mut each, &item_context)?;
+                            splice_children(&mut children, each.children);
+                        }
+                    } else if let Some(iterations) = expand_loop(&child_element, context) {
+                        for (mut item_element, item_context) in iterations {
+                            self.interpolate_element(&mut item_element, &item_context)?;
+                            children.push(GPMLNode::Element(item_element));
+                        }
+                    } else {
+                        self.interpolate_element(&mut child_element, context)?;
+                        children.push(GPMLNode::Element(child_element));
+                    }
                 }
                 GPMLNode::Text(text) => {
-                    *text = context.interpolate_string(text);
+                    push_text(&mut children, context.interpolate_string(&text));
                 }
                 GPMLNode::Expression(expr) => {
-                    if let Some(value) = context.get_variable(expr) {
-                        *child = GPMLNode::Text(value.as_string());
+                    if let Some(value) = context.get_variable(&expr) {
+                        push_text(&mut children, value.as_string());
+                    } else {
+                        children.push(GPMLNode::Expression(expr));
                     }
                 }
-                _ => {}
+                other => children.push(other),
             }
         }
+        element.children = children;
 
         Ok(())
     }
 
+    /// Load the files and their imports into the cache, so later loads and imports
+    /// of them don't read and parse the files again
+    pub fn preload(&mut self, paths: &[PathBuf]) -> GPMLResult<()> {
+        self.preload_with_progress(paths, |_, _| {})
+    }
+
+    /// Like [`ComponentResolver::preload`], calls `progress(loaded, total)` after each file
+    pub fn preload_with_progress(&mut self, paths: &[PathBuf], progress: impl Fn(usize, usize)) -> GPMLResult<()> {
+        for (ix, path) in paths.iter().enumerate() {
+            tracing::debug!("Preloading {:?}", path);
+            self.load_file(path)?;
+            progress(ix + 1, paths.len());
+        }
+        Ok(())
+    }
+
+    /// Move the cached documents of another resolver into this one, e.g. one that
+    /// preloaded the files in a background task
+    pub fn extend_cache(&mut self, other: ComponentResolver) {
+        self.cache.extend(other.cache);
+    }
+
+    /// Whether the document of the file is cached
+    pub fn is_cached(&self, path: &Path) -> bool {
+        self.cache.contains_key(path)
+    }
+
     pub fn clear_cache(&mut self) {
         self.cache.clear();
     }
@@ -282,6 +788,22 @@ impl ComponentResolver {
     pub fn remove_from_cache(&mut self, path: &Path) {
         self.cache.remove(path);
     }
+
+    /// Evict cached documents that define any of the given components
+    pub fn remove_components_from_cache<'a>(&mut self, names: impl IntoIterator<Item = &'a String>) {
+        let names: Vec<&String> = names.into_iter().collect();
+        self.cache.retain(|path, document| {
+            let defines_changed = matches!(
+                document,
+                GPMLNode::Document { components, .. }
+                    if components.iter().any(|c| names.contains(&&c.name))
+            );
+            if defines_changed {
+                tracing::debug!("Evicting cached document {:?}", path);
+            }
+            !defines_changed
+        });
+    }
 }
 
 impl Default for ComponentResolver {
@@ -299,7 +821,7 @@ pub fn resolve_element(
     tracing::debug!("Resolving element: tag={}", element.tag);
     
     // Check if this element refers to a custom component
-    if let Some(component_def) = context.get_component(&element.tag) {
+    if let Some(component_def) = context.get_component(&element.qualified_tag()) {
         tracing::info!("Found custom component definition for '{}'", element.tag);
         tracing::debug!("Component has {} parameters: {:?}", component_def.parameters.len(), component_def.parameters);
         
@@ -311,30 +833,51 @@ pub fn resolve_element(
             args.insert(key.clone(), interpolated_value);
         }
 
+        // Resolve slot content in the caller's context
+        let slots = collect_slot_content(element, context, resolver)?;
+
         // Instantiate the component
         tracing::info!("Instantiating component '{}'", element.tag);
-        resolver.instantiate_component(component_def, &args, context)
+        resolver.instantiate_component_with_slots(component_def, &args, &slots, context)
     } else {
         tracing::debug!("Element '{}' is not a custom component, resolving as regular element", element.tag);
         tracing::debug!("Available components: {:?}", context.components.keys().collect::<Vec<_>>());
         
         // This is a regular element, just resolve children
         let mut resolved = element.clone();
+        resolved.conditions = None;
         let mut resolved_children = Vec::new();
 
-        for child in &element.children {
+        let mut children = filter_conditional_children(&element.children, context);
+        if element.tag == "router" {
+            children = filter_routes(children, &context.current_path);
+        }
+
+        for child in &children {
             match child {
                 GPMLNode::Element(child_element) => {
-                    let resolved_child = resolve_element(child_element, context, resolver)?;
-                    resolved_children.push(GPMLNode::Element(resolved_child));
+                    if let Some(iterations) = expand_each(child_element, context) {
+                        for (each, item_context) in iterations {
+                            let resolved_each = resolve_element(&each, &item_context, resolver)?;
+                            splice_children(&mut resolved_children, resolved_each.children);
+                        }
+                    } else if let Some(iterations) = expand_loop(child_element, context) {
+                        for (item_element, item_context) in iterations {
+                            let resolved_child = resolve_element(&item_element, &item_context, resolver)?;
+                            resolved_children.push(GPMLNode::Element(resolved_child));
+                        }
+                    } else {
+                        let resolved_child = resolve_element(child_element, context, resolver)?;
+                        resolved_children.push(GPMLNode::Element(resolved_child));
+                    }
                 }
                 GPMLNode::Text(text) => {
                     let interpolated_text = context.interpolate_string(text);
-                    resolved_children.push(GPMLNode::Text(interpolated_text));
+                    push_text(&mut resolved_children, interpolated_text);
                 }
                 GPMLNode::Expression(expr) => {
                     if let Some(value) = context.get_variable(expr) {
-                        resolved_children.push(GPMLNode::Text(value.as_string()));
+                        push_text(&mut resolved_children, value.as_string());
                     } else {
                         resolved_children.push(child.clone());
                     }
@@ -349,7 +892,394 @@ pub fn resolve_element(
         for (_, value) in resolved.attributes.iter_mut() {
             *value = context.interpolate_attribute(value);
         }
+        apply_bound_value(&mut resolved, context);
 
         Ok(resolved)
     }
 }
+
+/// Collect the distinct `(namespace, tag)` of the element and its descendants
+fn collect_tags(element: &Element, tags: &mut Vec<(Option<String>, String)>) {
+    let tag = (element.namespace.clone(), element.tag.clone());
+    if !tags.contains(&tag) {
+        tags.push(tag);
+    }
+    for child in &element.children {
+        if let GPMLNode::Element(child) = child {
+            collect_tags(child, tags);
+        }
+    }
+}
+
+/// Add the `namespace` prefix to the unqualified tags of the element and its descendants
+/// that are one of the `names`
+fn qualify_tags(element: &mut Element, namespace: &str, names: &[String]) {
+    if element.namespace.is_none() && names.contains(&element.tag) {
+        element.namespace = Some(namespace.to_string());
+    }
+    for child in &mut element.children {
+        if let GPMLNode::Element(child) = child {
+            qualify_tags(child, namespace, names);
+        }
+    }
+}
+
+/// Path of the file defining the component `name` of a namespace declared as `namespace_path`:
+/// the namespace file itself, or `<name>.gpml` in the namespace directory.
+fn namespace_component_path(base_path: &Path, namespace_path: &str, name: &str) -> PathBuf {
+    let path = base_path.join(namespace_path);
+    if namespace_path.ends_with(".gpml") {
+        path
+    } else {
+        path.join(format!("{}.gpml", name))
+    }
+}
+
+/// Group the children of a component invocation by their `slot` attribute and resolve them.
+///
+/// Children without a `slot` attribute go to the default slot.
+fn collect_slot_content(
+    element: &Element,
+    context: &GPMLContext,
+    resolver: &ComponentResolver,
+) -> GPMLResult<HashMap<String, Vec<GPMLNode>>> {
+    let mut grouped: HashMap<String, Element> = HashMap::new();
+    for child in &element.children {
+        let mut child = child.clone();
+        let name = match &mut child {
+            GPMLNode::Element(child_element) => child_element
+                .attributes
+                .remove("slot")
+                .map(|v| v.as_string())
+                .unwrap_or_else(|| DEFAULT_SLOT.to_string()),
+            _ => DEFAULT_SLOT.to_string(),
+        };
+        grouped
+            .entry(name)
+            .or_insert_with(|| Element::new("slot".to_string()))
+            .children
+            .push(child);
+    }
+
+    let mut slots = HashMap::new();
+    for (name, wrapper) in grouped {
+        let resolved = resolve_element(&wrapper, context, resolver)?;
+        slots.insert(name, resolved.children);
+    }
+    Ok(slots)
+}
+
+/// Replace `<slot>` placeholders with the provided content, or with their fallback children
+fn fill_slots(element: &mut Element, slots: &HashMap<String, Vec<GPMLNode>>) {
+    let mut children = Vec::with_capacity(element.children.len());
+    for child in element.children.drain(..) {
+        match child {
+            GPMLNode::Element(slot) if slot.tag == "slot" => {
+                let name = slot
+                    .get_attribute("name")
+                    .map(|v| v.as_string())
+                    .unwrap_or_else(|| DEFAULT_SLOT.to_string());
+                match slots.get(&name) {
+                    Some(content) => children.extend(content.iter().cloned()),
+                    None => children.extend(slot.children),
+                }
+            }
+            GPMLNode::Element(mut child_element) => {
+                fill_slots(&mut child_element, slots);
+                children.push(GPMLNode::Element(child_element));
+            }
+            other => children.push(other),
+        }
+    }
+    element.children = children;
+}
+
+/// Collect the `<block name="...">` elements of a child template, keyed by name
+fn collect_blocks(element: &Element, blocks: &mut HashMap<String, Element>) {
+    if element.tag == "block" {
+        if let Some(name) = element.get_attribute("name") {
+            blocks.insert(name.as_string(), element.clone());
+            return;
+        }
+    }
+    for child in &element.children {
+        if let GPMLNode::Element(child) = child {
+            collect_blocks(child, blocks);
+        }
+    }
+}
+
+/// Replace the content of the parent template's blocks with the child's overrides
+fn override_blocks(element: &mut Element, blocks: &HashMap<String, Element>) {
+    if element.tag == "block" {
+        let name = element.get_attribute("name").map(|v| v.as_string());
+        if let Some(block) = name.and_then(|name| blocks.get(&name)) {
+            element.children = block.children.clone();
+            return;
+        }
+    }
+    for child in element.children.iter_mut() {
+        if let GPMLNode::Element(child) = child {
+            override_blocks(child, blocks);
+        }
+    }
+}
+
+/// Expand a `gpml:for="item in items"` loop into one clone of the element per array item.
+///
+/// Each clone is paired with a context in which the loop variable is bound to its item.
+/// Returns None when the element has no `gpml:for` attribute.
+pub fn expand_loop(element: &Element, context: &GPMLContext) -> Option<Vec<(Element, GPMLContext)>> {
+    let spec = element.get_attribute("gpml:for")?.as_string();
+
+    let mut template = element.clone();
+    template.attributes.remove("gpml:for");
+
+    let Some((variable, iterable)) = spec.split_once(" in ") else {
+        tracing::warn!("Invalid gpml:for on <{}>: expected 'item in items', got '{}'", element.tag, spec);
+        return Some(Vec::new());
+    };
+    let variable = variable.trim();
+    let items = iterable_items(iterable, &format!("gpml:for on <{}>", element.tag), context);
+
+    Some(
+        items
+            .iter()
+            .map(|item| {
+                let item_context = context
+                    .clone()
+                    .with_variable(variable.to_string(), item.clone());
+                (template.clone(), item_context)
+            })
+            .collect(),
+    )
+}
+
+/// Expand an `<each items="${list}" as="item" index="i">` element into one clone of it per
+/// array item.
+///
+/// Each clone is paired with a context in which `as` (default `item`) is bound to the item and
+/// the optional `index` to its position. The callers inline the children of the clones, so
+/// `<each>` doesn't render a wrapper element. Returns None when the element is not an `<each>`.
+pub fn expand_each(element: &Element, context: &GPMLContext) -> Option<Vec<(Element, GPMLContext)>> {
+    if element.tag != EACH_TAG {
+        return None;
+    }
+
+    let mut template = element.clone();
+    template.attributes.clear();
+
+    let variable = element.get_attribute("as").map(|v| v.as_string()).unwrap_or_else(|| "item".to_string());
+    let index = element.get_attribute("index").map(|v| v.as_string());
+    let items = match element.get_attribute("items") {
+        Some(AttributeValue::Array(items)) => items.clone(),
+        Some(value) => iterable_items(&value.as_string(), "<each>", context),
+        None => {
+            tracing::warn!("<each> is missing the 'items' attribute");
+            Vec::new()
+        }
+    };
+
+    Some(
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(ix, item)| {
+                let mut item_context = context.clone().with_variable(variable.clone(), item);
+                if let Some(index) = &index {
+                    item_context = item_context.with_variable(index.clone(), AttributeValue::Number(ix as f64));
+                }
+                (template.clone(), item_context)
+            })
+            .collect(),
+    )
+}
+
+/// Get the items of a loop `iterable`, an array literal or a variable name, optionally as `${name}`.
+///
+/// Returns no items and logs a warning prefixed by the `directive` when it is not an array.
+fn iterable_items(iterable: &str, directive: &str, context: &GPMLContext) -> Vec<AttributeValue> {
+    let iterable = iterable.trim();
+    let iterable = if iterable.starts_with("${") && iterable.ends_with('}') {
+        iterable[2..iterable.len() - 1].trim()
+    } else {
+        iterable
+    };
+
+    let items = if iterable.starts_with('[') {
+        GPMLParser::parse_attribute_value_str(iterable)
+    } else {
+        match context.get_variable(iterable) {
+            Some(value) => value.clone(),
+            None => {
+                tracing::warn!("{} references unknown variable '{}'", directive, iterable);
+                return Vec::new();
+            }
+        }
+    };
+
+    match items {
+        AttributeValue::Array(items) => items,
+        items => {
+            tracing::warn!("{} expects an array, got {:?}", directive, items);
+            Vec::new()
+        }
+    }
+}
+
+/// Evaluate `gpml:if` / `gpml:else-if` / `gpml:else` chains across a list of siblings.
+///
+/// Only the first branch whose condition holds is kept and its directive is cleared.
+/// An `else-if` or `else` that does not directly follow an `if` or `else-if` element is dropped.
+pub fn filter_conditional_children(children: &[GPMLNode], context: &GPMLContext) -> Vec<GPMLNode> {
+    let mut result = Vec::with_capacity(children.len());
+    // None when not inside a chain, otherwise whether a branch of the current chain was taken
+    let mut chain_taken: Option<bool> = None;
+
+    for child in children {
+        let GPMLNode::Element(element) = child else {
+            chain_taken = None;
+            result.push(child.clone());
+            continue;
+        };
+
+        let keep = match &element.conditions {
+            None => {
+                chain_taken = None;
+                true
+            }
+            Some(Condition::If(expr)) => {
+                let taken = context.evaluate_condition(expr);
+                chain_taken = Some(taken);
+                taken
+            }
+            Some(Condition::ElseIf(expr)) => match chain_taken {
+                Some(true) => false,
+                Some(false) => {
+                    let taken = context.evaluate_condition(expr);
+                    chain_taken = Some(taken);
+                    taken
+                }
+                None => {
+                    tracing::warn!("gpml:else-if on <{}> without a preceding gpml:if", element.tag);
+                    false
+                }
+            },
+            Some(Condition::Else) => {
+                let taken = match chain_taken {
+                    Some(taken) => !taken,
+                    None => {
+                        tracing::warn!("gpml:else on <{}> without a preceding gpml:if", element.tag);
+                        false
+                    }
+                };
+                chain_taken = None;
+                taken
+            }
+        };
+
+        if keep {
+            let mut element = element.clone();
+            element.conditions = None;
+            result.push(GPMLNode::Element(element));
+        }
+    }
+
+    result
+}
+
+/// Push resolved text, joined with the previous text node so that text mixed with expressions
+/// like `Total: ${count} items` renders as a single string
+fn push_text(children: &mut Vec<GPMLNode>, text: String) {
+    match children.last_mut() {
+        Some(GPMLNode::Text(last)) => last.push_str(&text),
+        _ => children.push(GPMLNode::Text(text)),
+    }
+}
+
+/// Inline the children of an expanded `<each>` into its parent's children
+fn splice_children(children: &mut Vec<GPMLNode>, each_children: Vec<GPMLNode>) {
+    for child in each_children {
+        match child {
+            GPMLNode::Text(text) => push_text(children, text),
+            other => children.push(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_cache() {
+        let dir = std::env::temp_dir().join(format!("gpml-disk-cache-{}", std::process::id()));
+        let cache_dir = dir.join("cache");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Home.gpml");
+        std::fs::write(&path, "<root><span>Home</span></root>").unwrap();
+
+        let cache_files = || -> Vec<std::path::PathBuf> {
+            std::fs::read_dir(&cache_dir)
+                .map(|entries| entries.map(|entry| entry.unwrap().path()).collect())
+                .unwrap_or_default()
+        };
+
+        assert!(ComponentResolver::new().with_disk_cache(&cache_dir).load_file(&path).is_ok());
+        let files = cache_files();
+        assert_eq!(files.len(), 1);
+
+        // The next session reads the cached document instead of parsing the file
+        let cached = GPMLParser::parse_file("def Cached() {\n    <div />\n}\n\n<root />").unwrap();
+        std::fs::write(&files[0], serde_json::to_vec(&cached).unwrap()).unwrap();
+        let context = ComponentResolver::new().with_disk_cache(&cache_dir).load_file(&path).unwrap();
+        assert!(context.get_component("Cached").is_some());
+
+        // A corrupted entry is parsed again and overwritten
+        std::fs::write(&files[0], "not json").unwrap();
+        let context = ComponentResolver::new().with_disk_cache(&cache_dir).load_file(&path).unwrap();
+        assert!(context.get_component("Cached").is_none());
+        assert!(serde_json::from_slice::<GPMLNode>(&std::fs::read(&files[0]).unwrap()).is_ok());
+
+        ComponentResolver::clear_disk_cache(&cache_dir).unwrap();
+        assert!(cache_files().is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_namespace_sibling_components() {
+        let dir = std::env::temp_dir().join(format!("gpml-namespace-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("ui.gpml"),
+            "def Button(label) {\n    <Frame><span>${label}</span></Frame>\n}\n\ndef Frame() {\n    <div><slot /></div>\n}\n",
+        )
+        .unwrap();
+        let path = dir.join("Home.gpml");
+        std::fs::write(&path, "<root xmlns:ui=\"./ui.gpml\"><ui:Button label=\"Save\" /></root>").unwrap();
+
+        let mut resolver = ComponentResolver::new();
+        let context = resolver.load_file(&path).unwrap();
+        let button = context.get_component("ui:Button").unwrap();
+        assert!(context.get_component("ui:Frame").is_some());
+        assert!(context.get_component("Frame").is_none());
+        assert_eq!(button.body.qualified_tag(), "ui:Frame");
+
+        let GPMLNode::Document { root: Some(root), .. } = GPMLParser::parse_file(&std::fs::read_to_string(&path).unwrap()).unwrap() else {
+            panic!("Expected a document with a root");
+        };
+        assert!(resolve_element(&root, &context, &resolver).is_ok());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_disk_cache_key() {
+        let content = "<root />";
+        assert_eq!(disk_cache_key(content), disk_cache_key(content));
+        assert_ne!(disk_cache_key(content), disk_cache_key("<root></root>"));
+        // Entries written by another version are not read back
+        assert_ne!(disk_cache_key(content), blake3::hash(content.as_bytes()));
+    }
+}
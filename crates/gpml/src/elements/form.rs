@@ -2,6 +2,8 @@ use crate::ast::*;
 use crate::error::*;
 use gpui::*;
 use gpui_component::{v_flex, ActiveTheme};
+use gpui_component::input::TextInput;
+use crate::binding::{attach_component_event_handlers, attach_event_handlers, bound_variable, BindingScope};
 use super::{ElementRenderer, render_child, apply_common_styles, extract_text_content, default_text_color};
 
 pub struct FormElement;
@@ -10,7 +12,7 @@ pub struct LegendElement;
 pub struct TextareaElement;
 
 impl ElementRenderer for FormElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -23,18 +25,18 @@ impl ElementRenderer for FormElement {
 
         form = apply_common_styles(form, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 form = form.child(child_element);
             }
         }
 
-        Ok(form.into_any_element())
+        Ok(attach_event_handlers(element, scope, form).into_any_element())
     }
 }
 
 impl ElementRenderer for FieldsetElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -45,33 +47,33 @@ impl ElementRenderer for FieldsetElement {
             .border_color(cx.theme().border)
             .rounded_md();
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 fieldset = fieldset.child(child_element);
             }
         }
 
-        Ok(fieldset.into_any_element())
+        Ok(attach_event_handlers(element, scope, fieldset).into_any_element())
     }
 }
 
 impl ElementRenderer for LegendElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let text_content = extract_text_content(element);
-        Ok(div()
+        let legend = div()
             .font_weight(FontWeight::BOLD)
             .text_color(default_text_color())
             .mb_2()
-            .child(text_content)
-            .into_any_element())
+            .child(text_content);
+        Ok(attach_event_handlers(element, scope, legend).into_any_element())
     }
 }
 
 impl ElementRenderer for TextareaElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -83,6 +85,13 @@ impl ElementRenderer for TextareaElement {
             .and_then(|v| v.as_number())
             .unwrap_or(3.0) as f32;
 
+        // Bound textareas render a real multi-line input backed by the canvas
+        if let Some(name) = bound_variable(element) {
+            if let Some(state) = scope.inputs.get(&name) {
+                return Ok(attach_component_event_handlers(element, scope, TextInput::new(state).h(px(rows * 24.0))));
+            }
+        }
+
         let mut textarea = div()
             .border_1()
             .border_color(cx.theme().border)
@@ -98,6 +107,6 @@ impl ElementRenderer for TextareaElement {
             textarea = textarea.child("[Textarea]");
         }
 
-        Ok(textarea.into_any_element())
+        Ok(attach_event_handlers(element, scope, textarea).into_any_element())
     }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use crate::ast::*;
 use crate::error::*;
 use gpui::*;
 use gpui_component::ActiveTheme;
+use crate::binding::{attach_event_handlers, BindingScope};
 use super::{ElementRenderer, extract_text_content, default_text_color, muted_text_color, apply_text_styles};
 
 pub struct StrongElement;
@@ -17,7 +18,7 @@ pub struct SubElement;
 pub struct SupElement;
 
 impl ElementRenderer for StrongElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -28,12 +29,12 @@ impl ElementRenderer for StrongElement {
             .child(text_content);
 
         strong = apply_text_styles(strong, element, cx);
-        Ok(strong.into_any_element())
+        Ok(attach_event_handlers(element, scope, strong).into_any_element())
     }
 }
 
 impl ElementRenderer for EmElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -44,12 +45,12 @@ impl ElementRenderer for EmElement {
             .child(text_content);
 
         em = apply_text_styles(em, element, cx);
-        Ok(em.into_any_element())
+        Ok(attach_event_handlers(element, scope, em).into_any_element())
     }
 }
 
 impl ElementRenderer for UnderlineElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -60,12 +61,12 @@ impl ElementRenderer for UnderlineElement {
             .child(text_content);
 
         u = apply_text_styles(u, element, cx);
-        Ok(u.into_any_element())
+        Ok(attach_event_handlers(element, scope, u).into_any_element())
     }
 }
 
 impl ElementRenderer for StrikethroughElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -76,12 +77,12 @@ impl ElementRenderer for StrikethroughElement {
             .child(text_content);
 
         s = apply_text_styles(s, element, cx);
-        Ok(s.into_any_element())
+        Ok(attach_event_handlers(element, scope, s).into_any_element())
     }
 }
 
 impl ElementRenderer for CodeElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -95,12 +96,12 @@ impl ElementRenderer for CodeElement {
             .child(text_content);
 
         code = apply_text_styles(code, element, cx);
-        Ok(code.into_any_element())
+        Ok(attach_event_handlers(element, scope, code).into_any_element())
     }
 }
 
 impl ElementRenderer for PreElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -114,12 +115,12 @@ impl ElementRenderer for PreElement {
             .child(text_content);
 
         pre = apply_text_styles(pre, element, cx);
-        Ok(pre.into_any_element())
+        Ok(attach_event_handlers(element, scope, pre).into_any_element())
     }
 }
 
 impl ElementRenderer for CiteElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -130,12 +131,12 @@ impl ElementRenderer for CiteElement {
             .child(text_content);
 
         cite = apply_text_styles(cite, element, cx);
-        Ok(cite.into_any_element())
+        Ok(attach_event_handlers(element, scope, cite).into_any_element())
     }
 }
 
 impl ElementRenderer for MarkElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -146,12 +147,12 @@ impl ElementRenderer for MarkElement {
             .child(text_content);
 
         mark = apply_text_styles(mark, element, cx);
-        Ok(mark.into_any_element())
+        Ok(attach_event_handlers(element, scope, mark).into_any_element())
     }
 }
 
 impl ElementRenderer for SmallElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -162,12 +163,12 @@ impl ElementRenderer for SmallElement {
             .child(text_content);
 
         small = apply_text_styles(small, element, cx);
-        Ok(small.into_any_element())
+        Ok(attach_event_handlers(element, scope, small).into_any_element())
     }
 }
 
 impl ElementRenderer for SubElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -179,12 +180,12 @@ impl ElementRenderer for SubElement {
             .child(text_content);
 
         sub = apply_text_styles(sub, element, cx);
-        Ok(sub.into_any_element())
+        Ok(attach_event_handlers(element, scope, sub).into_any_element())
     }
 }
 
 impl ElementRenderer for SupElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -196,6 +197,6 @@ impl ElementRenderer for SupElement {
             .child(text_content);
 
         sup = apply_text_styles(sup, element, cx);
-        Ok(sup.into_any_element())
+        Ok(attach_event_handlers(element, scope, sup).into_any_element())
     }
 }
\ No newline at end of file
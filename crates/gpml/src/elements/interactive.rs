@@ -1,7 +1,9 @@
 use crate::ast::*;
 use crate::error::*;
 use gpui::*;
-use gpui_component::{h_flex, button, checkbox, radio, switch, label, ActiveTheme, Disableable};
+use gpui_component::{h_flex, v_flex, button, checkbox, radio, switch, label, ActiveTheme, Disableable};
+use gpui_component::input::TextInput;
+use crate::binding::{attach_component_event_handlers, attach_event_handlers, bound_variable, update_bound_variable, BindingScope};
 use super::{ElementRenderer, extract_text_content, default_text_color, muted_text_color};
 
 pub struct ButtonElement;
@@ -10,9 +12,10 @@ pub struct CheckboxElement;
 pub struct RadioElement;
 pub struct SwitchElement;
 pub struct SliderElement;
+pub struct SelectElement;
 
 impl ElementRenderer for ButtonElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -24,22 +27,29 @@ impl ElementRenderer for ButtonElement {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        let mut button = button::Button::new("gpml-button")
+        let mut button = button::Button::new(scope.element_id(element))
             .label(text_content);
 
         if disabled {
             button = button.disabled(true);
         }
 
-        Ok(button.into_any_element())
+        Ok(attach_event_handlers(element, scope, button).into_any_element())
     }
 }
 
 impl ElementRenderer for InputElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
+        // Bound inputs render a real text input backed by the canvas
+        if let Some(name) = bound_variable(element) {
+            if let Some(state) = scope.inputs.get(&name) {
+                return Ok(attach_component_event_handlers(element, scope, TextInput::new(state)));
+            }
+        }
+
         let placeholder = element.get_attribute("placeholder")
             .map(|v| v.as_string())
             .unwrap_or_default();
@@ -69,12 +79,12 @@ impl ElementRenderer for InputElement {
                 .text_color(muted_text_color());
         }
 
-        Ok(input_div.into_any_element())
+        Ok(attach_event_handlers(element, scope, input_div).into_any_element())
     }
 }
 
 impl ElementRenderer for CheckboxElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -90,7 +100,7 @@ impl ElementRenderer for CheckboxElement {
             .map(|v| v.as_string())
             .unwrap_or_else(|| extract_text_content(element));
 
-        let mut checkbox = checkbox::Checkbox::new("gpml-checkbox")
+        let mut checkbox = checkbox::Checkbox::new(scope.element_id(element))
             .checked(checked);
 
         if disabled {
@@ -98,20 +108,20 @@ impl ElementRenderer for CheckboxElement {
         }
 
         if !label_text.is_empty() {
-            Ok(h_flex()
+            let row = h_flex()
                 .items_center()
                 .gap_2()
                 .child(checkbox)
-                .child(label::Label::new(label_text))
-                .into_any_element())
+                .child(label::Label::new(label_text));
+            Ok(attach_event_handlers(element, scope, row).into_any_element())
         } else {
-            Ok(checkbox.into_any_element())
+            Ok(attach_event_handlers(element, scope, checkbox).into_any_element())
         }
     }
 }
 
 impl ElementRenderer for RadioElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -127,23 +137,23 @@ impl ElementRenderer for RadioElement {
             .map(|v| v.as_string())
             .unwrap_or_else(|| extract_text_content(element));
 
-        let radio = radio::Radio::new("gpml-radio");
+        let radio = radio::Radio::new(scope.element_id(element));
 
         if !label_text.is_empty() {
-            Ok(h_flex()
+            let row = h_flex()
                 .items_center()
                 .gap_2()
                 .child(radio)
-                .child(label::Label::new(label_text))
-                .into_any_element())
+                .child(label::Label::new(label_text));
+            Ok(attach_event_handlers(element, scope, row).into_any_element())
         } else {
-            Ok(radio.into_any_element())
+            Ok(attach_event_handlers(element, scope, radio).into_any_element())
         }
     }
 }
 
 impl ElementRenderer for SwitchElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -155,19 +165,19 @@ impl ElementRenderer for SwitchElement {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        let mut switch = switch::Switch::new("gpml-switch")
+        let mut switch = switch::Switch::new(scope.element_id(element))
             .checked(checked);
 
         if disabled {
             switch = switch.disabled(true);
         }
 
-        Ok(switch.into_any_element())
+        Ok(attach_component_event_handlers(element, scope, switch))
     }
 }
 
 impl ElementRenderer for SliderElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -187,7 +197,7 @@ impl ElementRenderer for SliderElement {
             .and_then(|v| v.as_number())
             .unwrap_or(1.0);
 
-        Ok(div()
+        let slider = div()
             .h_8()
             .w_full()
             .border_1()
@@ -200,7 +210,66 @@ impl ElementRenderer for SliderElement {
                     .w(px(((value - min) / (max - min) * 100.0) as f32))
                     .bg(cx.theme().primary)
                     .rounded_full()
-            )
-            .into_any_element())
+            );
+        Ok(attach_event_handlers(element, scope, slider).into_any_element())
+    }
+}
+
+impl ElementRenderer for SelectElement {
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    where
+        T: 'static,
+    {
+        let selected = element.get_attribute("value").map(|v| v.as_string());
+        let bound = bound_variable(element);
+        let canvas = scope.canvas.clone();
+
+        let mut select = v_flex()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .py_1()
+            .bg(cx.theme().background)
+            .text_color(default_text_color());
+
+        let options = element
+            .children
+            .iter()
+            .filter_map(|child| child.as_element())
+            .filter(|child| child.tag == "option");
+
+        for (ix, option) in options.enumerate() {
+            let label = extract_text_content(option);
+            let value = option.get_attribute("value")
+                .map(|v| v.as_string())
+                .unwrap_or_else(|| label.clone());
+            let is_selected = selected.as_deref() == Some(value.as_str());
+
+            let mut row = div()
+                .id(("gpml-select-option", ix))
+                .px_3()
+                .py_1()
+                .cursor_pointer()
+                .child(if label.is_empty() { value.clone() } else { label });
+
+            if is_selected {
+                row = row.bg(cx.theme().accent);
+            }
+
+            if let (Some(name), Some(canvas)) = (bound.clone(), canvas.clone()) {
+                row = row.on_click(move |_, _, cx| {
+                    update_bound_variable(
+                        &canvas,
+                        name.clone(),
+                        AttributeValue::Literal(value.clone()),
+                        cx,
+                    );
+                });
+            }
+
+            select = select.child(row);
+        }
+
+        Ok(attach_event_handlers(element, scope, select).into_any_element())
     }
-}
\ No newline at end of file
+}
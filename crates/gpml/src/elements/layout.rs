@@ -3,6 +3,7 @@ use crate::error::*;
 use gpui::*;
 use gpui_component::ActiveTheme;
 use gpui_component::{h_flex, v_flex};
+use crate::binding::{attach_event_handlers, BindingScope};
 use super::{ElementRenderer, render_child, apply_common_styles, apply_flex_styles};
 
 pub struct DivElement;
@@ -10,7 +11,7 @@ pub struct FlexElement;
 pub struct RootElement;
 
 impl ElementRenderer for DivElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -18,18 +19,18 @@ impl ElementRenderer for DivElement {
 
         div_el = apply_common_styles(div_el, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 div_el = div_el.child(child_element);
             }
         }
 
-        Ok(div_el.into_any_element())
+        Ok(attach_event_handlers(element, scope, div_el).into_any_element())
     }
 }
 
 impl ElementRenderer for FlexElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -54,18 +55,18 @@ impl ElementRenderer for FlexElement {
 
         container = apply_flex_styles(container, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 container = container.child(child_element);
             }
         }
 
-        Ok(container.into_any_element())
+        Ok(attach_event_handlers(element, scope, container).into_any_element())
     }
 }
 
 impl ElementRenderer for RootElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -76,12 +77,12 @@ impl ElementRenderer for RootElement {
 
         root = apply_common_styles(root, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 root = root.child(child_element);
             }
         }
 
-        Ok(root.into_any_element())
+        Ok(attach_event_handlers(element, scope, root).into_any_element())
     }
 }
\ No newline at end of file
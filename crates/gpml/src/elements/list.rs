@@ -3,6 +3,7 @@ use crate::error::*;
 use gpui::*;
 use gpui_component::ActiveTheme;
 use gpui_component::{h_flex, v_flex};
+use crate::binding::{attach_event_handlers, BindingScope};
 use super::{ElementRenderer, render_child, muted_text_color, default_text_color, extract_text_content};
 
 pub struct UlElement;
@@ -14,7 +15,7 @@ pub struct DdElement;
 pub struct ListElement;
 
 impl ElementRenderer for UlElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -22,18 +23,18 @@ impl ElementRenderer for UlElement {
             .gap_1()
             .ml_4();
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 ul = ul.child(child_element);
             }
         }
 
-        Ok(ul.into_any_element())
+        Ok(attach_event_handlers(element, scope, ul).into_any_element())
     }
 }
 
 impl ElementRenderer for OlElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -42,7 +43,7 @@ impl ElementRenderer for OlElement {
             .ml_4();
 
         for (index, child) in element.children.iter().enumerate() {
-            if let Ok(child_element) = render_child(child, cx) {
+            if let Ok(child_element) = render_child(child, &scope.child(index), cx) {
                 let list_item = h_flex()
                     .gap_2()
                     .child(div().text_color(muted_text_color()).child(format!("{}.", index + 1)))
@@ -51,12 +52,12 @@ impl ElementRenderer for OlElement {
             }
         }
 
-        Ok(ol.into_any_element())
+        Ok(attach_event_handlers(element, scope, ol).into_any_element())
     }
 }
 
 impl ElementRenderer for LiElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -66,49 +67,49 @@ impl ElementRenderer for LiElement {
                 .text_color(muted_text_color())
                 .child("•"));
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 li = li.child(child_element);
             }
         }
 
-        Ok(li.into_any_element())
+        Ok(attach_event_handlers(element, scope, li).into_any_element())
     }
 }
 
 impl ElementRenderer for DlElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let mut dl = v_flex().gap_2();
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 dl = dl.child(child_element);
             }
         }
 
-        Ok(dl.into_any_element())
+        Ok(attach_event_handlers(element, scope, dl).into_any_element())
     }
 }
 
 impl ElementRenderer for DtElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let text_content = extract_text_content(element);
-        Ok(div()
+        let dt = div()
             .font_weight(FontWeight::BOLD)
             .text_color(default_text_color())
-            .child(text_content)
-            .into_any_element())
+            .child(text_content);
+        Ok(attach_event_handlers(element, scope, dt).into_any_element())
     }
 }
 
 impl ElementRenderer for DdElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -116,32 +117,32 @@ impl ElementRenderer for DdElement {
             .ml_4()
             .mb_2();
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 dd = dd.child(child_element);
             }
         }
 
-        Ok(dd.into_any_element())
+        Ok(attach_event_handlers(element, scope, dd).into_any_element())
     }
 }
 
 impl ElementRenderer for ListElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let mut list_items = Vec::new();
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 list_items.push(child_element);
             }
         }
 
-        Ok(v_flex()
+        let list = v_flex()
             .gap_1()
-            .children(list_items)
-            .into_any_element())
+            .children(list_items);
+        Ok(attach_event_handlers(element, scope, list).into_any_element())
     }
 }
\ No newline at end of file
@@ -1,10 +1,13 @@
 use crate::ast::*;
 use crate::error::*;
+use crate::binding::{attach_component_event_handlers, attach_event_handlers, BindingScope};
+use crate::router::{navigate_canvas, LINK_TO_ATTRIBUTE};
 use gpui::*;
 use gpui_component::{IconName, Icon, ActiveTheme};
 use super::{ElementRenderer, extract_text_content, default_text_color, apply_text_styles};
 
 pub struct LinkElement;
+pub struct RouteLinkElement;
 pub struct ImgElement;
 pub struct ImageElement;
 pub struct IconElement;
@@ -12,7 +15,7 @@ pub struct AvatarElement;
 pub struct BadgeElement;
 
 impl ElementRenderer for LinkElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -32,21 +35,49 @@ impl ElementRenderer for LinkElement {
         }
 
         link = apply_text_styles(link, element, cx);
-        Ok(link.into_any_element())
+        Ok(attach_event_handlers(element, scope, link).into_any_element())
+    }
+}
+
+impl ElementRenderer for RouteLinkElement {
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    where
+        T: 'static,
+    {
+        let text_content = extract_text_content(element);
+        let to = element.get_attribute(LINK_TO_ATTRIBUTE)
+            .map(|v| v.as_string())
+            .unwrap_or_default();
+        let canvas = scope.canvas.clone();
+
+        let mut link = div()
+            .id(SharedString::from(format!("gpml-link-{}", to)))
+            .text_color(cx.theme().primary)
+            .underline()
+            .cursor_pointer()
+            .hover(|style| style.text_color(cx.theme().primary.opacity(0.8)))
+            .child(text_content);
+
+        if let Some(canvas) = canvas {
+            link = link.on_click(move |_, _, cx| navigate_canvas(&canvas, to.clone(), cx));
+        }
+
+        link = apply_text_styles(link, element, cx);
+        Ok(attach_event_handlers(element, scope, link).into_any_element())
     }
 }
 
 impl ElementRenderer for ImgElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
-        ImageElement::render(element, cx)
+        ImageElement::render(element, scope, cx)
     }
 }
 
 impl ElementRenderer for ImageElement {
-    fn render<T>(element: &GPMLElement, _cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, _cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -56,10 +87,9 @@ impl ElementRenderer for ImageElement {
             .flex()
             .items_center()
             .justify_center()
-            .child(img("https://pub.lbkrs.com/files/202503/vEnnmgUM6bo362ya/sdk.svg").h_24())
-            .into_any_element();
+            .child(img("https://pub.lbkrs.com/files/202503/vEnnmgUM6bo362ya/sdk.svg").h_24());
 
-        Ok(hardcoded_test)
+        Ok(attach_event_handlers(element, scope, hardcoded_test).into_any_element())
 
         // Original dynamic code below (temporarily disabled)
         /*
@@ -126,7 +156,7 @@ impl ElementRenderer for ImageElement {
 }
 
 impl ElementRenderer for IconElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -153,41 +183,41 @@ impl ElementRenderer for IconElement {
             _ => IconName::CircleX,
         };
 
-        Ok(Icon::new(icon_name_enum)
-            .size(px(size as f32))
-            .into_any_element())
+        let icon = Icon::new(icon_name_enum)
+            .size(px(size as f32));
+        Ok(attach_component_event_handlers(element, scope, icon))
     }
 }
 
 impl ElementRenderer for AvatarElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         // Placeholder implementation
-        Ok(div()
+        let avatar = div()
             .w_8()
             .h_8()
             .rounded_full()
-            .bg(cx.theme().secondary)
-            .into_any_element())
+            .bg(cx.theme().secondary);
+        Ok(attach_event_handlers(element, scope, avatar).into_any_element())
     }
 }
 
 impl ElementRenderer for BadgeElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let text_content = extract_text_content(element);
-        Ok(div()
+        let badge = div()
             .px_2()
             .py_1()
             .bg(cx.theme().primary)
             .rounded_md()
             .text_xs()
             .text_color(default_text_color())
-            .child(text_content)
-            .into_any_element())
+            .child(text_content);
+        Ok(attach_event_handlers(element, scope, badge).into_any_element())
     }
 }
\ No newline at end of file
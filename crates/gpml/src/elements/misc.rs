@@ -2,6 +2,8 @@ use crate::ast::*;
 use crate::error::*;
 use gpui::*;
 use gpui_component::{scroll::ScrollbarAxis, ActiveTheme, StyledExt};
+use crate::portal::{portal_target, PortalScope};
+use crate::binding::{attach_event_handlers, BindingScope};
 use super::{ElementRenderer, render_child, apply_common_styles};
 
 pub struct ModalElement;
@@ -12,10 +14,33 @@ pub struct ResizableElement;
 pub struct BrElement;
 pub struct HrElement;
 pub struct NoopElement;
+pub struct PortalElement;
 pub struct TreeElement;
 
+impl ElementRenderer for PortalElement {
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    where
+        T: 'static,
+    {
+        let mut portal = div();
+        portal = apply_common_styles(portal, element);
+
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
+                portal = portal.child(child_element);
+            }
+        }
+
+        // Outside of a canvas render pass there is no overlay, render the content in place
+        match PortalScope::register(portal_target(element), portal.into_any_element()) {
+            Ok(()) => Ok(Empty.into_any_element()),
+            Err(portal) => Ok(portal),
+        }
+    }
+}
+
 impl ElementRenderer for ModalElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -24,89 +49,89 @@ impl ElementRenderer for ModalElement {
         let mut modal = div();
         modal = apply_common_styles(modal, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 modal = modal.child(child_element);
             }
         }
 
-        Ok(modal.into_any_element())
+        Ok(attach_event_handlers(element, scope, modal).into_any_element())
     }
 }
 
 impl ElementRenderer for PopoverElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let mut popover = div();
         popover = apply_common_styles(popover, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 popover = popover.child(child_element);
             }
         }
 
-        Ok(popover.into_any_element())
+        Ok(attach_event_handlers(element, scope, popover).into_any_element())
     }
 }
 
 impl ElementRenderer for TooltipElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let mut tooltip = div();
         tooltip = apply_common_styles(tooltip, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 tooltip = tooltip.child(child_element);
             }
         }
 
-        Ok(tooltip.into_any_element())
+        Ok(attach_event_handlers(element, scope, tooltip).into_any_element())
     }
 }
 
 impl ElementRenderer for ScrollElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let mut scroll_el = div().scrollable(ScrollbarAxis::Both);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 scroll_el = scroll_el.child(child_element);
             }
         }
 
-        Ok(scroll_el.into_any_element())
+        Ok(attach_event_handlers(element, scope, scroll_el).into_any_element())
     }
 }
 
 impl ElementRenderer for ResizableElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let mut resizable = div();
         resizable = apply_common_styles(resizable, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 resizable = resizable.child(child_element);
             }
         }
 
-        Ok(resizable.into_any_element())
+        Ok(attach_event_handlers(element, scope, resizable).into_any_element())
     }
 }
 
 impl ElementRenderer for BrElement {
-    fn render<T>(_element: &GPMLElement, _cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(_element: &GPMLElement, _scope: &BindingScope, _cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -118,7 +143,7 @@ impl ElementRenderer for BrElement {
 }
 
 impl ElementRenderer for HrElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -129,12 +154,12 @@ impl ElementRenderer for HrElement {
             .my_4();
 
         hr = apply_common_styles(hr, element);
-        Ok(hr.into_any_element())
+        Ok(attach_event_handlers(element, scope, hr).into_any_element())
     }
 }
 
 impl ElementRenderer for NoopElement {
-    fn render<T>(_element: &GPMLElement, _cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(_element: &GPMLElement, _scope: &BindingScope, _cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -143,19 +168,19 @@ impl ElementRenderer for NoopElement {
 }
 
 impl ElementRenderer for TreeElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let mut tree = div();
         tree = apply_common_styles(tree, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 tree = tree.child(child_element);
             }
         }
 
-        Ok(tree.into_any_element())
+        Ok(attach_event_handlers(element, scope, tree).into_any_element())
     }
 }
\ No newline at end of file
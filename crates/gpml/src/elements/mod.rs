@@ -12,20 +12,23 @@ pub mod misc;
 
 use crate::ast::*;
 use crate::error::*;
+use crate::binding::BindingScope;
+use crate::style::GPMLStylesheet;
 use gpui::*;
 
 pub trait ElementRenderer {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    /// Render `element`, the `scope` carries the canvas state of bound elements and event attributes
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static;
 }
 
-pub(crate) fn render_child<T>(child: &GPMLNode, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+pub(crate) fn render_child<T>(child: &GPMLNode, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
 where
     T: 'static,
 {
     match child {
-        GPMLNode::Element(element) => crate::renderer::GPMLRenderer::render_resolved_element_direct(element, cx),
+        GPMLNode::Element(element) => crate::renderer::GPMLRenderer::render_resolved_element_direct(element, scope, cx),
         GPMLNode::Text(text) => Ok(div().child(text.clone()).into_any_element()),
         _ => Ok(div().into_any_element()),
     }
@@ -43,58 +46,330 @@ pub(crate) fn muted_text_color() -> Hsla {
     gpui::rgb(0xcccccc).into()
 }
 
-pub(crate) fn apply_common_styles<T: Styled>(styled_el: T, _element: &GPMLElement) -> T {
-    styled_el
+pub(crate) fn apply_common_styles<T: Styled>(styled_el: T, element: &GPMLElement) -> T {
+    match GPMLStylesheet::current_style_for(element) {
+        Some(style) => style.apply_box(styled_el),
+        None => styled_el,
+    }
 }
 
-pub(crate) fn apply_flex_styles<T: ParentElement + Styled>(flex_el: T, _element: &GPMLElement) -> T {
-    flex_el
+pub(crate) fn apply_flex_styles<T: ParentElement + Styled>(flex_el: T, element: &GPMLElement) -> T {
+    match GPMLStylesheet::current_style_for(element) {
+        Some(style) => style.apply_gap(style.apply_box(flex_el)),
+        None => flex_el,
+    }
 }
 
-pub(crate) fn apply_text_styles<T, U>(text_el: T, _element: &GPMLElement, _cx: &mut Context<U>) -> T
+pub(crate) fn apply_text_styles<T, U>(text_el: T, element: &GPMLElement, _cx: &mut Context<U>) -> T
 where
     T: Styled,
     U: 'static,
 {
-    text_el
+    match GPMLStylesheet::current_style_for(element) {
+        Some(style) => style.apply_text(text_el),
+        None => text_el,
+    }
 }
 
+/// CSS named colors as `0xRRGGBB`
+static NAMED_COLORS: phf::Map<&'static str, u32> = phf::phf_map! {
+    "aliceblue" => 0xf0f8ff,
+    "antiquewhite" => 0xfaebd7,
+    "aqua" => 0x00ffff,
+    "aquamarine" => 0x7fffd4,
+    "azure" => 0xf0ffff,
+    "beige" => 0xf5f5dc,
+    "bisque" => 0xffe4c4,
+    "black" => 0x000000,
+    "blanchedalmond" => 0xffebcd,
+    "blue" => 0x0000ff,
+    "blueviolet" => 0x8a2be2,
+    "brown" => 0xa52a2a,
+    "burlywood" => 0xdeb887,
+    "cadetblue" => 0x5f9ea0,
+    "chartreuse" => 0x7fff00,
+    "chocolate" => 0xd2691e,
+    "coral" => 0xff7f50,
+    "cornflowerblue" => 0x6495ed,
+    "cornsilk" => 0xfff8dc,
+    "crimson" => 0xdc143c,
+    "cyan" => 0x00ffff,
+    "darkblue" => 0x00008b,
+    "darkcyan" => 0x008b8b,
+    "darkgoldenrod" => 0xb8860b,
+    "darkgray" => 0xa9a9a9,
+    "darkgreen" => 0x006400,
+    "darkgrey" => 0xa9a9a9,
+    "darkkhaki" => 0xbdb76b,
+    "darkmagenta" => 0x8b008b,
+    "darkolivegreen" => 0x556b2f,
+    "darkorange" => 0xff8c00,
+    "darkorchid" => 0x9932cc,
+    "darkred" => 0x8b0000,
+    "darksalmon" => 0xe9967a,
+    "darkseagreen" => 0x8fbc8f,
+    "darkslateblue" => 0x483d8b,
+    "darkslategray" => 0x2f4f4f,
+    "darkslategrey" => 0x2f4f4f,
+    "darkturquoise" => 0x00ced1,
+    "darkviolet" => 0x9400d3,
+    "deeppink" => 0xff1493,
+    "deepskyblue" => 0x00bfff,
+    "dimgray" => 0x696969,
+    "dimgrey" => 0x696969,
+    "dodgerblue" => 0x1e90ff,
+    "firebrick" => 0xb22222,
+    "floralwhite" => 0xfffaf0,
+    "forestgreen" => 0x228b22,
+    "fuchsia" => 0xff00ff,
+    "gainsboro" => 0xdcdcdc,
+    "ghostwhite" => 0xf8f8ff,
+    "gold" => 0xffd700,
+    "goldenrod" => 0xdaa520,
+    "gray" => 0x808080,
+    "green" => 0x008000,
+    "greenyellow" => 0xadff2f,
+    "grey" => 0x808080,
+    "honeydew" => 0xf0fff0,
+    "hotpink" => 0xff69b4,
+    "indianred" => 0xcd5c5c,
+    "indigo" => 0x4b0082,
+    "ivory" => 0xfffff0,
+    "khaki" => 0xf0e68c,
+    "lavender" => 0xe6e6fa,
+    "lavenderblush" => 0xfff0f5,
+    "lawngreen" => 0x7cfc00,
+    "lemonchiffon" => 0xfffacd,
+    "lightblue" => 0xadd8e6,
+    "lightcoral" => 0xf08080,
+    "lightcyan" => 0xe0ffff,
+    "lightgoldenrodyellow" => 0xfafad2,
+    "lightgray" => 0xd3d3d3,
+    "lightgreen" => 0x90ee90,
+    "lightgrey" => 0xd3d3d3,
+    "lightpink" => 0xffb6c1,
+    "lightsalmon" => 0xffa07a,
+    "lightseagreen" => 0x20b2aa,
+    "lightskyblue" => 0x87cefa,
+    "lightslategray" => 0x778899,
+    "lightslategrey" => 0x778899,
+    "lightsteelblue" => 0xb0c4de,
+    "lightyellow" => 0xffffe0,
+    "lime" => 0x00ff00,
+    "limegreen" => 0x32cd32,
+    "linen" => 0xfaf0e6,
+    "magenta" => 0xff00ff,
+    "maroon" => 0x800000,
+    "mediumaquamarine" => 0x66cdaa,
+    "mediumblue" => 0x0000cd,
+    "mediumorchid" => 0xba55d3,
+    "mediumpurple" => 0x9370db,
+    "mediumseagreen" => 0x3cb371,
+    "mediumslateblue" => 0x7b68ee,
+    "mediumspringgreen" => 0x00fa9a,
+    "mediumturquoise" => 0x48d1cc,
+    "mediumvioletred" => 0xc71585,
+    "midnightblue" => 0x191970,
+    "mintcream" => 0xf5fffa,
+    "mistyrose" => 0xffe4e1,
+    "moccasin" => 0xffe4b5,
+    "navajowhite" => 0xffdead,
+    "navy" => 0x000080,
+    "oldlace" => 0xfdf5e6,
+    "olive" => 0x808000,
+    "olivedrab" => 0x6b8e23,
+    "orange" => 0xffa500,
+    "orangered" => 0xff4500,
+    "orchid" => 0xda70d6,
+    "palegoldenrod" => 0xeee8aa,
+    "palegreen" => 0x98fb98,
+    "paleturquoise" => 0xafeeee,
+    "palevioletred" => 0xdb7093,
+    "papayawhip" => 0xffefd5,
+    "peachpuff" => 0xffdab9,
+    "peru" => 0xcd853f,
+    "pink" => 0xffc0cb,
+    "plum" => 0xdda0dd,
+    "powderblue" => 0xb0e0e6,
+    "purple" => 0x800080,
+    "red" => 0xff0000,
+    "rosybrown" => 0xbc8f8f,
+    "royalblue" => 0x4169e1,
+    "saddlebrown" => 0x8b4513,
+    "salmon" => 0xfa8072,
+    "sandybrown" => 0xf4a460,
+    "seagreen" => 0x2e8b57,
+    "seashell" => 0xfff5ee,
+    "sienna" => 0xa0522d,
+    "silver" => 0xc0c0c0,
+    "skyblue" => 0x87ceeb,
+    "slateblue" => 0x6a5acd,
+    "slategray" => 0x708090,
+    "slategrey" => 0x708090,
+    "snow" => 0xfffafa,
+    "springgreen" => 0x00ff7f,
+    "steelblue" => 0x4682b4,
+    "tan" => 0xd2b48c,
+    "teal" => 0x008080,
+    "thistle" => 0xd8bfd8,
+    "tomato" => 0xff6347,
+    "turquoise" => 0x40e0d0,
+    "violet" => 0xee82ee,
+    "wheat" => 0xf5deb3,
+    "white" => 0xffffff,
+    "whitesmoke" => 0xf5f5f5,
+    "yellow" => 0xffff00,
+    "yellowgreen" => 0x9acd32,
+};
+
+/// Parse a CSS color: a named color, `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`,
+/// `rgb(r, g, b)`, `rgba(r, g, b, a)`, `hsl(h, s%, l%)` or `hsla(h, s%, l%, a)`
 pub(crate) fn parse_color(color_str: &str) -> Option<Hsla> {
-    match color_str {
-        "red" => Some(gpui::red()),
-        "green" => Some(gpui::green()),
-        "blue" => Some(gpui::blue()),
-        "yellow" => Some(gpui::yellow()),
-        "black" => Some(gpui::black()),
-        "white" => Some(gpui::white()),
-        "gray" | "grey" => Some(gpui::rgb(0x808080).into()),
-        "transparent" => Some(gpui::rgba(0x00000000).into()),
-        _ => {
-            if color_str.starts_with('#') {
-                let hex = &color_str[1..];
-                if hex.len() == 6 {
-                    if let (Ok(r), Ok(g), Ok(b)) = (
-                        u8::from_str_radix(&hex[0..2], 16),
-                        u8::from_str_radix(&hex[2..4], 16),
-                        u8::from_str_radix(&hex[4..6], 16),
-                    ) {
-                        let a: u8 = 0xFF;
-                        let hex_value = ((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8) | (a as u32);
-                        return Some(gpui::rgba(hex_value).into());
-                    }
-                } else if hex.len() == 8 {
-                    if let (Ok(r), Ok(g), Ok(b), Ok(a)) = (
-                        u8::from_str_radix(&hex[0..2], 16),
-                        u8::from_str_radix(&hex[2..4], 16),
-                        u8::from_str_radix(&hex[4..6], 16),
-                        u8::from_str_radix(&hex[6..8], 16),
-                    ) {
-                        let hex_value = ((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8) | (a as u32);
-                        return Some(gpui::rgba(hex_value).into());
-                    }
-                }
-            }
-            None
+    let color_str = color_str.trim();
+    let lower = color_str.to_ascii_lowercase();
+
+    if lower == "transparent" {
+        return Some(gpui::rgba(0x00000000).into());
+    }
+    if let Some(hex) = lower.strip_prefix('#') {
+        return parse_hex_color(hex);
+    }
+    if let Some((name, args)) = lower.strip_suffix(')').and_then(|s| s.split_once('(')) {
+        let args: Vec<&str> = args
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .collect();
+        return match name.trim() {
+            "rgb" | "rgba" => parse_rgb_args(&args),
+            "hsl" | "hsla" => parse_hsl_args(&args),
+            _ => None,
+        };
+    }
+
+    NAMED_COLORS
+        .get(lower.as_str())
+        .map(|rgb| gpui::rgba((rgb << 8) | 0xFF).into())
+}
+
+fn parse_hex_color(hex: &str) -> Option<Hsla> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    // Expand a 4 bit channel to 8 bits: 0xA -> 0xAA
+    let expand = |v: u32| (v << 4) | v;
+    let rgba = match hex.len() {
+        3 => (expand(value >> 8) << 24) | (expand((value >> 4) & 0xF) << 16) | (expand(value & 0xF) << 8) | 0xFF,
+        4 => (expand(value >> 12) << 24) | (expand((value >> 8) & 0xF) << 16) | (expand((value >> 4) & 0xF) << 8) | expand(value & 0xF),
+        6 => (value << 8) | 0xFF,
+        8 => value,
+        _ => return None,
+    };
+    Some(gpui::rgba(rgba).into())
+}
+
+/// Parse an alpha value: `0.5` or `50%`
+fn parse_alpha(s: &str) -> Option<f32> {
+    let alpha = match s.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok()? / 100.0,
+        None => s.parse::<f32>().ok()?,
+    };
+    alpha.is_finite().then(|| alpha.clamp(0.0, 1.0))
+}
+
+fn parse_rgb_args(args: &[&str]) -> Option<Hsla> {
+    if args.len() != 3 && args.len() != 4 {
+        return None;
+    }
+    // A channel is `0..=255` or a percentage
+    let channel = |s: &str| -> Option<f32> {
+        let value = match s.strip_suffix('%') {
+            Some(percent) => percent.parse::<f32>().ok()? / 100.0,
+            None => s.parse::<f32>().ok()? / 255.0,
+        };
+        value.is_finite().then(|| value.clamp(0.0, 1.0))
+    };
+    let a = match args.get(3) {
+        Some(alpha) => parse_alpha(alpha)?,
+        None => 1.0,
+    };
+    Some(
+        Rgba {
+            r: channel(args[0])?,
+            g: channel(args[1])?,
+            b: channel(args[2])?,
+            a,
         }
+        .into(),
+    )
+}
+
+fn parse_hsl_args(args: &[&str]) -> Option<Hsla> {
+    if args.len() != 3 && args.len() != 4 {
+        return None;
+    }
+    let hue = args[0].strip_suffix("deg").unwrap_or(args[0]).parse::<f32>().ok()?;
+    let percent = |s: &str| -> Option<f32> {
+        let value = s.strip_suffix('%')?.parse::<f32>().ok()? / 100.0;
+        value.is_finite().then(|| value.clamp(0.0, 1.0))
+    };
+    if !hue.is_finite() {
+        return None;
     }
-}
\ No newline at end of file
+    let a = match args.get(3) {
+        Some(alpha) => parse_alpha(alpha)?,
+        None => 1.0,
+    };
+    Some(hsla(hue.rem_euclid(360.0) / 360.0, percent(args[1])?, percent(args[2])?, a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn assert_rgba_eq(a: Hsla, b: Hsla, tolerance: f32) {
+        let (a, b) = (Rgba::from(a), Rgba::from(b));
+        for (x, y) in [(a.r, b.r), (a.g, b.g), (a.b, b.b), (a.a, b.a)] {
+            assert!((x - y).abs() <= tolerance, "{:?} != {:?}", a, b);
+        }
+    }
+
+    fn to_hex(color: Hsla) -> String {
+        let rgba = Rgba::from(color);
+        let byte = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        format!("#{:02x}{:02x}{:02x}{:02x}", byte(rgba.r), byte(rgba.g), byte(rgba.b), byte(rgba.a))
+    }
+
+    #[test]
+    fn test_parse_color() {
+        let tolerance = 0.001;
+        assert_eq!(NAMED_COLORS.len(), 147);
+        assert_rgba_eq(parse_color("red").unwrap(), gpui::rgb(0xff0000).into(), tolerance);
+        assert_rgba_eq(parse_color("CornflowerBlue").unwrap(), gpui::rgb(0x6495ed).into(), tolerance);
+        assert_rgba_eq(parse_color("#f00").unwrap(), gpui::rgb(0xff0000).into(), tolerance);
+        assert_rgba_eq(parse_color("#ff000080").unwrap(), gpui::rgba(0xff000080).into(), tolerance);
+        assert_rgba_eq(parse_color("rgb(255, 128, 0)").unwrap(), gpui::rgb(0xff8000).into(), tolerance);
+        assert_rgba_eq(parse_color("rgba(0, 0, 255, 0.5)").unwrap(), gpui::rgba(0x0000ff80).into(), 0.01);
+        assert_rgba_eq(parse_color("rgb(100%, 0%, 0%)").unwrap(), gpui::rgb(0xff0000).into(), tolerance);
+        assert_rgba_eq(parse_color("hsl(120, 100%, 50%)").unwrap(), gpui::rgb(0x00ff00).into(), tolerance);
+        assert_rgba_eq(parse_color("hsla(240deg, 100%, 50%, 0.25)").unwrap(), gpui::rgba(0x0000ff40).into(), 0.01);
+        assert_rgba_eq(parse_color("transparent").unwrap(), gpui::rgba(0x00000000).into(), tolerance);
+
+        assert!(parse_color("notacolor").is_none());
+        assert!(parse_color("#12").is_none());
+        assert!(parse_color("#gggggg").is_none());
+        assert!(parse_color("rgb(1, 2)").is_none());
+        assert!(parse_color("hsl(120, 100, 50)").is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_color_hex_roundtrip(h in 0.0f32..1.0, s in 0.0f32..=1.0, l in 0.0f32..=1.0, a in 0.0f32..=1.0) {
+            let color = hsla(h, s, l, a);
+            let parsed = parse_color(&to_hex(color)).unwrap();
+            // 8 bits per channel in the hex string
+            assert_rgba_eq(parsed, color, 0.5 / 255.0 + 0.0001);
+        }
+    }
+}
@@ -2,13 +2,14 @@ use crate::ast::*;
 use crate::error::*;
 use gpui::*;
 use gpui_component::ActiveTheme;
+use crate::binding::{attach_event_handlers, BindingScope};
 use super::{ElementRenderer, render_child, extract_text_content, muted_text_color};
 
 pub struct BlockquoteElement;
 pub struct QElement;
 
 impl ElementRenderer for BlockquoteElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -20,26 +21,26 @@ impl ElementRenderer for BlockquoteElement {
             .italic()
             .text_color(muted_text_color());
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 blockquote = blockquote.child(child_element);
             }
         }
 
-        Ok(blockquote.into_any_element())
+        Ok(attach_event_handlers(element, scope, blockquote).into_any_element())
     }
 }
 
 impl ElementRenderer for QElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let text_content = extract_text_content(element);
-        Ok(div()
+        let q = div()
             .italic()
             .text_color(muted_text_color())
-            .child(format!("\"{}\"", text_content))
-            .into_any_element())
+            .child(format!("\"{}\"", text_content));
+        Ok(attach_event_handlers(element, scope, q).into_any_element())
     }
 }
\ No newline at end of file
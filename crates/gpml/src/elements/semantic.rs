@@ -2,6 +2,7 @@ use crate::ast::*;
 use crate::error::*;
 use gpui::*;
 use gpui_component::ActiveTheme;
+use crate::binding::{attach_event_handlers, BindingScope};
 use super::{ElementRenderer, render_child, apply_common_styles};
 
 pub struct ArticleElement;
@@ -13,7 +14,7 @@ pub struct FooterElement;
 pub struct MainElement;
 
 impl ElementRenderer for ArticleElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -24,18 +25,18 @@ impl ElementRenderer for ArticleElement {
 
         article = apply_common_styles(article, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 article = article.child(child_element);
             }
         }
 
-        Ok(article.into_any_element())
+        Ok(attach_event_handlers(element, scope, article).into_any_element())
     }
 }
 
 impl ElementRenderer for SectionElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -45,18 +46,18 @@ impl ElementRenderer for SectionElement {
 
         section = apply_common_styles(section, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 section = section.child(child_element);
             }
         }
 
-        Ok(section.into_any_element())
+        Ok(attach_event_handlers(element, scope, section).into_any_element())
     }
 }
 
 impl ElementRenderer for AsideElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -69,18 +70,18 @@ impl ElementRenderer for AsideElement {
 
         aside = apply_common_styles(aside, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 aside = aside.child(child_element);
             }
         }
 
-        Ok(aside.into_any_element())
+        Ok(attach_event_handlers(element, scope, aside).into_any_element())
     }
 }
 
 impl ElementRenderer for NavElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -91,18 +92,18 @@ impl ElementRenderer for NavElement {
 
         nav = apply_common_styles(nav, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 nav = nav.child(child_element);
             }
         }
 
-        Ok(nav.into_any_element())
+        Ok(attach_event_handlers(element, scope, nav).into_any_element())
     }
 }
 
 impl ElementRenderer for HeaderElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -115,18 +116,18 @@ impl ElementRenderer for HeaderElement {
 
         header = apply_common_styles(header, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 header = header.child(child_element);
             }
         }
 
-        Ok(header.into_any_element())
+        Ok(attach_event_handlers(element, scope, header).into_any_element())
     }
 }
 
 impl ElementRenderer for FooterElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -140,18 +141,18 @@ impl ElementRenderer for FooterElement {
 
         footer = apply_common_styles(footer, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 footer = footer.child(child_element);
             }
         }
 
-        Ok(footer.into_any_element())
+        Ok(attach_event_handlers(element, scope, footer).into_any_element())
     }
 }
 
 impl ElementRenderer for MainElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -162,12 +163,12 @@ impl ElementRenderer for MainElement {
 
         main = apply_common_styles(main, element);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 main = main.child(child_element);
             }
         }
 
-        Ok(main.into_any_element())
+        Ok(attach_event_handlers(element, scope, main).into_any_element())
     }
 }
\ No newline at end of file
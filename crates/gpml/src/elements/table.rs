@@ -2,6 +2,7 @@ use crate::ast::*;
 use crate::error::*;
 use gpui::*;
 use gpui_component::{v_flex, h_flex, ActiveTheme};
+use crate::binding::{attach_event_handlers, BindingScope};
 use super::{ElementRenderer, render_child, extract_text_content, default_text_color};
 
 pub struct TableElement;
@@ -14,7 +15,7 @@ pub struct ThElement;
 pub struct CaptionElement;
 
 impl ElementRenderer for TableElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -24,53 +25,53 @@ impl ElementRenderer for TableElement {
             .rounded_md()
             .overflow_hidden();
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 table = table.child(child_element);
             }
         }
 
-        Ok(table.into_any_element())
+        Ok(attach_event_handlers(element, scope, table).into_any_element())
     }
 }
 
 impl ElementRenderer for TheadElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let mut thead = v_flex()
             .bg(cx.theme().secondary.opacity(0.1));
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 thead = thead.child(child_element);
             }
         }
 
-        Ok(thead.into_any_element())
+        Ok(attach_event_handlers(element, scope, thead).into_any_element())
     }
 }
 
 impl ElementRenderer for TbodyElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let mut tbody = v_flex();
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 tbody = tbody.child(child_element);
             }
         }
 
-        Ok(tbody.into_any_element())
+        Ok(attach_event_handlers(element, scope, tbody).into_any_element())
     }
 }
 
 impl ElementRenderer for TfootElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -79,18 +80,18 @@ impl ElementRenderer for TfootElement {
             .border_t_1()
             .border_color(cx.theme().border);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 tfoot = tfoot.child(child_element);
             }
         }
 
-        Ok(tfoot.into_any_element())
+        Ok(attach_event_handlers(element, scope, tfoot).into_any_element())
     }
 }
 
 impl ElementRenderer for TrElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -98,18 +99,18 @@ impl ElementRenderer for TrElement {
             .border_b_1()
             .border_color(cx.theme().border);
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 tr = tr.child(child_element);
             }
         }
 
-        Ok(tr.into_any_element())
+        Ok(attach_event_handlers(element, scope, tr).into_any_element())
     }
 }
 
 impl ElementRenderer for TdElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -119,18 +120,18 @@ impl ElementRenderer for TdElement {
             .border_color(cx.theme().border)
             .flex_1();
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 td = td.child(child_element);
             }
         }
 
-        Ok(td.into_any_element())
+        Ok(attach_event_handlers(element, scope, td).into_any_element())
     }
 }
 
 impl ElementRenderer for ThElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -141,28 +142,28 @@ impl ElementRenderer for ThElement {
             .font_weight(FontWeight::BOLD)
             .flex_1();
 
-        for child in &element.children {
-            if let Ok(child_element) = render_child(child, cx) {
+        for (ix, child) in element.children.iter().enumerate() {
+            if let Ok(child_element) = render_child(child, &scope.child(ix), cx) {
                 th = th.child(child_element);
             }
         }
 
-        Ok(th.into_any_element())
+        Ok(attach_event_handlers(element, scope, th).into_any_element())
     }
 }
 
 impl ElementRenderer for CaptionElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let text_content = extract_text_content(element);
-        Ok(div()
+        let caption = div()
             .p_2()
             .text_center()
             .font_weight(FontWeight::BOLD)
             .text_color(default_text_color())
-            .child(text_content)
-            .into_any_element())
+            .child(text_content);
+        Ok(attach_event_handlers(element, scope, caption).into_any_element())
     }
 }
\ No newline at end of file
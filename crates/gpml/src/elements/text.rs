@@ -3,6 +3,7 @@ use crate::error::*;
 use gpui::*;
 use gpui_component::ActiveTheme;
 use gpui_component::label;
+use crate::binding::{attach_component_event_handlers, attach_event_handlers, BindingScope};
 use super::{ElementRenderer, extract_text_content, default_text_color, apply_text_styles, parse_color};
 
 pub struct HeadingElement;
@@ -22,7 +23,7 @@ pub enum HeadingLevel {
 }
 
 impl HeadingElement {
-    pub fn render<T>(element: &GPMLElement, cx: &mut Context<T>, level: HeadingLevel) -> GPMLResult<AnyElement>
+    pub fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>, level: HeadingLevel) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -45,12 +46,12 @@ impl HeadingElement {
 
         heading = apply_text_styles(heading, element, cx);
 
-        Ok(heading.into_any_element())
+        Ok(attach_event_handlers(element, scope, heading).into_any_element())
     }
 }
 
 impl ElementRenderer for ParagraphElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -70,12 +71,12 @@ impl ElementRenderer for ParagraphElement {
             }
         }
 
-        Ok(p.into_any_element())
+        Ok(attach_event_handlers(element, scope, p).into_any_element())
     }
 }
 
 impl ElementRenderer for TextElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -98,22 +99,22 @@ impl ElementRenderer for TextElement {
             }
         }
 
-        Ok(text_el.into_any_element())
+        Ok(attach_event_handlers(element, scope, text_el).into_any_element())
     }
 }
 
 impl ElementRenderer for LabelElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         let text_content = extract_text_content(element);
-        Ok(label::Label::new(text_content).into_any_element())
+        Ok(attach_component_event_handlers(element, scope, label::Label::new(text_content)))
     }
 }
 
 impl ElementRenderer for SpanElement {
-    fn render<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
@@ -123,6 +124,6 @@ impl ElementRenderer for SpanElement {
             .child(text_content);
 
         span = apply_text_styles(span, element, cx);
-        Ok(span.into_any_element())
+        Ok(attach_event_handlers(element, scope, span).into_any_element())
     }
 }
\ No newline at end of file
@@ -0,0 +1,7 @@
+//! Render engines for the [`gpui_component::viewport::Viewport`]
+
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
+
+#[cfg(feature = "wgpu")]
+pub use self::wgpu::WgpuRenderEngine;
@@ -0,0 +1,380 @@
+//! A [`RenderEngine`] that renders with wgpu and reads the frame back to the CPU.
+//!
+//! # Synchronization
+//!
+//! The [`Viewport`](gpui_component::viewport::Viewport) calls [`RenderEngine::render`] on its
+//! render thread and presents the framebuffer right after it returns, so `render()` blocks
+//! until the GPU work is done: the frame is drawn into an offscreen texture, copied into a
+//! mappable buffer, and the buffer is mapped with `Device::poll(Maintain::Wait)` before the
+//! rows are copied into the [`Framebuffer`]. The framebuffer always contains a complete frame
+//! when `render()` returns `Ok`.
+
+use std::borrow::Cow;
+use std::sync::mpsc;
+
+use gpui_component::viewport::{Framebuffer, FramebufferFormat, RenderEngine, RenderError};
+
+/// The format of the offscreen texture, matching [`FramebufferFormat::Rgba8`]
+const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const BYTES_PER_PIXEL: u32 = 4;
+
+/// Builds the render pipeline once the device is created in [`RenderEngine::initialize`]
+type PipelineFactory = Box<dyn Fn(&wgpu::Device, wgpu::TextureFormat) -> wgpu::RenderPipeline + Send + Sync>;
+
+const TRIANGLE_SHADER: &str = r#"
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var positions = array<vec2<f32>, 3>(
+        vec2<f32>(0.0, 0.5),
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, -0.5),
+    );
+    var colors = array<vec3<f32>, 3>(
+        vec3<f32>(1.0, 0.0, 0.0),
+        vec3<f32>(0.0, 1.0, 0.0),
+        vec3<f32>(0.0, 0.0, 1.0),
+    );
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(positions[index], 0.0, 1.0);
+    out.color = colors[index];
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
+"#;
+
+/// Build the demo pipeline that draws a colored triangle with 3 vertices and no vertex buffers
+pub fn triangle_pipeline(device: &wgpu::Device, format: wgpu::TextureFormat) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("gpml-triangle-shader"),
+        source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(TRIANGLE_SHADER)),
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("gpml-triangle-pipeline"),
+        layout: None,
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: "vs_main",
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: "fs_main",
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+    })
+}
+
+/// GPU resources created in [`RenderEngine::initialize`]
+struct GpuState {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: Option<wgpu::RenderPipeline>,
+    target: Option<RenderTarget>,
+}
+
+/// The offscreen texture and the readback buffer for the current size
+struct RenderTarget {
+    width: u32,
+    height: u32,
+    /// Bytes per row of the readback buffer, aligned to `COPY_BYTES_PER_ROW_ALIGNMENT`
+    padded_bytes_per_row: u32,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    readback: wgpu::Buffer,
+}
+
+impl RenderTarget {
+    fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("gpml-wgpu-target"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TEXTURE_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let padded_bytes_per_row = padded_bytes_per_row(width);
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("gpml-wgpu-readback"),
+            size: (padded_bytes_per_row * height) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            width,
+            height,
+            padded_bytes_per_row,
+            texture,
+            view,
+            readback,
+        }
+    }
+}
+
+/// Round the row size up to the alignment required by `copy_texture_to_buffer`
+fn padded_bytes_per_row(width: u32) -> u32 {
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    (width * BYTES_PER_PIXEL).div_ceil(align) * align
+}
+
+/// Render engine drawing a user-provided wgpu pipeline into the viewport framebuffer
+pub struct WgpuRenderEngine {
+    pipeline_factory: Option<PipelineFactory>,
+    vertex_count: u32,
+    clear_color: wgpu::Color,
+    gpu: Option<GpuState>,
+    notify_callback: Option<Box<dyn Fn() + Send + Sync>>,
+}
+
+impl WgpuRenderEngine {
+    /// Create an engine that only clears the frame, use [`Self::with_pipeline`] to draw
+    pub fn new() -> Self {
+        Self {
+            pipeline_factory: None,
+            vertex_count: 3,
+            clear_color: wgpu::Color::BLACK,
+            gpu: None,
+            notify_callback: None,
+        }
+    }
+
+    /// Create an engine drawing the demo triangle, see [`triangle_pipeline`]
+    pub fn triangle() -> Self {
+        Self::new().with_pipeline(triangle_pipeline)
+    }
+
+    /// Set the pipeline to draw each frame.
+    ///
+    /// The device only exists after [`RenderEngine::initialize`], so the pipeline is built
+    /// by `factory` with the device and the texture format of the render target.
+    pub fn with_pipeline(
+        mut self,
+        factory: impl Fn(&wgpu::Device, wgpu::TextureFormat) -> wgpu::RenderPipeline + Send + Sync + 'static,
+    ) -> Self {
+        self.pipeline_factory = Some(Box::new(factory));
+        self
+    }
+
+    /// Set the number of vertices drawn with the pipeline, default 3
+    pub fn with_vertex_count(mut self, vertex_count: u32) -> Self {
+        self.vertex_count = vertex_count;
+        self
+    }
+
+    /// Set the color the frame is cleared to before drawing, default black
+    pub fn with_clear_color(mut self, clear_color: wgpu::Color) -> Self {
+        self.clear_color = clear_color;
+        self
+    }
+
+    fn create_gpu_state(&self) -> Result<GpuState, RenderError> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        }))
+        .ok_or_else(|| RenderError::InitializationFailed("No wgpu adapter available".to_string()))?;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("gpml-wgpu-device"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::downlevel_defaults(),
+            },
+            None,
+        ))
+        .map_err(|e| RenderError::InitializationFailed(format!("Failed to create wgpu device: {}", e)))?;
+
+        let pipeline = self
+            .pipeline_factory
+            .as_ref()
+            .map(|factory| factory(&device, TEXTURE_FORMAT));
+
+        Ok(GpuState {
+            device,
+            queue,
+            pipeline,
+            target: None,
+        })
+    }
+}
+
+impl Default for WgpuRenderEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderEngine for WgpuRenderEngine {
+    fn render(&mut self, framebuffer: &mut Framebuffer) -> Result<(), RenderError> {
+        if framebuffer.format != FramebufferFormat::Rgba8 {
+            return Err(RenderError::RenderFailed(format!(
+                "Unsupported framebuffer format: {:?}",
+                framebuffer.format
+            )));
+        }
+        if framebuffer.width == 0 || framebuffer.height == 0 {
+            return Ok(());
+        }
+
+        let gpu = self
+            .gpu
+            .as_mut()
+            .ok_or_else(|| RenderError::RenderFailed("wgpu engine is not initialized".to_string()))?;
+
+        // Recreate the render target when the viewport is resized
+        if gpu
+            .target
+            .as_ref()
+            .map_or(true, |target| target.width != framebuffer.width || target.height != framebuffer.height)
+        {
+            gpu.target = Some(RenderTarget::new(&gpu.device, framebuffer.width, framebuffer.height));
+        }
+        let Some(target) = gpu.target.as_ref() else {
+            return Ok(());
+        };
+
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("gpml-wgpu-frame"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("gpml-wgpu-pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &target.view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(self.clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            if let Some(pipeline) = &gpu.pipeline {
+                pass.set_pipeline(pipeline);
+                pass.draw(0..self.vertex_count, 0..1);
+            }
+        }
+
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &target.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &target.readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(target.padded_bytes_per_row),
+                    rows_per_image: Some(target.height),
+                },
+            },
+            wgpu::Extent3d {
+                width: target.width,
+                height: target.height,
+                depth_or_array_layers: 1,
+            },
+        );
+        gpu.queue.submit(Some(encoder.finish()));
+
+        // Block until the copy is done and the buffer is mapped, see the module docs
+        let slice = target.readback.slice(..);
+        let (tx, rx) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        gpu.device.poll(wgpu::Maintain::Wait);
+        rx.recv()
+            .map_err(|e| RenderError::RenderFailed(format!("Readback was dropped: {}", e)))?
+            .map_err(|e| RenderError::RenderFailed(format!("Failed to map readback buffer: {}", e)))?;
+
+        {
+            let mapped = slice.get_mapped_range();
+            let row_len = (target.width * BYTES_PER_PIXEL) as usize;
+            for (row, src) in mapped
+                .chunks_exact(target.padded_bytes_per_row as usize)
+                .take(target.height as usize)
+                .enumerate()
+            {
+                let offset = row * framebuffer.pitch as usize;
+                framebuffer.buffer[offset..offset + row_len].copy_from_slice(&src[..row_len]);
+            }
+        }
+        target.readback.unmap();
+
+        framebuffer.mark_dirty(None);
+        if let Some(callback) = &self.notify_callback {
+            callback();
+        }
+
+        Ok(())
+    }
+
+    fn initialize(&mut self) -> Result<(), RenderError> {
+        self.gpu = Some(self.create_gpu_state()?);
+        Ok(())
+    }
+
+    fn cleanup(&mut self) {
+        self.gpu = None;
+    }
+
+    fn on_resize(&mut self, _width: u32, _height: u32) {
+        // The render target is recreated lazily in `render` with the framebuffer size
+        if let Some(gpu) = self.gpu.as_mut() {
+            gpu.target = None;
+        }
+    }
+
+    fn set_notify_callback(&mut self, callback: Box<dyn Fn() + Send + Sync>) {
+        self.notify_callback = Some(callback);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::padded_bytes_per_row;
+
+    #[test]
+    fn test_padded_bytes_per_row() {
+        assert_eq!(padded_bytes_per_row(1), 256);
+        assert_eq!(padded_bytes_per_row(64), 256);
+        assert_eq!(padded_bytes_per_row(65), 512);
+        assert_eq!(padded_bytes_per_row(1920), 7680);
+    }
+}
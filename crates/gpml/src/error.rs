@@ -1,43 +1,190 @@
+use crate::ast::ParamType;
+use rust_i18n::t;
+use std::fmt;
+use std::ops::Range;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum GPMLError {
-    #[error("Parse error: {message} at line {line}, column {column}")]
     ParseError {
         message: String,
         line: usize,
         column: usize,
+        /// Source span and snippet, when the error position is known
+        location: Option<SourceLocation>,
     },
 
-    #[error("File not found: {path}")]
     FileNotFound { path: String },
 
-    #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
-    #[error("Component '{name}' not found")]
     ComponentNotFound { name: String },
 
-    #[error("Import error: {message}")]
     ImportError { message: String },
 
-    #[error("Render error: {message}")]
     RenderError { message: String },
 
-    #[error("Invalid attribute value: {message}")]
     InvalidAttributeValue { message: String },
 
-    #[error("Parameter mismatch: expected {expected}, got {actual}")]
     ParameterMismatch { expected: usize, actual: usize },
 
-    #[error("Circular dependency detected: {path}")]
     CircularDependency { path: String },
 
-    #[error("Syntax error: {message}")]
     SyntaxError { message: String },
 
-    #[error("Type error: {message}")]
     TypeError { message: String },
+
+    TypeMismatch { param: String, expected: ParamType, got: String },
+
+    ScriptError { message: String },
+}
+
+impl GPMLError {
+    /// Build a parse error pointing at `span` (byte offsets) inside `source`
+    pub fn parse_at(message: impl Into<String>, source: &str, span: Range<usize>) -> Self {
+        let index = LineIndex::new(source);
+        let (line, column) = index.line_col(source, span.start);
+        let source_line = index.line_text(source, line).to_string();
+
+        GPMLError::ParseError {
+            message: message.into(),
+            line,
+            column,
+            location: Some(SourceLocation { span, source_line }),
+        }
+    }
+}
+
+/// Messages are translated to the current `rust_i18n` locale, see `locales/`.
+impl fmt::Display for GPMLError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GPMLError::ParseError { message, line, column, location } => {
+                write!(f, "{}", t!("gpml.error.parse_error", message = message, line = line, column = column))?;
+                if let Some(location) = location {
+                    write!(f, "\n{}", location.render_snippet(*line, *column))?;
+                }
+                Ok(())
+            }
+            GPMLError::FileNotFound { path } => write!(f, "{}", t!("gpml.error.file_not_found", path = path)),
+            GPMLError::IoError(e) => write!(f, "{}", t!("gpml.error.io_error", error = e)),
+            GPMLError::ComponentNotFound { name } => write!(f, "{}", t!("gpml.error.component_not_found", name = name)),
+            GPMLError::ImportError { message } => write!(f, "{}", t!("gpml.error.import_error", message = message)),
+            GPMLError::RenderError { message } => write!(f, "{}", t!("gpml.error.render_error", message = message)),
+            GPMLError::InvalidAttributeValue { message } => {
+                write!(f, "{}", t!("gpml.error.invalid_attribute_value", message = message))
+            }
+            GPMLError::ParameterMismatch { expected, actual } => {
+                write!(f, "{}", t!("gpml.error.parameter_mismatch", expected = expected, actual = actual))
+            }
+            GPMLError::CircularDependency { path } => {
+                write!(f, "{}", t!("gpml.error.circular_dependency", path = path))
+            }
+            GPMLError::SyntaxError { message } => write!(f, "{}", t!("gpml.error.syntax_error", message = message)),
+            GPMLError::TypeError { message } => write!(f, "{}", t!("gpml.error.type_error", message = message)),
+            GPMLError::TypeMismatch { param, expected, got } => {
+                write!(f, "{}", t!("gpml.error.type_mismatch", param = param, expected = expected, got = got))
+            }
+            GPMLError::ScriptError { message } => write!(f, "{}", t!("gpml.error.script_error", message = message)),
+        }
+    }
 }
 
 pub type GPMLResult<T> = Result<T, GPMLError>;
+
+/// Position of a parse error in the source text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// Byte range of the offending input
+    pub span: Range<usize>,
+    /// Full text of the line containing the start of the span
+    pub source_line: String,
+}
+
+impl SourceLocation {
+    /// Render the source line with a caret under the error, in the style of rustc
+    pub fn render_snippet(&self, line: usize, column: usize) -> String {
+        let gutter = line.to_string().len();
+        let line_chars = self.source_line.chars().count();
+        let caret_len = self
+            .span
+            .len()
+            .min(line_chars.saturating_sub(column.saturating_sub(1)))
+            .max(1);
+
+        format!(
+            "{pad} --> {line}:{column}\n{pad} |\n{line} | {text}\n{pad} | {offset}{carets}",
+            pad = " ".repeat(gutter),
+            text = self.source_line,
+            offset = " ".repeat(column.saturating_sub(1)),
+            carets = "^".repeat(caret_len),
+        )
+    }
+}
+
+/// A malformed part of the source skipped by [`crate::GPMLParser::parse_document_lenient`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Byte offset of the malformed input
+    pub byte_offset: usize,
+    pub message: String,
+}
+
+/// Pre-computed newline index for converting byte offsets to 1-based (line, column) pairs
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+        Self { line_starts }
+    }
+
+    /// Convert a byte offset to a 1-based line and a 1-based column counted in characters
+    pub fn line_col(&self, source: &str, offset: usize) -> (usize, usize) {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next) => next - 1,
+        };
+        let start = self.line_starts[line];
+        let column = source
+            .get(start..offset)
+            .map(|prefix| prefix.chars().count())
+            .unwrap_or(offset.saturating_sub(start));
+        (line + 1, column + 1)
+    }
+
+    /// Get the text of a 1-based line, without the trailing newline
+    pub fn line_text<'a>(&self, source: &'a str, line: usize) -> &'a str {
+        let Some(&start) = self.line_starts.get(line.saturating_sub(1)) else {
+            return "";
+        };
+        let end = self.line_starts.get(line).map(|&end| end - 1).unwrap_or(source.len());
+        source.get(start..end).unwrap_or("").trim_end_matches('\r')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_localized_messages() {
+        let err = GPMLError::ComponentNotFound { name: "Card".to_string() };
+        assert_eq!(err.to_string(), "Component 'Card' not found");
+        assert_eq!(
+            t!("gpml.error.component_not_found", locale = "de", name = "Card"),
+            "Komponente 'Card' nicht gefunden"
+        );
+
+        let err = GPMLError::ParameterMismatch { expected: 2, actual: 3 };
+        assert_eq!(err.to_string(), "Parameter mismatch: expected 2, got 3");
+        assert_eq!(
+            t!("gpml.error.parameter_mismatch", locale = "de", expected = 2, actual = 3),
+            "Falsche Parameteranzahl: 2 erwartet, 3 erhalten"
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::error::*;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher, Event, EventKind};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::time::{Duration, SystemTime};
@@ -10,6 +10,8 @@ pub struct HotReloadManager {
     watcher: Option<RecommendedWatcher>,
     receiver: Option<Receiver<notify::Result<Event>>>,
     watched_files: HashSet<PathBuf>,
+    /// Recursively watched directories and the file extensions accepted in them
+    watched_directories: HashMap<PathBuf, Vec<String>>,
     last_change_times: std::collections::HashMap<PathBuf, SystemTime>,
     debounce_duration: Duration,
 }
@@ -20,6 +22,7 @@ impl HotReloadManager {
             watcher: None,
             receiver: None,
             watched_files: HashSet::new(),
+            watched_directories: HashMap::new(),
             last_change_times: std::collections::HashMap::new(),
             debounce_duration: Duration::from_millis(100),
         }
@@ -74,6 +77,54 @@ impl HotReloadManager {
         Ok(())
     }
 
+    /// Recursively watch a directory for changes to files with the given extensions
+    ///
+    /// The `extensions` are matched without the leading dot (e.g. `"gpml"`), an empty
+    /// list accepts any file. Changes are reported by `check_for_changes` with the
+    /// absolute path of the changed file.
+    pub fn watch_directory(&mut self, path: &Path, extensions: &[&str]) -> GPMLResult<()> {
+        tracing::info!("HotReloadManager: Watching directory {:?} for {:?}", path, extensions);
+
+        if !path.is_dir() {
+            tracing::error!("HotReloadManager: Not a directory: {:?}", path);
+            return Err(GPMLError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            std::env::current_dir()?.join(path)
+        };
+
+        if self.watcher.is_none() {
+            let (sender, receiver) = mpsc::channel();
+            let watcher = notify::recommended_watcher(sender)
+                .map_err(|e| GPMLError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("Failed to create file watcher: {}", e)
+                )))?;
+            self.watcher = Some(watcher);
+            self.receiver = Some(receiver);
+        }
+
+        if let Some(ref mut watcher) = self.watcher {
+            watcher.watch(&path, RecursiveMode::Recursive)
+                .map_err(|e| GPMLError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("Failed to watch path: {}", e)
+                )))?;
+        }
+
+        let extensions = extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.').to_ascii_lowercase())
+            .collect();
+        self.watched_directories.insert(path, extensions);
+        Ok(())
+    }
+
     /// Add a specific file to the watch list
     pub fn add_watched_file(&mut self, path: impl AsRef<Path>) {
         let path = path.as_ref();
@@ -148,6 +199,10 @@ impl HotReloadManager {
     }
 
     fn is_watched_file(&self, path: &Path) -> bool {
+        if self.is_in_watched_directory(path) {
+            return true;
+        }
+
         // Check if this is a GPML file
         let is_gpml = path.extension().and_then(|s| s.to_str()) == Some("gpml");
         
@@ -174,6 +229,19 @@ impl HotReloadManager {
         result
     }
 
+    fn is_in_watched_directory(&self, path: &Path) -> bool {
+        let extension = path
+            .extension()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_ascii_lowercase());
+
+        self.watched_directories.iter().any(|(dir, extensions)| {
+            path.starts_with(dir)
+                && (extensions.is_empty()
+                    || extension.as_ref().map_or(false, |ext| extensions.contains(ext)))
+        })
+    }
+
     fn should_process_change(&self, path: &Path) -> bool {
         if let Some(last_change) = self.last_change_times.get(path) {
             if let Ok(elapsed) = SystemTime::now().duration_since(*last_change) {
@@ -191,6 +259,7 @@ impl HotReloadManager {
         self.watcher = None;
         self.receiver = None;
         self.watched_files.clear();
+        self.watched_directories.clear();
         self.last_change_times.clear();
     }
 
@@ -207,7 +276,8 @@ impl HotReloadManager {
 
     /// Check if currently watching any files
     pub fn is_watching(&self) -> bool {
-        self.watcher.is_some() && !self.watched_files.is_empty()
+        self.watcher.is_some()
+            && (!self.watched_files.is_empty() || !self.watched_directories.is_empty())
     }
 }
 
@@ -300,3 +370,26 @@ impl Default for AsyncHotReloadManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watched_directory_extension_filter() {
+        let mut manager = HotReloadManager::new();
+        manager
+            .watched_directories
+            .insert(PathBuf::from("/project/ui"), vec!["gpml".to_string()]);
+
+        assert!(manager.is_watched_file(Path::new("/project/ui/main.gpml")));
+        assert!(manager.is_watched_file(Path::new("/project/ui/components/Card.GPML")));
+        assert!(!manager.is_watched_file(Path::new("/project/ui/notes.txt")));
+        assert!(!manager.is_watched_file(Path::new("/project/other/main.gpml")));
+
+        manager
+            .watched_directories
+            .insert(PathBuf::from("/project/assets"), vec![]);
+        assert!(manager.is_watched_file(Path::new("/project/assets/logo.png")));
+    }
+}
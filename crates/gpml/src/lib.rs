@@ -5,9 +5,19 @@ pub mod error;
 pub mod parser;
 pub mod renderer;
 pub mod style;
+pub mod animation;
+pub mod script;
 pub mod hot_reload;
 pub mod canvas;
 pub mod bundled_assets;
+pub mod serializer;
+pub mod binding;
+pub mod router;
+pub mod portal;
+pub mod engines;
+
+// Error messages follow the locale set with `gpui_component::set_locale`
+rust_i18n::i18n!("locales", fallback = "en");
 
 // Re-export main types for convenience
 pub use ast::*;
@@ -16,9 +26,15 @@ pub use error::*;
 pub use parser::*;
 pub use renderer::*;
 pub use style::*;
+pub use animation::*;
+pub use script::*;
 pub use hot_reload::*;
 pub use canvas::*;
 pub use bundled_assets::*;
+pub use serializer::*;
+pub use binding::*;
+pub use router::*;
+pub use portal::*;
 
 // Re-export for backward compatibility
 use gpui::*;
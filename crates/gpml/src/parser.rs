@@ -1,10 +1,12 @@
 use crate::ast::*;
+use crate::error::*;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_until, take_while1},
     character::complete::{alpha1, alphanumeric1, char, multispace0, space0, space1},
     combinator::opt,
     multi::{many0, separated_list0},
+    sequence::delimited,
     IResult, Parser,
 };
 use quick_xml::events::{Event, BytesStart};
@@ -18,6 +20,9 @@ impl GPMLParser {
     /// Parse a complete GPML document
     pub fn parse_document(input: &str) -> IResult<&str, GPMLNode> {
         let (input, _) = multispace0.parse(input)?;
+        let (input, extends) = opt(
+            (parse_extends, multispace0).map(|(path, _)| path)
+        ).parse(input)?;
         let (input, imports) = many0(
             (parse_import, multispace0).map(|(import, _)| import)
         ).parse(input)?;
@@ -32,15 +37,52 @@ impl GPMLParser {
         let (input, _) = multispace0.parse(input)?;
 
         Ok((input, GPMLNode::Document {
+            extends,
             imports,
             components,
             root,
         }))
     }
 
+    /// Parse a document that may contain malformed markup, e.g. while it is being edited.
+    ///
+    /// On a parse error the bytes until the next `<` (or the end of the input) are skipped and
+    /// recorded as a warning, so the valid elements around them can still be rendered. Unclosed
+    /// elements are closed at the end of the input.
+    pub fn parse_document_lenient(input: &str) -> (GPMLNode, Vec<ParseWarning>) {
+        let mut parser = LenientParser { source: input, warnings: Vec::new() };
+        let document = parser.document(input);
+        (document, parser.warnings)
+    }
+
+    /// Like [`GPMLParser::parse_file`], but recovers from malformed markup with
+    /// [`GPMLParser::parse_document_lenient`]. Warning offsets point into `content`.
+    pub fn parse_file_lenient(content: &str) -> (GPMLNode, Vec<ParseWarning>) {
+        let (cleaned, removals) = remove_html_comments(content);
+        let (document, mut warnings) = Self::parse_document_lenient(&cleaned);
+        for warning in &mut warnings {
+            warning.byte_offset = original_offset(&removals, warning.byte_offset);
+        }
+        (document, warnings)
+    }
+
     /// Parse XML elements using quick-xml for better performance and correctness
-    pub fn parse_xml_element(xml_content: &str) -> Result<Element, String> {
+    ///
+    /// Errors point at the reader position within `xml_content`.
+    pub fn parse_xml_element(xml_content: &str) -> GPMLResult<Element> {
         let mut reader = Reader::from_str(xml_content);
+        Self::parse_xml_events(&mut reader).map_err(|message| {
+            let position = (reader.buffer_position() as usize).min(xml_content.len());
+            // Point at the start of the tag the reader stopped in
+            let start = xml_content
+                .get(..position)
+                .and_then(|before| before.rfind('<'))
+                .unwrap_or(position);
+            GPMLError::parse_at(message, xml_content, start..position.max(start + 1))
+        })
+    }
+
+    fn parse_xml_events(reader: &mut Reader<&[u8]>) -> Result<Element, String> {
         reader.config_mut().trim_text_start = true;
         reader.config_mut().trim_text_end = true;
 
@@ -86,13 +128,7 @@ impl GPMLParser {
                         .trim();
                     if !text_str.is_empty() {
                         if let Some(ref mut element) = current_element {
-                            // Check if this is an expression
-                            if text_str.starts_with("${") && text_str.ends_with("}") {
-                                let expr = &text_str[2..text_str.len()-1];
-                                element.children.push(GPMLNode::Expression(expr.to_string()));
-                            } else {
-                                element.children.push(GPMLNode::Text(text_str.to_string()));
-                            }
+                            element.children.extend(split_text_expressions(text_str));
                         }
                     }
                 }
@@ -113,7 +149,8 @@ impl GPMLParser {
 
         let mut attributes = HashMap::new();
 
-        for attr in e.attributes() {
+        // HTML-style attributes so that valueless flags like `gpml:else` are accepted
+        for attr in e.html_attributes() {
             let attr = attr.map_err(|e| format!("Attribute parse error: {}", e))?;
             let key = std::str::from_utf8(attr.key.as_ref())
                 .map_err(|e| format!("Invalid attribute key: {}", e))?
@@ -127,21 +164,45 @@ impl GPMLParser {
             attributes.insert(key, value);
         }
 
+        let conditions = extract_condition(&mut attributes);
+        let (namespace, tag_name) = split_namespace(tag_name);
+
         Ok(Element {
             tag: tag_name,
             attributes,
             children: Vec::new(),
             self_closing: false,
+            conditions,
+            namespace,
         })
     }
 
-    fn parse_attribute_value_str(value_str: &str) -> AttributeValue {
+    pub(crate) fn parse_attribute_value_str(value_str: &str) -> AttributeValue {
         // Check if it's an expression
         if value_str.starts_with("${") && value_str.ends_with("}") {
             let expr = &value_str[2..value_str.len()-1];
             return AttributeValue::Expression(expr.to_string());
         }
 
+        // Check if it's an array literal
+        let trimmed = value_str.trim();
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            let items = split_array_items(&trimmed[1..trimmed.len() - 1])
+                .into_iter()
+                .map(|item| {
+                    let quoted = item.len() >= 2
+                        && ((item.starts_with('"') && item.ends_with('"'))
+                            || (item.starts_with('\'') && item.ends_with('\'')));
+                    if quoted {
+                        AttributeValue::Literal(item[1..item.len() - 1].to_string())
+                    } else {
+                        Self::parse_attribute_value_str(item)
+                    }
+                })
+                .collect();
+            return AttributeValue::Array(items);
+        }
+
         // Try to parse as number
         if let Ok(num) = value_str.parse::<f64>() {
             return AttributeValue::Number(num);
@@ -156,48 +217,280 @@ impl GPMLParser {
     }
     
     /// Parse a GPML file from string content
-    pub fn parse_file(content: &str) -> Result<GPMLNode, String> {
-        // Remove HTML-style comments (<!-- ... -->) before parsing so comments
-        // never become text nodes or affect spacing in the rendered output.
-        fn remove_html_comments(s: &str) -> String {
-            let mut out = String::new();
-            let mut start = 0usize;
-            let len = s.len();
-            while start < len {
-                if let Some(idx) = s[start..].find("<!--") {
-                    out.push_str(&s[start..start + idx]);
-                    // find closing --> after the comment start
-                    if let Some(end_idx) = s[start + idx + 4..].find("-->") {
-                        // advance start past the closing "-->"
-                        start = start + idx + 4 + end_idx + 3;
-                        continue;
-                    } else {
-                        // unmatched comment start - stop and append rest
-                        break;
-                    }
-                } else {
-                    out.push_str(&s[start..]);
-                    break;
-                }
-            }
-            out
-        }
+    ///
+    /// Parse errors carry the line, column and a source snippet of the original content.
+    pub fn parse_file(content: &str) -> GPMLResult<GPMLNode> {
+        let (cleaned, removals) = remove_html_comments(content);
+        let original_offset = |offset: usize| original_offset(&removals, offset);
 
-        let cleaned = remove_html_comments(content);
         match Self::parse_document(&cleaned) {
             Ok((remaining, document)) => {
-                let trimmed_remaining = remaining.trim();
-                if trimmed_remaining.is_empty() {
-                    Ok(document)
-                } else {
-                    Err(format!("Unexpected content after parsing: {}", trimmed_remaining))
+                let trimmed_remaining = remaining.trim_start();
+                if trimmed_remaining.trim_end().is_empty() {
+                    return Ok(document);
                 }
+
+                let offset = cleaned.len() - trimmed_remaining.len();
+
+                // Leftover markup usually means the XML parser rejected an element,
+                // re-run it to report its more precise position
+                if trimmed_remaining.starts_with('<') {
+                    if let Err(GPMLError::ParseError { message, location: Some(location), .. }) =
+                        Self::parse_xml_element(trimmed_remaining)
+                    {
+                        let start = original_offset(offset + location.span.start);
+                        let end = original_offset(offset + location.span.end);
+                        return Err(GPMLError::parse_at(message, content, start..end.max(start)));
+                    }
+                }
+
+                let line_len = trimmed_remaining.find('\n').unwrap_or(trimmed_remaining.len());
+                let start = original_offset(offset);
+                Err(GPMLError::parse_at(
+                    "Unexpected content after parsing",
+                    content,
+                    start..start + line_len,
+                ))
             },
-            Err(e) => Err(format!("Parse error: {:?}", e))
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+                let start = original_offset(cleaned.len() - e.input.len());
+                Err(GPMLError::parse_at(
+                    format!("Unexpected input ({:?})", e.code),
+                    content,
+                    start..start + 1,
+                ))
+            }
+            Err(e) => Err(GPMLError::ParseError {
+                message: format!("{:?}", e),
+                line: 0,
+                column: 0,
+                location: None,
+            }),
         }
     }
 }
 
+/// Component-level changes between two parses of the same GPML file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeSummary {
+    /// Components whose source changed
+    pub changed: Vec<String>,
+    /// Components that did not exist in the previous parse
+    pub added: Vec<String>,
+    /// Components that no longer exist
+    pub removed: Vec<String>,
+    /// Whether the root element (or anything outside component definitions) changed
+    pub root_changed: bool,
+    /// Whether an `import` was added, removed or retargeted
+    pub imports_changed: bool,
+}
+
+impl ChangeSummary {
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.added.is_empty() && self.removed.is_empty() && !self.root_changed && !self.imports_changed
+    }
+
+    /// Names of components that must be evicted from caches (changed or removed)
+    pub fn invalidated(&self) -> impl Iterator<Item = &String> {
+        self.changed.iter().chain(self.removed.iter())
+    }
+}
+
+/// Parser that remembers the component definitions of a file between parses,
+/// so that a re-parse reports which components actually changed.
+///
+/// Components are compared by a checksum of their source text.
+#[derive(Debug, Default)]
+pub struct IncrementalParser {
+    components: HashMap<String, ComponentDef>,
+    checksums: HashMap<String, u64>,
+    root_checksum: Option<u64>,
+    imports_checksum: Option<u64>,
+    /// Recover from malformed markup with [`GPMLParser::parse_file_lenient`]
+    lenient: bool,
+    warnings: Vec<ParseWarning>,
+}
+
+impl IncrementalParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse with [`GPMLParser::parse_file_lenient`], the skipped markup is available
+    /// from [`IncrementalParser::warnings`]
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+
+    /// Parse `content` and compare its components against the previous parse
+    pub fn parse(&mut self, content: &str) -> GPMLResult<(GPMLNode, ChangeSummary)> {
+        let document = if self.lenient {
+            let (document, warnings) = GPMLParser::parse_file_lenient(content);
+            self.warnings = warnings;
+            document
+        } else {
+            GPMLParser::parse_file(content)?
+        };
+
+        let (cleaned, _) = remove_html_comments(content);
+        let (imports, sources, rest) = component_sources(&cleaned);
+        let root_checksum = checksum(rest.trim());
+        let imports_checksum = checksum(&imports.join("\n"));
+
+        let mut summary = ChangeSummary {
+            root_changed: self.root_checksum != Some(root_checksum),
+            imports_changed: self.imports_checksum != Some(imports_checksum),
+            ..Default::default()
+        };
+
+        let mut checksums = HashMap::new();
+        for (name, source) in sources {
+            let sum = checksum(source);
+            match self.checksums.get(&name) {
+                None => summary.added.push(name.clone()),
+                Some(previous) if *previous != sum => summary.changed.push(name.clone()),
+                Some(_) => {}
+            }
+            checksums.insert(name, sum);
+        }
+        summary.removed = self
+            .checksums
+            .keys()
+            .filter(|name| !checksums.contains_key(*name))
+            .cloned()
+            .collect();
+
+        self.components.clear();
+        if let GPMLNode::Document { components, .. } = &document {
+            for component in components {
+                self.components.insert(component.name.clone(), component.clone());
+            }
+        }
+        self.checksums = checksums;
+        self.root_checksum = Some(root_checksum);
+        self.imports_checksum = Some(imports_checksum);
+
+        Ok((document, summary))
+    }
+
+    /// Component definitions from the last successful parse
+    pub fn components(&self) -> &HashMap<String, ComponentDef> {
+        &self.components
+    }
+
+    /// Malformed markup skipped by the last lenient parse
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Forget all state, the next parse reports every component as added
+    pub fn reset(&mut self) {
+        self.components.clear();
+        self.checksums.clear();
+        self.root_checksum = None;
+        self.imports_checksum = None;
+        self.warnings.clear();
+    }
+}
+
+/// Split a comment-free document into the source text of its imports and of each component
+/// definition, returning the remaining text that follows the definitions
+fn component_sources(input: &str) -> (Vec<&str>, Vec<(String, &str)>, &str) {
+    let mut imports = Vec::new();
+    let mut input = input.trim_start();
+    while let Ok((rest, _)) = parse_import(input) {
+        imports.push(input[..input.len() - rest.len()].trim_end());
+        input = rest.trim_start();
+    }
+
+    let mut sources = Vec::new();
+    while let Ok((rest, component)) = parse_component_def(input) {
+        sources.push((component.name, &input[..input.len() - rest.len()]));
+        input = rest.trim_start();
+    }
+    (imports, sources, input)
+}
+
+fn checksum(source: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Remove HTML-style comments (<!-- ... -->) so comments never become text nodes
+/// or affect spacing in the rendered output.
+///
+/// Each removal is recorded as (offset in the cleaned text, total bytes removed so far)
+/// so that error offsets can be mapped back to the original content.
+fn remove_html_comments(s: &str) -> (String, Vec<(usize, usize)>) {
+    let mut out = String::new();
+    let mut removals = Vec::new();
+    let mut removed = 0usize;
+    let mut start = 0usize;
+    let len = s.len();
+    while start < len {
+        if let Some(idx) = s[start..].find("<!--") {
+            out.push_str(&s[start..start + idx]);
+            // find closing --> after the comment start
+            if let Some(end_idx) = s[start + idx + 4..].find("-->") {
+                // advance start past the closing "-->"
+                removed += 4 + end_idx + 3;
+                removals.push((out.len(), removed));
+                start = start + idx + 4 + end_idx + 3;
+                continue;
+            } else {
+                // unmatched comment start - stop and append rest
+                break;
+            }
+        } else {
+            out.push_str(&s[start..]);
+            break;
+        }
+    }
+    (out, removals)
+}
+
+/// Map an offset in the text cleaned by [`remove_html_comments`] back to the original content
+fn original_offset(removals: &[(usize, usize)], offset: usize) -> usize {
+    offset
+        + removals
+            .iter()
+            .take_while(|(at, _)| *at <= offset)
+            .last()
+            .map(|(_, removed)| *removed)
+            .unwrap_or(0)
+}
+
+/// Split the inside of an array literal on top-level commas, respecting nested brackets and quotes
+fn split_array_items(content: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+
+    for (i, c) in content.char_indices() {
+        match c {
+            '"' | '\'' if quote == Some(c) => quote = None,
+            '"' | '\'' if quote.is_none() => quote = Some(c),
+            '[' if quote.is_none() => depth += 1,
+            ']' if quote.is_none() => depth = depth.saturating_sub(1),
+            ',' if quote.is_none() && depth == 0 => {
+                items.push(content[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    let last = content[start..].trim();
+    if !last.is_empty() {
+        items.push(last);
+    }
+    items
+}
+
 /// Parse import statement: import ./path.gpml as Name  
 fn parse_import(input: &str) -> IResult<&str, Import> {
     let (input, _) = tag("import").parse(input)?;
@@ -214,6 +507,18 @@ fn parse_import(input: &str) -> IResult<&str, Import> {
     }))
 }
 
+/// Parse template inheritance: extends "./Base.gpml"
+fn parse_extends(input: &str) -> IResult<&str, String> {
+    let (input, _) = tag("extends").parse(input)?;
+    let (input, _) = space1.parse(input)?;
+    let (input, path) = alt((
+        delimited(char::<&str, nom::error::Error<&str>>('"'), take_until("\""), char('"')),
+        delimited(char('\''), take_until("'"), char('\'')),
+        take_while1(|c: char| !c.is_whitespace()),
+    )).parse(input)?;
+    Ok((input, path.to_string()))
+}
+
 /// Parse export statement: export ComponentName
 fn parse_export(input: &str) -> IResult<&str, String> {
     let (input, _) = tag("export").parse(input)?;
@@ -222,17 +527,44 @@ fn parse_export(input: &str) -> IResult<&str, String> {
     Ok((input, name))
 }
 
-/// Parse component definition: def ComponentName(param1, param2) { ... }
+/// Parse a component parameter with an optional type annotation: `name` or `name: Type`
+fn parse_parameter(input: &str) -> IResult<&str, (String, Option<ParamType>)> {
+    let (input, name) = parse_identifier.parse(input)?;
+    let (input, colon) = opt(
+        (space0, char::<&str, nom::error::Error<&str>>(':'), space0)
+    ).parse(input)?;
+    if colon.is_none() {
+        return Ok((input, (name, None)));
+    }
+
+    // An unknown type name is a hard failure so it is reported at the annotation
+    let (rest, annotation) = parse_identifier.parse(input)?;
+    match ParamType::from_name(&annotation) {
+        Some(ty) => Ok((rest, (name, Some(ty)))),
+        None => Err(nom::Err::Failure(nom::error::Error::new(input, nom::error::ErrorKind::Verify))),
+    }
+}
+
+/// Parse component definition: def ComponentName(param1, param2: Type) { ... }
 fn parse_component_def(input: &str) -> IResult<&str, ComponentDef> {
     let (input, _) = tag("def").parse(input)?;
     let (input, _) = space1.parse(input)?;
-    let (input, name) = parse_identifier.parse(input)?;
-    let (input, _) = space0.parse(input)?;
+    let (name_input, name) = parse_identifier.parse(input)?;
+    // `<include>` is reserved for partial file inclusion, `<each>` for iteration and `<portal>` for overlays
+    if name == INCLUDE_TAG || name == EACH_TAG || name == PORTAL_TAG {
+        return Err(nom::Err::Failure(nom::error::Error::new(input, nom::error::ErrorKind::Verify)));
+    }
+    let (input, _) = space0.parse(name_input)?;
     let (input, _) = char::<&str, nom::error::Error<&str>>('(').parse(input)?;
     let (input, parameters) = separated_list0(
         (space0, char::<&str, nom::error::Error<&str>>(','), space0).map(|(_, _, _)| ()),
-        parse_identifier
+        parse_parameter
     ).parse(input)?;
+    let parameter_types = parameters
+        .iter()
+        .filter_map(|(name, ty)| ty.map(|ty| (name.clone(), ty)))
+        .collect();
+    let parameters = parameters.into_iter().map(|(name, _)| name).collect();
     let (input, _) = space0.parse(input)?;
     let (input, _) = char::<&str, nom::error::Error<&str>>(')').parse(input)?;
     let (input, _) = space0.parse(input)?;
@@ -242,10 +574,14 @@ fn parse_component_def(input: &str) -> IResult<&str, ComponentDef> {
     let (input, _) = multispace0.parse(input)?;
     let (input, _) = char::<&str, nom::error::Error<&str>>('}').parse(input)?;
     
+    let slots = ComponentDef::collect_slots(&body);
+
     Ok((input, ComponentDef {
         name,
         parameters,
+        parameter_types,
         body,
+        slots,
     }))
 }
 
@@ -296,7 +632,8 @@ fn extract_and_parse_xml_element(input: &str) -> Result<(Element, usize), String
                 }
                 if depth == 0 {
                     let element_str = &trimmed[..=i];
-                    let element = GPMLParser::parse_xml_element(element_str)?;
+                    let element = GPMLParser::parse_xml_element(element_str)
+                        .map_err(|e| e.to_string())?;
                     return Ok((element, start_offset + i + 1));
                 }
             }
@@ -324,72 +661,230 @@ fn parse_element(input: &str) -> IResult<&str, Element> {
     )).parse(input)
 }
 
-/// Parse a self-closing element like <input />
-fn parse_self_closing_element(input: &str) -> IResult<&str, Element> {
+/// Parse the start of a tag up to its closing `>` or `/>`, like `<div class="card"`
+fn parse_start_tag(input: &str) -> IResult<&str, (String, Vec<(String, AttributeValue)>)> {
     let (input, _) = char::<&str, nom::error::Error<&str>>('<').parse(input)?;
     let (input, tag_name) = parse_tag_name.parse(input)?;
     let (input, attributes) = many0(
         (space1, parse_attribute).map(|(_, attr)| attr)
     ).parse(input)?;
     let (input, _) = space0.parse(input)?;
+    Ok((input, (tag_name, attributes)))
+}
+
+/// Parse a closing tag like `</div>`
+fn parse_end_tag(input: &str) -> IResult<&str, String> {
+    let (input, _) = tag("</").parse(input)?;
+    let (input, tag_name) = parse_tag_name.parse(input)?;
+    let (input, _) = space0.parse(input)?;
+    let (input, _) = char::<&str, nom::error::Error<&str>>('>').parse(input)?;
+    Ok((input, tag_name))
+}
+
+/// Parse a self-closing element like <input />
+fn parse_self_closing_element(input: &str) -> IResult<&str, Element> {
+    let (input, (tag_name, attributes)) = parse_start_tag(input)?;
     let (input, _) = tag("/>").parse(input)?;
     
     let mut attr_map = HashMap::new();
     for (key, value) in attributes {
         attr_map.insert(key, value);
     }
+    let conditions = extract_condition(&mut attr_map);
+    let (namespace, tag_name) = split_namespace(tag_name);
     
     Ok((input, Element {
         tag: tag_name,
         attributes: attr_map,
         children: vec![],
         self_closing: true,
+        conditions,
+        namespace,
     }))
 }
 
 /// Parse a paired element like <div>content</div>
 fn parse_paired_element(input: &str) -> IResult<&str, Element> {
-    let (input, _) = char::<&str, nom::error::Error<&str>>('<').parse(input)?;
-    let (input, tag_name) = parse_tag_name.parse(input)?;
-    let (input, attributes) = many0(
-        (space1, parse_attribute).map(|(_, attr)| attr)
-    ).parse(input)?;
-    let (input, _) = space0.parse(input)?;
+    let (input, (tag_name, attributes)) = parse_start_tag(input)?;
     let (input, _) = char::<&str, nom::error::Error<&str>>('>').parse(input)?;
     let (input, _) = multispace0.parse(input)?;
     let (input, children) = many0(parse_node).parse(input)?;
+    let children = children.into_iter().flatten().collect();
     let (input, _) = multispace0.parse(input)?;
-    let (input, _) = tag("</").parse(input)?;
-    let (input, _closing_tag) = parse_tag_name.parse(input)?;
-    let (input, _) = space0.parse(input)?;
-    let (input, _) = char::<&str, nom::error::Error<&str>>('>').parse(input)?;
+    let (input, _closing_tag) = parse_end_tag(input)?;
     
     let mut attr_map = HashMap::new();
     for (key, value) in attributes {
         attr_map.insert(key, value);
     }
+    let conditions = extract_condition(&mut attr_map);
+    let (namespace, tag_name) = split_namespace(tag_name);
     
     Ok((input, Element {
         tag: tag_name,
         attributes: attr_map,
         children,
         self_closing: false,
+        conditions,
+        namespace,
     }))
 }
 
-/// Parse any type of node (element, text, expression)
-fn parse_node(input: &str) -> IResult<&str, GPMLNode> {
+/// Recovering parser behind [`GPMLParser::parse_document_lenient`]
+struct LenientParser<'a> {
+    source: &'a str,
+    warnings: Vec<ParseWarning>,
+}
+
+impl<'a> LenientParser<'a> {
+    fn warn(&mut self, input: &str, message: impl Into<String>) {
+        self.warnings.push(ParseWarning {
+            byte_offset: self.source.len() - input.len(),
+            message: message.into(),
+        });
+    }
+
+    fn document(&mut self, input: &'a str) -> GPMLNode {
+        let mut input = input.trim_start();
+        let mut extends = None;
+        if let Ok((rest, path)) = parse_extends(input) {
+            extends = Some(path);
+            input = rest;
+        }
+
+        let mut imports = Vec::new();
+        let mut components = Vec::new();
+        let mut root = None;
+        loop {
+            input = input.trim_start();
+            if input.is_empty() {
+                break;
+            }
+
+            if input.starts_with('<') {
+                if root.is_some() {
+                    self.warn(input, "Unexpected content after the root element");
+                    break;
+                }
+                match self.element(input) {
+                    Some((rest, element)) => {
+                        root = Some(element);
+                        input = rest;
+                    }
+                    None => {
+                        self.warn(input, "Malformed element");
+                        input = skip_to_element(&input[1..]);
+                    }
+                }
+            } else if let Ok((rest, import)) = parse_import(input) {
+                imports.push(import);
+                input = rest;
+            } else if let Ok((rest, component)) = parse_component_def(input) {
+                components.push(component);
+                input = rest;
+            } else if let Ok((rest, _)) = parse_export(input) {
+                input = rest;
+            } else {
+                self.warn(input, "Expected an import, a component definition or an element");
+                input = skip_to_element(input);
+            }
+        }
+
+        GPMLNode::Document {
+            extends,
+            imports,
+            components,
+            root,
+        }
+    }
+
+    /// Parse an element, recovering from malformed children. Returns `None` when the
+    /// start tag itself is malformed.
+    fn element(&mut self, input: &'a str) -> Option<(&'a str, Element)> {
+        if let Ok(result) = parse_element_hybrid(input) {
+            return Some(result);
+        }
+
+        let (input, (tag_name, attributes)) = parse_start_tag(input).ok()?;
+        let mut attributes = attributes.into_iter().collect::<HashMap<_, _>>();
+        let conditions = extract_condition(&mut attributes);
+        let (namespace, tag_name) = split_namespace(tag_name);
+        let mut element = Element {
+            tag: tag_name,
+            attributes,
+            children: vec![],
+            self_closing: false,
+            conditions,
+            namespace,
+        };
+
+        if let Ok((rest, _)) = tag::<&str, &str, nom::error::Error<&str>>("/>").parse(input) {
+            element.self_closing = true;
+            return Some((rest, element));
+        }
+        let (mut input, _) = char::<&str, nom::error::Error<&str>>('>').parse(input).ok()?;
+
+        loop {
+            input = input.trim_start();
+            if input.is_empty() {
+                self.warn(input, format!("Unclosed element <{}>", element.tag));
+                break;
+            }
+
+            if input.starts_with("</") {
+                if let Ok((rest, _)) = parse_end_tag(input) {
+                    input = rest;
+                    break;
+                }
+                self.warn(input, format!("Malformed closing tag of <{}>", element.tag));
+                input = skip_to_element(&input[2..]);
+            } else if input.starts_with('<') {
+                match self.element(input) {
+                    Some((rest, child)) => {
+                        element.children.push(GPMLNode::Element(child));
+                        input = rest;
+                    }
+                    None => {
+                        self.warn(input, "Malformed element");
+                        input = skip_to_element(&input[1..]);
+                    }
+                }
+            } else if let Ok((rest, nodes)) = parse_text_node(input) {
+                element.children.extend(nodes);
+                input = rest;
+            }
+        }
+
+        Some((input, element))
+    }
+}
+
+/// Skip the input until the next `<`, or to the end of the input
+fn skip_to_element(input: &str) -> &str {
+    &input[input.find('<').unwrap_or(input.len())..]
+}
+
+/// Parse any type of node (element, or text mixed with expressions)
+fn parse_node(input: &str) -> IResult<&str, Vec<GPMLNode>> {
     let (input, _) = multispace0.parse(input)?;
     alt((
-        parse_element_hybrid.map(GPMLNode::Element),
-        parse_expression,
+        parse_element_hybrid.map(|element| vec![GPMLNode::Element(element)]),
         parse_text_node,
     )).parse(input)
 }
 
 /// Parse element attributes like name="value"
+///
+/// The reserved `gpml:else` flag may be written without a value.
 fn parse_attribute(input: &str) -> IResult<&str, (String, AttributeValue)> {
     let (input, name) = parse_attribute_name.parse(input)?;
+    if name == "gpml:else" {
+        let (input, value) = opt(
+            (space0, char::<&str, nom::error::Error<&str>>('='), space0, parse_attribute_value)
+                .map(|(_, _, _, value)| value)
+        ).parse(input)?;
+        return Ok((input, (name, value.unwrap_or(AttributeValue::Boolean(true)))));
+    }
     let (input, _) = space0.parse(input)?;
     let (input, _) = char::<&str, nom::error::Error<&str>>('=').parse(input)?;
     let (input, _) = space0.parse(input)?;
@@ -397,10 +892,25 @@ fn parse_attribute(input: &str) -> IResult<&str, (String, AttributeValue)> {
     Ok((input, (name, value)))
 }
 
-/// Parse attribute name (alphanumeric with dashes/underscores)
+/// Move the reserved `gpml:if` / `gpml:else-if` / `gpml:else` attributes out of the attribute map
+fn extract_condition(attributes: &mut HashMap<String, AttributeValue>) -> Option<Condition> {
+    let mut condition = None;
+    for name in ["gpml:if", "gpml:else-if", "gpml:else"] {
+        if let Some(value) = attributes.remove(name) {
+            if condition.is_none() {
+                condition = Condition::from_attribute(name, &value);
+            } else {
+                tracing::warn!("Ignoring '{}', element already has a conditional attribute", name);
+            }
+        }
+    }
+    condition
+}
+
+/// Parse attribute name (alphanumeric with dashes/underscores, and colons for namespaced attributes)
 fn parse_attribute_name(input: &str) -> IResult<&str, String> {
     let (input, start) = alt((alpha1::<&str, nom::error::Error<&str>>, tag("_"))).parse(input)?;
-    let (input, rest) = many0(alt((alphanumeric1::<&str, nom::error::Error<&str>>, tag("-"), tag("_")))).parse(input)?;
+    let (input, rest) = many0(alt((alphanumeric1::<&str, nom::error::Error<&str>>, tag("-"), tag("_"), tag(":")))).parse(input)?;
     
     let mut result = start.to_string();
     for part in rest {
@@ -454,8 +964,21 @@ fn parse_unquoted_value(input: &str) -> IResult<&str, AttributeValue> {
     Ok((input, value))
 }
 
-/// Parse tag names (allowing all alphanumeric + dash/underscore, including uppercase)
+/// Parse tag names (allowing all alphanumeric + dash/underscore, including uppercase), with
+/// an optional namespace prefix like `ui:Button`
 fn parse_tag_name(input: &str) -> IResult<&str, String> {
+    let (input, name) = parse_tag_name_part(input)?;
+    let (input, local_name) = opt(
+        (char::<&str, nom::error::Error<&str>>(':'), parse_tag_name_part).map(|(_, name)| name)
+    ).parse(input)?;
+
+    match local_name {
+        Some(local_name) => Ok((input, format!("{}:{}", name, local_name))),
+        None => Ok((input, name)),
+    }
+}
+
+fn parse_tag_name_part(input: &str) -> IResult<&str, String> {
     let (input, start) = alt((alpha1::<&str, nom::error::Error<&str>>, tag("_"))).parse(input)?;
     let (input, rest) = many0(alt((alphanumeric1::<&str, nom::error::Error<&str>>, tag("-"), tag("_")))).parse(input)?;
     
@@ -466,6 +989,16 @@ fn parse_tag_name(input: &str) -> IResult<&str, String> {
     Ok((input, result))
 }
 
+/// Split a tag name like `ui:Button` into its namespace prefix and local name
+fn split_namespace(tag_name: String) -> (Option<String>, String) {
+    match tag_name.split_once(':') {
+        Some((namespace, name)) if !namespace.is_empty() && !name.is_empty() => {
+            (Some(namespace.to_string()), name.to_string())
+        }
+        _ => (None, tag_name),
+    }
+}
+
 /// Parse identifier (for component names, variables, etc.)
 fn parse_identifier(input: &str) -> IResult<&str, String> {
     let (input, start) = alpha1::<&str, nom::error::Error<&str>>.parse(input)?;
@@ -478,18 +1011,32 @@ fn parse_identifier(input: &str) -> IResult<&str, String> {
     Ok((input, result))
 }
 
-/// Parse text content between elements
-fn parse_text_node(input: &str) -> IResult<&str, GPMLNode> {
-    let (input, content) = take_while1(|c: char| c != '<' && c != '$' && !c.is_whitespace()).parse(input)?;
-    Ok((input, GPMLNode::Text(content.to_string())))
+/// Parse text content between elements, split into text and `${...}` expression nodes
+fn parse_text_node(input: &str) -> IResult<&str, Vec<GPMLNode>> {
+    let (input, content) = take_while1(|c: char| c != '<').parse(input)?;
+    Ok((input, split_text_expressions(content.trim_end())))
 }
 
-/// Parse expression like ${variable}
-fn parse_expression(input: &str) -> IResult<&str, GPMLNode> {
-    let (input, _) = tag("${").parse(input)?;
-    let (input, expr) = take_until("}").parse(input)?;
-    let (input, _) = char::<&str, nom::error::Error<&str>>('}').parse(input)?;
-    Ok((input, GPMLNode::Expression(expr.trim().to_string())))
+/// Split text like `Total: ${count} items` into text and expression nodes.
+///
+/// An unterminated `${` is kept as text.
+pub fn split_text_expressions(text: &str) -> Vec<GPMLNode> {
+    let mut nodes = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        if start > 0 {
+            nodes.push(GPMLNode::Text(rest[..start].to_string()));
+        }
+        nodes.push(GPMLNode::Expression(rest[start + 2..start + 2 + len].trim().to_string()));
+        rest = &rest[start + 2 + len + 1..];
+    }
+    if !rest.is_empty() {
+        nodes.push(GPMLNode::Text(rest.to_string()));
+    }
+    nodes
 }
 
 #[cfg(test)]
@@ -605,6 +1152,38 @@ export Card"#;
         }
     }
 
+    #[test]
+    fn test_text_with_expressions() {
+        use crate::component::{resolve_element, ComponentResolver, GPMLContext};
+
+        let element = GPMLParser::parse_xml_element("<p>Total: ${count} items</p>").unwrap();
+        assert_eq!(
+            element.children,
+            vec![
+                GPMLNode::Text("Total: ".to_string()),
+                GPMLNode::Expression("count".to_string()),
+                GPMLNode::Text(" items".to_string()),
+            ]
+        );
+
+        let (_, nom_element) = parse_paired_element("<p>Total: ${count} items</p>").unwrap();
+        assert_eq!(nom_element.children, element.children);
+
+        let context = GPMLContext::new(".").with_variable("count".to_string(), AttributeValue::Number(5.0));
+        let resolved = resolve_element(&element, &context, &ComponentResolver::new()).unwrap();
+        assert_eq!(resolved.children, vec![GPMLNode::Text("Total: 5 items".to_string())]);
+
+        assert_eq!(
+            split_text_expressions("${a} and ${b}"),
+            vec![
+                GPMLNode::Expression("a".to_string()),
+                GPMLNode::Text(" and ".to_string()),
+                GPMLNode::Expression("b".to_string()),
+            ]
+        );
+        assert_eq!(split_text_expressions("Price: ${"), vec![GPMLNode::Text("Price: ${".to_string())]);
+    }
+
     #[test]
     fn test_xml_parser_self_closing() {
         let xml = r#"<input type="text" value="${name}" />"#;
@@ -723,4 +1302,605 @@ export Card"#;
             assert_eq!(element.children.len(), 3); // header, main, footer
         }
     }
+
+    #[test]
+    fn test_parse_conditional_attributes() {
+        let xml = r#"<div><p gpml:if="${logged_in}">Welcome</p><p gpml:else-if="guest">Hi guest</p><p gpml:else>Sign in</p></div>"#;
+        let result = GPMLParser::parse_xml_element(xml);
+        assert!(result.is_ok());
+        if let Ok(element) = result {
+            assert_eq!(element.children.len(), 3);
+            let conditions: Vec<_> = element
+                .children
+                .iter()
+                .filter_map(|c| c.as_element())
+                .map(|e| e.conditions.clone())
+                .collect();
+            assert_eq!(conditions[0], Some(Condition::If("logged_in".to_string())));
+            assert_eq!(conditions[1], Some(Condition::ElseIf("guest".to_string())));
+            assert_eq!(conditions[2], Some(Condition::Else));
+
+            // Reserved attributes are not kept as regular attributes
+            let first = element.children[0].as_element().unwrap();
+            assert!(first.get_attribute("gpml:if").is_none());
+        }
+    }
+
+    #[test]
+    fn test_parse_conditional_attributes_nom() {
+        let input = r#"<br gpml:else />"#;
+        let result = parse_self_closing_element(input);
+        assert!(result.is_ok());
+        if let Ok((remaining, element)) = result {
+            assert_eq!(element.conditions, Some(Condition::Else));
+            assert!(element.attributes.is_empty());
+            assert_eq!(remaining, "");
+        }
+    }
+
+    #[test]
+    fn test_conditional_rendering_chain() {
+        use crate::component::{filter_conditional_children, GPMLContext};
+
+        let xml = r#"<div><p gpml:if="show">A</p><p gpml:else-if="mode == 'b'">B</p><p gpml:else>C</p><span>D</span></div>"#;
+        let element = GPMLParser::parse_xml_element(xml).unwrap();
+
+        let visible = |context: &GPMLContext| -> Vec<String> {
+            filter_conditional_children(&element.children, context)
+                .iter()
+                .filter_map(|c| c.as_element())
+                .map(|e| e.get_text_content())
+                .collect()
+        };
+
+        let context = GPMLContext::new(".")
+            .with_variable("show".to_string(), AttributeValue::Boolean(true));
+        assert_eq!(visible(&context), vec!["A", "D"]);
+
+        let context = GPMLContext::new(".")
+            .with_variable("show".to_string(), AttributeValue::Boolean(false))
+            .with_variable("mode".to_string(), AttributeValue::Literal("b".to_string()));
+        assert_eq!(visible(&context), vec!["B", "D"]);
+
+        let context = GPMLContext::new(".");
+        assert_eq!(visible(&context), vec!["C", "D"]);
+    }
+
+    #[test]
+    fn test_orphan_else_is_dropped() {
+        use crate::component::{filter_conditional_children, GPMLContext};
+
+        let xml = r#"<div><p>A</p><p gpml:else>B</p></div>"#;
+        let element = GPMLParser::parse_xml_element(xml).unwrap();
+        let children = filter_conditional_children(&element.children, &GPMLContext::new("."));
+        assert_eq!(children.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_array_attribute() {
+        let value = GPMLParser::parse_attribute_value_str(r#"[1, "two", [true, x]]"#);
+        assert_eq!(
+            value,
+            AttributeValue::Array(vec![
+                AttributeValue::Number(1.0),
+                AttributeValue::Literal("two".to_string()),
+                AttributeValue::Array(vec![
+                    AttributeValue::Boolean(true),
+                    AttributeValue::Literal("x".to_string()),
+                ]),
+            ])
+        );
+        assert_eq!(GPMLParser::parse_attribute_value_str("[]"), AttributeValue::Array(vec![]));
+    }
+
+    fn resolve_with(xml: &str, context: &crate::component::GPMLContext) -> Element {
+        let element = GPMLParser::parse_xml_element(xml).unwrap();
+        crate::component::resolve_element(&element, context, &crate::component::ComponentResolver::new()).unwrap()
+    }
+
+    #[test]
+    fn test_for_loop_empty_and_single() {
+        use crate::component::GPMLContext;
+
+        let xml = r#"<ul><li gpml:for="item in items">${item}</li></ul>"#;
+
+        let context = GPMLContext::new(".")
+            .with_variable("items".to_string(), AttributeValue::Array(vec![]));
+        assert!(resolve_with(xml, &context).children.is_empty());
+
+        let context = GPMLContext::new(".").with_variable(
+            "items".to_string(),
+            AttributeValue::Array(vec![AttributeValue::Literal("only".to_string())]),
+        );
+        let resolved = resolve_with(xml, &context);
+        assert_eq!(resolved.children.len(), 1);
+        let li = resolved.children[0].as_element().unwrap();
+        assert_eq!(li.get_text_content(), "only");
+        assert!(li.get_attribute("gpml:for").is_none());
+    }
+
+    #[test]
+    fn test_for_loop_nested() {
+        use crate::component::GPMLContext;
+
+        let xml = r#"<table><tr gpml:for="row in rows"><td gpml:for="cell in row">${cell}</td></tr></table>"#;
+        let rows = GPMLParser::parse_attribute_value_str("[[a, b], [c]]");
+        let context = GPMLContext::new(".").with_variable("rows".to_string(), rows);
+
+        let resolved = resolve_with(xml, &context);
+        let cells: Vec<Vec<String>> = resolved
+            .children
+            .iter()
+            .filter_map(|row| row.as_element())
+            .map(|row| {
+                row.children
+                    .iter()
+                    .filter_map(|cell| cell.as_element())
+                    .map(|cell| cell.get_text_content())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(cells, vec![vec!["a", "b"], vec!["c"]]);
+    }
+
+    #[test]
+    fn test_each_inlines_children() {
+        use crate::component::GPMLContext;
+
+        let xml = r#"<list><each items="${names}" as="name" index="i"><div>${i}: ${name}</div><hr /></each></list>"#;
+        let context = GPMLContext::new(".")
+            .with_variable("names".to_string(), GPMLParser::parse_attribute_value_str("[Ada, Bob]"));
+
+        let resolved = resolve_with(xml, &context);
+        let tags: Vec<&str> = resolved
+            .children
+            .iter()
+            .filter_map(|child| child.as_element())
+            .map(|child| child.tag.as_str())
+            .collect();
+        assert_eq!(tags, vec!["div", "hr", "div", "hr"]);
+        assert_eq!(resolved.children[0].as_element().unwrap().get_text_content(), "0: Ada");
+        assert_eq!(resolved.children[2].as_element().unwrap().get_text_content(), "1: Bob");
+
+        let context = GPMLContext::new(".")
+            .with_variable("names".to_string(), AttributeValue::Literal("not an array".to_string()));
+        assert!(resolve_with(xml, &context).children.is_empty());
+    }
+
+    #[test]
+    fn test_each_in_component_body() {
+        use crate::component::{ComponentResolver, GPMLContext};
+
+        let document = GPMLParser::parse_file(
+            r#"def Tags(tags) {
+    <div><each items="${tags}"><span>${item}</span></each></div>
+}"#,
+        )
+        .unwrap();
+        let GPMLNode::Document { components, .. } = document else {
+            panic!("expected document");
+        };
+
+        let mut args = HashMap::new();
+        args.insert(
+            "tags".to_string(),
+            GPMLParser::parse_attribute_value_str("[x, y]"),
+        );
+        let body = ComponentResolver::new()
+            .instantiate_component(&components[0], &args, &GPMLContext::new("."))
+            .unwrap();
+        assert_eq!(body.children.len(), 2);
+        assert_eq!(body.get_text_content(), "xy");
+    }
+
+    #[test]
+    fn test_for_loop_in_component_body() {
+        use crate::component::{ComponentResolver, GPMLContext};
+
+        let document = GPMLParser::parse_file(
+            r#"def Tags(tags) {
+    <div><span gpml:for="tag in tags">${tag}</span></div>
+}"#,
+        )
+        .unwrap();
+        let GPMLNode::Document { components, .. } = document else {
+            panic!("expected document");
+        };
+
+        let mut args = HashMap::new();
+        args.insert(
+            "tags".to_string(),
+            GPMLParser::parse_attribute_value_str("[x, y, z]"),
+        );
+        let body = ComponentResolver::new()
+            .instantiate_component(&components[0], &args, &GPMLContext::new("."))
+            .unwrap();
+        assert_eq!(body.children.len(), 3);
+        assert_eq!(body.get_text_content(), "xyz");
+    }
+
+    #[test]
+    fn test_typed_parameters() {
+        use crate::component::{ComponentResolver, GPMLContext};
+
+        let document = GPMLParser::parse_file(
+            r#"def Card(title: String, count : Number, open: Boolean, extra) {
+    <div>${title}</div>
+}"#,
+        )
+        .unwrap();
+        let GPMLNode::Document { components, .. } = document else {
+            panic!("expected document");
+        };
+        let card = &components[0];
+        assert_eq!(card.parameters, vec!["title", "count", "open", "extra"]);
+        assert_eq!(card.parameter_type("count"), ParamType::Number);
+        assert_eq!(card.parameter_type("open"), ParamType::Boolean);
+        assert_eq!(card.parameter_type("extra"), ParamType::Any);
+
+        let mut args = HashMap::new();
+        args.insert("title".to_string(), AttributeValue::Literal("Hi".to_string()));
+        args.insert("count".to_string(), AttributeValue::Literal("3".to_string()));
+        args.insert("open".to_string(), AttributeValue::Boolean(true));
+        args.insert("extra".to_string(), AttributeValue::Array(vec![]));
+
+        let mut resolver = ComponentResolver::new();
+        resolver.set_strict_types(true);
+        let context = GPMLContext::new(".");
+        assert!(resolver.instantiate_component(card, &args, &context).is_ok());
+
+        args.insert("count".to_string(), AttributeValue::Literal("many".to_string()));
+        let error = resolver.instantiate_component(card, &args, &context).unwrap_err();
+        assert!(matches!(
+            error,
+            GPMLError::TypeMismatch { ref param, expected: ParamType::Number, .. } if param == "count"
+        ));
+
+        resolver.set_strict_types(false);
+        assert!(resolver.instantiate_component(card, &args, &context).is_ok());
+
+        let error = GPMLParser::parse_file("def Card(title: Text) {\n    <div />\n}").unwrap_err();
+        assert!(matches!(error, GPMLError::ParseError { column: 17, .. }));
+    }
+
+    #[test]
+    fn test_template_inheritance() {
+        use crate::component::ComponentResolver;
+
+        let dir = std::env::temp_dir().join(format!("gpml-extends-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("Base.gpml"),
+            r#"def Title(text) {
+    <h1>${text}</h1>
+}
+
+<div>
+    <block name="sidebar"><span>Default sidebar</span></block>
+    <block name="content"><span>Default content</span></block>
+</div>"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("Page.gpml"),
+            r#"extends "./Base.gpml"
+
+<root>
+    <block name="content"><Title text="Hello" /></block>
+</root>"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("LoopA.gpml"), "extends \"./LoopB.gpml\"\n\n<div />").unwrap();
+        std::fs::write(dir.join("LoopB.gpml"), "extends ./LoopA.gpml\n\n<div />").unwrap();
+
+        let page = dir.join("Page.gpml");
+        let document = GPMLParser::parse_file(&std::fs::read_to_string(&page).unwrap()).unwrap();
+        assert!(matches!(
+            &document,
+            GPMLNode::Document { extends: Some(path), .. } if path == "./Base.gpml"
+        ));
+
+        let mut resolver = ComponentResolver::new();
+        let GPMLNode::Document { extends, components, root, .. } =
+            resolver.resolve_extends(&page, document).unwrap()
+        else {
+            panic!("expected document");
+        };
+        assert!(extends.is_none());
+        assert!(components.iter().any(|c| c.name == "Title"));
+
+        let root = root.unwrap();
+        assert_eq!(root.tag, "div");
+        let blocks: Vec<_> = root.children.iter().filter_map(|c| c.as_element()).collect();
+        assert_eq!(blocks[0].get_text_content(), "Default sidebar");
+        assert_eq!(blocks[1].children[0].as_element().unwrap().tag, "Title");
+
+        let error = resolver.load_file(dir.join("LoopA.gpml")).unwrap_err();
+        assert!(matches!(error, GPMLError::CircularDependency { .. }));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_include() {
+        use crate::component::ComponentResolver;
+
+        let dir = std::env::temp_dir().join(format!("gpml-include-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("Header.gpml"),
+            "def Logo(text) {\n    <h1>${text}</h1>\n}\n\n<header><Logo text=\"GPML\" /></header>",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("Page.gpml"),
+            "<root><include src=\"./Header.gpml\" /><p>Body</p></root>",
+        )
+        .unwrap();
+        std::fs::write(dir.join("CycleA.gpml"), "<div><include src=\"./CycleB.gpml\" /></div>").unwrap();
+        std::fs::write(dir.join("CycleB.gpml"), "<div><include src=\"./CycleA.gpml\" /></div>").unwrap();
+
+        let page = dir.join("Page.gpml");
+        let document = GPMLParser::parse_file(&std::fs::read_to_string(&page).unwrap()).unwrap();
+        let mut resolver = ComponentResolver::new();
+        let GPMLNode::Document { components, root, .. } = resolver.resolve_includes(&page, document).unwrap() else {
+            panic!("expected document");
+        };
+        assert!(components.iter().any(|c| c.name == "Logo"));
+
+        let root = root.unwrap();
+        let header = root.children[0].as_element().unwrap();
+        assert_eq!(header.tag, "header");
+        assert_eq!(header.children[0].as_element().unwrap().tag, "Logo");
+        assert_eq!(root.children[1].as_element().unwrap().tag, "p");
+
+        let context = resolver.load_file(&page).unwrap();
+        assert!(context.get_component("Logo").is_some());
+
+        let error = resolver.load_file(dir.join("CycleA.gpml")).unwrap_err();
+        assert!(matches!(error, GPMLError::CircularDependency { .. }));
+
+        assert!(GPMLParser::parse_file("def include(src) {\n    <div />\n}").is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_namespaces() {
+        use crate::component::{resolve_element, ComponentResolver};
+
+        let dir = std::env::temp_dir().join(format!("gpml-namespaces-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("ui")).unwrap();
+        std::fs::create_dir_all(dir.join("common")).unwrap();
+        std::fs::write(dir.join("ui/Button.gpml"), "def Button(label) {\n    <button>${label}</button>\n}").unwrap();
+        std::fs::write(dir.join("layout.gpml"), "def Button() {\n    <div class=\"row\" />\n}\n\ndef Grid() {\n    <div class=\"grid\" />\n}").unwrap();
+        std::fs::write(dir.join("common/Card.gpml"), "def Card() {\n    <section />\n}").unwrap();
+        std::fs::write(
+            dir.join("Page.gpml"),
+            "<root xmlns=\"./common\" xmlns:ui=\"./ui\" xmlns:layout=\"./layout.gpml\">\n    <ui:Button label=\"Save\" />\n    <layout:Button />\n    <layout:Grid></layout:Grid>\n    <Card />\n    <p>Text</p>\n</root>",
+        )
+        .unwrap();
+        std::fs::write(dir.join("Undeclared.gpml"), "<root><x:Button /></root>").unwrap();
+
+        let document = GPMLParser::parse_file(&std::fs::read_to_string(dir.join("Page.gpml")).unwrap()).unwrap();
+        let GPMLNode::Document { root: Some(root), .. } = document else {
+            panic!("expected root element");
+        };
+        let button = root.children[0].as_element().unwrap();
+        assert_eq!(button.namespace.as_deref(), Some("ui"));
+        assert_eq!(button.tag, "Button");
+        assert_eq!(button.qualified_tag(), "ui:Button");
+        assert_eq!(root.namespace_declarations().get(&None).map(String::as_str), Some("./common"));
+
+        let mut resolver = ComponentResolver::new();
+        let page = dir.join("Page.gpml");
+        let context = resolver.load_file(&page).unwrap();
+        assert!(context.get_component("ui:Button").is_some());
+        assert!(context.get_component("layout:Button").is_some());
+        assert!(context.get_component("Card").is_some());
+        assert!(context.get_component("p").is_none());
+
+        let resolved = resolve_element(&root, &context, &resolver).unwrap();
+        let tags: Vec<_> = resolved.children.iter().filter_map(|c| c.as_element()).map(|e| e.tag.as_str()).collect();
+        assert_eq!(tags, ["button", "div", "div", "section", "p"]);
+
+        let error = resolver.load_file(dir.join("Undeclared.gpml")).unwrap_err();
+        assert!(matches!(error, GPMLError::ImportError { .. }));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_preload() {
+        use crate::component::ComponentResolver;
+        use std::cell::RefCell;
+
+        let dir = std::env::temp_dir().join(format!("gpml-preload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("Home.gpml"), "def Button(label) {\n    <button>${label}</button>\n}\n\n<root><Button label=\"Go\" /></root>").unwrap();
+        std::fs::write(dir.join("Settings.gpml"), "<root><span>Settings</span></root>").unwrap();
+
+        let paths = vec![dir.join("Home.gpml"), dir.join("Settings.gpml")];
+        let progress = RefCell::new(Vec::new());
+        let mut resolver = ComponentResolver::new();
+        resolver
+            .preload_with_progress(&paths, |loaded, total| progress.borrow_mut().push((loaded, total)))
+            .unwrap();
+        assert_eq!(progress.into_inner(), vec![(1, 2), (2, 2)]);
+        assert!(paths.iter().all(|path| resolver.is_cached(path)));
+
+        // Preloaded files are served from the cache
+        std::fs::remove_file(dir.join("Settings.gpml")).unwrap();
+        let mut canvas_resolver = ComponentResolver::new();
+        canvas_resolver.extend_cache(resolver);
+        assert!(canvas_resolver.load_file(&paths[1]).is_ok());
+
+        let error = ComponentResolver::new().preload(&paths).unwrap_err();
+        assert!(matches!(error, GPMLError::FileNotFound { .. }));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_component_slots() {
+        use crate::component::{resolve_element, ComponentResolver, GPMLContext};
+
+        let document = GPMLParser::parse_file(
+            r#"def Panel(title) {
+    <div>
+        <header><slot name="header"><h1>${title}</h1></slot></header>
+        <main><slot /></main>
+        <footer><slot name="footer"><span>Default footer</span></slot></footer>
+    </div>
+}"#,
+        )
+        .unwrap();
+        let GPMLNode::Document { components, .. } = document else {
+            panic!("expected document");
+        };
+        let panel = &components[0];
+        assert_eq!(panel.slots.len(), 3);
+        assert!(panel.slots["header"].is_some());
+        assert!(panel.slots[DEFAULT_SLOT].is_none());
+
+        let mut context = GPMLContext::new(".").with_variable(
+            "user".to_string(),
+            AttributeValue::Literal("Ada".to_string()),
+        );
+        context.add_component(panel.clone());
+
+        let call = GPMLParser::parse_xml_element(
+            r#"<Panel title="Fallback"><h2 slot="header">Custom</h2><p>${user}</p></Panel>"#,
+        )
+        .unwrap();
+        let resolved = resolve_element(&call, &context, &ComponentResolver::new()).unwrap();
+
+        let section = |index: usize| resolved.children[index].as_element().unwrap();
+        let header = section(0).children[0].as_element().unwrap();
+        assert_eq!(header.tag, "h2");
+        assert!(header.get_attribute("slot").is_none());
+        assert_eq!(section(1).get_text_content(), "Ada");
+        assert_eq!(section(2).get_text_content(), "Default footer");
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let input = "<!-- header comment -->\n<root>\n</root>\n  oops";
+        let error = GPMLParser::parse_file(input).unwrap_err();
+        match &error {
+            GPMLError::ParseError { line, column, location, .. } => {
+                assert_eq!((*line, *column), (4, 3));
+                let location = location.as_ref().unwrap();
+                assert_eq!(&input[location.span.clone()], "oops");
+                assert_eq!(location.source_line, "  oops");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let rendered = error.to_string();
+        assert!(rendered.contains(" --> 4:3"));
+        assert!(rendered.ends_with(" |   ^^^^"));
+    }
+
+    #[test]
+    fn test_xml_error_location() {
+        let error = GPMLParser::parse_xml_element("<div>\n  <p></span>\n</div>").unwrap_err();
+        match error {
+            GPMLError::ParseError { line, location, .. } => {
+                assert_eq!(line, 2);
+                assert!(location.is_some());
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_line_index() {
+        let source = "ab\ncdé\n\nx";
+        let index = LineIndex::new(source);
+        assert_eq!(index.line_col(source, 0), (1, 1));
+        assert_eq!(index.line_col(source, 3), (2, 1));
+        assert_eq!(index.line_col(source, 7), (2, 4));
+        assert_eq!(index.line_col(source, 9), (4, 1));
+        assert_eq!(index.line_text(source, 2), "cdé");
+        assert_eq!(index.line_text(source, 3), "");
+    }
+
+    #[test]
+    fn test_incremental_parser_change_summary() {
+        let mut parser = IncrementalParser::new();
+        let v1 = r#"def A(x) {
+    <p>${x}</p>
+}
+def B(y) {
+    <span>${y}</span>
+}
+
+<root><A x="1" /></root>"#;
+        let (_, summary) = parser.parse(v1).unwrap();
+        assert_eq!(summary.added.len(), 2);
+        assert!(summary.root_changed);
+
+        let (_, summary) = parser.parse(v1).unwrap();
+        assert!(summary.is_empty());
+
+        let v2 = r#"def A(x) {
+    <h1>${x}</h1>
+}
+<!-- B removed, C added -->
+def C(z) {
+    <em>${z}</em>
+}
+
+<root><A x="1" /></root>"#;
+        let (_, summary) = parser.parse(v2).unwrap();
+        assert_eq!(summary.changed, vec!["A".to_string()]);
+        assert_eq!(summary.added, vec!["C".to_string()]);
+        assert_eq!(summary.removed, vec!["B".to_string()]);
+        assert!(!summary.root_changed);
+        assert!(parser.components().contains_key("C"));
+    }
+
+    #[test]
+    fn test_incremental_parser_import_change() {
+        let mut parser = IncrementalParser::new();
+        let v1 = "import ./Card.gpml as Card\n\n<root><Card /></root>";
+        let (_, summary) = parser.parse(v1).unwrap();
+        assert!(summary.imports_changed);
+
+        let (_, summary) = parser.parse(v1).unwrap();
+        assert!(summary.is_empty());
+
+        let v2 = "import ./Panel.gpml as Card\n\n<root><Card /></root>";
+        let (_, summary) = parser.parse(v2).unwrap();
+        assert!(summary.imports_changed);
+        assert!(!summary.root_changed);
+        assert!(!summary.is_empty());
+
+        let (_, summary) = parser.parse("<root><Card /></root>").unwrap();
+        assert!(summary.imports_changed);
+        assert!(!summary.root_changed);
+    }
+
+    #[test]
+    fn test_parse_document_lenient() {
+        let input = "<root>\n    <h1>Title</h1>\n    <div class=\"broken\n    <p>Still here</p>\n    <span>Unclosed\n";
+        let (document, warnings) = GPMLParser::parse_document_lenient(input);
+
+        let GPMLNode::Document { root: Some(root), .. } = document else {
+            panic!("Expected a document with a root");
+        };
+        let tags = root.children.iter().filter_map(|c| c.as_element()).map(|e| e.tag.as_str()).collect::<Vec<_>>();
+        assert_eq!(tags, vec!["h1", "p", "span"]);
+
+        let messages = warnings.iter().map(|w| w.message.as_str()).collect::<Vec<_>>();
+        assert_eq!(messages, vec!["Malformed element", "Unclosed element <span>", "Unclosed element <root>"]);
+        assert_eq!(warnings[0].byte_offset, input.find("<div").unwrap());
+
+        // Leading garbage is skipped up to the root element
+        let (document, warnings) = GPMLParser::parse_file_lenient("<!-- note --> oops\n<root />");
+        assert!(matches!(document, GPMLNode::Document { root: Some(_), .. }));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].byte_offset, 14);
+    }
 }
@@ -0,0 +1,99 @@
+use crate::ast::*;
+use gpui::{AnyElement, SharedString};
+use std::cell::RefCell;
+
+/// Attribute of a `<portal>` naming the overlay layer its content is rendered into
+pub const PORTAL_TARGET_ATTRIBUTE: &str = "target";
+
+/// Layer of a `<portal>` without a `target` attribute
+pub const DEFAULT_PORTAL_TARGET: &str = "overlay";
+
+thread_local! {
+    static CURRENT_PORTALS: RefCell<Option<Vec<(SharedString, AnyElement)>>> = const { RefCell::new(None) };
+}
+
+/// Collects the content of the `<portal>` elements while a GPML tree is being rendered.
+///
+/// The canvas installs the scope for the duration of a render pass with [`PortalScope::enter`],
+/// the portals register their rendered content instead of returning it to their parent, and the
+/// canvas renders it in overlay layers above the document, out of the clipping of the parents.
+pub struct PortalScope;
+
+impl PortalScope {
+    /// Collect the portal content until [`PortalScopeGuard::finish`] is called or the guard is dropped
+    pub fn enter() -> PortalScopeGuard {
+        let previous = CURRENT_PORTALS.with(|portals| portals.borrow_mut().replace(Vec::new()));
+        PortalScopeGuard { previous: Some(previous) }
+    }
+
+    /// Register the rendered content of a portal, the element is given back when no render pass
+    /// installed a scope so the caller can render it in place
+    pub fn register(target: SharedString, element: AnyElement) -> Result<(), AnyElement> {
+        CURRENT_PORTALS.with(|portals| match portals.borrow_mut().as_mut() {
+            Some(portals) => {
+                portals.push((target, element));
+                Ok(())
+            }
+            None => Err(element),
+        })
+    }
+}
+
+/// Restores the previous portal scope when finished or dropped
+pub struct PortalScopeGuard {
+    previous: Option<Option<Vec<(SharedString, AnyElement)>>>,
+}
+
+impl PortalScopeGuard {
+    /// Stop collecting and return the registered content with its target, in document order
+    pub fn finish(mut self) -> Vec<(SharedString, AnyElement)> {
+        self.restore().unwrap_or_default()
+    }
+
+    fn restore(&mut self) -> Option<Vec<(SharedString, AnyElement)>> {
+        let previous = self.previous.take()?;
+        CURRENT_PORTALS.with(|portals| std::mem::replace(&mut *portals.borrow_mut(), previous))
+    }
+}
+
+impl Drop for PortalScopeGuard {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+/// Get the overlay layer a `<portal>` renders into, `"overlay"` by default
+pub fn portal_target(element: &Element) -> SharedString {
+    element
+        .get_attribute(PORTAL_TARGET_ATTRIBUTE)
+        .map(|v| v.as_string())
+        .filter(|target| !target.is_empty())
+        .unwrap_or_else(|| DEFAULT_PORTAL_TARGET.to_string())
+        .into()
+}
+
+/// Group the portal content by target, the targets keep the order of their first portal
+pub fn group_portals(portals: Vec<(SharedString, AnyElement)>) -> Vec<(SharedString, Vec<AnyElement>)> {
+    let mut layers: Vec<(SharedString, Vec<AnyElement>)> = Vec::new();
+    for (target, element) in portals {
+        match layers.iter_mut().find(|(name, _)| *name == target) {
+            Some((_, elements)) => elements.push(element),
+            None => layers.push((target, vec![element])),
+        }
+    }
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portal_target() {
+        let portal = GPMLElement::new(PORTAL_TAG.to_string());
+        assert_eq!(portal_target(&portal).to_string(), "overlay");
+
+        let portal = portal.with_attribute(PORTAL_TARGET_ATTRIBUTE.to_string(), AttributeValue::Literal("body".to_string()));
+        assert_eq!(portal_target(&portal).to_string(), "body");
+    }
+}
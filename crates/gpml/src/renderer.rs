@@ -2,12 +2,58 @@ use crate::ast::*;
 use crate::error::*;
 use crate::component::*;
 use crate::elements::*;
+use crate::binding::BindingScope;
+use crate::style::GPMLStylesheet;
+use crate::animation::{collect_animations, GPMLAnimations};
 use gpui::*;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
 /// GPML renderer that converts GPML AST to GPUI elements
 pub struct GPMLRenderer;
 
+/// An edit of a GPML tree computed by [`GPMLRenderer::diff`].
+///
+/// Paths are the child indexes from the root node. [`DiffOp::Remove`] and the `from` of
+/// [`DiffOp::Move`] address the old tree, the other paths address the new tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffOp {
+    /// The node was inserted
+    Insert { path: Vec<usize>, node: GPMLNode },
+    /// The node was removed
+    Remove { path: Vec<usize> },
+    /// The attributes of the element or the text changed, an element is given without its
+    /// children which are diffed separately
+    Update { path: Vec<usize>, node: GPMLNode },
+    /// An unchanged node moved to another position of its parent
+    Move { from: Vec<usize>, to: Vec<usize> },
+}
+
 impl GPMLRenderer {
+    /// Compute the edits turning the `old` tree into the `new` tree.
+    ///
+    /// The children are matched with a longest common subsequence of their identity (tag and
+    /// `key` or `id` attribute of elements, content of text nodes). An unmatched child equal to an
+    /// unmatched child of the other tree is a move, the others are removed or inserted.
+    pub fn diff(old: &GPMLNode, new: &GPMLNode) -> Vec<DiffOp> {
+        let mut ops = Vec::new();
+        match (old, new) {
+            (GPMLNode::Document { root: Some(old), .. }, GPMLNode::Document { root: Some(new), .. }) => {
+                diff_elements(old, new, &[], &[], &mut ops);
+            }
+            _ => diff_nodes(old, new, &[], &[], &mut ops),
+        }
+        ops
+    }
+
+    /// Apply the edits computed by [`GPMLRenderer::diff`] to the `old` tree, returns the new tree.
+    ///
+    /// The unchanged subtrees are taken from the `old` tree, fails when an edit doesn't address a
+    /// node of the trees.
+    pub fn apply(old: &GPMLNode, ops: &[DiffOp]) -> GPMLResult<GPMLNode> {
+        apply_diff(old, ops, &mut |_, _| {})
+    }
+
     /// Render a GPML element to a GPUI element
     pub fn render_element<T>(
         element: &GPMLElement,
@@ -18,134 +64,508 @@ impl GPMLRenderer {
     where
         T: 'static,
     {
+        // A root element with a falsy `gpml:if` renders nothing
+        if !context.is_element_visible(element) {
+            return Ok(Empty.into_any_element());
+        }
+
         // First resolve any custom components
         let resolved_element = resolve_element(element, context, resolver)?;
 
-        // Then render to GPUI
-        Self::render_resolved_element(&resolved_element, cx)
+        // Then render to GPUI, passing the canvas handle down to the `gpml:bind` elements,
+        // with the rules of the `<style>` blocks and `<animation>` definitions available to the element renderers
+        let scope = BindingScope::new(context.canvas_handle.clone());
+        let _stylesheet = Rc::new(GPMLStylesheet::from_element(&resolved_element)).enter();
+        let mut animations = context.animations.clone();
+        animations.extend(collect_animations(&resolved_element));
+        let _animations = Rc::new(GPMLAnimations::new(animations, context.animation_generation)).enter();
+        Self::render_resolved_element(&resolved_element, &scope, cx)
     }
 
     /// Render an already resolved GPML element to a GPUI element (skips component resolution)
-    pub fn render_resolved_element_direct<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    pub fn render_resolved_element_direct<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    where
+        T: 'static,
+    {
+        Self::render_resolved_element(element, scope, cx)
+    }
+
+    fn render_resolved_element<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
-        Self::render_resolved_element(element, cx)
+        let rendered = Self::render_tag(element, scope, cx)?;
+        Ok(GPMLAnimations::animate_current(element, rendered))
     }
 
-    fn render_resolved_element<T>(element: &GPMLElement, cx: &mut Context<T>) -> GPMLResult<AnyElement>
+    fn render_tag<T>(element: &GPMLElement, scope: &BindingScope, cx: &mut Context<T>) -> GPMLResult<AnyElement>
     where
         T: 'static,
     {
         match element.tag.as_str() {
             // Layout containers
-            "div" => layout::DivElement::render(element, cx),
-            "flex" => layout::FlexElement::render(element, cx),
-            "root" => layout::RootElement::render(element, cx),
+            "div" => layout::DivElement::render(element, scope, cx),
+            "flex" => layout::FlexElement::render(element, scope, cx),
+            "root" => layout::RootElement::render(element, scope, cx),
 
             // Semantic elements
-            "article" => semantic::ArticleElement::render(element, cx),
-            "section" => semantic::SectionElement::render(element, cx),
-            "aside" => semantic::AsideElement::render(element, cx),
-            "nav" => semantic::NavElement::render(element, cx),
-            "header" => semantic::HeaderElement::render(element, cx),
-            "footer" => semantic::FooterElement::render(element, cx),
-            "main" => semantic::MainElement::render(element, cx),
+            "article" => semantic::ArticleElement::render(element, scope, cx),
+            "section" => semantic::SectionElement::render(element, scope, cx),
+            "aside" => semantic::AsideElement::render(element, scope, cx),
+            "nav" => semantic::NavElement::render(element, scope, cx),
+            "header" => semantic::HeaderElement::render(element, scope, cx),
+            "footer" => semantic::FooterElement::render(element, scope, cx),
+            "main" => semantic::MainElement::render(element, scope, cx),
 
             // Text elements
-            "h1" => text::HeadingElement::render(element, cx, text::HeadingLevel::H1),
-            "h2" => text::HeadingElement::render(element, cx, text::HeadingLevel::H2),
-            "h3" => text::HeadingElement::render(element, cx, text::HeadingLevel::H3),
-            "h4" => text::HeadingElement::render(element, cx, text::HeadingLevel::H4),
-            "h5" => text::HeadingElement::render(element, cx, text::HeadingLevel::H5),
-            "h6" => text::HeadingElement::render(element, cx, text::HeadingLevel::H6),
-            "p" => text::ParagraphElement::render(element, cx),
-            "text" => text::TextElement::render(element, cx),
-            "label" => text::LabelElement::render(element, cx),
-            "span" => text::SpanElement::render(element, cx),
+            "h1" => text::HeadingElement::render(element, scope, cx, text::HeadingLevel::H1),
+            "h2" => text::HeadingElement::render(element, scope, cx, text::HeadingLevel::H2),
+            "h3" => text::HeadingElement::render(element, scope, cx, text::HeadingLevel::H3),
+            "h4" => text::HeadingElement::render(element, scope, cx, text::HeadingLevel::H4),
+            "h5" => text::HeadingElement::render(element, scope, cx, text::HeadingLevel::H5),
+            "h6" => text::HeadingElement::render(element, scope, cx, text::HeadingLevel::H6),
+            "p" => text::ParagraphElement::render(element, scope, cx),
+            "text" => text::TextElement::render(element, scope, cx),
+            "label" => text::LabelElement::render(element, scope, cx),
+            "span" => text::SpanElement::render(element, scope, cx),
 
             // Text formatting
-            "strong" | "b" => formatting::StrongElement::render(element, cx),
-            "em" | "i" => formatting::EmElement::render(element, cx),
-            "u" => formatting::UnderlineElement::render(element, cx),
-            "s" => formatting::StrikethroughElement::render(element, cx),
-            "code" => formatting::CodeElement::render(element, cx),
-            "pre" => formatting::PreElement::render(element, cx),
-            "cite" => formatting::CiteElement::render(element, cx),
-            "mark" => formatting::MarkElement::render(element, cx),
-            "small" => formatting::SmallElement::render(element, cx),
-            "sub" => formatting::SubElement::render(element, cx),
-            "sup" => formatting::SupElement::render(element, cx),
+            "strong" | "b" => formatting::StrongElement::render(element, scope, cx),
+            "em" | "i" => formatting::EmElement::render(element, scope, cx),
+            "u" => formatting::UnderlineElement::render(element, scope, cx),
+            "s" => formatting::StrikethroughElement::render(element, scope, cx),
+            "code" => formatting::CodeElement::render(element, scope, cx),
+            "pre" => formatting::PreElement::render(element, scope, cx),
+            "cite" => formatting::CiteElement::render(element, scope, cx),
+            "mark" => formatting::MarkElement::render(element, scope, cx),
+            "small" => formatting::SmallElement::render(element, scope, cx),
+            "sub" => formatting::SubElement::render(element, scope, cx),
+            "sup" => formatting::SupElement::render(element, scope, cx),
 
             // Lists
-            "ul" => list::UlElement::render(element, cx),
-            "ol" => list::OlElement::render(element, cx),
-            "li" => list::LiElement::render(element, cx),
-            "dl" => list::DlElement::render(element, cx),
-            "dt" => list::DtElement::render(element, cx),
-            "dd" => list::DdElement::render(element, cx),
+            "ul" => list::UlElement::render(element, scope, cx),
+            "ol" => list::OlElement::render(element, scope, cx),
+            "li" => list::LiElement::render(element, scope, cx),
+            "dl" => list::DlElement::render(element, scope, cx),
+            "dt" => list::DtElement::render(element, scope, cx),
+            "dd" => list::DdElement::render(element, scope, cx),
 
             // Links and media
-            "a" => media::LinkElement::render(element, cx),
-            "img" => media::ImgElement::render(element, cx),
+            "a" => media::LinkElement::render(element, scope, cx),
+            "link" if element.get_attribute(crate::router::LINK_TO_ATTRIBUTE).is_some() => {
+                media::RouteLinkElement::render(element, scope, cx)
+            }
+            "img" => media::ImgElement::render(element, scope, cx),
 
             // Tables (HTML elements)
-            "table" => table::TableElement::render(element, cx),
-            "thead" => table::TheadElement::render(element, cx),
-            "tbody" => table::TbodyElement::render(element, cx),
-            "tfoot" => table::TfootElement::render(element, cx),
-            "tr" => table::TrElement::render(element, cx),
-            "td" => table::TdElement::render(element, cx),
-            "th" => table::ThElement::render(element, cx),
-            "caption" => table::CaptionElement::render(element, cx),
+            "table" => table::TableElement::render(element, scope, cx),
+            "thead" => table::TheadElement::render(element, scope, cx),
+            "tbody" => table::TbodyElement::render(element, scope, cx),
+            "tfoot" => table::TfootElement::render(element, scope, cx),
+            "tr" => table::TrElement::render(element, scope, cx),
+            "td" => table::TdElement::render(element, scope, cx),
+            "th" => table::ThElement::render(element, scope, cx),
+            "caption" => table::CaptionElement::render(element, scope, cx),
 
             // Forms
-            "form" => form::FormElement::render(element, cx),
-            "fieldset" => form::FieldsetElement::render(element, cx),
-            "legend" => form::LegendElement::render(element, cx),
-            "textarea" => form::TextareaElement::render(element, cx),
+            "form" => form::FormElement::render(element, scope, cx),
+            "fieldset" => form::FieldsetElement::render(element, scope, cx),
+            "legend" => form::LegendElement::render(element, scope, cx),
+            "textarea" => form::TextareaElement::render(element, scope, cx),
 
             // Quotes
-            "blockquote" => quote::BlockquoteElement::render(element, cx),
-            "q" => quote::QElement::render(element, cx),
+            "blockquote" => quote::BlockquoteElement::render(element, scope, cx),
+            "q" => quote::QElement::render(element, scope, cx),
 
             // Line breaks and separators
-            "br" => misc::BrElement::render(element, cx),
-            "hr" => misc::HrElement::render(element, cx),
+            "br" => misc::BrElement::render(element, scope, cx),
+            "hr" => misc::HrElement::render(element, scope, cx),
 
             // Interactive elements
-            "button" => interactive::ButtonElement::render(element, cx),
-            "input" => interactive::InputElement::render(element, cx),
-            "checkbox" => interactive::CheckboxElement::render(element, cx),
-            "radio" => interactive::RadioElement::render(element, cx),
-            "slider" => interactive::SliderElement::render(element, cx),
-            "switch" => interactive::SwitchElement::render(element, cx),
+            "button" => interactive::ButtonElement::render(element, scope, cx),
+            "input" => interactive::InputElement::render(element, scope, cx),
+            "checkbox" => interactive::CheckboxElement::render(element, scope, cx),
+            "radio" => interactive::RadioElement::render(element, scope, cx),
+            "slider" => interactive::SliderElement::render(element, scope, cx),
+            "switch" => interactive::SwitchElement::render(element, scope, cx),
+            "select" => interactive::SelectElement::render(element, scope, cx),
 
             // Layout and structure
-            "modal" => misc::ModalElement::render(element, cx),
-            "popover" => misc::PopoverElement::render(element, cx),
-            "tooltip" => misc::TooltipElement::render(element, cx),
-            "scroll" => misc::ScrollElement::render(element, cx),
-            "resizable" => misc::ResizableElement::render(element, cx),
+            "modal" => misc::ModalElement::render(element, scope, cx),
+            "popover" => misc::PopoverElement::render(element, scope, cx),
+            "tooltip" => misc::TooltipElement::render(element, scope, cx),
+            "scroll" => misc::ScrollElement::render(element, scope, cx),
+            "resizable" => misc::ResizableElement::render(element, scope, cx),
 
             // Display elements
-            "icon" => media::IconElement::render(element, cx),
-            "image" => media::ImageElement::render(element, cx),
-            "badge" => media::BadgeElement::render(element, cx),
-            "avatar" => media::AvatarElement::render(element, cx),
+            "icon" => media::IconElement::render(element, scope, cx),
+            "image" => media::ImageElement::render(element, scope, cx),
+            "badge" => media::BadgeElement::render(element, scope, cx),
+            "avatar" => media::AvatarElement::render(element, scope, cx),
 
             // Lists and data (GPML-specific)
-            "list" => list::ListElement::render(element, cx),
-            "tree" => misc::TreeElement::render(element, cx),
+            "list" => list::ListElement::render(element, scope, cx),
+            "tree" => misc::TreeElement::render(element, scope, cx),
+
+            // Overlay content, rendered by the canvas above the document
+            PORTAL_TAG => misc::PortalElement::render(element, scope, cx),
+
+            // Routing, the resolver keeps only the matching route
+            "router" | "route" => layout::DivElement::render(element, scope, cx),
+
+            // Slot placeholder outside of a component body renders its fallback content
+            "slot" | "block" => layout::DivElement::render(element, scope, cx),
 
             // No-op elements (parse but don't render)
-            "script" | "style" | "meta" | "link" | "base" => misc::NoopElement::render(element, cx),
+            // An unresolved <include> renders nothing, the resolver inlines the included root
+            "script" | "style" | "meta" | "link" | "base" | "animation" | "keyframe" | "include" => misc::NoopElement::render(element, scope, cx),
 
             // Unknown tag - render as div with warning
             _ => {
                 tracing::warn!("Unknown GPML tag: {}", element.tag);
-                layout::DivElement::render(element, cx)
+                layout::DivElement::render(element, scope, cx)
+            }
+        }
+    }
+}
+fn diff_nodes(old: &GPMLNode, new: &GPMLNode, old_path: &[usize], new_path: &[usize], ops: &mut Vec<DiffOp>) {
+    match (old, new) {
+        (GPMLNode::Element(old), GPMLNode::Element(new)) if old.tag == new.tag => {
+            diff_elements(old, new, old_path, new_path, ops);
+        }
+        (GPMLNode::Text(_), GPMLNode::Text(_)) | (GPMLNode::Expression(_), GPMLNode::Expression(_)) if old != new => {
+            ops.push(DiffOp::Update { path: new_path.to_vec(), node: new.clone() });
+        }
+        _ if old != new => {
+            ops.push(DiffOp::Remove { path: old_path.to_vec() });
+            ops.push(DiffOp::Insert { path: new_path.to_vec(), node: new.clone() });
+        }
+        _ => {}
+    }
+}
+
+fn diff_elements(old: &Element, new: &Element, old_path: &[usize], new_path: &[usize], ops: &mut Vec<DiffOp>) {
+    if old.attributes != new.attributes || old.self_closing != new.self_closing || old.conditions != new.conditions {
+        let node = Element { children: Vec::new(), ..new.clone() };
+        ops.push(DiffOp::Update { path: new_path.to_vec(), node: GPMLNode::Element(node) });
+    }
+    diff_children(&old.children, &new.children, old_path, new_path, ops);
+}
+
+fn diff_children(old: &[GPMLNode], new: &[GPMLNode], old_path: &[usize], new_path: &[usize], ops: &mut Vec<DiffOp>) {
+    let child_path = |path: &[usize], ix: usize| {
+        let mut path = path.to_vec();
+        path.push(ix);
+        path
+    };
+
+    let matches = lcs_matches(old, new);
+    let mut removed = (0..old.len()).filter(|ix| !matches.iter().any(|(old_ix, _)| old_ix == ix)).collect::<Vec<_>>();
+    let inserted = (0..new.len()).filter(|ix| !matches.iter().any(|(_, new_ix)| new_ix == ix));
+
+    for (old_ix, new_ix) in &matches {
+        diff_nodes(&old[*old_ix], &new[*new_ix], &child_path(old_path, *old_ix), &child_path(new_path, *new_ix), ops);
+    }
+
+    let mut inserts = Vec::new();
+    for new_ix in inserted {
+        match removed.iter().position(|old_ix| old[*old_ix] == new[new_ix]) {
+            Some(pos) => {
+                let old_ix = removed.remove(pos);
+                ops.push(DiffOp::Move { from: child_path(old_path, old_ix), to: child_path(new_path, new_ix) });
+            }
+            None => inserts.push(DiffOp::Insert { path: child_path(new_path, new_ix), node: new[new_ix].clone() }),
+        }
+    }
+    ops.extend(removed.into_iter().map(|old_ix| DiffOp::Remove { path: child_path(old_path, old_ix) }));
+    ops.extend(inserts);
+}
+
+/// The edits of a diff keyed by path
+struct DiffEdits<'a> {
+    /// Old paths of the removed and moved nodes
+    removed: HashSet<&'a [usize]>,
+    /// New paths of the inserted and moved nodes
+    placed: HashMap<&'a [usize], Placed<'a>>,
+    /// New paths of the updated nodes
+    updates: HashMap<&'a [usize], &'a GPMLNode>,
+}
+
+enum Placed<'a> {
+    Inserted(&'a GPMLNode),
+    Moved(&'a [usize]),
+}
+
+impl<'a> DiffEdits<'a> {
+    fn new(ops: &'a [DiffOp]) -> Self {
+        let mut edits = Self { removed: HashSet::new(), placed: HashMap::new(), updates: HashMap::new() };
+        for op in ops {
+            match op {
+                DiffOp::Insert { path, node } => {
+                    edits.placed.insert(path.as_slice(), Placed::Inserted(node));
+                }
+                DiffOp::Remove { path } => {
+                    edits.removed.insert(path.as_slice());
+                }
+                DiffOp::Update { path, node } => {
+                    edits.updates.insert(path.as_slice(), node);
+                }
+                DiffOp::Move { from, to } => {
+                    edits.removed.insert(from.as_slice());
+                    edits.placed.insert(to.as_slice(), Placed::Moved(from.as_slice()));
+                }
+            }
+        }
+        edits
+    }
+}
+
+/// Called with the new path of each node of the new tree and its path in the old tree, or `None`
+/// for the inserted nodes whose descendants aren't reported
+pub(crate) type KeptNodes<'a> = dyn FnMut(&[usize], Option<&[usize]>) + 'a;
+
+/// Apply the edits to the `old` tree, reporting the nodes to `kept`
+pub(crate) fn apply_diff(old: &GPMLNode, ops: &[DiffOp], kept: &mut KeptNodes) -> GPMLResult<GPMLNode> {
+    let edits = DiffEdits::new(ops);
+    match old {
+        // Paths of the diff of two documents start at their root element
+        GPMLNode::Document { extends, imports, components, root: Some(root) } => {
+            let root = GPMLNode::Element(root.clone());
+            match apply_node(&root, &[], &[], &root, &edits, kept)? {
+                GPMLNode::Element(root) => Ok(GPMLNode::Document {
+                    extends: extends.clone(),
+                    imports: imports.clone(),
+                    components: components.clone(),
+                    root: Some(root),
+                }),
+                _ => Err(invalid_edit(&[])),
             }
         }
+        _ => apply_node(old, &[], &[], old, &edits, kept),
+    }
+}
+
+fn apply_node(
+    old: &GPMLNode,
+    old_path: &[usize],
+    new_path: &[usize],
+    old_root: &GPMLNode,
+    edits: &DiffEdits,
+    kept: &mut KeptNodes,
+) -> GPMLResult<GPMLNode> {
+    if let Some(node) = place_node(new_path, old_root, edits, kept) {
+        return node;
+    }
+    kept(new_path, Some(old_path));
+
+    let GPMLNode::Element(old_element) = old else {
+        return match edits.updates.get(new_path) {
+            Some(node) => Ok((*node).clone()),
+            None => Ok(old.clone()),
+        };
+    };
+    let mut element = match edits.updates.get(new_path) {
+        Some(GPMLNode::Element(node)) => Element { children: Vec::new(), ..node.clone() },
+        Some(_) => return Err(invalid_edit(new_path)),
+        None => Element {
+            tag: old_element.tag.clone(),
+            attributes: old_element.attributes.clone(),
+            children: Vec::new(),
+            self_closing: old_element.self_closing,
+            conditions: old_element.conditions.clone(),
+            namespace: old_element.namespace.clone(),
+        },
+    };
+
+    let child_path = |path: &[usize], ix: usize| {
+        let mut path = path.to_vec();
+        path.push(ix);
+        path
+    };
+    // The matched children keep their order, the inserted and moved ones fill the gaps
+    let mut matched = old_element
+        .children
+        .iter()
+        .enumerate()
+        .filter(|(ix, _)| !edits.removed.contains(child_path(old_path, *ix).as_slice()));
+    let placed = edits.placed.keys().filter(|path| path.len() == new_path.len() + 1 && path.starts_with(new_path)).count();
+    let count = matched.clone().count() + placed;
+    for new_ix in 0..count {
+        let path = child_path(new_path, new_ix);
+        let child = match place_node(&path, old_root, edits, kept) {
+            Some(child) => child?,
+            None => {
+                let (old_ix, child) = matched.next().ok_or_else(|| invalid_edit(&path))?;
+                apply_node(child, &child_path(old_path, old_ix), &path, old_root, edits, kept)?
+            }
+        };
+        element.children.push(child);
+    }
+    Ok(GPMLNode::Element(element))
+}
+
+/// The node inserted or moved to the `path`, if any
+fn place_node(
+    path: &[usize],
+    old_root: &GPMLNode,
+    edits: &DiffEdits,
+    kept: &mut KeptNodes,
+) -> Option<GPMLResult<GPMLNode>> {
+    match edits.placed.get(path)? {
+        Placed::Inserted(node) => {
+            kept(path, None);
+            Some(Ok((*node).clone()))
+        }
+        Placed::Moved(from) => match node_at(old_root, from) {
+            Some(node) => {
+                report_subtree(node, path, from, kept);
+                Some(Ok(node.clone()))
+            }
+            None => Some(Err(invalid_edit(from))),
+        },
+    }
+}
+
+/// Report a moved subtree, its nodes keep their relative paths
+fn report_subtree(node: &GPMLNode, new_path: &[usize], old_path: &[usize], kept: &mut KeptNodes) {
+    kept(new_path, Some(old_path));
+    if let GPMLNode::Element(element) = node {
+        for (ix, child) in element.children.iter().enumerate() {
+            let (mut new_path, mut old_path) = (new_path.to_vec(), old_path.to_vec());
+            new_path.push(ix);
+            old_path.push(ix);
+            report_subtree(child, &new_path, &old_path, kept);
+        }
+    }
+}
+
+fn node_at<'a>(root: &'a GPMLNode, path: &[usize]) -> Option<&'a GPMLNode> {
+    path.iter().try_fold(root, |node, ix| match node {
+        GPMLNode::Element(element) => element.children.get(*ix),
+        _ => None,
+    })
+}
+
+fn invalid_edit(path: &[usize]) -> GPMLError {
+    GPMLError::RenderError {
+        message: format!("Invalid tree edit at path {:?}", path),
+    }
+}
+
+/// Whether two nodes are the same node of the tree, possibly with changed content
+fn same_identity(old: &GPMLNode, new: &GPMLNode) -> bool {
+    match (old, new) {
+        (GPMLNode::Element(old), GPMLNode::Element(new)) => {
+            old.tag == new.tag && ["key", "id"].iter().all(|name| old.get_attribute(name) == new.get_attribute(name))
+        }
+        (GPMLNode::Text(_), GPMLNode::Text(_)) | (GPMLNode::Expression(_), GPMLNode::Expression(_)) => true,
+        _ => old == new,
+    }
+}
+
+/// Index pairs of the longest common subsequence of the children by identity
+fn lcs_matches(old: &[GPMLNode], new: &[GPMLNode]) -> Vec<(usize, usize)> {
+    // lengths[i][j] is the LCS length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if same_identity(&old[i], &new[j]) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut matches = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if same_identity(&old[i], &new[j]) {
+            matches.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(tag: &str, children: Vec<GPMLNode>) -> GPMLNode {
+        let mut element = GPMLElement::new(tag.to_string());
+        element.children = children;
+        GPMLNode::Element(element)
+    }
+
+    fn text(s: &str) -> GPMLNode {
+        GPMLNode::Text(s.to_string())
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let tree = element("root", vec![element("p", vec![text("Hello")])]);
+        assert_eq!(GPMLRenderer::diff(&tree, &tree.clone()), vec![]);
+    }
+
+    #[test]
+    fn test_diff_insert_remove_update() {
+        let old = element("root", vec![element("h1", vec![text("Title")]), element("p", vec![])]);
+        let new = element("root", vec![element("h1", vec![text("New title")]), element("span", vec![])]);
+        assert_eq!(
+            GPMLRenderer::diff(&old, &new),
+            vec![
+                DiffOp::Update { path: vec![0, 0], node: text("New title") },
+                DiffOp::Remove { path: vec![1] },
+                DiffOp::Insert { path: vec![1], node: element("span", vec![]) },
+            ]
+        );
+
+        let GPMLNode::Element(mut updated) = new.clone() else { unreachable!() };
+        updated.attributes.insert("padding".to_string(), AttributeValue::Number(4.0));
+        assert_eq!(
+            GPMLRenderer::diff(&new, &GPMLNode::Element(updated.clone())),
+            vec![DiffOp::Update { path: vec![], node: GPMLNode::Element(Element { children: vec![], ..updated }) }]
+        );
+    }
+
+    #[test]
+    fn test_diff_move() {
+        let a = element("a", vec![text("A")]);
+        let b = element("b", vec![text("B")]);
+        let c = element("c", vec![text("C")]);
+        let old = element("root", vec![a.clone(), b.clone(), c.clone()]);
+        let new = element("root", vec![c, a, b]);
+        assert_eq!(GPMLRenderer::diff(&old, &new), vec![DiffOp::Move { from: vec![2], to: vec![0] }]);
+    }
+
+    #[test]
+    fn test_apply_diff() {
+        let a = element("a", vec![text("A")]);
+        let b = element("b", vec![element("p", vec![text("B")])]);
+        let c = element("c", vec![text("C")]);
+        let old = element("root", vec![a.clone(), b.clone(), c.clone(), text("end")]);
+
+        let GPMLNode::Element(mut updated) = element("b", vec![element("p", vec![text("B2")]), element("span", vec![])]) else { unreachable!() };
+        updated.attributes.insert("padding".to_string(), AttributeValue::Number(4.0));
+        let new_trees = vec![
+            old.clone(),
+            element("root", vec![c.clone(), element("x", vec![]), a.clone(), GPMLNode::Element(updated), text("fin")]),
+            element("root", vec![]),
+            element("other", vec![a.clone()]),
+            text("replaced"),
+        ];
+        for new in new_trees {
+            let ops = GPMLRenderer::diff(&old, &new);
+            assert_eq!(GPMLRenderer::apply(&old, &ops).unwrap(), new, "{:?}", ops);
+        }
+
+        let ops = vec![DiffOp::Insert { path: vec![9], node: text("x") }];
+        assert!(GPMLRenderer::apply(&old, &ops).is_err());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,134 @@
+use crate::ast::*;
+use crate::canvas::GPMLCanvas;
+use gpui::{App, WeakEntity};
+
+/// Attribute of a `<link>` naming the path it navigates to
+pub const LINK_TO_ATTRIBUTE: &str = "to";
+
+/// Whether a `<route path="...">` pattern matches the path.
+///
+/// `"*"` matches every path, a trailing `/*` segment matches the rest of the path
+/// (`"/users/*"` matches `"/users"` and `"/users/42/edit"`), and `:name` segments match any
+/// single segment. Trailing slashes are ignored.
+pub fn route_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim();
+    if pattern == "*" {
+        return true;
+    }
+
+    let mut path_segments = path.split('/').filter(|s| !s.is_empty());
+    for segment in pattern.split('/').filter(|s| !s.is_empty()) {
+        if segment == "*" {
+            return true;
+        }
+        match path_segments.next() {
+            Some(actual) if segment.starts_with(':') || segment == actual => {}
+            _ => return false,
+        }
+    }
+    path_segments.next().is_none()
+}
+
+/// Index of the route to show for the path: the first matching route, catch-all `"*"` routes
+/// only match when no other route does
+pub fn select_route<'a>(patterns: impl IntoIterator<Item = &'a str>, path: &str) -> Option<usize> {
+    let mut fallback = None;
+    for (ix, pattern) in patterns.into_iter().enumerate() {
+        if pattern.trim() == "*" {
+            fallback = fallback.or(Some(ix));
+        } else if route_matches(pattern, path) {
+            return Some(ix);
+        }
+    }
+    fallback
+}
+
+/// Keep only the `<route>` child of a `<router>` matching the path, other children are kept
+pub fn filter_routes(children: Vec<GPMLNode>, path: &str) -> Vec<GPMLNode> {
+    let route_path = |node: &GPMLNode| match node {
+        GPMLNode::Element(element) if element.tag == "route" => Some(
+            element
+                .get_attribute("path")
+                .map(|v| v.as_string())
+                .unwrap_or_default(),
+        ),
+        _ => None,
+    };
+
+    let patterns = children.iter().filter_map(route_path).collect::<Vec<_>>();
+    let selected = select_route(patterns.iter().map(|p| p.as_str()), path);
+
+    let mut route_ix = 0;
+    children
+        .into_iter()
+        .filter(|child| {
+            if route_path(child).is_none() {
+                return true;
+            }
+            route_ix += 1;
+            selected == Some(route_ix - 1)
+        })
+        .collect()
+}
+
+/// Navigate the canvas to the path, used by `<link to="...">`
+pub fn navigate_canvas(canvas: &WeakEntity<GPMLCanvas>, path: String, cx: &mut App) {
+    let result = canvas.update(cx, |canvas, cx| canvas.navigate(&path, cx));
+    if let Err(e) = result {
+        tracing::warn!("Failed to navigate: {:?}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_matches() {
+        assert!(route_matches("/home", "/home"));
+        assert!(route_matches("/home", "/home/"));
+        assert!(route_matches("/", "/"));
+        assert!(!route_matches("/home", "/settings"));
+        assert!(!route_matches("/home", "/home/profile"));
+        assert!(!route_matches("/home/profile", "/home"));
+
+        assert!(route_matches("/users/:id", "/users/42"));
+        assert!(!route_matches("/users/:id", "/users"));
+
+        assert!(route_matches("*", "/anything/at/all"));
+        assert!(route_matches("/users/*", "/users"));
+        assert!(route_matches("/users/*", "/users/42/edit"));
+        assert!(!route_matches("/users/*", "/settings"));
+    }
+
+    #[test]
+    fn test_select_route() {
+        let patterns = ["*", "/home", "/settings"];
+        assert_eq!(select_route(patterns, "/settings"), Some(2));
+        assert_eq!(select_route(patterns, "/missing"), Some(0));
+        assert_eq!(select_route(["/home"], "/missing"), None);
+    }
+
+    #[test]
+    fn test_filter_routes() {
+        let route = |path: &str| {
+            GPMLNode::Element(
+                GPMLElement::new("route".to_string())
+                    .with_attribute("path".to_string(), AttributeValue::Literal(path.to_string())),
+            )
+        };
+        let children = vec![
+            GPMLNode::Element(GPMLElement::new("nav".to_string())),
+            route("/home"),
+            route("/settings"),
+        ];
+
+        let filtered = filter_routes(children, "/settings");
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(filtered[0].as_element().unwrap().tag, "nav");
+        assert_eq!(
+            filtered[1].as_element().unwrap().get_attribute("path").map(|v| v.as_string()),
+            Some("/settings".to_string())
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use crate::ast::*;
+use crate::error::*;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, Scope, AST};
+use std::collections::HashMap;
+
+/// Maximum number of operations a script may run, guards against infinite loops
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// Runs the `<script lang="rhai">` blocks of a GPML document.
+///
+/// The context variables are exposed to the script as a Rhai scope, and every
+/// top-level variable of the scope is written back after the script ran, so a
+/// script can derive new variables from the injected ones:
+///
+/// ```gpml
+/// <script lang="rhai">
+///     let total = price * quantity;
+///     let label = `Total: ${total}`;
+/// </script>
+/// ```
+///
+/// The engine is sandboxed, modules can't be imported (so there is no file access),
+/// `eval` is disabled and the number of operations is limited. Compiled scripts are
+/// cached by source, so unchanged scripts are not recompiled on hot reload.
+pub struct GPMLScriptEngine {
+    engine: Engine,
+    cache: HashMap<String, AST>,
+}
+
+impl GPMLScriptEngine {
+    pub fn new() -> Self {
+        let mut engine = Engine::new();
+        engine.set_module_resolver(DummyModuleResolver::new());
+        engine.disable_symbol("eval");
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.on_print(|text| tracing::info!("GPML script: {}", text));
+        engine.on_debug(|text, _, pos| tracing::debug!("GPML script {}: {}", pos, text));
+
+        Self {
+            engine,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Run all scripts of the tree in document order, updating `variables` in place
+    pub fn run_scripts(
+        &mut self,
+        root: &Element,
+        variables: &mut HashMap<String, AttributeValue>,
+    ) -> GPMLResult<()> {
+        let scripts = collect_scripts(root);
+        // Drop the compiled scripts that are no longer in the document
+        self.cache.retain(|source, _| scripts.contains(source));
+
+        for source in &scripts {
+            self.run(source, variables)?;
+        }
+        Ok(())
+    }
+
+    /// Run a single script, updating `variables` in place
+    pub fn run(
+        &mut self,
+        source: &str,
+        variables: &mut HashMap<String, AttributeValue>,
+    ) -> GPMLResult<()> {
+        if !self.cache.contains_key(source) {
+            let ast = self.engine.compile(source).map_err(|e| GPMLError::ScriptError {
+                message: e.to_string(),
+            })?;
+            self.cache.insert(source.to_string(), ast);
+        }
+        let ast = &self.cache[source];
+
+        let mut scope = Scope::new();
+        for (name, value) in variables.iter() {
+            scope.push_dynamic(name.as_str(), to_dynamic(value));
+        }
+
+        self.engine
+            .run_ast_with_scope(&mut scope, ast)
+            .map_err(|e| GPMLError::ScriptError {
+                message: e.to_string(),
+            })?;
+
+        for (name, _, value) in scope.iter() {
+            variables.insert(name.to_string(), from_dynamic(value));
+        }
+        Ok(())
+    }
+}
+
+impl Default for GPMLScriptEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collect the source of the Rhai `<script>` blocks in the tree, in document order.
+///
+/// Scripts without a `lang` attribute are Rhai, other languages are skipped.
+pub fn collect_scripts(root: &Element) -> Vec<String> {
+    fn walk(element: &Element, scripts: &mut Vec<String>) {
+        if element.tag == "script" {
+            let lang = element.get_attribute("lang").map(|v| v.as_string());
+            match lang.as_deref() {
+                None | Some("rhai") => {
+                    // Script text is XML text, `<` has to be written as `&lt;`
+                    let source = element.get_text_content();
+                    let source = quick_xml::escape::unescape(&source)
+                        .map(|s| s.into_owned())
+                        .unwrap_or(source);
+                    scripts.push(source);
+                }
+                Some(lang) => tracing::warn!("Unsupported script language '{}', skipping", lang),
+            }
+            return;
+        }
+        for child in &element.children {
+            if let GPMLNode::Element(child) = child {
+                walk(child, scripts);
+            }
+        }
+    }
+
+    let mut scripts = Vec::new();
+    walk(root, &mut scripts);
+    scripts
+}
+
+fn to_dynamic(value: &AttributeValue) -> Dynamic {
+    match value {
+        AttributeValue::Literal(s) => Dynamic::from(s.clone()),
+        AttributeValue::Expression(_) => Dynamic::from(value.as_string()),
+        AttributeValue::Number(n) => Dynamic::from_float(*n),
+        AttributeValue::Boolean(b) => Dynamic::from_bool(*b),
+        AttributeValue::Array(items) => Dynamic::from_array(items.iter().map(to_dynamic).collect()),
+    }
+}
+
+fn from_dynamic(value: Dynamic) -> AttributeValue {
+    if let Ok(b) = value.as_bool() {
+        AttributeValue::Boolean(b)
+    } else if let Ok(n) = value.as_float() {
+        AttributeValue::Number(n)
+    } else if let Ok(n) = value.as_int() {
+        AttributeValue::Number(n as f64)
+    } else if value.is::<rhai::Array>() {
+        let items = value.cast::<rhai::Array>();
+        AttributeValue::Array(items.into_iter().map(from_dynamic).collect())
+    } else {
+        AttributeValue::Literal(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::GPMLParser;
+
+    #[test]
+    fn test_run_script() {
+        let mut variables = HashMap::new();
+        variables.insert("price".to_string(), AttributeValue::Number(2.5));
+        variables.insert("quantity".to_string(), AttributeValue::Number(4.0));
+        variables.insert("tags".to_string(), AttributeValue::Array(vec![]));
+
+        let mut engine = GPMLScriptEngine::new();
+        engine
+            .run(
+                r#"
+                let total = price * quantity;
+                let label = `Total: ${total}`;
+                let expensive = total > 5.0;
+                tags.push(label.len());
+                "#,
+                &mut variables,
+            )
+            .unwrap();
+
+        assert_eq!(variables["total"], AttributeValue::Number(10.0));
+        assert_eq!(variables["label"], AttributeValue::Literal("Total: 10.0".to_string()));
+        assert_eq!(variables["expensive"], AttributeValue::Boolean(true));
+        assert_eq!(variables["tags"], AttributeValue::Array(vec![AttributeValue::Number(11.0)]));
+        assert_eq!(engine.cache.len(), 1);
+    }
+
+    #[test]
+    fn test_script_errors() {
+        let mut engine = GPMLScriptEngine::new();
+        let mut variables = HashMap::new();
+
+        assert!(matches!(
+            engine.run("let x = ;", &mut variables),
+            Err(GPMLError::ScriptError { .. })
+        ));
+        assert!(matches!(
+            engine.run("missing + 1", &mut variables),
+            Err(GPMLError::ScriptError { .. })
+        ));
+        // Sandboxed: no module imports, no eval, no infinite loops
+        assert!(engine.run(r#"import "std" as std;"#, &mut variables).is_err());
+        assert!(engine.run(r#"eval("1")"#, &mut variables).is_err());
+        assert!(engine.run("loop {}", &mut variables).is_err());
+    }
+
+    #[test]
+    fn test_collect_scripts() {
+        let element = GPMLParser::parse_xml_element(
+            r#"<root>
+    <script lang="rhai">let small = count &lt; 3;</script>
+    <div><script>let y = 1;</script></div>
+    <script lang="lua">y = 2</script>
+</root>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            collect_scripts(&element),
+            vec!["let small = count < 3;".to_string(), "let y = 1;".to_string()]
+        );
+    }
+}
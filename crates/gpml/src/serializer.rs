@@ -0,0 +1,377 @@
+use crate::ast::*;
+use crate::error::*;
+use gpui_component::json_ui::{UiChild, UiComponent, UiValue};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Converts GPML documents to and from the JSON UI format used by `JsonCanvas`.
+///
+/// The root element is emitted as a `UiComponent` (`type`, `props`, `children`). Documents
+/// additionally carry `imports` and `components` keys, which `JsonCanvas` ignores, so that
+/// the JSON can be converted back to GPML without loss.
+pub struct GPMLSerializer;
+
+impl GPMLSerializer {
+    /// Serialize any GPML node to JSON
+    pub fn to_json(node: &GPMLNode) -> GPMLResult<Value> {
+        match node {
+            GPMLNode::Document { extends, imports, components, root } => {
+                let mut object = match root {
+                    Some(root) => match Self::component_to_value(Self::element_to_component(root))? {
+                        Value::Object(object) => object,
+                        _ => Map::new(),
+                    },
+                    None => Map::new(),
+                };
+
+                if let Some(extends) = extends {
+                    object.insert("extends".to_string(), Value::String(extends.clone()));
+                }
+                if !imports.is_empty() {
+                    object.insert(
+                        "imports".to_string(),
+                        Value::Array(imports.iter().map(Self::import_to_json).collect()),
+                    );
+                }
+                if !components.is_empty() {
+                    let components = components
+                        .iter()
+                        .map(Self::component_def_to_json)
+                        .collect::<GPMLResult<Vec<_>>>()?;
+                    object.insert("components".to_string(), Value::Array(components));
+                }
+
+                Ok(Value::Object(object))
+            }
+            GPMLNode::Import(import) => Ok(Self::import_to_json(import)),
+            GPMLNode::ComponentDef(def) => Self::component_def_to_json(def),
+            GPMLNode::Element(element) => Self::component_to_value(Self::element_to_component(element)),
+            GPMLNode::Text(text) => Ok(Value::String(text.clone())),
+            GPMLNode::Expression(expr) => Ok(Value::String(format!("${{{}}}", expr))),
+        }
+    }
+
+    /// Serialize a GPML node to a pretty-printed JSON string
+    pub fn to_json_string(node: &GPMLNode) -> GPMLResult<String> {
+        let value = Self::to_json(node)?;
+        serde_json::to_string_pretty(&value).map_err(|e| GPMLError::RenderError {
+            message: format!("Failed to encode JSON: {}", e),
+        })
+    }
+
+    /// Convert a single GPML element into a `UiComponent`
+    pub fn element_to_component(element: &Element) -> UiComponent {
+        let mut props: HashMap<String, UiValue> = element
+            .attributes
+            .iter()
+            .map(|(name, value)| (name.clone(), Self::attribute_to_ui_value(value)))
+            .collect();
+
+        match &element.conditions {
+            Some(Condition::If(expr)) => {
+                props.insert("gpml:if".to_string(), UiValue::String(expr.clone()));
+            }
+            Some(Condition::ElseIf(expr)) => {
+                props.insert("gpml:else-if".to_string(), UiValue::String(expr.clone()));
+            }
+            Some(Condition::Else) => {
+                props.insert("gpml:else".to_string(), UiValue::Boolean(true));
+            }
+            None => {}
+        }
+
+        let children = element
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                GPMLNode::Element(child) => Some(UiChild::Component(Self::element_to_component(child))),
+                GPMLNode::Text(text) => Some(UiChild::Text(text.clone())),
+                GPMLNode::Expression(expr) => Some(UiChild::Text(format!("${{{}}}", expr))),
+                _ => None,
+            })
+            .collect();
+
+        UiComponent {
+            component_type: element.qualified_tag(),
+            props,
+            children,
+            reference: None,
+        }
+    }
+
+    /// Convert an attribute value, expressions are kept in their `${...}` form
+    pub fn attribute_to_ui_value(value: &AttributeValue) -> UiValue {
+        match value {
+            AttributeValue::Literal(s) => UiValue::String(s.clone()),
+            AttributeValue::Expression(expr) => UiValue::String(format!("${{{}}}", expr)),
+            AttributeValue::Number(n) => UiValue::Number(*n),
+            AttributeValue::Boolean(b) => UiValue::Boolean(*b),
+            AttributeValue::Array(items) => {
+                UiValue::Array(items.iter().map(Self::attribute_to_ui_value).collect())
+            }
+        }
+    }
+
+    fn component_to_value(component: UiComponent) -> GPMLResult<Value> {
+        serde_json::to_value(component).map_err(|e| GPMLError::RenderError {
+            message: format!("Failed to encode JSON: {}", e),
+        })
+    }
+
+    fn import_to_json(import: &Import) -> Value {
+        let mut object = Map::new();
+        object.insert("path".to_string(), Value::String(import.path.clone()));
+        object.insert("alias".to_string(), Value::String(import.alias.clone()));
+        Value::Object(object)
+    }
+
+    fn component_def_to_json(def: &ComponentDef) -> GPMLResult<Value> {
+        let mut object = Map::new();
+        object.insert("name".to_string(), Value::String(def.name.clone()));
+        object.insert(
+            "parameters".to_string(),
+            Value::Array(def.parameters.iter().cloned().map(Value::String).collect()),
+        );
+        if !def.parameter_types.is_empty() {
+            object.insert(
+                "parameter_types".to_string(),
+                Value::Object(
+                    def.parameter_types
+                        .iter()
+                        .map(|(name, ty)| (name.clone(), Value::String(ty.name().to_string())))
+                        .collect(),
+                ),
+            );
+        }
+        object.insert(
+            "body".to_string(),
+            Self::component_to_value(Self::element_to_component(&def.body))?,
+        );
+        Ok(Value::Object(object))
+    }
+
+    /// Deserialize JSON produced by [`GPMLSerializer::to_json`] back into a GPML document
+    pub fn from_json(value: &Value) -> GPMLResult<GPMLNode> {
+        let object = value.as_object().ok_or_else(|| GPMLError::SyntaxError {
+            message: "Expected a JSON object at the document root".to_string(),
+        })?;
+
+        let imports = match object.get("imports") {
+            Some(imports) => Self::array(imports, "imports")?
+                .iter()
+                .map(|import| {
+                    Ok(Import {
+                        path: Self::string_field(import, "path")?,
+                        alias: Self::string_field(import, "alias")?,
+                    })
+                })
+                .collect::<GPMLResult<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+
+        let components = match object.get("components") {
+            Some(components) => Self::array(components, "components")?
+                .iter()
+                .map(|component| {
+                    let parameters = match component.get("parameters") {
+                        Some(parameters) => Self::array(parameters, "parameters")?
+                            .iter()
+                            .map(|p| {
+                                p.as_str().map(str::to_string).ok_or_else(|| GPMLError::SyntaxError {
+                                    message: "Component parameters must be strings".to_string(),
+                                })
+                            })
+                            .collect::<GPMLResult<Vec<_>>>()?,
+                        None => Vec::new(),
+                    };
+                    let parameter_types = match component.get("parameter_types") {
+                        Some(types) => types
+                            .as_object()
+                            .ok_or_else(|| GPMLError::SyntaxError {
+                                message: "Expected 'parameter_types' to be an object".to_string(),
+                            })?
+                            .iter()
+                            .map(|(name, ty)| {
+                                ty.as_str()
+                                    .and_then(ParamType::from_name)
+                                    .map(|ty| (name.clone(), ty))
+                                    .ok_or_else(|| GPMLError::SyntaxError {
+                                        message: format!("Invalid type for parameter '{}'", name),
+                                    })
+                            })
+                            .collect::<GPMLResult<HashMap<_, _>>>()?,
+                        None => HashMap::new(),
+                    };
+                    let body = Self::element_from_json(component.get("body").ok_or_else(|| {
+                        GPMLError::SyntaxError {
+                            message: "Component definition is missing 'body'".to_string(),
+                        }
+                    })?)?;
+                    let slots = ComponentDef::collect_slots(&body);
+
+                    Ok(ComponentDef {
+                        name: Self::string_field(component, "name")?,
+                        parameters,
+                        parameter_types,
+                        body,
+                        slots,
+                    })
+                })
+                .collect::<GPMLResult<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+
+        let root = if object.contains_key("type") {
+            Some(Self::element_from_json(value)?)
+        } else {
+            None
+        };
+
+        let extends = match object.get("extends") {
+            Some(extends) => Some(extends.as_str().map(str::to_string).ok_or_else(|| {
+                GPMLError::SyntaxError {
+                    message: "Expected 'extends' to be a string".to_string(),
+                }
+            })?),
+            None => None,
+        };
+
+        Ok(GPMLNode::Document { extends, imports, components, root })
+    }
+
+    /// Deserialize a single `UiComponent` JSON object into a GPML element
+    pub fn element_from_json(value: &Value) -> GPMLResult<Element> {
+        let mut element = Element::new(Self::string_field(value, "type")?);
+
+        if let Some(props) = value.get("props") {
+            let props = props.as_object().ok_or_else(|| GPMLError::SyntaxError {
+                message: "'props' must be an object".to_string(),
+            })?;
+            for (name, prop) in props {
+                let attribute = Self::attribute_from_json(prop)?;
+                match Condition::from_attribute(name, &attribute) {
+                    Some(condition) => element.conditions = Some(condition),
+                    None => {
+                        element.attributes.insert(name.clone(), attribute);
+                    }
+                }
+            }
+        }
+
+        if let Some(children) = value.get("children") {
+            for child in Self::array(children, "children")? {
+                let node = match child {
+                    Value::String(text) => {
+                        if text.starts_with("${") && text.ends_with('}') {
+                            GPMLNode::Expression(text[2..text.len() - 1].to_string())
+                        } else {
+                            GPMLNode::Text(text.clone())
+                        }
+                    }
+                    _ => GPMLNode::Element(Self::element_from_json(child)?),
+                };
+                element.children.push(node);
+            }
+        }
+
+        element.self_closing = element.children.is_empty();
+        Ok(element)
+    }
+
+    fn attribute_from_json(value: &Value) -> GPMLResult<AttributeValue> {
+        match value {
+            Value::String(s) if s.starts_with("${") && s.ends_with('}') => {
+                Ok(AttributeValue::Expression(s[2..s.len() - 1].to_string()))
+            }
+            Value::String(s) => Ok(AttributeValue::Literal(s.clone())),
+            Value::Bool(b) => Ok(AttributeValue::Boolean(*b)),
+            Value::Number(n) => n.as_f64().map(AttributeValue::Number).ok_or_else(|| {
+                GPMLError::InvalidAttributeValue {
+                    message: format!("Number out of range: {}", n),
+                }
+            }),
+            Value::Array(items) => Ok(AttributeValue::Array(
+                items
+                    .iter()
+                    .map(Self::attribute_from_json)
+                    .collect::<GPMLResult<Vec<_>>>()?,
+            )),
+            other => Err(GPMLError::InvalidAttributeValue {
+                message: format!("Unsupported attribute value: {}", other),
+            }),
+        }
+    }
+
+    fn array<'a>(value: &'a Value, field: &str) -> GPMLResult<&'a Vec<Value>> {
+        value.as_array().ok_or_else(|| GPMLError::SyntaxError {
+            message: format!("'{}' must be an array", field),
+        })
+    }
+
+    fn string_field(value: &Value, field: &str) -> GPMLResult<String> {
+        value
+            .get(field)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| GPMLError::SyntaxError {
+                message: format!("Missing string field '{}'", field),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::GPMLParser;
+
+    #[test]
+    fn test_round_trip_document() {
+        let input = r#"import ./Card.gpml as Card
+
+def Badge(label) {
+    <span class="badge">${label}</span>
+}
+
+<root>
+    <Card title="Hello" count="${count}" tags="[a, 2, true]" />
+    <p gpml:if="visible">Shown</p>
+</root>"#;
+        let document = GPMLParser::parse_file(input).unwrap();
+        let json = GPMLSerializer::to_json(&document).unwrap();
+
+        assert_eq!(json["type"], "root");
+        assert_eq!(json["imports"][0]["path"], "./Card.gpml");
+        assert_eq!(json["components"][0]["parameters"][0], "label");
+        assert_eq!(json["children"][0]["props"]["count"], "${count}");
+
+        let restored = GPMLSerializer::from_json(&json).unwrap();
+        let GPMLNode::Document { imports, components, root, .. } = restored else {
+            panic!("expected document");
+        };
+        assert_eq!(imports[0].alias, "Card");
+        assert_eq!(components[0].parameters, vec!["label".to_string()]);
+
+        let root = root.unwrap();
+        let card = root.children[0].as_element().unwrap();
+        assert_eq!(card.get_attribute("count"), Some(&AttributeValue::Expression("count".to_string())));
+        assert_eq!(
+            card.get_attribute("tags"),
+            Some(&AttributeValue::Array(vec![
+                AttributeValue::Literal("a".to_string()),
+                AttributeValue::Number(2.0),
+                AttributeValue::Boolean(true),
+            ]))
+        );
+        let p = root.children[1].as_element().unwrap();
+        assert_eq!(p.conditions, Some(Condition::If("visible".to_string())));
+    }
+
+    #[test]
+    fn test_json_is_valid_ui_component() {
+        let element = GPMLParser::parse_xml_element(r#"<div padding="4"><h1>Title</h1></div>"#).unwrap();
+        let json = GPMLSerializer::to_json(&GPMLNode::Element(element)).unwrap();
+        let component: UiComponent = serde_json::from_value(json).unwrap();
+        assert_eq!(component.component_type, "div");
+        assert_eq!(component.children.len(), 1);
+    }
+}
@@ -1,11 +1,25 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 use gpui::*;
 use gpui_component::*;
-use crate::ast::GPMLElement;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_until, take_while1},
+    character::complete::{char, multispace0, multispace1},
+    combinator::opt,
+    multi::many0,
+    IResult, Parser,
+};
+use crate::ast::{GPMLElement, GPMLNode};
 use crate::elements::parse_color;
 
+thread_local! {
+    static CURRENT_STYLESHEET: RefCell<Option<Rc<GPMLStylesheet>>> = const { RefCell::new(None) };
+}
+
 /// Lightweight style model parsed from inline `style` attribute.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Style {
     pub props: HashMap<String, String>,
 }
@@ -40,6 +54,36 @@ impl Style {
         }
     }
 
+    /// Apply the box props (width/height/padding/margin/background) to any Styled element
+    pub fn apply_box<T: Styled>(&self, mut styled: T) -> T {
+        if let Some(pxv) = self.get("width").and_then(|v| Style::parse_px(v)) { styled = styled.w(px(pxv)); }
+        if let Some(pxv) = self.get("height").and_then(|v| Style::parse_px(v)) { styled = styled.h(px(pxv)); }
+        if let Some(pxv) = self.get("padding").and_then(|v| Style::parse_px(v)) { styled = styled.p(px(pxv)); }
+        if let Some(pxv) = self.get("margin").and_then(|v| Style::parse_px(v)) { styled = styled.m(px(pxv)); }
+        if let Some(color) = self.get("background").or_else(|| self.get("background-color")).and_then(|v| parse_color(v)) {
+            styled = styled.bg(color);
+        }
+        styled
+    }
+
+    /// Apply the text props (font-size/color/font-weight) to any Styled element
+    pub fn apply_text<T: Styled>(&self, mut styled: T) -> T {
+        if let Some(pxv) = self.get("font-size").and_then(|v| Style::parse_px(v)) { styled = styled.text_size(px(pxv)); }
+        if let Some(color) = self.get("color").and_then(|v| parse_color(v)) { styled = styled.text_color(color); }
+        match self.get("font-weight").map(|v| v.as_str()) {
+            Some("bold") => styled = styled.font_weight(FontWeight::BOLD),
+            Some("normal") => styled = styled.font_weight(FontWeight::NORMAL),
+            _ => {}
+        }
+        styled
+    }
+
+    /// Apply the `gap` prop to a flex container
+    pub fn apply_gap<T: Styled>(&self, mut styled: T) -> T {
+        if let Some(pxv) = self.get("gap").and_then(|v| Style::parse_px(v)) { styled = styled.gap(px(pxv)); }
+        styled
+    }
+
     /// Apply common style props (width/height/padding/margin/background) to a Div
     pub fn apply_common_to_div(div_el: Div, element: &GPMLElement) -> Div {
         Self::apply_common_to_styled(div_el, element)
@@ -157,3 +201,225 @@ impl Style {
         text_el
     }
 }
+
+/// Selector of a [`StyleRule`], only single tag names and `.class` selectors are supported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    /// Matches elements by tag name: `button`
+    Tag(String),
+    /// Matches elements having the class in their `class` attribute: `.primary`
+    Class(String),
+}
+
+impl Selector {
+    pub fn matches(&self, element: &GPMLElement) -> bool {
+        match self {
+            Selector::Tag(tag) => element.tag == *tag,
+            Selector::Class(class) => element
+                .get_attribute("class")
+                .map(|v| v.as_string().split_whitespace().any(|c| c == class))
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// A `selector { prop: value }` rule from a `<style>` block
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleRule {
+    pub selector: Selector,
+    pub style: Style,
+}
+
+/// Rules collected from the `<style>` blocks of a document.
+///
+/// The stylesheet is installed for the duration of a render pass with [`GPMLStylesheet::enter`]
+/// and looked up by the shared `apply_*_styles` helpers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GPMLStylesheet {
+    pub rules: Vec<StyleRule>,
+}
+
+impl GPMLStylesheet {
+    pub fn parse(input: &str) -> Self {
+        Self { rules: parse_stylesheet(input) }
+    }
+
+    /// Collect the rules of all `<style>` blocks in the tree, in document order
+    pub fn from_element(root: &GPMLElement) -> Self {
+        fn walk(element: &GPMLElement, rules: &mut Vec<StyleRule>) {
+            if element.tag == "style" {
+                rules.extend(parse_stylesheet(&element.get_text_content()));
+                return;
+            }
+            for child in &element.children {
+                if let GPMLNode::Element(child) = child {
+                    walk(child, rules);
+                }
+            }
+        }
+
+        let mut rules = Vec::new();
+        walk(root, &mut rules);
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Merge the props of all rules matching the element, class rules take precedence
+    /// over tag rules and later rules over earlier ones
+    pub fn style_for(&self, element: &GPMLElement) -> Option<Style> {
+        let mut props = HashMap::new();
+        let tag_rules = self.rules.iter().filter(|rule| matches!(rule.selector, Selector::Tag(_)));
+        let class_rules = self.rules.iter().filter(|rule| matches!(rule.selector, Selector::Class(_)));
+        for rule in tag_rules.chain(class_rules) {
+            if rule.selector.matches(element) {
+                props.extend(rule.style.props.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+        }
+        (!props.is_empty()).then(|| Style { props })
+    }
+
+    /// Install this stylesheet until the returned guard is dropped
+    pub fn enter(self: Rc<Self>) -> StylesheetGuard {
+        let previous = CURRENT_STYLESHEET.with(|current| current.borrow_mut().replace(self));
+        StylesheetGuard { previous }
+    }
+
+    /// Get the merged style of the element from the current stylesheet, if a render pass installed one
+    pub fn current_style_for(element: &GPMLElement) -> Option<Style> {
+        CURRENT_STYLESHEET.with(|current| current.borrow().as_ref().and_then(|sheet| sheet.style_for(element)))
+    }
+}
+
+/// Restores the previous [`GPMLStylesheet`] when dropped
+pub struct StylesheetGuard {
+    previous: Option<Rc<GPMLStylesheet>>,
+}
+
+impl Drop for StylesheetGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT_STYLESHEET.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Parse the text content of a `<style>` block: `selector { prop: value; ... }`
+///
+/// Invalid rules are skipped with a warning.
+pub fn parse_stylesheet(input: &str) -> Vec<StyleRule> {
+    let mut rules = Vec::new();
+    let mut input = input;
+    loop {
+        if let Ok((rest, _)) = skip_ignored(input) {
+            input = rest;
+        }
+        if input.is_empty() {
+            break;
+        }
+
+        match parse_rule(input) {
+            Ok((rest, rule)) => {
+                rules.push(rule);
+                input = rest;
+            }
+            Err(_) => {
+                tracing::warn!("Skipping invalid style rule: {}", input.lines().next().unwrap_or_default());
+                match input.find('}') {
+                    Some(end) => input = &input[end + 1..],
+                    None => break,
+                }
+            }
+        }
+    }
+    rules
+}
+
+/// Skip whitespace and `/* ... */` comments
+fn skip_ignored(input: &str) -> IResult<&str, ()> {
+    let comment = (tag("/*"), take_until("*/"), tag("*/")).map(|_| "");
+    many0(alt((multispace1, comment))).map(|_| ()).parse(input)
+}
+
+fn parse_selector(input: &str) -> IResult<&str, Selector> {
+    let (input, dot) = opt(char::<&str, nom::error::Error<&str>>('.')).parse(input)?;
+    let (input, name) = take_while1(|c: char| c.is_alphanumeric() || c == '-' || c == '_').parse(input)?;
+    let selector = match dot {
+        Some(_) => Selector::Class(name.to_string()),
+        None => Selector::Tag(name.to_string()),
+    };
+    Ok((input, selector))
+}
+
+fn parse_rule(input: &str) -> IResult<&str, StyleRule> {
+    let (input, selector) = parse_selector(input)?;
+    let (input, _) = multispace0.parse(input)?;
+    let (input, _) = char::<&str, nom::error::Error<&str>>('{').parse(input)?;
+    let (input, body) = take_until("}").parse(input)?;
+    let (input, _) = char::<&str, nom::error::Error<&str>>('}').parse(input)?;
+    Ok((input, StyleRule { selector, style: Style::from_inline(body) }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::AttributeValue;
+
+    #[test]
+    fn test_parse_stylesheet() {
+        let rules = parse_stylesheet(
+            r#"
+            /* buttons */
+            button { padding: 8px; color: red }
+            .card{background:#ffffff;}
+            div > p { color: blue; }
+            .title { font-size: 24px; }
+            "#,
+        );
+
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].selector, Selector::Tag("button".to_string()));
+        assert_eq!(rules[0].style.get("padding").map(|v| v.as_str()), Some("8px"));
+        assert_eq!(rules[0].style.get("color").map(|v| v.as_str()), Some("red"));
+        assert_eq!(rules[1].selector, Selector::Class("card".to_string()));
+        assert_eq!(rules[1].style.get("background").map(|v| v.as_str()), Some("#ffffff"));
+        assert_eq!(rules[2].selector, Selector::Class("title".to_string()));
+    }
+
+    #[test]
+    fn test_stylesheet_style_for() {
+        let sheet = GPMLStylesheet::parse(".primary { color: blue; } button { color: red; padding: 4px; }");
+
+        let plain = GPMLElement::new("button".to_string());
+        let style = sheet.style_for(&plain).unwrap();
+        assert_eq!(style.get("color").map(|v| v.as_str()), Some("red"));
+
+        let primary = GPMLElement::new("button".to_string())
+            .with_attribute("class".to_string(), AttributeValue::Literal("large primary".to_string()));
+        let style = sheet.style_for(&primary).unwrap();
+        assert_eq!(style.get("color").map(|v| v.as_str()), Some("blue"));
+        assert_eq!(style.get("padding").map(|v| v.as_str()), Some("4px"));
+
+        assert!(sheet.style_for(&GPMLElement::new("div".to_string())).is_none());
+    }
+
+    #[test]
+    fn test_stylesheet_from_element() {
+        let root = GPMLElement::new("root".to_string())
+            .with_child(GPMLNode::Element(
+                GPMLElement::new("style".to_string())
+                    .with_child(GPMLNode::Text("h1 { color: red; }".to_string())),
+            ))
+            .with_child(GPMLNode::Element(
+                GPMLElement::new("div".to_string()).with_child(GPMLNode::Element(
+                    GPMLElement::new("style".to_string())
+                        .with_child(GPMLNode::Text(".note { color: gray; }".to_string())),
+                )),
+            ));
+
+        let sheet = GPMLStylesheet::from_element(&root);
+        assert_eq!(sheet.rules.len(), 2);
+        assert_eq!(sheet.rules[1].selector, Selector::Class("note".to_string()));
+    }
+}
@@ -1,4 +1,6 @@
-use std::{cell::RefCell, ops::Range, rc::Rc, str::FromStr, sync::Arc, time::Duration};
+use std::{
+    cell::RefCell, ops::Range, path::PathBuf, rc::Rc, str::FromStr, sync::Arc, time::Duration,
+};
 
 use anyhow::Ok;
 use gpui::{prelude::FluentBuilder, *};
@@ -8,8 +10,8 @@ use gpui_component::{
     h_flex,
     highlighter::{Diagnostic, DiagnosticSeverity, Language, LanguageConfig, LanguageRegistry},
     input::{
-        self, CodeActionProvider, CompletionProvider, InputEvent, InputState, Position, Rope,
-        RopeExt, TabSize, TextInput,
+        self, CodeActionProvider, CompletionProvider, GoToDefinitionProvider, InputEvent,
+        InputState, Position, Rope, RopeExt, TabSize, TextInput,
     },
     v_flex, ActiveTheme, ContextModal, IconName, IndexPath, Selectable, Sizable,
 };
@@ -41,6 +43,8 @@ pub struct Example {
     line_number: bool,
     need_update: bool,
     soft_wrap: bool,
+    minimap: bool,
+    read_only: bool,
     lsp_store: ExampleLspStore,
     _subscriptions: Vec<Subscription>,
 }
@@ -173,6 +177,19 @@ impl CompletionProvider for ExampleLspStore {
     }
 }
 
+impl GoToDefinitionProvider for ExampleLspStore {
+    fn go_to_definition(
+        &self,
+        _state: Entity<InputState>,
+        _offset: usize,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Task<Option<(PathBuf, usize)>> {
+        // A real LSP store would send a `textDocument/definition` request here.
+        Task::ready(None)
+    }
+}
+
 impl CodeActionProvider for ExampleLspStore {
     fn id(&self) -> SharedString {
         "LspStore".into()
@@ -448,11 +465,13 @@ impl Example {
                     hard_tabs: false,
                 })
                 .soft_wrap(false)
+                .minimap(true)
                 .default_value(default_language.1)
                 .placeholder("Enter your code here...");
 
             editor.set_completion_provider(Some(Rc::new(lsp_store.clone())), cx);
             editor.add_code_action_provider(Rc::new(lsp_store.clone()), cx);
+            editor.set_go_to_definition_provider(Some(Rc::new(lsp_store.clone())), cx);
             editor.add_code_action_provider(Rc::new(TextConvertor), cx);
 
             editor
@@ -496,6 +515,8 @@ impl Example {
             line_number: true,
             need_update: false,
             soft_wrap: false,
+            minimap: true,
+            read_only: false,
             lsp_store,
             _subscriptions,
         }
@@ -572,6 +593,22 @@ impl Example {
         cx.notify();
     }
 
+    fn toggle_minimap(&mut self, _: &ClickEvent, window: &mut Window, cx: &mut Context<Self>) {
+        self.minimap = !self.minimap;
+        self.editor.update(cx, |state, cx| {
+            state.set_minimap(self.minimap, window, cx);
+        });
+        cx.notify();
+    }
+
+    fn toggle_read_only(&mut self, _: &ClickEvent, window: &mut Window, cx: &mut Context<Self>) {
+        self.read_only = !self.read_only;
+        self.editor.update(cx, |state, cx| {
+            state.set_read_only(self.read_only, window, cx);
+        });
+        cx.notify();
+    }
+
     fn lint_document(&self, cx: &mut Context<Self>) {
         // Subscribe to input changes and perform linting with AutoCorrect for markers example.
         let value = self.editor.read(cx).value().clone();
@@ -692,7 +729,45 @@ impl Render for Example {
                                         .label("Soft Wrap")
                                         .selected(self.soft_wrap)
                                         .on_click(cx.listener(Self::toggle_soft_wrap))
-                                }),
+                                })
+                                .child({
+                                    Button::new("minimap")
+                                        .ghost()
+                                        .xsmall()
+                                        .label("Minimap")
+                                        .selected(self.minimap)
+                                        .on_click(cx.listener(Self::toggle_minimap))
+                                })
+                                .child({
+                                    Button::new("read-only")
+                                        .ghost()
+                                        .xsmall()
+                                        .label("Read Only")
+                                        .selected(self.read_only)
+                                        .on_click(cx.listener(Self::toggle_read_only))
+                                })
+                                .child(
+                                    Button::new("fold-all")
+                                        .ghost()
+                                        .xsmall()
+                                        .label("Fold All")
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.editor.update(cx, |state, cx| {
+                                                state.fold_all(window, cx);
+                                            });
+                                        })),
+                                )
+                                .child(
+                                    Button::new("unfold-all")
+                                        .ghost()
+                                        .xsmall()
+                                        .label("Unfold All")
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.editor.update(cx, |state, cx| {
+                                                state.unfold_all(window, cx);
+                                            });
+                                        })),
+                                ),
                         )
                         .child({
                             let position = self.editor.read(cx).cursor_position();
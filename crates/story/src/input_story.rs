@@ -147,6 +147,12 @@ impl InputStory {
             InputEvent::PressEnter { secondary } => println!("PressEnter secondary: {}", secondary),
             InputEvent::Focus => println!("Focus"),
             InputEvent::Blur => println!("Blur"),
+            InputEvent::TextReplaced(count) => println!("TextReplaced: {}", count),
+            InputEvent::SnippetExited => println!("SnippetExited"),
+            InputEvent::FileDrop(path) => println!("FileDrop: {}", path.display()),
+            InputEvent::GoToDefinition(path, offset) => {
+                println!("GoToDefinition: {}:{}", path.display(), offset)
+            }
         };
     }
 }
@@ -160,6 +160,12 @@ impl NumberInputStory {
             }
             InputEvent::Focus => println!("Focus"),
             InputEvent::Blur => println!("Blur"),
+            InputEvent::TextReplaced(count) => println!("TextReplaced: {}", count),
+            InputEvent::SnippetExited => println!("SnippetExited"),
+            InputEvent::FileDrop(path) => println!("FileDrop: {}", path.display()),
+            InputEvent::GoToDefinition(path, offset) => {
+                println!("GoToDefinition: {}:{}", path.display(), offset)
+            }
         }
     }
 
@@ -632,7 +632,8 @@ impl TableStory {
         });
 
         let delegate = StockTableDelegate::new(5000);
-        let table = cx.new(|cx| Table::new(delegate, window, cx));
+        let table =
+            cx.new(|cx| Table::new(delegate, window, cx).empty_message("No stocks to display"));
 
         cx.subscribe_in(&table, window, Self::on_table_event)
             .detach();
@@ -19,6 +19,7 @@ pub struct Checkbox {
     label: Option<Text>,
     children: Vec<AnyElement>,
     checked: bool,
+    indeterminate: bool,
     disabled: bool,
     size: Size,
     on_click: Option<Box<dyn Fn(&bool, &mut Window, &mut App) + 'static>>,
@@ -33,6 +34,7 @@ impl Checkbox {
             label: None,
             children: Vec::new(),
             checked: false,
+            indeterminate: false,
             disabled: false,
             size: Size::default(),
             on_click: None,
@@ -49,6 +51,14 @@ impl Checkbox {
         self
     }
 
+    /// Show a dash instead of the check mark, for a partially checked group.
+    ///
+    /// Clicking an indeterminate checkbox still toggles the `checked` state.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
     pub fn on_click(mut self, handler: impl Fn(&bool, &mut Window, &mut App) + 'static) -> Self {
         self.on_click = Some(Box::new(handler));
         self
@@ -158,7 +168,8 @@ pub(crate) fn checkbox_check_icon(
 impl RenderOnce for Checkbox {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let checked = self.checked;
-        let border_color = if checked {
+        let filled = checked || self.indeterminate;
+        let border_color = if filled {
             cx.theme().primary
         } else {
             cx.theme().input
@@ -204,18 +215,29 @@ impl RenderOnce for Checkbox {
                         .border_color(color)
                         .rounded(radius)
                         .when(cx.theme().shadow && !self.disabled, |this| this.shadow_xs())
-                        .map(|this| match self.checked {
+                        .map(|this| match filled {
                             false => this.bg(cx.theme().background),
                             _ => this.bg(color),
                         })
-                        .child(checkbox_check_icon(
-                            self.id,
-                            self.size,
-                            checked,
-                            self.disabled,
-                            window,
-                            cx,
-                        )),
+                        .map(|this| {
+                            if self.indeterminate {
+                                this.items_center().justify_center().child(
+                                    svg()
+                                        .path(IconName::Minus.path())
+                                        .size_3()
+                                        .text_color(cx.theme().primary_foreground),
+                                )
+                            } else {
+                                this.child(checkbox_check_icon(
+                                    self.id,
+                                    self.size,
+                                    checked,
+                                    self.disabled,
+                                    window,
+                                    cx,
+                                ))
+                            }
+                        }),
                 )
                 .when(self.label.is_some() || !self.children.is_empty(), |this| {
                     this.child(
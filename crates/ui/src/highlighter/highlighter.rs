@@ -1,4 +1,8 @@
-use crate::{highlighter::LanguageRegistry, input::RopeExt as _, ActiveTheme};
+use crate::{
+    highlighter::{LanguageRegistry, SemanticHighlight, SemanticTokenType},
+    input::RopeExt as _,
+    ActiveTheme,
+};
 
 use anyhow::{anyhow, Context, Result};
 use gpui::{App, HighlightStyle, SharedString};
@@ -20,6 +24,8 @@ pub struct SyntaxHighlighter {
     language: SharedString,
     query: Option<Query>,
     injection_queries: HashMap<SharedString, Query>,
+    /// The query for the foldable ranges.
+    folds_query: Option<Query>,
 
     locals_pattern_index: usize,
     highlights_pattern_index: usize,
@@ -37,6 +43,8 @@ pub struct SyntaxHighlighter {
     parser: Parser,
     /// The last parsed tree.
     tree: Option<Tree>,
+    /// The semantic tokens from a LSP server, styled on top of the syntax highlights.
+    semantic: SemanticHighlight,
 }
 
 struct TextProvider<'a>(&'a Rope);
@@ -164,9 +172,8 @@ impl SyntaxHighlighter {
             ));
         };
 
-        let mut parser = Parser::new();
-        parser
-            .set_language(&config.language)
+        let mut parser = registry
+            .parser(&config.language)
             .context("parse set_language")?;
 
         // Concatenate the query strings, keeping track of the start offset of each section.
@@ -266,12 +273,25 @@ impl SyntaxHighlighter {
             }
         }
 
+        let folds_query = if config.folds.is_empty() {
+            None
+        } else {
+            match Query::new(&config.language, &config.folds) {
+                Ok(q) => Some(q),
+                Err(e) => {
+                    tracing::error!("failed to build folds query for {:?}: {:?}", config.name, e);
+                    None
+                }
+            }
+        };
+
         // let highlight_indices = vec![None; query.capture_names().len()];
 
         Ok(Self {
             language: config.name.clone(),
             query: Some(query),
             injection_queries,
+            folds_query,
 
             locals_pattern_index,
             highlights_pattern_index,
@@ -285,6 +305,7 @@ impl SyntaxHighlighter {
             text: Rope::new(),
             parser,
             tree: None,
+            semantic: SemanticHighlight::default(),
         })
     }
 
@@ -300,6 +321,11 @@ impl SyntaxHighlighter {
             return;
         }
 
+        match &edit {
+            Some(edit) => self.semantic.shift(edit),
+            None => self.semantic = SemanticHighlight::default(),
+        }
+
         let edit = edit.unwrap_or(InputEdit {
             start_byte: 0,
             old_end_byte: 0,
@@ -333,6 +359,14 @@ impl SyntaxHighlighter {
         self.text = text.clone();
     }
 
+    /// Set the semantic tokens (by byte ranges in the text) received from a LSP server.
+    ///
+    /// The tokens take priority over the syntax highlights in the overlapping ranges,
+    /// they are moved by the next edits, and cleared when the whole text is replaced.
+    pub fn apply_semantic_tokens(&mut self, tokens: Vec<(Range<usize>, SemanticTokenType)>) {
+        self.semantic = SemanticHighlight::new(tokens);
+    }
+
     /// Match the visible ranges of nodes in the Tree for highlighting.
     fn match_styles(&self, range: Range<usize>, cx: &App) -> Vec<HighlightItem> {
         let mut highlights = vec![];
@@ -433,10 +467,9 @@ impl SyntaxHighlighter {
         let Some(config) = LanguageRegistry::global(cx).language(injection_language) else {
             return cache;
         };
-        let mut parser = Parser::new();
-        if parser.set_language(&config.language).is_err() {
+        let Ok(mut parser) = LanguageRegistry::global(cx).parser(&config.language) else {
             return cache;
-        }
+        };
 
         let source = content.as_bytes();
         let Some(tree) = parser.parse(source, None) else {
@@ -575,6 +608,15 @@ impl SyntaxHighlighter {
         }
         // dbg!(iter_count);
 
+        // The semantic tokens are pushed last to cover the syntax highlights.
+        for (token_range, token_type) in self.semantic.tokens_in(range) {
+            let Some(style) = token_type.style(theme) else {
+                continue;
+            };
+            let token_range = token_range.start.max(range.start)..token_range.end.min(range.end);
+            styles.push((token_range, style));
+        }
+
         // If the matched styles is empty, return a default range.
         if styles.len() == 0 {
             return vec![(start_offset..range.end, HighlightStyle::default())];
@@ -590,6 +632,44 @@ impl SyntaxHighlighter {
 
         styles
     }
+
+    /// Returns the foldable line ranges of the last parsed tree.
+    ///
+    /// The `start` of each range is the line to fold at, and the lines in `start + 1..end`
+    /// will be hidden when it folded.
+    pub fn foldable_ranges(&self, text: &Rope) -> Vec<Range<usize>> {
+        let (Some(tree), Some(query)) = (&self.tree, &self.folds_query) else {
+            return vec![];
+        };
+
+        let mut ranges = vec![];
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), TextProvider(text));
+        while let Some(query_match) = matches.next() {
+            for cap in query_match.captures {
+                let start = cap.node.start_position();
+                let end = cap.node.end_position();
+                // The node ends at the start of a line, that line is not a part of it.
+                let end_row = if end.column == 0 {
+                    end.row
+                } else {
+                    end.row + 1
+                };
+                ranges.push(start.row..end_row);
+            }
+        }
+
+        normalize_fold_ranges(ranges)
+    }
+}
+
+/// Keep the outermost range of the ranges start at the same line, and drop the ranges
+/// that have nothing to hide, the result is sorted by the start line.
+fn normalize_fold_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.retain(|range| range.end > range.start + 1);
+    ranges.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+    ranges.dedup_by_key(|range| range.start);
+    ranges
 }
 
 /// To merge intersection ranges, let the subsequent range cover
@@ -800,4 +880,13 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_normalize_fold_ranges() {
+        assert_eq!(
+            normalize_fold_ranges(vec![5..8, 0..10, 2..3, 0..4, 5..7, 9..9]),
+            vec![0..10, 5..8]
+        );
+        assert_eq!(normalize_fold_ranges(vec![]), Vec::<Range<usize>>::new());
+    }
 }
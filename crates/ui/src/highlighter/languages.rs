@@ -358,6 +358,22 @@ impl Language {
             ),
         };
 
+        let folds = match self {
+            Self::Json => include_str!("languages/json/folds.scm"),
+            #[cfg(feature = "tree-sitter-languages")]
+            Self::Rust => include_str!("languages/rust/folds.scm"),
+            #[cfg(feature = "tree-sitter-languages")]
+            Self::Go => include_str!("languages/go/folds.scm"),
+            #[cfg(feature = "tree-sitter-languages")]
+            Self::JavaScript => include_str!("languages/javascript/folds.scm"),
+            #[cfg(feature = "tree-sitter-languages")]
+            Self::TypeScript => include_str!("languages/typescript/folds.scm"),
+            #[cfg(feature = "tree-sitter-languages")]
+            Self::Html => include_str!("languages/html/folds.scm"),
+            #[cfg(feature = "tree-sitter-languages")]
+            _ => "",
+        };
+
         let language = tree_sitter::Language::new(language);
 
         LanguageConfig::new(
@@ -368,6 +384,7 @@ impl Language {
             injection,
             locals,
         )
+        .folds(folds)
     }
 }
 
@@ -0,0 +1,7 @@
+[
+  (block)
+  (field_declaration_list)
+  (literal_value)
+  (argument_list)
+  (import_spec_list)
+] @fold
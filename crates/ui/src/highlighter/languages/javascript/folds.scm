@@ -0,0 +1,10 @@
+[
+  (statement_block)
+  (class_body)
+  (switch_body)
+  (object)
+  (array)
+  (arguments)
+  (formal_parameters)
+  (template_string)
+] @fold
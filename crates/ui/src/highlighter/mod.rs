@@ -2,11 +2,13 @@ mod diagnostics;
 mod highlighter;
 mod languages;
 mod registry;
+mod semantic;
 
 pub use diagnostics::*;
 pub use highlighter::*;
 pub use languages::*;
 pub use registry::*;
+pub use semantic::*;
 
 use gpui::App;
 
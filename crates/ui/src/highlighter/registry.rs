@@ -66,6 +66,8 @@ pub struct LanguageConfig {
     pub highlights: SharedString,
     pub injections: SharedString,
     pub locals: SharedString,
+    /// The query for the foldable ranges, the nodes captured as `@fold` can be folded.
+    pub folds: SharedString,
 }
 
 impl LanguageConfig {
@@ -84,8 +86,15 @@ impl LanguageConfig {
             highlights: SharedString::from(highlights.to_string()),
             injections: SharedString::from(injections.to_string()),
             locals: SharedString::from(locals.to_string()),
+            folds: SharedString::default(),
         }
     }
+
+    /// Set the query for the foldable ranges.
+    pub fn folds(mut self, folds: &str) -> Self {
+        self.folds = SharedString::from(folds.to_string());
+        self
+    }
 }
 
 /// Theme for Tree-sitter Highlight
@@ -460,10 +469,35 @@ impl HighlightTheme {
     }
 }
 
+/// The error of registering a language in the [`LanguageRegistry`].
+#[derive(Debug)]
+pub enum HighlightError {
+    /// The WASM grammar failed to load.
+    #[cfg(feature = "tree-sitter-wasm")]
+    Wasm(tree_sitter::WasmError),
+    /// The query is invalid for the language.
+    Query(tree_sitter::QueryError),
+}
+
+impl std::fmt::Display for HighlightError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[cfg(feature = "tree-sitter-wasm")]
+            HighlightError::Wasm(err) => write!(f, "Failed to load WASM grammar: {}", err),
+            HighlightError::Query(err) => write!(f, "Invalid query: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for HighlightError {}
+
 /// Registry for code highlighter languages.
 #[derive(Clone)]
 pub struct LanguageRegistry {
     languages: HashMap<String, LanguageConfig>,
+    /// The engine running the languages loaded with [`LanguageRegistry::register_from_wasm`].
+    #[cfg(feature = "tree-sitter-wasm")]
+    wasm_engine: tree_sitter::wasmtime::Engine,
 }
 
 impl gpui::Global for LanguageRegistry {}
@@ -481,6 +515,8 @@ impl LanguageRegistry {
     pub fn new() -> Self {
         let mut registry = Self {
             languages: HashMap::new(),
+            #[cfg(feature = "tree-sitter-wasm")]
+            wasm_engine: tree_sitter::wasmtime::Engine::default(),
         };
 
         for language in languages::Language::all() {
@@ -494,6 +530,45 @@ impl LanguageRegistry {
         self.languages.insert(lang.to_string(), config.clone());
     }
 
+    /// Register a language from a tree-sitter grammar compiled to WASM (`tree-sitter build --wasm`),
+    /// to support languages that are not compiled into the binary.
+    ///
+    /// The `name` is the name of the grammar, e.g. `toml` for the `tree_sitter_toml` grammar.
+    #[cfg(feature = "tree-sitter-wasm")]
+    pub fn register_from_wasm(
+        &mut self,
+        name: &str,
+        wasm_bytes: &[u8],
+        highlights_query: &str,
+    ) -> Result<(), HighlightError> {
+        let mut store =
+            tree_sitter::WasmStore::new(&self.wasm_engine).map_err(HighlightError::Wasm)?;
+        let language = store
+            .load_language(name, wasm_bytes)
+            .map_err(HighlightError::Wasm)?;
+        tree_sitter::Query::new(&language, highlights_query).map_err(HighlightError::Query)?;
+
+        self.register(
+            name,
+            &LanguageConfig::new(name, language, vec![], highlights_query, "", ""),
+        );
+        Ok(())
+    }
+
+    /// Create a parser for the language, with a WASM store for the languages loaded from WASM.
+    pub(crate) fn parser(
+        &self,
+        language: &tree_sitter::Language,
+    ) -> anyhow::Result<tree_sitter::Parser> {
+        let mut parser = tree_sitter::Parser::new();
+        #[cfg(feature = "tree-sitter-wasm")]
+        if language.is_wasm() {
+            parser.set_wasm_store(tree_sitter::WasmStore::new(&self.wasm_engine)?)?;
+        }
+        parser.set_language(language)?;
+        Ok(parser)
+    }
+
     /// Returns a reference to the map of registered languages.
     pub fn languages(&self) -> &HashMap<String, LanguageConfig> {
         &self.languages
@@ -532,4 +607,64 @@ mod tests {
         assert!(registry.language("javascript").is_some());
         assert!(registry.language("js").is_some());
     }
+
+    #[cfg(feature = "tree-sitter-wasm")]
+    #[test]
+    fn test_register_from_wasm_invalid() {
+        use super::{HighlightError, LanguageRegistry};
+        let mut registry = LanguageRegistry::new();
+        let count = registry.languages().len();
+
+        let err = registry
+            .register_from_wasm("toml", b"not a wasm module", "")
+            .unwrap_err();
+        assert!(matches!(err, HighlightError::Wasm(_)));
+        assert_eq!(registry.languages().len(), count);
+    }
+
+    #[cfg(feature = "tree-sitter-wasm")]
+    #[test]
+    fn test_register_from_wasm() {
+        use super::LanguageRegistry;
+        use tree_sitter::{Query, QueryCursor, StreamingIterator as _};
+
+        let mut registry = LanguageRegistry::new();
+        registry
+            .register_from_wasm(
+                "toml",
+                include_bytes!("../../tests/fixtures/tree-sitter-toml.wasm"),
+                "(bare_key) @property\n(string) @string\n(integer) @number",
+            )
+            .unwrap();
+
+        let config = registry.language("toml").unwrap();
+        assert!(config.language.is_wasm());
+
+        let source = "[package]\nname = \"gpui-component\"\nversion = 1\n";
+        let mut parser = registry.parser(&config.language).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        assert!(!tree.root_node().has_error());
+
+        let query = Query::new(&config.language, &config.highlights).unwrap();
+        let mut cursor = QueryCursor::new();
+        let mut captures = cursor.captures(&query, tree.root_node(), source.as_bytes());
+        let mut highlights = vec![];
+        while let Some((query_match, ix)) = captures.next() {
+            let capture = query_match.captures[*ix];
+            highlights.push((
+                query.capture_names()[capture.index as usize],
+                &source[capture.node.byte_range()],
+            ));
+        }
+        assert_eq!(
+            highlights,
+            vec![
+                ("property", "package"),
+                ("property", "name"),
+                ("string", "\"gpui-component\""),
+                ("property", "version"),
+                ("number", "1"),
+            ]
+        );
+    }
 }
@@ -0,0 +1,208 @@
+use std::ops::Range;
+
+use gpui::HighlightStyle;
+use tree_sitter::InputEdit;
+
+use super::SyntaxColors;
+
+/// The subset of the LSP semantic token types that can be highlighted.
+///
+/// Each type is mapped to a highlight name of the [`SyntaxColors`] of the theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SemanticTokenType {
+    Namespace,
+    Type,
+    Class,
+    Enum,
+    Interface,
+    Struct,
+    TypeParameter,
+    Parameter,
+    Variable,
+    Property,
+    EnumMember,
+    Function,
+    Method,
+    Macro,
+    Keyword,
+    Modifier,
+    Comment,
+    String,
+    Number,
+    Regexp,
+    Operator,
+    Decorator,
+}
+
+impl SemanticTokenType {
+    /// Convert from a LSP token type, returns `None` for the unsupported types.
+    pub fn from_lsp(token_type: &lsp_types::SemanticTokenType) -> Option<Self> {
+        use lsp_types::SemanticTokenType as Lsp;
+
+        let token_type = match token_type {
+            t if *t == Lsp::NAMESPACE => Self::Namespace,
+            t if *t == Lsp::TYPE => Self::Type,
+            t if *t == Lsp::CLASS => Self::Class,
+            t if *t == Lsp::ENUM => Self::Enum,
+            t if *t == Lsp::INTERFACE => Self::Interface,
+            t if *t == Lsp::STRUCT => Self::Struct,
+            t if *t == Lsp::TYPE_PARAMETER => Self::TypeParameter,
+            t if *t == Lsp::PARAMETER => Self::Parameter,
+            t if *t == Lsp::VARIABLE => Self::Variable,
+            t if *t == Lsp::PROPERTY => Self::Property,
+            t if *t == Lsp::ENUM_MEMBER => Self::EnumMember,
+            t if *t == Lsp::FUNCTION => Self::Function,
+            t if *t == Lsp::METHOD => Self::Method,
+            t if *t == Lsp::MACRO => Self::Macro,
+            t if *t == Lsp::KEYWORD => Self::Keyword,
+            t if *t == Lsp::MODIFIER => Self::Modifier,
+            t if *t == Lsp::COMMENT => Self::Comment,
+            t if *t == Lsp::STRING => Self::String,
+            t if *t == Lsp::NUMBER => Self::Number,
+            t if *t == Lsp::REGEXP => Self::Regexp,
+            t if *t == Lsp::OPERATOR => Self::Operator,
+            t if *t == Lsp::DECORATOR => Self::Decorator,
+            _ => return None,
+        };
+
+        Some(token_type)
+    }
+
+    /// The highlight name in the theme to style this token type.
+    pub fn highlight_name(&self) -> &'static str {
+        match self {
+            Self::Namespace
+            | Self::Type
+            | Self::Class
+            | Self::Interface
+            | Self::Struct
+            | Self::TypeParameter => "type",
+            Self::Enum => "enum",
+            Self::Parameter | Self::Variable => "variable",
+            Self::Property => "property",
+            Self::EnumMember => "variant",
+            Self::Function | Self::Method => "function",
+            Self::Macro | Self::Decorator => "preproc",
+            Self::Keyword | Self::Modifier => "keyword",
+            Self::Comment => "comment",
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Regexp => "string.regex",
+            Self::Operator => "operator",
+        }
+    }
+
+    /// The style of this token type in the theme.
+    pub fn style(&self, theme: &SyntaxColors) -> Option<HighlightStyle> {
+        theme.style(self.highlight_name())
+    }
+}
+
+/// The semantic tokens received from a LSP server, by byte ranges in the text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SemanticHighlight {
+    pub tokens: Vec<(Range<usize>, SemanticTokenType)>,
+}
+
+impl SemanticHighlight {
+    pub fn new(mut tokens: Vec<(Range<usize>, SemanticTokenType)>) -> Self {
+        tokens.sort_by_key(|(range, _)| range.start);
+        Self { tokens }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Returns the tokens that intersect with the `range`.
+    pub fn tokens_in(
+        &self,
+        range: &Range<usize>,
+    ) -> impl Iterator<Item = &(Range<usize>, SemanticTokenType)> {
+        let range = range.clone();
+        self.tokens
+            .iter()
+            .skip_while(move |(token_range, _)| token_range.end <= range.start)
+            .take_while(move |(token_range, _)| token_range.start < range.end)
+    }
+
+    /// Move the tokens after the edit, the tokens inside the edited range are removed
+    /// until the server sends the new tokens.
+    pub fn shift(&mut self, edit: &InputEdit) {
+        let old_end = edit.old_end_byte;
+        let new_end = edit.new_end_byte;
+        self.tokens.retain_mut(|(range, _)| {
+            if range.end <= edit.start_byte {
+                true
+            } else if range.start >= old_end {
+                range.start = range.start - old_end + new_end;
+                range.end = range.end - old_end + new_end;
+                true
+            } else {
+                false
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tree_sitter::{InputEdit, Point};
+
+    use super::{SemanticHighlight, SemanticTokenType};
+
+    fn edit(start: usize, old_end: usize, new_end: usize) -> InputEdit {
+        InputEdit {
+            start_byte: start,
+            old_end_byte: old_end,
+            new_end_byte: new_end,
+            start_position: Point::new(0, start),
+            old_end_position: Point::new(0, old_end),
+            new_end_position: Point::new(0, new_end),
+        }
+    }
+
+    #[test]
+    fn test_semantic_token_type_from_lsp() {
+        assert_eq!(
+            SemanticTokenType::from_lsp(&lsp_types::SemanticTokenType::FUNCTION),
+            Some(SemanticTokenType::Function)
+        );
+        assert_eq!(
+            SemanticTokenType::from_lsp(&lsp_types::SemanticTokenType::new("unknown")),
+            None
+        );
+        assert_eq!(SemanticTokenType::Method.highlight_name(), "function");
+    }
+
+    #[test]
+    fn test_semantic_highlight_shift() {
+        let mut highlight = SemanticHighlight::new(vec![
+            (10..14, SemanticTokenType::Function),
+            (0..3, SemanticTokenType::Keyword),
+            (4..8, SemanticTokenType::Variable),
+        ]);
+        assert_eq!(highlight.tokens_in(&(5..12)).count(), 2);
+
+        // Insert 2 bytes at 9.
+        highlight.shift(&edit(9, 9, 11));
+        assert_eq!(
+            highlight.tokens,
+            vec![
+                (0..3, SemanticTokenType::Keyword),
+                (4..8, SemanticTokenType::Variable),
+                (12..16, SemanticTokenType::Function),
+            ]
+        );
+
+        // Replace 5..7 with 1 byte.
+        highlight.shift(&edit(5, 7, 6));
+        assert_eq!(
+            highlight.tokens,
+            vec![
+                (0..3, SemanticTokenType::Keyword),
+                (11..15, SemanticTokenType::Function),
+            ]
+        );
+    }
+}
@@ -48,6 +48,8 @@ where
     }
 
     /// Set the maximum number of undo steps to keep, defaults to 1000.
+    ///
+    /// The items grouped in one version count as a single step, 0 disables undo.
     pub fn max_undo(mut self, max_undo: usize) -> Self {
         self.max_undo = max_undo;
         self
@@ -84,9 +86,32 @@ where
 
     pub fn push(&mut self, item: I) {
         let version = self.inc_version();
+        self.push_with_version(item, version);
+    }
+
+    /// Push an item as a new version, it will not be grouped with the previous items.
+    pub fn push_separate(&mut self, item: I) {
+        self.version += 1;
+        self.last_changed_at = Instant::now();
+        self.push_with_version(item, self.version);
+    }
 
-        if self.undos.len() >= self.max_undo {
-            self.undos.remove(0);
+    fn push_with_version(&mut self, item: I, version: usize) {
+        if self.max_undo == 0 {
+            return;
+        }
+
+        // Evict the oldest versions as a whole, so an undo never restores half of a group.
+        if self.undos.last().map(|c| c.version()) != Some(version) {
+            while self.versions_count() >= self.max_undo {
+                let oldest = self.undos[0].version();
+                let len = self
+                    .undos
+                    .iter()
+                    .take_while(|c| c.version() == oldest)
+                    .count();
+                self.undos.drain(..len);
+            }
         }
 
         if self.unique {
@@ -99,6 +124,27 @@ where
         self.undos.push(item);
     }
 
+    /// The number of versions in the undo stack, the items of a version are adjacent.
+    fn versions_count(&self) -> usize {
+        let mut count = 0;
+        let mut last_version = None;
+        for item in &self.undos {
+            if last_version != Some(item.version()) {
+                count += 1;
+                last_version = Some(item.version());
+            }
+        }
+        count
+    }
+
+    /// Push multiple items as one version, so they are undone and redone together.
+    pub fn push_all(&mut self, items: impl IntoIterator<Item = I>) {
+        let version = self.inc_version();
+        for item in items {
+            self.push_with_version(item, version);
+        }
+    }
+
     /// Get the undo stack.
     pub fn undos(&self) -> &Vec<I> {
         &self.undos
@@ -236,6 +282,24 @@ mod tests {
         assert_eq!(history.undo().is_none(), true);
     }
 
+    #[test]
+    fn test_history_push_separate() {
+        let mut history: History<TabIndex> = History::new().group_interval(Duration::from_secs(60));
+        history.push(0.into());
+        history.push(1.into());
+        history.push_separate(2.into());
+        history.push(3.into());
+
+        let changes = history.undo().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].tab_index, 3);
+        assert_eq!(changes[1].tab_index, 2);
+
+        let changes = history.undo().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(history.undo().is_none());
+    }
+
     #[test]
     fn test_unique_history() {
         let mut history: History<TabIndex> = History::new().max_undo(100).unique();
@@ -285,4 +349,71 @@ mod tests {
         assert_eq!(history.undos().len(), 0);
         assert_eq!(history.redos().len(), 4);
     }
+
+    #[test]
+    fn test_history_max_undo_versions() {
+        let mut history: History<TabIndex> = History::new().max_undo(2);
+        history.push_all([0.into(), 1.into()]);
+        history.push_all([2.into(), 3.into(), 4.into()]);
+        history.push(5.into());
+
+        // The oldest version is evicted as a whole
+        assert_eq!(
+            history
+                .undos()
+                .iter()
+                .map(|c| c.tab_index)
+                .collect::<Vec<_>>(),
+            vec![2, 3, 4, 5]
+        );
+        assert_eq!(history.undo().unwrap().len(), 1);
+        assert_eq!(history.undo().unwrap().len(), 3);
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn test_history_max_undo_zero() {
+        let mut history: History<TabIndex> = History::new().max_undo(0);
+        history.push(0.into());
+        history.push_all([1.into(), 2.into()]);
+
+        assert!(history.undos().is_empty());
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn test_history_push_all() {
+        let mut history: History<TabIndex> = History::new();
+        history.push(0.into());
+        history.push_all([1.into(), 2.into(), 3.into()]);
+        history.push(4.into());
+
+        assert_eq!(
+            history
+                .undo()
+                .unwrap()
+                .iter()
+                .map(|c| c.tab_index)
+                .collect::<Vec<_>>(),
+            vec![4]
+        );
+        assert_eq!(
+            history
+                .undo()
+                .unwrap()
+                .iter()
+                .map(|c| c.tab_index)
+                .collect::<Vec<_>>(),
+            vec![3, 2, 1]
+        );
+        assert_eq!(
+            history
+                .redo()
+                .unwrap()
+                .iter()
+                .map(|c| c.tab_index)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
 }
@@ -84,6 +84,7 @@ pub enum IconName {
     ThumbsUp,
     TriangleAlert,
     User,
+    WholeWord,
     WindowClose,
     WindowMaximize,
     WindowMinimize,
@@ -170,6 +171,7 @@ impl IconName {
             Self::ThumbsUp => "icons/thumbs-up.svg",
             Self::TriangleAlert => "icons/triangle-alert.svg",
             Self::User => "icons/user.svg",
+            Self::WholeWord => "icons/whole-word.svg",
             Self::WindowClose => "icons/window-close.svg",
             Self::WindowMaximize => "icons/window-maximize.svg",
             Self::WindowMinimize => "icons/window-minimize.svg",
@@ -0,0 +1,145 @@
+use gpui::{Context, EntityInputHandler as _, Window};
+use rope::Rope;
+use sum_tree::Bias;
+
+use super::{InputState, RopeExt as _};
+
+/// The default pairs of [`InputState::with_auto_close`].
+pub(super) fn default_auto_close_pairs() -> Vec<(String, String)> {
+    [("(", ")"), ("[", "]"), ("{", "}"), ("\"", "\""), ("'", "'")]
+        .into_iter()
+        .map(|(open, close)| (open.to_string(), close.to_string()))
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum AutoClose {
+    /// Insert the typed text followed by the closing text.
+    Insert(String),
+    /// Move the cursor over the closing text that is already after the cursor.
+    Skip,
+}
+
+impl InputState {
+    /// Set the pairs closed automatically when typing the opening text in the code editor,
+    /// e.g. typing `(` inserts `()` with the cursor in between, an empty list disables it.
+    ///
+    /// Default: `()`, `[]`, `{}`, `""` and `''`.
+    pub fn with_auto_close(mut self, pairs: &[(&str, &str)]) -> Self {
+        self.auto_close_pairs = pairs
+            .iter()
+            .map(|(open, close)| (open.to_string(), close.to_string()))
+            .collect();
+        self
+    }
+
+    /// Add a pair closed automatically when typing the `open` text, see [`Self::with_auto_close`].
+    pub fn add_auto_close_pair(&mut self, open: impl Into<String>, close: impl Into<String>) {
+        let (open, close) = (open.into(), close.into());
+        self.auto_close_pairs.retain(|(o, _)| *o != open);
+        self.auto_close_pairs.push((open, close));
+    }
+
+    /// Handle the typed text for the auto close pairs, returns true if the text is handled.
+    pub(super) fn auto_close(
+        &mut self,
+        new_text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if !self.mode.is_code_editor()
+            || !self.selected_range.is_empty()
+            || self.ime_marked_range.is_some()
+            || self.has_additional_cursors()
+        {
+            return false;
+        }
+
+        let offset = self.cursor();
+        match auto_close_edit(&self.text, offset, new_text, &self.auto_close_pairs) {
+            Some(AutoClose::Skip) => {
+                let offset = offset + new_text.len();
+                self.selected_range = (offset..offset).into();
+                self.update_preferred_column();
+                self.pause_blink_cursor(cx);
+                cx.notify();
+            }
+            Some(AutoClose::Insert(text)) => {
+                let range_utf16 = self.range_to_utf16(&(offset..offset));
+                self.replace_text_in_range(Some(range_utf16), &text, window, cx);
+                let offset = offset + new_text.len();
+                self.selected_range = (offset..offset).into();
+            }
+            None => return false,
+        }
+        true
+    }
+}
+
+/// Decide how the `typed` text at the `offset` is handled by the auto close `pairs`.
+fn auto_close_edit(
+    text: &Rope,
+    offset: usize,
+    typed: &str,
+    pairs: &[(String, String)],
+) -> Option<AutoClose> {
+    if typed.is_empty() {
+        return None;
+    }
+
+    let end = offset + typed.len();
+    if pairs.iter().any(|(_, close)| close == typed)
+        && end <= text.len()
+        && text.clip_offset(end, Bias::Left) == end
+        && text.slice(offset..end).to_string() == typed
+    {
+        return Some(AutoClose::Skip);
+    }
+
+    let (open, close) = pairs.iter().find(|(open, _)| open == typed)?;
+    // Don't close a quote typed after a word, e.g. the apostrophe of `don't`.
+    if open == close && offset > 0 {
+        let prev_ix = text.clip_offset(offset - 1, Bias::Left);
+        if text
+            .char_at(prev_ix)
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+        {
+            return None;
+        }
+    }
+
+    Some(AutoClose::Insert(format!("{}{}", open, close)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rope::Rope;
+
+    use super::{auto_close_edit, default_auto_close_pairs, AutoClose};
+
+    #[test]
+    fn test_auto_close_edit() {
+        let pairs = default_auto_close_pairs();
+        let edit = |text: &str, offset: usize, typed: &str| {
+            auto_close_edit(&Rope::from(text), offset, typed, &pairs)
+        };
+
+        assert_eq!(edit("foo", 3, "("), Some(AutoClose::Insert("()".into())));
+        assert_eq!(edit("foo()", 4, ")"), Some(AutoClose::Skip));
+        assert_eq!(edit("foo(", 4, ")"), None);
+        assert_eq!(edit("foo", 3, "a"), None);
+        assert_eq!(edit("", 0, "\""), Some(AutoClose::Insert("\"\"".into())));
+        assert_eq!(edit("\"\"", 1, "\""), Some(AutoClose::Skip));
+        assert_eq!(edit("don", 3, "'"), None);
+        assert_eq!(
+            edit("let s = ", 8, "'"),
+            Some(AutoClose::Insert("''".into()))
+        );
+
+        let pairs = vec![("<!--".to_string(), "-->".to_string())];
+        assert_eq!(
+            auto_close_edit(&Rope::from("a"), 1, "<!--", &pairs),
+            Some(AutoClose::Insert("<!---->".into()))
+        );
+    }
+}
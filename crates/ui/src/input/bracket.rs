@@ -0,0 +1,100 @@
+use std::ops::Range;
+
+use rope::Rope;
+use sum_tree::Bias;
+
+use super::RopeExt as _;
+
+const BRACKET_PAIRS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+/// The max bytes to scan for the matching bracket, to avoid slowing down on large text.
+const MAX_SCAN_LEN: usize = 50_000;
+
+/// Find the bracket adjacent to the `offset` (the one after is preferred) and its matching bracket.
+///
+/// Returns the byte ranges of the adjacent bracket and the matching bracket.
+pub(super) fn matching_bracket_ranges(
+    text: &Rope,
+    offset: usize,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let after = text.char_at(offset).map(|c| (offset, c));
+    let before = if offset > 0 {
+        let ix = text.clip_offset(offset - 1, Bias::Left);
+        text.char_at(ix).map(|c| (ix, c))
+    } else {
+        None
+    };
+
+    after.into_iter().chain(before).find_map(|(ix, c)| {
+        let matched_ix = find_matching_bracket(text, ix, c)?;
+        // The brackets are all ASCII characters.
+        Some((ix..ix + 1, matched_ix..matched_ix + 1))
+    })
+}
+
+/// Scan forward for the opening bracket or backward for the closing bracket at `ix`,
+/// the nested pairs are skipped.
+fn find_matching_bracket(text: &Rope, ix: usize, c: char) -> Option<usize> {
+    if let Some(&(open, close)) = BRACKET_PAIRS.iter().find(|(open, _)| *open == c) {
+        let start = ix + 1;
+        let end = text.clip_offset((start + MAX_SCAN_LEN).min(text.len()), Bias::Left);
+        let mut depth = 0;
+        for (i, ch) in text.slice(start..end).to_string().char_indices() {
+            if ch == open {
+                depth += 1;
+            } else if ch == close {
+                if depth == 0 {
+                    return Some(start + i);
+                }
+                depth -= 1;
+            }
+        }
+    } else if let Some(&(open, close)) = BRACKET_PAIRS.iter().find(|(_, close)| *close == c) {
+        let start = text.clip_offset(ix.saturating_sub(MAX_SCAN_LEN), Bias::Right);
+        let mut depth = 0;
+        for (i, ch) in text.slice(start..ix).to_string().char_indices().rev() {
+            if ch == close {
+                depth += 1;
+            } else if ch == open {
+                if depth == 0 {
+                    return Some(start + i);
+                }
+                depth -= 1;
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use rope::Rope;
+
+    use super::matching_bracket_ranges;
+
+    #[test]
+    fn test_matching_bracket_ranges() {
+        let text = Rope::from("fn foo(a: [u8; 2]) {\n    bar(a[0]);\n}");
+
+        // Before the `(`
+        assert_eq!(matching_bracket_ranges(&text, 6), Some((6..7, 17..18)));
+        // After the `)`, scan backward
+        assert_eq!(matching_bracket_ranges(&text, 18), Some((17..18, 6..7)));
+        // Before the `{`, across lines and skip the nested pairs
+        assert_eq!(matching_bracket_ranges(&text, 19), Some((19..20, 36..37)));
+        assert_eq!(matching_bracket_ranges(&text, 37), Some((36..37, 19..20)));
+        // Between `]` and `)`, the one after is preferred
+        assert_eq!(matching_bracket_ranges(&text, 17), Some((17..18, 6..7)));
+        // No bracket
+        assert_eq!(matching_bracket_ranges(&text, 2), None);
+        assert_eq!(matching_bracket_ranges(&text, 0), None);
+
+        // Unmatched
+        let text = Rope::from("foo(bar");
+        assert_eq!(matching_bracket_ranges(&text, 3), None);
+        // Multi-byte characters
+        let text = Rope::from("(中文)");
+        assert_eq!(matching_bracket_ranges(&text, 0), Some((0..1, 7..8)));
+        assert_eq!(matching_bracket_ranges(&text, 8), Some((7..8, 0..1)));
+    }
+}
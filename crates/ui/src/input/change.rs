@@ -1,6 +1,9 @@
 use std::fmt::Debug;
 
-use crate::{history::HistoryItem, input::Selection};
+use crate::{
+    history::{History, HistoryItem},
+    input::Selection,
+};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Change {
@@ -28,6 +31,56 @@ impl Change {
     }
 }
 
+impl Change {
+    /// Returns true if this is a single character insertion.
+    fn is_char_insertion(&self) -> bool {
+        self.old_text.is_empty() && is_single_char(&self.new_text)
+    }
+
+    /// Returns true if this is a single character deletion.
+    fn is_char_deletion(&self) -> bool {
+        self.new_text.is_empty() && is_single_char(&self.old_text)
+    }
+
+    /// Returns true if this change continues the `last` change by typing or deleting
+    /// a single character, so they can be undone as one.
+    pub(super) fn can_group_with(&self, last: &Change) -> bool {
+        if self.is_char_insertion() && last.is_char_insertion() {
+            return last.new_range.end == self.old_range.start;
+        }
+
+        if self.is_char_deletion() && last.is_char_deletion() {
+            // Backspace or delete forward.
+            return self.old_range.end == last.old_range.start
+                || self.old_range.start == last.old_range.start;
+        }
+
+        false
+    }
+}
+
+fn is_single_char(text: &str) -> bool {
+    let mut chars = text.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if c != '\n')
+}
+
+/// Pop the changes of the last undo step, none if the text can't be edited so the undo stack
+/// of a read-only input is kept.
+pub(super) fn undo_changes(history: &mut History<Change>, editable: bool) -> Option<Vec<Change>> {
+    if !editable {
+        return None;
+    }
+    history.undo()
+}
+
+/// Pop the changes of the last redo step, none if the text can't be edited.
+pub(super) fn redo_changes(history: &mut History<Change>, editable: bool) -> Option<Vec<Change>> {
+    if !editable {
+        return None;
+    }
+    history.redo()
+}
+
 impl HistoryItem for Change {
     fn version(&self) -> usize {
         self.version
@@ -37,3 +90,52 @@ impl HistoryItem for Change {
         self.version = version;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{redo_changes, undo_changes, Change};
+    use crate::history::History;
+
+    #[test]
+    fn test_can_group_with() {
+        let typed_a = Change::new(0..0, "", 0..1, "a");
+        let typed_b = Change::new(1..1, "", 1..2, "b");
+        assert!(typed_b.can_group_with(&typed_a));
+        // Not continuous
+        let typed_c = Change::new(5..5, "", 5..6, "c");
+        assert!(!typed_c.can_group_with(&typed_b));
+        // Newline and paste are not grouped
+        let newline = Change::new(2..2, "", 2..3, "\n");
+        assert!(!newline.can_group_with(&typed_b));
+        let paste = Change::new(2..2, "", 2..7, "hello");
+        assert!(!paste.can_group_with(&typed_b));
+
+        // Backspace
+        let back_1 = Change::new(4..5, "o", 4..4, "");
+        let back_2 = Change::new(3..4, "l", 3..3, "");
+        assert!(back_2.can_group_with(&back_1));
+        // Delete forward
+        let delete_2 = Change::new(4..5, "x", 4..4, "");
+        assert!(delete_2.can_group_with(&back_1));
+        // Deletion after insertion
+        assert!(!back_1.can_group_with(&typed_b));
+    }
+
+    #[test]
+    fn test_read_only_keeps_history() {
+        let mut history = History::new();
+        history.push(Change::new(0..0, "", 0..1, "a"));
+
+        assert!(undo_changes(&mut history, false).is_none());
+        assert_eq!(history.undos().len(), 1);
+
+        let changes = undo_changes(&mut history, true).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(history.redos().len(), 1);
+
+        assert!(redo_changes(&mut history, false).is_none());
+        assert_eq!(history.redos().len(), 1);
+        assert_eq!(redo_changes(&mut history, true).unwrap().len(), 1);
+        assert_eq!(history.undos().len(), 1);
+    }
+}
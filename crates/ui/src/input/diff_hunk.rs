@@ -0,0 +1,157 @@
+use std::ops::Range;
+
+use gpui::Context;
+use rope::Rope;
+
+use super::{InputState, RopeExt as _};
+
+/// The kind of a [`DiffHunk`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// A changed range of lines compared to a base version of the text, e.g. from git.
+///
+/// The hunks are shown as colored bars between the line numbers and the text of a code editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffHunk {
+    /// The first line (0-based) of the hunk.
+    pub start_line: usize,
+    /// The line (0-based, exclusive) after the last line of the hunk.
+    ///
+    /// A [`DiffKind::Removed`] hunk has no lines, it is shown as a caret at the top of the `start_line`.
+    pub end_line: usize,
+    pub kind: DiffKind,
+}
+
+impl DiffHunk {
+    pub fn new(lines: Range<usize>, kind: DiffKind) -> Self {
+        Self {
+            start_line: lines.start,
+            end_line: lines.end,
+            kind,
+        }
+    }
+
+    /// Returns true if the hunk has no lines, e.g. a [`DiffKind::Removed`] hunk.
+    pub fn is_empty(&self) -> bool {
+        self.end_line <= self.start_line
+    }
+
+    /// Returns true if the `line` is in the hunk.
+    pub fn contains(&self, line: usize) -> bool {
+        (self.start_line..self.end_line).contains(&line)
+    }
+}
+
+impl InputState {
+    /// Set the diff hunks to show in the gutter, replacing the previous ones.
+    ///
+    /// The hunks are pre-computed by the caller, they move with their lines when lines are
+    /// inserted or removed above them.
+    pub fn set_diff_hunks(&mut self, hunks: Vec<DiffHunk>, cx: &mut Context<Self>) {
+        self.diff_hunks = hunks;
+        cx.notify();
+    }
+
+    /// Remove all the diff hunks.
+    pub fn clear_diff_hunks(&mut self, cx: &mut Context<Self>) {
+        self.diff_hunks.clear();
+        cx.notify();
+    }
+
+    /// Get the diff hunks.
+    pub fn diff_hunks(&self) -> &[DiffHunk] {
+        &self.diff_hunks
+    }
+
+    /// Move the hunks after the edited lines like [`Self::shift_gutter_decorations`],
+    /// the hunks that overlap the edited lines are removed until the caller updates them.
+    pub(super) fn shift_diff_hunks(
+        &mut self,
+        old_text: &Rope,
+        range: &Range<usize>,
+        new_text: &str,
+    ) {
+        if self.diff_hunks.is_empty() {
+            return;
+        }
+
+        let start_row = old_text.offset_to_point(range.start).row as usize;
+        let end_row = old_text.offset_to_point(range.end).row as usize;
+        let new_end_row = start_row + new_text.matches('\n').count();
+        self.diff_hunks = shift_hunks(
+            std::mem::take(&mut self.diff_hunks),
+            start_row..end_row,
+            new_end_row,
+        );
+    }
+}
+
+/// Move the hunks after the edited `old_rows` to end at `new_end_row`,
+/// and the hunks that overlap the edited rows are removed.
+fn shift_hunks(hunks: Vec<DiffHunk>, old_rows: Range<usize>, new_end_row: usize) -> Vec<DiffHunk> {
+    hunks
+        .into_iter()
+        .filter_map(|mut hunk| {
+            let before = if hunk.is_empty() {
+                hunk.start_line <= old_rows.start
+            } else {
+                hunk.end_line <= old_rows.start
+            };
+
+            if before {
+                Some(hunk)
+            } else if hunk.start_line > old_rows.end {
+                hunk.start_line = hunk.start_line - old_rows.end + new_end_row;
+                hunk.end_line = hunk.end_line - old_rows.end + new_end_row;
+                Some(hunk)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{shift_hunks, DiffHunk, DiffKind};
+
+    #[test]
+    fn test_shift_hunks() {
+        let hunks = vec![
+            DiffHunk::new(0..2, DiffKind::Added),
+            DiffHunk::new(3..3, DiffKind::Removed),
+            DiffHunk::new(5..7, DiffKind::Modified),
+        ];
+
+        // Insert 2 lines at line 3.
+        assert_eq!(
+            shift_hunks(hunks.clone(), 3..3, 5),
+            vec![
+                DiffHunk::new(0..2, DiffKind::Added),
+                DiffHunk::new(3..3, DiffKind::Removed),
+                DiffHunk::new(7..9, DiffKind::Modified),
+            ]
+        );
+        // Edit the line 1.
+        assert_eq!(
+            shift_hunks(hunks.clone(), 1..1, 1),
+            vec![
+                DiffHunk::new(3..3, DiffKind::Removed),
+                DiffHunk::new(5..7, DiffKind::Modified),
+            ]
+        );
+        // Remove the lines 5 and 6.
+        assert_eq!(
+            shift_hunks(hunks, 4..6, 4),
+            vec![
+                DiffHunk::new(0..2, DiffKind::Added),
+                DiffHunk::new(3..3, DiffKind::Removed),
+            ]
+        );
+    }
+}
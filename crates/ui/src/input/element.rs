@@ -1,24 +1,40 @@
-use std::{ops::Range, rc::Rc};
+use std::{ops::Range, rc::Rc, time::Instant};
 
 use gpui::{
-    fill, point, px, relative, size, App, Bounds, Corners, Element, ElementId, ElementInputHandler,
-    Entity, GlobalElementId, Half, HighlightStyle, IntoElement, LayoutId, MouseButton,
-    MouseMoveEvent, Path, Pixels, Point, SharedString, Size, Style, TextAlign, TextRun,
-    UnderlineStyle, Window, WrappedLine,
+    fill, point, px, relative, size, AnyElement, App, Bounds, Corners, Element, ElementId,
+    ElementInputHandler, Entity, Font, FontStyle, FontWeight, GlobalElementId, Half,
+    HighlightStyle, IntoElement, LayoutId, MouseButton, MouseMoveEvent, Path, PathBuilder, Pixels,
+    Point, SharedString, Size, Style, TextAlign, TextRun, TransformationMatrix, UnderlineStyle,
+    Window, WrappedLine,
 };
 use rope::Rope;
 use smallvec::SmallVec;
 
 use crate::{
     input::{blink_cursor::CURSOR_WIDTH, RopeExt as _},
-    ActiveTheme as _, Colorize, Root,
+    ActiveTheme as _, Colorize, IconName, Root,
 };
 
-use super::{mode::InputMode, InputState, LastLayout};
+use super::{
+    bracket::matching_bracket_ranges,
+    gutter::{GUTTER_DECORATION_SIZE, GUTTER_DECORATION_WIDTH},
+    indent_guide::{active_indent_guide, indent_columns, indent_levels},
+    metrics::RenderMetrics,
+    minimap::{MinimapElement, MINIMAP_WIDTH},
+    mode::InputMode,
+    DiffKind, InputState, LastLayout,
+};
 
 const BOTTOM_MARGIN_ROWS: usize = 3;
 pub(super) const RIGHT_MARGIN: Pixels = px(10.);
 pub(super) const LINE_NUMBER_RIGHT_MARGIN: Pixels = px(10.);
+/// The width of the fold icons area in the right of the line numbers.
+pub(super) const FOLD_ICON_WIDTH: Pixels = px(14.);
+const FOLD_ICON_SIZE: Pixels = px(12.);
+/// The diff bars are painted in the margin on the right of the line numbers.
+const DIFF_BAR_MARGIN: Pixels = px(3.);
+const DIFF_BAR_WIDTH: Pixels = px(3.);
+const DIFF_CARET_SIZE: Pixels = px(4.);
 
 pub(super) struct TextElement {
     state: Entity<InputState>,
@@ -79,8 +95,11 @@ impl TextElement {
         if let Some(ime_marked_range) = &state.ime_marked_range {
             selected_range = (ime_marked_range.end..ime_marked_range.end).into();
         }
+        selected_range = (state.display_offset(selected_range.start)
+            ..state.display_offset(selected_range.end))
+            .into();
 
-        let cursor = state.cursor();
+        let cursor = state.display_offset(state.cursor());
         let mut current_row = None;
         let mut scroll_offset = state.scroll_handle.offset();
         let mut cursor_bounds = None;
@@ -110,7 +129,7 @@ impl TextElement {
                 break;
             }
 
-            let in_visible_range = ix >= visible_range.start;
+            let in_visible_range = ix >= visible_range.start && !wrap_line.is_hidden();
             if let Some(line) = in_visible_range
                 .then(|| lines.get(ix.saturating_sub(visible_range.start)))
                 .flatten()
@@ -258,7 +277,13 @@ impl TextElement {
         let mut offset_y = visible_top;
         let mut line_corners = vec![];
 
-        for line in lines.iter() {
+        for (ix, line) in lines.iter().enumerate() {
+            if last_layout.is_hidden(ix) {
+                // +1 for skip the last `\n`
+                prev_lines_offset += line.len() + 1;
+                continue;
+            }
+
             let line_size = line.size(line_height);
             let line_wrap_width = line_size.width;
 
@@ -415,10 +440,206 @@ impl TextElement {
 
         let range = start_ix.max(last_layout.visible_range_offset.start)
             ..end_ix.min(last_layout.visible_range_offset.end);
+        if state.masked {
+            let range = state.display_offset(range.start)..state.display_offset(range.end);
+            // The masked text is a single line, that may be longer than the text.
+            let mut last_layout = last_layout.clone();
+            last_layout.visible_range_offset.end = range.end;
+            return Self::layout_match_range(range, &last_layout, bounds);
+        }
 
         Self::layout_match_range(range, &last_layout, bounds)
     }
 
+    /// Layout the brackets adjacent to the cursor and its matching bracket, only for the code editor.
+    fn layout_matching_brackets(
+        &self,
+        last_layout: &LastLayout,
+        bounds: &mut Bounds<Pixels>,
+        cx: &mut App,
+    ) -> Vec<Path<Pixels>> {
+        let state = self.state.read(cx);
+        if !state.mode.is_code_editor()
+            || !state.selected_range.is_empty()
+            || state.ime_marked_range.is_some()
+        {
+            return vec![];
+        }
+
+        let Some((bracket, matched)) = matching_bracket_ranges(&state.text, state.cursor()) else {
+            return vec![];
+        };
+
+        [bracket, matched]
+            .into_iter()
+            .filter_map(|range| Self::layout_match_range(range, last_layout, bounds))
+            .collect()
+    }
+
+    /// Layout the indent guides of the visible lines, with true for the guide of the innermost
+    /// block around the cursor.
+    ///
+    /// Must be called after `layout_cursor`, the `bounds` has included the scroll offset.
+    fn layout_indent_guides(
+        &self,
+        last_layout: &LastLayout,
+        bounds: &Bounds<Pixels>,
+        current_row: Option<usize>,
+        space_width: Pixels,
+        cx: &mut App,
+    ) -> Vec<(Bounds<Pixels>, bool)> {
+        let state = self.state.read(cx);
+        if !state.indent_guides || !state.mode.is_multi_line() {
+            return vec![];
+        }
+        let tab_size = state.mode.tab_size().map_or(2, |tab| tab.tab_size.max(1));
+
+        let visible_range = &last_layout.visible_range;
+        let indents = visible_range
+            .clone()
+            .map(|row| indent_columns(&state.text.line(row).to_string(), tab_size))
+            .map(|columns| columns.map(|columns| columns / tab_size))
+            .collect::<Vec<_>>();
+        let levels = indent_levels(&indents);
+        let active = current_row
+            .and_then(|row| row.checked_sub(visible_range.start))
+            .and_then(|ix| active_indent_guide(&levels, ix));
+
+        let indent_width = space_width * tab_size as f32;
+        let mut guides = vec![];
+        let mut offset_y = last_layout.visible_top;
+        for (ix, line) in last_layout.lines.iter().enumerate() {
+            if last_layout.is_hidden(ix) {
+                continue;
+            }
+
+            let height = line.size(last_layout.line_height).height;
+            for level in 0..levels.get(ix).copied().unwrap_or(0) {
+                let is_active = active.as_ref().is_some_and(|(active_level, rows)| {
+                    *active_level == level && rows.contains(&ix)
+                });
+                let origin = point(
+                    bounds.origin.x + last_layout.line_number_width + indent_width * level as f32,
+                    bounds.origin.y + offset_y,
+                );
+                guides.push((Bounds::new(origin, size(px(1.), height)), is_active));
+            }
+            offset_y += height;
+        }
+
+        guides
+    }
+
+    /// Layout the ghost text after the cursor, the first line starts at the cursor and the next
+    /// lines at the start of the following rows, over the text.
+    fn layout_ghost_text(
+        &self,
+        last_layout: &LastLayout,
+        bounds: &Bounds<Pixels>,
+        cursor_bounds: Option<Bounds<Pixels>>,
+        cursor_scroll_offset: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<(Point<Pixels>, WrappedLine)> {
+        let state = self.state.read(cx);
+        let (Some(text), Some(cursor_bounds)) = (state.visible_ghost_text(), cursor_bounds) else {
+            return vec![];
+        };
+
+        let style = window.text_style();
+        let font_size = style.font_size.to_pixels(window.rem_size());
+        let run = TextRun {
+            len: text.len(),
+            font: Font {
+                style: FontStyle::Italic,
+                ..style.font()
+            },
+            color: cx.theme().muted_foreground.opacity(0.5),
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+        };
+        let Ok(lines) =
+            window
+                .text_system()
+                .shape_text(text.clone(), font_size, &[run], None, None)
+        else {
+            return vec![];
+        };
+
+        let mut origin = point(
+            cursor_bounds.left(),
+            cursor_bounds.top() + cursor_scroll_offset.y,
+        );
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(ix, line)| {
+                if ix > 0 {
+                    origin = point(
+                        bounds.left() + last_layout.line_number_width,
+                        origin.y + last_layout.line_height,
+                    );
+                }
+                (origin, line)
+            })
+            .collect()
+    }
+
+    /// Layout the cursors and selections of the additional cursors in the visible lines.
+    ///
+    /// Must be called after `layout_cursor`, the `bounds` has included the scroll offset.
+    fn layout_additional_cursors(
+        &self,
+        last_layout: &LastLayout,
+        bounds: &mut Bounds<Pixels>,
+        cx: &mut App,
+    ) -> (Vec<Bounds<Pixels>>, Vec<Path<Pixels>>) {
+        let state = self.state.read(cx);
+        let visible_range_offset = &last_layout.visible_range_offset;
+        let line_height = last_layout.line_height;
+
+        let mut cursor_bounds = vec![];
+        let mut selection_paths = vec![];
+        for selection in state.additional_cursors.iter() {
+            let range = selection.start.max(visible_range_offset.start)
+                ..selection.end.min(visible_range_offset.end);
+            if let Some(path) = Self::layout_match_range(range, last_layout, bounds) {
+                selection_paths.push(path);
+            }
+
+            if selection.end < visible_range_offset.start
+                || selection.end > visible_range_offset.end
+            {
+                continue;
+            }
+
+            let mut prev_lines_offset = visible_range_offset.start;
+            let mut offset_y = last_layout.visible_top;
+            for (ix, line) in last_layout.lines.iter().enumerate() {
+                if last_layout.is_hidden(ix) {
+                    prev_lines_offset += line.len() + 1;
+                    continue;
+                }
+
+                let offset = selection.end.saturating_sub(prev_lines_offset);
+                if let Some(pos) = line.position_for_index(offset, line_height) {
+                    cursor_bounds.push(Bounds::new(
+                        bounds.origin + point(last_layout.line_number_width, offset_y) + pos,
+                        size(CURSOR_WIDTH, line_height),
+                    ));
+                    break;
+                }
+
+                offset_y += line.size(line_height).height;
+                // +1 for skip the last `\n`
+                prev_lines_offset += line.len() + 1;
+            }
+        }
+
+        (cursor_bounds, selection_paths)
+    }
+
     /// Calculate the visible range of lines in the viewport.
     ///
     /// Returns
@@ -513,6 +734,12 @@ pub(super) struct PrepaintState {
     last_layout: LastLayout,
     /// The lines only contains the visible lines in the viewport, based on `visible_range`.
     line_numbers: Option<Vec<SmallVec<[WrappedLine; 1]>>>,
+    /// The visible line index and the folded state of the foldable lines.
+    fold_icons: Vec<(usize, bool)>,
+    /// The width of the decoration columns on the left of the line numbers.
+    gutter_decorations_width: Pixels,
+    /// The placeholder to paint after the folded lines.
+    fold_placeholder: Option<WrappedLine>,
     /// Size of the scrollable area by entire lines.
     scroll_size: Size<Pixels>,
     cursor_bounds: Option<Bounds<Pixels>>,
@@ -520,7 +747,17 @@ pub(super) struct PrepaintState {
     /// row index (zero based), no wrap, same line as the cursor.
     current_row: Option<usize>,
     selection_path: Option<Path<Pixels>>,
+    /// The paths of the bracket adjacent to the cursor and its matching bracket.
+    matching_bracket_paths: Vec<Path<Pixels>>,
+    additional_cursor_bounds: Vec<Bounds<Pixels>>,
+    additional_selection_paths: Vec<Path<Pixels>>,
     search_match_paths: Vec<(Path<Pixels>, bool)>,
+    /// The indent guides, true for the active one.
+    indent_guides: Vec<(Bounds<Pixels>, bool)>,
+    /// The lines of the ghost text with their origin.
+    ghost_text_lines: Vec<(Point<Pixels>, WrappedLine)>,
+    /// The timings of the prepaint, `None` if the metrics are not enabled.
+    metrics: Option<RenderMetrics>,
     bounds: Bounds<Pixels>,
 }
 
@@ -561,7 +798,8 @@ fn print_points_as_svg_path(
 }
 
 impl Element for TextElement {
-    type RequestLayoutState = ();
+    /// The minimap element if it is enabled.
+    type RequestLayoutState = Option<AnyElement>;
     type PrepaintState = PrepaintState;
 
     fn id(&self) -> Option<ElementId> {
@@ -599,7 +837,17 @@ impl Element for TextElement {
             style.size.height = line_height.into();
         };
 
-        (window.request_layout(style, [], cx), ())
+        // Allocate the right column for the minimap.
+        let mut minimap = state
+            .show_minimap()
+            .then(|| MinimapElement::new(self.state.clone()).into_any_element());
+        let child_layout_ids = minimap
+            .as_mut()
+            .map(|minimap| minimap.request_layout(window, cx))
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        (window.request_layout(style, child_layout_ids, cx), minimap)
     }
 
     fn prepaint(
@@ -607,10 +855,16 @@ impl Element for TextElement {
         _id: Option<&GlobalElementId>,
         _: Option<&gpui::InspectorElementId>,
         bounds: Bounds<Pixels>,
-        _request_layout: &mut Self::RequestLayoutState,
+        minimap: &mut Self::RequestLayoutState,
         window: &mut Window,
         cx: &mut App,
     ) -> Self::PrepaintState {
+        let prepaint_start = Instant::now();
+        if let Some(minimap) = minimap.as_mut() {
+            minimap.prepaint(window, cx);
+        }
+        let bounds = text_bounds(bounds, minimap);
+
         let state = self.state.read(cx);
         let line_height = window.line_height();
 
@@ -636,15 +890,30 @@ impl Element for TextElement {
         let style = window.text_style();
         let font_size = style.font_size.to_pixels(window.rem_size());
         let mut bounds = bounds;
+        let placeholder_lines = state
+            .placeholder_lines
+            .as_ref()
+            .filter(|_| is_empty && multi_line);
 
-        let (display_text, text_color) = if is_empty {
+        let (display_text, text_color) = if let Some(lines) = placeholder_lines {
+            (
+                Rope::from(lines.join("\n").as_str()),
+                cx.theme().muted_foreground,
+            )
+        } else if is_empty {
             (
                 Rope::from(placeholder.as_str()),
                 cx.theme().muted_foreground,
             )
         } else if state.masked {
             (
-                Rope::from("*".repeat(text.chars_count())),
+                Rope::from(
+                    state
+                        .mask_character
+                        .to_string()
+                        .repeat(text.chars_count())
+                        .as_str(),
+                ),
                 cx.theme().foreground,
             )
         } else {
@@ -667,11 +936,12 @@ impl Element for TextElement {
             }],
             None,
         );
+        let gutter_decorations_width = state.gutter_decorations_width();
         let line_number_width = if state.mode.line_number() {
-            empty_line_number.width + px(6.) + LINE_NUMBER_RIGHT_MARGIN
+            empty_line_number.width + px(6.) + FOLD_ICON_WIDTH + LINE_NUMBER_RIGHT_MARGIN
         } else {
             px(0.)
-        };
+        } + gutter_decorations_width;
 
         let run = TextRun {
             len: display_text.len(),
@@ -694,7 +964,27 @@ impl Element for TextElement {
             strikethrough: None,
         };
 
-        let runs = if !is_empty {
+        let runs = if let Some(lines) = placeholder_lines {
+            // The first line is the title.
+            let title_len = (lines[0].len() + 1).min(display_text.len());
+            vec![
+                TextRun {
+                    len: title_len,
+                    font: Font {
+                        weight: FontWeight::BOLD,
+                        ..style.font()
+                    },
+                    ..run.clone()
+                },
+                TextRun {
+                    len: display_text.len() - title_len,
+                    ..run.clone()
+                },
+            ]
+            .into_iter()
+            .filter(|run| run.len > 0)
+            .collect()
+        } else if !is_empty {
             if let Some(highlight_styles) = highlight_styles {
                 let mut runs = vec![];
 
@@ -718,6 +1008,8 @@ impl Element for TextElement {
                 vec![run]
             }
         } else if let Some(ime_marked_range) = &state.ime_marked_range {
+            let ime_marked_range = state.display_offset(ime_marked_range.start)
+                ..state.display_offset(ime_marked_range.end);
             // IME marked text
             vec![
                 TextRun {
@@ -748,18 +1040,46 @@ impl Element for TextElement {
         };
 
         // NOTE: Here 50 lines about 150µs
-        // let measure = crate::Measure::new("shape_text");
-        let visible_text = display_text
-            .slice_rows(visible_range.start as u32..visible_range.end as u32)
-            .to_string();
+        let shape_text_start = Instant::now();
+        let visible_text = if placeholder_lines.is_some() {
+            // The whole placeholder is visible, the empty text only has 1 line.
+            display_text.to_string()
+        } else {
+            display_text
+                .slice_rows(visible_range.start as u32..visible_range.end as u32)
+                .to_string()
+        };
 
-        let lines = window
+        let visible_text: SharedString = visible_text.into();
+        let mut lines = window
             .text_system()
-            .shape_text(visible_text.into(), font_size, &runs, wrap_width, None)
+            .shape_text(visible_text.clone(), font_size, &runs, wrap_width, None)
             .expect("failed to shape text");
-        // measure.end();
+        let shape_text_duration = shape_text_start.elapsed();
 
         let mut longest_line_width = wrap_width.unwrap_or(px(0.));
+        if wrap_width.is_some() && state.smart_wrap_threshold.is_some() && !is_empty {
+            // Lines under the smart wrap threshold are not wrapped, use their unwrapped layout.
+            let unwrapped_lines = window
+                .text_system()
+                .shape_text(visible_text, font_size, &runs, None, None)
+                .expect("failed to shape text");
+            for (ix, unwrapped_line) in unwrapped_lines.into_iter().enumerate() {
+                let row = visible_range.start + ix;
+                let soft_wrapped = state
+                    .text_wrapper
+                    .lines
+                    .get(row)
+                    .map_or(true, |line| line.is_soft_wrapped());
+                if soft_wrapped || ix >= lines.len() {
+                    continue;
+                }
+
+                longest_line_width = longest_line_width.max(unwrapped_line.unwrapped_layout.width);
+                lines[ix] = unwrapped_line;
+            }
+        }
+
         if state.mode.is_multi_line() && !state.soft_wrap && lines.len() > 1 {
             let longtest_line: SharedString = state
                 .text
@@ -809,6 +1129,7 @@ impl Element for TextElement {
             line_number_width,
             lines: Rc::new(lines),
             cursor_bounds: None,
+            hidden_rows: Rc::new(state.text_wrapper.hidden_rows().to_vec()),
         };
 
         // `position_for_index` for example
@@ -848,6 +1169,36 @@ impl Element for TextElement {
 
         let search_match_paths = self.layout_search_matches(&last_layout, &mut bounds, cx);
         let selection_path = self.layout_selections(&last_layout, &mut bounds, cx);
+        let matching_bracket_paths = self.layout_matching_brackets(&last_layout, &mut bounds, cx);
+        let (additional_cursor_bounds, additional_selection_paths) =
+            self.layout_additional_cursors(&last_layout, &mut bounds, cx);
+
+        let space_width = window
+            .text_system()
+            .shape_line(
+                " ".into(),
+                font_size,
+                &[TextRun {
+                    len: 1,
+                    font: style.font(),
+                    color: gpui::black(),
+                    background_color: None,
+                    underline: None,
+                    strikethrough: None,
+                }],
+                None,
+            )
+            .width;
+        let indent_guides =
+            self.layout_indent_guides(&last_layout, &bounds, current_row, space_width, cx);
+        let ghost_text_lines = self.layout_ghost_text(
+            &last_layout,
+            &bounds,
+            cursor_bounds,
+            cursor_scroll_offset,
+            window,
+            cx,
+        );
 
         let state = self.state.read(cx);
         let line_numbers = if state.mode.line_number() {
@@ -872,6 +1223,11 @@ impl Element for TextElement {
 
             // build line numbers
             for (ix, line) in last_layout.lines.iter().enumerate() {
+                if last_layout.is_hidden(ix) {
+                    line_numbers.push(SmallVec::new());
+                    continue;
+                }
+
                 let ix = last_layout.visible_range.start + ix;
                 let line_no = ix + 1;
 
@@ -897,16 +1253,68 @@ impl Element for TextElement {
             None
         };
 
+        let mut fold_icons = vec![];
+        if state.mode.line_number() {
+            for range in state.fold_ranges.iter() {
+                if last_layout.visible_range.contains(&range.start) {
+                    let ix = range.start - last_layout.visible_range.start;
+                    if !last_layout.is_hidden(ix) {
+                        fold_icons.push((ix, state.is_folded(range.start)));
+                    }
+                }
+            }
+        }
+
+        let fold_placeholder = if state.folded.is_empty() {
+            None
+        } else {
+            let text: SharedString = " ⋯ ".into();
+            window
+                .text_system()
+                .shape_text(
+                    text.clone(),
+                    font_size,
+                    &[TextRun {
+                        len: text.len(),
+                        font: style.font(),
+                        color: cx.theme().muted_foreground,
+                        background_color: Some(cx.theme().muted),
+                        underline: None,
+                        strikethrough: None,
+                    }],
+                    None,
+                    None,
+                )
+                .ok()
+                .and_then(|lines| lines.into_iter().next())
+        };
+
+        let metrics = (state.metrics_enabled || crate::measure_enable()).then(|| RenderMetrics {
+            shape_text_duration,
+            prepaint_duration: prepaint_start.elapsed(),
+            visible_lines: last_layout.visible_range.len(),
+            ..Default::default()
+        });
+
         PrepaintState {
             bounds,
             last_layout,
             scroll_size,
             line_numbers,
+            fold_icons,
+            gutter_decorations_width: state.gutter_decorations_width(),
+            fold_placeholder,
             cursor_bounds,
             cursor_scroll_offset,
             current_row,
             selection_path,
+            matching_bracket_paths,
+            additional_cursor_bounds,
+            additional_selection_paths,
             search_match_paths,
+            indent_guides,
+            ghost_text_lines,
+            metrics,
         }
     }
 
@@ -915,11 +1323,13 @@ impl Element for TextElement {
         _id: Option<&GlobalElementId>,
         _: Option<&gpui::InspectorElementId>,
         input_bounds: Bounds<Pixels>,
-        _request_layout: &mut Self::RequestLayoutState,
+        minimap: &mut Self::RequestLayoutState,
         prepaint: &mut Self::PrepaintState,
         window: &mut Window,
         cx: &mut App,
     ) {
+        let paint_start = Instant::now();
+        let input_bounds = text_bounds(input_bounds, minimap);
         let focus_handle = self.state.read(cx).focus_handle.clone();
         let show_cursor = self.state.read(cx).show_cursor(window, cx);
         let focused = focus_handle.is_focused(window);
@@ -957,6 +1367,11 @@ impl Element for TextElement {
             }
         });
 
+        // Dim the read-only input with a tinted background
+        if self.state.read(cx).read_only {
+            window.paint_quad(fill(input_bounds, cx.theme().muted.opacity(0.5)));
+        }
+
         // Paint multi line text
         let line_height = window.line_height();
         let origin = bounds.origin;
@@ -964,7 +1379,8 @@ impl Element for TextElement {
         let invisible_top_padding = prepaint.last_layout.visible_top;
 
         let mut mask_offset_y = px(0.);
-        if self.state.read(cx).masked {
+        let state = self.state.read(cx);
+        if state.masked && state.mask_character == '*' {
             // Move down offset for vertical centering the *****
             if cfg!(target_os = "macos") {
                 mask_offset_y = px(3.);
@@ -1014,25 +1430,71 @@ impl Element for TextElement {
             if let Some(path) = prepaint.selection_path.take() {
                 window.paint_path(path, cx.theme().selection);
             }
+            for path in prepaint.matching_bracket_paths.drain(..) {
+                window.paint_path(path, cx.theme().bracket_match);
+            }
+            for path in prepaint.additional_selection_paths.drain(..) {
+                window.paint_path(path, cx.theme().selection);
+            }
+        }
+
+        // Paint indent guides
+        for (guide_bounds, is_active) in prepaint.indent_guides.iter() {
+            let color = if *is_active {
+                cx.theme().border
+            } else {
+                cx.theme().muted_foreground.opacity(0.3)
+            };
+            window.paint_quad(fill(*guide_bounds, color));
         }
 
         // Paint text
         let mut offset_y = mask_offset_y + invisible_top_padding;
-        for line in prepaint.last_layout.lines.iter() {
+        for (ix, line) in prepaint.last_layout.lines.iter().enumerate() {
+            if prepaint.last_layout.is_hidden(ix) {
+                continue;
+            }
+
             let p = point(
                 origin.x + prepaint.last_layout.line_number_width,
                 origin.y + offset_y,
             );
             _ = line.paint(p, line_height, TextAlign::Left, None, window, cx);
+
+            // Paint the placeholder after the folded line.
+            if let Some(placeholder) = prepaint.fold_placeholder.as_ref() {
+                if prepaint.last_layout.is_hidden(ix + 1) {
+                    if let Some(pos) = line.position_for_index(line.len(), line_height) {
+                        _ = placeholder.paint(
+                            p + pos,
+                            line_height,
+                            TextAlign::Left,
+                            None,
+                            window,
+                            cx,
+                        );
+                    }
+                }
+            }
+
             offset_y += line.size(line_height).height;
         }
 
+        // Paint the ghost text after the cursor
+        for (origin, line) in prepaint.ghost_text_lines.iter() {
+            _ = line.paint(*origin, line_height, TextAlign::Left, None, window, cx);
+        }
+
         // Paint blinking cursor
         if focused && show_cursor {
             if let Some(mut cursor_bounds) = prepaint.cursor_bounds.take() {
                 cursor_bounds.origin.y += prepaint.cursor_scroll_offset.y;
                 window.paint_quad(fill(cursor_bounds, cx.theme().caret));
             }
+            // The additional cursors share the blink state with the primary cursor.
+            for cursor_bounds in prepaint.additional_cursor_bounds.drain(..) {
+                window.paint_quad(fill(cursor_bounds, cx.theme().caret));
+            }
         }
 
         // Paint line numbers
@@ -1055,8 +1517,36 @@ impl Element for TextElement {
             // Each item is the normal lines.
             for (ix, lines) in line_numbers.iter().enumerate() {
                 let row = visible_range.start + ix;
+
+                if let Some((_, folded)) = prepaint.fold_icons.iter().find(|(i, _)| *i == ix) {
+                    let icon = if *folded {
+                        IconName::ChevronRight
+                    } else {
+                        IconName::ChevronDown
+                    };
+                    let icon_bounds = Bounds::new(
+                        point(
+                            input_bounds.origin.x + prepaint.last_layout.line_number_width
+                                - LINE_NUMBER_RIGHT_MARGIN
+                                - FOLD_ICON_WIDTH,
+                            origin.y + offset_y + (line_height - FOLD_ICON_SIZE).half(),
+                        ),
+                        size(FOLD_ICON_SIZE, FOLD_ICON_SIZE),
+                    );
+                    _ = window.paint_svg(
+                        icon_bounds,
+                        icon.path(),
+                        TransformationMatrix::unit(),
+                        cx.theme().muted_foreground,
+                        cx,
+                    );
+                }
+
                 for line in lines {
-                    let p = point(input_bounds.origin.x, origin.y + offset_y);
+                    let p = point(
+                        input_bounds.origin.x + prepaint.gutter_decorations_width,
+                        origin.y + offset_y,
+                    );
 
                     let is_active = prepaint.current_row == Some(row);
                     let line_size = line.size(line_height);
@@ -1080,6 +1570,83 @@ impl Element for TextElement {
             }
         }
 
+        // Paint diff hunks in the margin between the line numbers and the text
+        if prepaint.line_numbers.is_some() && !self.state.read(cx).diff_hunks.is_empty() {
+            let state = self.state.read(cx);
+            let x = input_bounds.origin.x + prepaint.last_layout.line_number_width
+                - LINE_NUMBER_RIGHT_MARGIN
+                + DIFF_BAR_MARGIN;
+            let mut offset_y = invisible_top_padding;
+            for (ix, line) in prepaint.last_layout.lines.iter().enumerate() {
+                if prepaint.last_layout.is_hidden(ix) {
+                    continue;
+                }
+
+                let row = visible_range.start + ix;
+                let top = origin.y + offset_y;
+                let line_size = line.size(line_height);
+                for hunk in state.diff_hunks.iter() {
+                    let color = match hunk.kind {
+                        DiffKind::Added => cx.theme().green,
+                        DiffKind::Modified => cx.theme().yellow,
+                        DiffKind::Removed => cx.theme().red,
+                    };
+
+                    if hunk.contains(row) {
+                        window.paint_quad(fill(
+                            Bounds::new(point(x, top), size(DIFF_BAR_WIDTH, line_size.height)),
+                            color,
+                        ));
+                    } else if hunk.is_empty() && hunk.start_line == row {
+                        // A caret pointing at the position of the removed lines.
+                        let mut builder = PathBuilder::fill();
+                        builder.move_to(point(x, top - DIFF_CARET_SIZE));
+                        builder.line_to(point(x + DIFF_CARET_SIZE, top));
+                        builder.line_to(point(x, top + DIFF_CARET_SIZE));
+                        builder.close();
+                        if let Ok(path) = builder.build() {
+                            window.paint_path(path, color);
+                        }
+                    }
+                }
+
+                offset_y += line_size.height;
+            }
+        }
+
+        // Paint gutter decorations
+        if prepaint.gutter_decorations_width > px(0.) {
+            let state = self.state.read(cx);
+            let mut offset_y = invisible_top_padding;
+            for (ix, line) in prepaint.last_layout.lines.iter().enumerate() {
+                if prepaint.last_layout.is_hidden(ix) {
+                    continue;
+                }
+
+                let row = visible_range.start + ix;
+                for (col, decoration) in state.gutter_decorations(row).iter().enumerate() {
+                    let icon_bounds = Bounds::new(
+                        point(
+                            input_bounds.origin.x
+                                + GUTTER_DECORATION_WIDTH * col as f32
+                                + (GUTTER_DECORATION_WIDTH - GUTTER_DECORATION_SIZE).half(),
+                            origin.y + offset_y + (line_height - GUTTER_DECORATION_SIZE).half(),
+                        ),
+                        size(GUTTER_DECORATION_SIZE, GUTTER_DECORATION_SIZE),
+                    );
+                    _ = window.paint_svg(
+                        icon_bounds,
+                        decoration.icon.path(),
+                        TransformationMatrix::unit(),
+                        decoration.color,
+                        cx,
+                    );
+                }
+
+                offset_y += line.size(line_height).height;
+            }
+        }
+
         self.state.update(cx, |state, cx| {
             state.last_layout = Some(prepaint.last_layout.clone());
             state.last_bounds = Some(bounds);
@@ -1090,9 +1657,32 @@ impl Element for TextElement {
             state
                 .scroll_handle
                 .set_offset(prepaint.cursor_scroll_offset);
+            state.render_metrics = prepaint.metrics.map(|metrics| RenderMetrics {
+                paint_duration: paint_start.elapsed(),
+                ..metrics
+            });
             cx.notify();
         });
 
+        if let Some(minimap) = minimap.as_mut() {
+            minimap.paint(window, cx);
+        }
+
         self.paint_mouse_listeners(window, cx);
     }
 }
+
+/// Exclude the minimap column from the bounds.
+fn text_bounds(bounds: Bounds<Pixels>, minimap: &Option<AnyElement>) -> Bounds<Pixels> {
+    if minimap.is_none() {
+        return bounds;
+    }
+
+    Bounds::new(
+        bounds.origin,
+        size(
+            (bounds.size.width - MINIMAP_WIDTH).max(px(0.)),
+            bounds.size.height,
+        ),
+    )
+}
@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use gpui::{Context, ExternalPaths, Window};
+
+use super::{InputEvent, InputState};
+
+impl InputState {
+    /// Insert the paths dropped from the OS at the drop position, as string literals of the
+    /// language of the code editor.
+    ///
+    /// [`InputEvent::FileDrop`] is emitted for each path before it is inserted.
+    pub(super) fn on_drop_paths(
+        &mut self,
+        paths: &ExternalPaths,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode.is_code_editor() || self.disabled || self.read_only {
+            return;
+        }
+
+        let language = self.mode.language().unwrap_or_default();
+        let literals = paths
+            .paths()
+            .iter()
+            .map(|path| {
+                cx.emit(InputEvent::FileDrop(path.clone()));
+                path_literal(&language, path)
+            })
+            .collect::<Vec<_>>();
+        if literals.is_empty() {
+            return;
+        }
+
+        let offset = self.index_for_mouse_position(window.mouse_position(), window, cx);
+        self.selected_range = (offset..offset).into();
+        self.insert(literals.join(", "), window, cx);
+        self.focus(window, cx);
+    }
+}
+
+/// Format the `path` as a string literal of the `language`.
+///
+/// The path is inserted as is for the languages without string literals, e.g. Markdown.
+fn path_literal(language: &str, path: &Path) -> String {
+    let path = path.to_string_lossy();
+    match language {
+        "bash" | "make" => format!("'{}'", path.replace('\'', "'\\''")),
+        "sql" => format!("'{}'", path.replace('\'', "''")),
+        "c" | "cmake" | "cpp" | "csharp" | "css" | "elixir" | "go" | "graphql" | "java"
+        | "javascript" | "json" | "proto" | "python" | "ruby" | "rust" | "scala" | "swift"
+        | "toml" | "tsx" | "typescript" | "yaml" | "zig" => {
+            format!("\"{}\"", path.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::path_literal;
+
+    #[test]
+    fn test_path_literal() {
+        let path = Path::new("src/it's \"main\".rs");
+        assert_eq!(
+            path_literal("rust", path),
+            r#""src/it's \"main\".rs""#.to_string()
+        );
+        assert_eq!(
+            path_literal("bash", path),
+            r#"'src/it'\''s "main".rs'"#.to_string()
+        );
+        assert_eq!(
+            path_literal("sql", path),
+            r#"'src/it''s "main".rs'"#.to_string()
+        );
+        assert_eq!(
+            path_literal("markdown", path),
+            r#"src/it's "main".rs"#.to_string()
+        );
+        assert_eq!(
+            path_literal("python", Path::new(r"C:\Users\main.py")),
+            r#""C:\\Users\\main.py""#.to_string()
+        );
+    }
+}
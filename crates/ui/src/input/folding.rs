@@ -0,0 +1,281 @@
+use std::{collections::HashSet, ops::Range};
+
+use gpui::{Context, Pixels, Point, Window};
+use rope::Rope;
+
+use super::{
+    element::{FOLD_ICON_WIDTH, LINE_NUMBER_RIGHT_MARGIN},
+    InputState, RopeExt as _,
+};
+
+impl InputState {
+    /// Get the foldable line ranges, only available in [`super::InputMode::CodeEditor`] mode
+    /// with a language that has the folds query.
+    ///
+    /// The `start` of each range is the line to fold at, and the lines in `start + 1..end`
+    /// will be hidden when it folded.
+    pub fn fold_ranges(&self) -> &[Range<usize>] {
+        &self.fold_ranges
+    }
+
+    /// Returns true if the range starts at the `line` is folded.
+    pub fn is_folded(&self, line: usize) -> bool {
+        self.folded.contains(&line)
+    }
+
+    /// Fold the range starts at the `line`, or the innermost range contains it.
+    pub fn fold_at(&mut self, line: usize, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(range) = fold_range_for_line(&self.fold_ranges, line) else {
+            return;
+        };
+
+        self.folded.insert(range.start);
+        self.apply_folds(cx);
+    }
+
+    /// Unfold the folded range starts at the `line`, or the innermost folded range contains it.
+    pub fn unfold_at(&mut self, line: usize, _: &mut Window, cx: &mut Context<Self>) {
+        let folded_ranges = self
+            .fold_ranges
+            .iter()
+            .filter(|range| self.folded.contains(&range.start))
+            .cloned()
+            .collect::<Vec<_>>();
+        let Some(range) = fold_range_for_line(&folded_ranges, line) else {
+            return;
+        };
+
+        self.folded.remove(&range.start);
+        self.apply_folds(cx);
+    }
+
+    /// Fold all the foldable ranges.
+    pub fn fold_all(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.folded = self.fold_ranges.iter().map(|range| range.start).collect();
+        self.apply_folds(cx);
+    }
+
+    /// Unfold all the folded ranges.
+    pub fn unfold_all(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        if self.folded.is_empty() {
+            return;
+        }
+
+        self.folded.clear();
+        self.apply_folds(cx);
+    }
+
+    /// Toggle the fold of the range starts at the `line`.
+    ///
+    /// Returns false if there is no foldable range starts at the `line`.
+    pub(super) fn toggle_fold_at(
+        &mut self,
+        line: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if !self.fold_ranges.iter().any(|range| range.start == line) {
+            return false;
+        }
+
+        if self.is_folded(line) {
+            self.unfold_at(line, window, cx);
+        } else {
+            self.fold_at(line, window, cx);
+        }
+        true
+    }
+
+    /// Returns true if the `position` is in the fold icons area of the line number gutter.
+    pub(super) fn is_in_fold_gutter(&self, position: Point<Pixels>) -> bool {
+        if !self.mode.line_number() || self.fold_ranges.is_empty() {
+            return false;
+        }
+        let Some(last_layout) = self.last_layout.as_ref() else {
+            return false;
+        };
+
+        let x = position.x - self.input_bounds.origin.x;
+        let right = last_layout.line_number_width - LINE_NUMBER_RIGHT_MARGIN;
+        x >= right - FOLD_ICON_WIDTH && x < right
+    }
+
+    /// Update the foldable ranges from the highlighter.
+    ///
+    /// The folded lines that are no longer the start of a foldable range will be unfolded.
+    pub(super) fn update_fold_ranges(&mut self, cx: &mut Context<Self>) {
+        self.fold_ranges = self.mode.foldable_ranges(&self.text);
+        if self.folded.is_empty() && self.text_wrapper.hidden_rows().is_empty() {
+            return;
+        }
+
+        let fold_ranges = &self.fold_ranges;
+        self.folded
+            .retain(|line| fold_ranges.iter().any(|range| range.start == *line));
+        self.apply_folds(cx);
+    }
+
+    /// Move the folded lines after the edit by the number of changed lines.
+    ///
+    /// This must be called before [`Self::update_fold_ranges`], the `old_text` is the text before the edit.
+    pub(super) fn shift_folds(&mut self, old_text: &Rope, range: &Range<usize>, new_text: &str) {
+        if self.folded.is_empty() {
+            return;
+        }
+
+        let start_row = old_text.offset_to_point(range.start).row as usize;
+        let end_row = old_text.offset_to_point(range.end).row as usize;
+        let new_end_row = start_row + new_text.matches('\n').count();
+        self.folded = shift_folded(&self.folded, start_row..end_row, new_end_row);
+    }
+
+    /// Unfold the folds that hide the line of the `offset`.
+    pub(super) fn reveal_offset(&mut self, offset: usize, cx: &mut Context<Self>) {
+        if self.folded.is_empty() {
+            return;
+        }
+
+        let row = self.text.offset_to_point(offset).row as usize;
+        let fold_ranges = &self.fold_ranges;
+        let folded_len = self.folded.len();
+        self.folded.retain(|line| {
+            !fold_ranges
+                .iter()
+                .any(|range| range.start == *line && range.start < row && row < range.end)
+        });
+
+        if self.folded.len() != folded_len {
+            self.apply_folds(cx);
+        }
+    }
+
+    /// Get the closest visible row of the `row`, search downward if `down` is true.
+    pub(super) fn visible_row(&self, row: usize, down: bool) -> usize {
+        let last_row = self.text.lines_len().saturating_sub(1);
+        let mut row = row;
+        while let Some(rows) = self
+            .text_wrapper
+            .hidden_rows()
+            .iter()
+            .find(|rows| rows.contains(&row))
+        {
+            row = if down && rows.end <= last_row {
+                rows.end
+            } else {
+                rows.start.saturating_sub(1)
+            };
+        }
+        row
+    }
+
+    fn apply_folds(&mut self, cx: &mut Context<Self>) {
+        self.text_wrapper
+            .set_hidden_rows(hidden_rows(&self.fold_ranges, &self.folded));
+
+        // Keep the cursor out of the hidden lines.
+        let row = self.text.offset_to_point(self.cursor()).row as usize;
+        if self.text_wrapper.is_hidden(row) {
+            let offset = self.text.line_end_offset(self.visible_row(row, false));
+            self.selected_range = (offset..offset).into();
+            self.additional_cursors.clear();
+        }
+
+        self.mode.update_auto_grow(&self.text_wrapper);
+        cx.notify();
+    }
+}
+
+/// Get the range starts at the `line`, or the innermost range contains it.
+fn fold_range_for_line(fold_ranges: &[Range<usize>], line: usize) -> Option<Range<usize>> {
+    fold_ranges
+        .iter()
+        .find(|range| range.start == line)
+        .or_else(|| {
+            fold_ranges
+                .iter()
+                .filter(|range| range.contains(&line))
+                .min_by_key(|range| range.len())
+        })
+        .cloned()
+}
+
+/// Get the rows hidden by the `folded` ranges, the overlapping rows are merged.
+///
+/// The `fold_ranges` must be sorted by the start line.
+fn hidden_rows(fold_ranges: &[Range<usize>], folded: &HashSet<usize>) -> Vec<Range<usize>> {
+    let mut rows: Vec<Range<usize>> = vec![];
+    for range in fold_ranges
+        .iter()
+        .filter(|range| folded.contains(&range.start))
+    {
+        let hidden = range.start + 1..range.end;
+        match rows.last_mut() {
+            Some(last) if hidden.start <= last.end => last.end = last.end.max(hidden.end),
+            _ => rows.push(hidden),
+        }
+    }
+    rows
+}
+
+/// Move the folded lines after the edited `old_rows` to end at `new_end_row`,
+/// and the folded lines inside the edited rows are removed.
+fn shift_folded(
+    folded: &HashSet<usize>,
+    old_rows: Range<usize>,
+    new_end_row: usize,
+) -> HashSet<usize> {
+    folded
+        .iter()
+        .filter_map(|&line| {
+            if line <= old_rows.start {
+                Some(line)
+            } else if line <= old_rows.end {
+                None
+            } else {
+                Some(line - old_rows.end + new_end_row)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{fold_range_for_line, hidden_rows, shift_folded};
+
+    #[test]
+    fn test_fold_range_for_line() {
+        let fold_ranges = vec![0..10, 2..5, 6..9];
+        assert_eq!(fold_range_for_line(&fold_ranges, 0), Some(0..10));
+        assert_eq!(fold_range_for_line(&fold_ranges, 2), Some(2..5));
+        assert_eq!(fold_range_for_line(&fold_ranges, 3), Some(2..5));
+        assert_eq!(fold_range_for_line(&fold_ranges, 5), Some(0..10));
+        assert_eq!(fold_range_for_line(&fold_ranges, 10), None);
+    }
+
+    #[test]
+    fn test_hidden_rows() {
+        let fold_ranges = vec![0..10, 2..5, 6..9, 12..15];
+        assert_eq!(hidden_rows(&fold_ranges, &HashSet::new()), vec![]);
+        assert_eq!(
+            hidden_rows(&fold_ranges, &HashSet::from([2, 6, 12])),
+            vec![3..5, 7..9, 13..15]
+        );
+        assert_eq!(
+            hidden_rows(&fold_ranges, &HashSet::from([0, 6, 12])),
+            vec![1..10, 13..15]
+        );
+    }
+
+    #[test]
+    fn test_shift_folded() {
+        let folded = HashSet::from([1, 4, 8]);
+        // Insert 2 lines at line 2
+        assert_eq!(shift_folded(&folded, 2..2, 4), HashSet::from([1, 6, 10]));
+        // Delete the lines 3..5
+        assert_eq!(shift_folded(&folded, 3..5, 3), HashSet::from([1, 6]));
+        // Edit in the fold start line
+        assert_eq!(shift_folded(&folded, 4..4, 4), HashSet::from([1, 4, 8]));
+    }
+}
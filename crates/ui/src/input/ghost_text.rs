@@ -0,0 +1,55 @@
+use gpui::{Context, SharedString, Window};
+
+use super::InputState;
+
+impl InputState {
+    /// Show the `text` after the cursor while the cursor is at the `offset`, e.g. an inline
+    /// completion of a code assistant, the text can have multiple lines.
+    ///
+    /// The ghost text is only painted, it is not a part of the value and doesn't move the cursor.
+    /// Press `tab` or call [`InputState::accept_ghost_text`] to insert it.
+    pub fn set_ghost_text(
+        &mut self,
+        offset: usize,
+        text: impl Into<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        self.ghost_text = Some((offset, text.into()));
+        cx.notify();
+    }
+
+    /// Remove the ghost text.
+    pub fn clear_ghost_text(&mut self, cx: &mut Context<Self>) {
+        if self.ghost_text.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Returns the offset and the text of the ghost text.
+    pub fn ghost_text(&self) -> Option<(usize, &SharedString)> {
+        self.ghost_text
+            .as_ref()
+            .map(|(offset, text)| (*offset, text))
+    }
+
+    /// Returns the ghost text if it is shown, the cursor must be at its offset without a selection.
+    pub(super) fn visible_ghost_text(&self) -> Option<&SharedString> {
+        let (offset, text) = self.ghost_text.as_ref()?;
+        if self.masked || !self.selected_range.is_empty() || self.cursor() != *offset {
+            return None;
+        }
+
+        Some(text).filter(|text| !text.is_empty())
+    }
+
+    /// Insert the ghost text at the cursor, returns false if no ghost text is shown.
+    pub fn accept_ghost_text(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some(text) = self.visible_ghost_text().cloned() else {
+            return false;
+        };
+
+        self.ghost_text = None;
+        self.insert(text, window, cx);
+        true
+    }
+}
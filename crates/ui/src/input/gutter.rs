@@ -0,0 +1,276 @@
+use std::{collections::HashMap, ops::Range, sync::Arc};
+
+use gpui::{
+    deferred, div, px, AnyElement, App, Context, Hsla, IntoElement as _, ParentElement as _,
+    Pixels, Point, SharedString, Styled as _, Window,
+};
+use rope::Rope;
+
+use super::{InputState, RopeExt as _};
+use crate::{ActiveTheme as _, IconName};
+
+/// The width of a decoration column in the gutter.
+pub(super) const GUTTER_DECORATION_WIDTH: Pixels = px(16.);
+pub(super) const GUTTER_DECORATION_SIZE: Pixels = px(12.);
+
+/// An icon in the gutter of a multi-line input at a line, e.g. a breakpoint, a bookmark or git blame.
+///
+/// The decorations are painted in columns on the left of the line numbers, the gutter grows
+/// to fit the line with the most decorations.
+#[derive(Clone)]
+pub struct GutterDecoration {
+    pub icon: IconName,
+    pub color: Hsla,
+    pub tooltip: Option<SharedString>,
+    /// Called with the line (0-based) of the decoration when it is clicked.
+    pub on_click: Option<Arc<dyn Fn(usize, &mut Window, &mut App)>>,
+}
+
+impl GutterDecoration {
+    pub fn new(icon: IconName, color: impl Into<Hsla>) -> Self {
+        Self {
+            icon,
+            color: color.into(),
+            tooltip: None,
+            on_click: None,
+        }
+    }
+
+    /// Set the tooltip to show when the decoration is hovered.
+    pub fn tooltip(mut self, tooltip: impl Into<SharedString>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Set the handler to call when the decoration is clicked.
+    pub fn on_click(mut self, handler: impl Fn(usize, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Arc::new(handler));
+        self
+    }
+}
+
+impl InputState {
+    /// Add a decoration to the gutter at the `line` (0-based), after the existing ones of the line.
+    ///
+    /// The decorations move with their line when lines are inserted or removed above it.
+    pub fn add_gutter_decoration(
+        &mut self,
+        line: usize,
+        decoration: GutterDecoration,
+        cx: &mut Context<Self>,
+    ) {
+        self.gutter_decorations
+            .entry(line)
+            .or_default()
+            .push(decoration);
+        cx.notify();
+    }
+
+    /// Remove all the gutter decorations of the `line`.
+    pub fn remove_gutter_decorations(&mut self, line: usize, cx: &mut Context<Self>) {
+        if self.gutter_decorations.remove(&line).is_some() {
+            self.hovered_gutter_decoration = None;
+            cx.notify();
+        }
+    }
+
+    /// Remove all the gutter decorations.
+    pub fn clear_gutter_decorations(&mut self, cx: &mut Context<Self>) {
+        self.gutter_decorations.clear();
+        self.hovered_gutter_decoration = None;
+        cx.notify();
+    }
+
+    /// Get the gutter decorations of the `line`.
+    pub fn gutter_decorations(&self, line: usize) -> &[GutterDecoration] {
+        self.gutter_decorations
+            .get(&line)
+            .map(|decorations| decorations.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The width of the decoration columns in the gutter, 0 if there is no decoration.
+    pub(super) fn gutter_decorations_width(&self) -> Pixels {
+        if !self.mode.is_multi_line() {
+            return px(0.);
+        }
+
+        let columns = self
+            .gutter_decorations
+            .values()
+            .map(|decorations| decorations.len())
+            .max()
+            .unwrap_or(0);
+        GUTTER_DECORATION_WIDTH * columns as f32
+    }
+
+    /// Get the line and the index of the decoration at the `position` in the gutter.
+    fn gutter_decoration_at(
+        &self,
+        position: Point<Pixels>,
+        window: &Window,
+        cx: &App,
+    ) -> Option<(usize, usize)> {
+        if self.gutter_decorations.is_empty() || self.last_layout.is_none() {
+            return None;
+        }
+
+        let x = position.x - self.input_bounds.origin.x;
+        if x < px(0.) || x >= self.gutter_decorations_width() {
+            return None;
+        }
+
+        let offset = self.index_for_mouse_position(position, window, cx);
+        let line = self.text.offset_to_point(offset).row as usize;
+        let ix = (x / GUTTER_DECORATION_WIDTH) as usize;
+        (ix < self.gutter_decorations(line).len()).then_some((line, ix))
+    }
+
+    /// Call the click handler of the decoration at the `position`.
+    ///
+    /// Returns true if a decoration was clicked.
+    pub(super) fn click_gutter_decoration(
+        &mut self,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some((line, ix)) = self.gutter_decoration_at(position, window, cx) else {
+            return false;
+        };
+        let Some(on_click) = self.gutter_decorations(line)[ix].on_click.clone() else {
+            return false;
+        };
+
+        on_click(line, window, cx);
+        true
+    }
+
+    /// Update the hovered decoration to show its tooltip.
+    pub(super) fn update_hovered_gutter_decoration(
+        &mut self,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let hovered = self
+            .gutter_decoration_at(position, window, cx)
+            .filter(|(line, ix)| self.gutter_decorations(*line)[*ix].tooltip.is_some());
+        if hovered != self.hovered_gutter_decoration {
+            self.hovered_gutter_decoration = hovered;
+            cx.notify();
+        }
+    }
+
+    /// Move the decorations after the edited lines like [`Self::shift_folds`],
+    /// the decorations inside the edited lines are removed.
+    pub(super) fn shift_gutter_decorations(
+        &mut self,
+        old_text: &Rope,
+        range: &Range<usize>,
+        new_text: &str,
+    ) {
+        if self.gutter_decorations.is_empty() {
+            return;
+        }
+
+        let start_row = old_text.offset_to_point(range.start).row as usize;
+        let end_row = old_text.offset_to_point(range.end).row as usize;
+        let new_end_row = start_row + new_text.matches('\n').count();
+        self.gutter_decorations = shift_decorations(
+            std::mem::take(&mut self.gutter_decorations),
+            start_row..end_row,
+            new_end_row,
+        );
+        self.hovered_gutter_decoration = None;
+    }
+
+    /// Render the tooltip of the hovered decoration.
+    pub(super) fn render_gutter_tooltip(&self, cx: &App) -> Option<AnyElement> {
+        let (line, ix) = self.hovered_gutter_decoration?;
+        let tooltip = self.gutter_decorations(line).get(ix)?.tooltip.clone()?;
+        let last_layout = self.last_layout.as_ref()?;
+        let (_, _, pos) = self.line_and_position_for_offset(self.text.line_start_offset(line));
+        let pos = pos?;
+
+        let scroll_origin = self.scroll_handle.offset();
+        let x = GUTTER_DECORATION_WIDTH * (ix + 1) as f32;
+        let y = pos.y + scroll_origin.y + last_layout.line_height;
+
+        Some(
+            deferred(
+                div()
+                    .absolute()
+                    .left(x)
+                    .top(y)
+                    .px_2()
+                    .py_0p5()
+                    .text_sm()
+                    .bg(cx.theme().popover)
+                    .text_color(cx.theme().popover_foreground)
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .rounded(px(6.))
+                    .shadow_md()
+                    .child(tooltip),
+            )
+            .into_any_element(),
+        )
+    }
+}
+
+/// Move the decorations after the edited `old_rows` to end at `new_end_row`,
+/// and the decorations inside the edited rows are removed.
+fn shift_decorations(
+    decorations: HashMap<usize, Vec<GutterDecoration>>,
+    old_rows: Range<usize>,
+    new_end_row: usize,
+) -> HashMap<usize, Vec<GutterDecoration>> {
+    decorations
+        .into_iter()
+        .filter_map(|(line, decorations)| {
+            if line <= old_rows.start {
+                Some((line, decorations))
+            } else if line <= old_rows.end {
+                None
+            } else {
+                Some((line - old_rows.end + new_end_row, decorations))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use gpui::black;
+
+    use super::{shift_decorations, GutterDecoration};
+    use crate::IconName;
+
+    #[test]
+    fn test_shift_decorations() {
+        let decorations = [1, 3, 5, 8]
+            .into_iter()
+            .map(|line| (line, vec![GutterDecoration::new(IconName::Dash, black())]))
+            .collect::<HashMap<_, _>>();
+
+        let lines = |decorations: HashMap<usize, Vec<GutterDecoration>>| {
+            let mut lines = decorations.into_keys().collect::<Vec<_>>();
+            lines.sort();
+            lines
+        };
+
+        // Insert 2 lines at line 3.
+        assert_eq!(
+            lines(shift_decorations(decorations.clone(), 3..3, 5)),
+            vec![1, 3, 7, 10]
+        );
+        // Remove the lines 3..5.
+        assert_eq!(
+            lines(shift_decorations(decorations, 3..5, 3)),
+            vec![1, 3, 6]
+        );
+    }
+}
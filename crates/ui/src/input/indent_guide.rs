@@ -0,0 +1,92 @@
+use std::ops::Range;
+
+/// The indent width in columns of the `line`, a tab counts as `tab_size` columns.
+///
+/// Returns `None` for a blank line.
+pub(super) fn indent_columns(line: &str, tab_size: usize) -> Option<usize> {
+    let mut columns = 0;
+    for c in line.chars() {
+        match c {
+            ' ' => columns += 1,
+            '\t' => columns += tab_size,
+            '\n' | '\r' => return None,
+            _ => return Some(columns),
+        }
+    }
+
+    None
+}
+
+/// The indent levels of the lines, `None` items are blank lines.
+///
+/// A blank line takes the smaller level of the lines around it, so the guides of a block
+/// are not broken by its blank lines.
+pub(super) fn indent_levels(indents: &[Option<usize>]) -> Vec<usize> {
+    let mut levels = vec![0; indents.len()];
+    let mut prev = None;
+    for (ix, indent) in indents.iter().enumerate() {
+        match indent {
+            Some(level) => {
+                levels[ix] = *level;
+                prev = Some(*level);
+            }
+            None => {
+                let next = indents[ix + 1..].iter().find_map(|indent| *indent);
+                levels[ix] = match (prev, next) {
+                    (Some(prev), Some(next)) => prev.min(next),
+                    (Some(level), None) | (None, Some(level)) => level,
+                    (None, None) => 0,
+                };
+            }
+        }
+    }
+
+    levels
+}
+
+/// Returns the level and the line indexes of the guide of the innermost block around the
+/// line at `ix`, or `None` if the line is not indented.
+pub(super) fn active_indent_guide(levels: &[usize], ix: usize) -> Option<(usize, Range<usize>)> {
+    let level = levels.get(ix)?.checked_sub(1)?;
+    let start = levels[..ix]
+        .iter()
+        .rposition(|l| *l <= level)
+        .map_or(0, |start| start + 1);
+    let end = levels[ix..]
+        .iter()
+        .position(|l| *l <= level)
+        .map_or(levels.len(), |end| ix + end);
+
+    Some((level, start..end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{active_indent_guide, indent_columns, indent_levels};
+
+    #[test]
+    fn test_indent_columns() {
+        assert_eq!(indent_columns("fn main() {", 4), Some(0));
+        assert_eq!(indent_columns("    let a = 1;", 4), Some(4));
+        assert_eq!(indent_columns("\t  a", 4), Some(6));
+        assert_eq!(indent_columns("    ", 4), None);
+        assert_eq!(indent_columns("", 4), None);
+    }
+
+    #[test]
+    fn test_indent_levels() {
+        assert_eq!(
+            indent_levels(&[Some(0), Some(1), None, Some(2), None, Some(0)]),
+            vec![0, 1, 1, 2, 0, 0]
+        );
+        assert_eq!(indent_levels(&[None, Some(1), None]), vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_active_indent_guide() {
+        let levels = [0, 1, 2, 2, 1, 0];
+        assert_eq!(active_indent_guide(&levels, 2), Some((1, 2..4)));
+        assert_eq!(active_indent_guide(&levels, 4), Some((0, 1..5)));
+        assert_eq!(active_indent_guide(&levels, 0), None);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, ops::Range, rc::Rc};
+use std::{cell::RefCell, ops::Range, path::PathBuf, rc::Rc};
 
 use anyhow::Result;
 use gpui::{App, Context, Entity, EntityInputHandler, SharedString, Task, Window};
@@ -9,7 +9,7 @@ use rope::Rope;
 
 use crate::input::{
     popovers::{CodeActionItem, CodeActionMenu, CompletionMenu, ContextMenu},
-    InputState, RopeExt,
+    InputEvent, InputState, RopeExt,
 };
 
 /// A trait for providing code completions based on the current input state and context.
@@ -68,6 +68,19 @@ pub trait CodeActionProvider {
     ) -> Task<Result<()>>;
 }
 
+/// A trait for finding the definition of the symbol at an offset, e.g. with a LSP server.
+pub trait GoToDefinitionProvider {
+    /// Returns the file and the byte offset in it of the definition of the symbol at the
+    /// `offset`, or `None` if there is no definition.
+    fn go_to_definition(
+        &self,
+        state: Entity<InputState>,
+        offset: usize,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Option<(PathBuf, usize)>>;
+}
+
 impl InputState {
     pub(crate) fn hide_context_menu(&mut self, cx: &mut Context<Self>) {
         self.context_menu = None;
@@ -306,6 +319,33 @@ impl InputState {
         .detach();
     }
 
+    /// Find the definition of the symbol at the `offset` with the go-to-definition provider,
+    /// and emit [`InputEvent::GoToDefinition`] for the host to open it.
+    ///
+    /// Returns false if there is no provider.
+    pub(super) fn handle_go_to_definition(
+        &mut self,
+        offset: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(provider) = self.mode.go_to_definition_provider().cloned() else {
+            return false;
+        };
+
+        let task = provider.go_to_definition(cx.entity(), offset, window, cx);
+        cx.spawn_in(window, async move |editor, cx| {
+            if let Some((path, offset)) = task.await {
+                _ = editor.update(cx, |_, cx| {
+                    cx.emit(InputEvent::GoToDefinition(path, offset));
+                });
+            }
+        })
+        .detach();
+
+        true
+    }
+
     /// Apply a list of [`lsp_types::TextEdit`] to mutate the text.
     pub fn apply_lsp_edits(
         &mut self,
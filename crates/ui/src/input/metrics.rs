@@ -0,0 +1,88 @@
+use std::{fmt, time::Duration};
+
+use super::InputState;
+
+/// The timings of the last render of the [`super::TextInput`], recorded when
+/// [`super::TextInput::with_metrics_enabled`] is set or the `GPUI_MEASUREMENTS` env is set.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RenderMetrics {
+    /// The time to shape the visible lines.
+    pub shape_text_duration: Duration,
+    /// The time of the prepaint, including `shape_text_duration`.
+    pub prepaint_duration: Duration,
+    pub paint_duration: Duration,
+    /// The number of lines laid out, the visible lines of the viewport.
+    pub visible_lines: usize,
+}
+
+impl fmt::Display for RenderMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = [
+            ("shape text", format!("{:?}", self.shape_text_duration)),
+            ("prepaint", format!("{:?}", self.prepaint_duration)),
+            ("paint", format!("{:?}", self.paint_duration)),
+            ("visible lines", self.visible_lines.to_string()),
+        ];
+        let name_width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        let value_width = rows
+            .iter()
+            .map(|(_, value)| value.chars().count())
+            .max()
+            .unwrap_or(0);
+        let border = format!(
+            "+{}+{}+",
+            "-".repeat(name_width + 2),
+            "-".repeat(value_width + 2)
+        );
+
+        writeln!(f, "{}", border)?;
+        for (name, value) in rows {
+            writeln!(f, "| {:<name_width$} | {:>value_width$} |", name, value)?;
+        }
+        write!(f, "{}", border)
+    }
+}
+
+impl InputState {
+    /// Returns the timings of the last render, `None` if the metrics are not enabled.
+    pub fn render_metrics(&self) -> Option<&RenderMetrics> {
+        self.render_metrics.as_ref()
+    }
+
+    /// Print the timings of the last render as a table to stderr.
+    pub fn dump_metrics(&self) {
+        if let Some(metrics) = &self.render_metrics {
+            eprintln!("{}", metrics);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RenderMetrics;
+
+    #[test]
+    fn test_render_metrics_table() {
+        let metrics = RenderMetrics {
+            shape_text_duration: Duration::from_micros(150),
+            prepaint_duration: Duration::from_micros(420),
+            paint_duration: Duration::from_micros(85),
+            visible_lines: 42,
+        };
+
+        assert_eq!(
+            metrics.to_string(),
+            [
+                "+---------------+-------+",
+                "| shape text    | 150µs |",
+                "| prepaint      | 420µs |",
+                "| paint         |  85µs |",
+                "| visible lines |    42 |",
+                "+---------------+-------+",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use std::{ops::Range, rc::Rc};
+
+use gpui::{
+    fill, point, px, relative, size, App, Bounds, Context, DispatchPhase, Element, ElementId,
+    Entity, GlobalElementId, Half, Hsla, IntoElement, LayoutId, MouseDownEvent, Pixels, Position,
+    Style, Window,
+};
+use rope::Rope;
+
+use super::{mode::InputMode, InputState, RopeExt as _};
+use crate::{ActiveTheme as _, Colorize as _};
+
+/// The width of the minimap column in the right of the input.
+pub(super) const MINIMAP_WIDTH: Pixels = px(120.);
+const LINE_HEIGHT: Pixels = px(2.);
+const CHAR_WIDTH: Pixels = px(1.);
+const TAB_COLUMNS: usize = 4;
+const MAX_COLUMNS: usize = 120;
+
+/// The colored blocks of the text lines, recomputed only when the text changed.
+#[derive(Clone, Default)]
+pub(super) struct MinimapCache {
+    text: Rope,
+    /// The blocks of each line, the range is the columns of the block.
+    lines: Rc<Vec<Vec<(Range<usize>, Hsla)>>>,
+}
+
+impl InputState {
+    /// Set true to show the minimap on the right side, only for multi-line input, default is false.
+    pub fn minimap(mut self, enabled: bool) -> Self {
+        self.minimap_enabled = enabled;
+        self
+    }
+
+    /// Update the minimap visibility, only for multi-line input.
+    pub fn set_minimap(&mut self, enabled: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.minimap_enabled = enabled;
+        cx.notify();
+    }
+
+    /// Returns true if the minimap is enabled and the input is multi-line.
+    pub(super) fn show_minimap(&self) -> bool {
+        self.minimap_enabled && self.mode.is_multi_line()
+    }
+
+    /// Get the minimap blocks of the lines, recompute them if the text has changed.
+    fn minimap_lines(&mut self, cx: &mut Context<Self>) -> Rc<Vec<Vec<(Range<usize>, Hsla)>>> {
+        if let Some(cache) = self.minimap_cache.as_ref() {
+            if cache.text.eq(&self.text) {
+                return cache.lines.clone();
+            }
+        }
+
+        let default_color = cx.theme().foreground.opacity(0.6);
+        let highlighter_ref = match &self.mode {
+            InputMode::CodeEditor { highlighter, .. } => Some(highlighter.borrow()),
+            _ => None,
+        };
+        let highlighter = highlighter_ref.as_ref().and_then(|h| h.as_ref());
+
+        let mut lines = Vec::with_capacity(self.text.lines_len());
+        let mut offset = 0;
+        for line in self.text.lines() {
+            let line_text = line.to_string();
+            let range = offset..offset + line_text.len();
+            let styles = highlighter
+                .map(|highlighter| highlighter.styles(&range, cx))
+                .unwrap_or_default();
+
+            lines.push(line_blocks(&line_text, |ix| {
+                styles
+                    .iter()
+                    .find(|(range, _)| range.contains(&(offset + ix)))
+                    .and_then(|(_, style)| style.color)
+                    .unwrap_or(default_color)
+            }));
+
+            // +1 for `\n`
+            offset = range.end + 1;
+        }
+
+        let lines = Rc::new(lines);
+        self.minimap_cache = Some(MinimapCache {
+            text: self.text.clone(),
+            lines: lines.clone(),
+        });
+        lines
+    }
+
+    /// Scroll to let the `row` at the center of the viewport.
+    fn scroll_to_row_center(&mut self, row: usize, cx: &mut Context<Self>) {
+        let Some(last_layout) = self.last_layout.as_ref() else {
+            return;
+        };
+
+        let line_height = last_layout.line_height;
+        let row_offset_y = self
+            .text_wrapper
+            .lines
+            .iter()
+            .take(row)
+            .fold(px(0.), |y, line| y + line.height(line_height));
+
+        let mut scroll_offset = self.scroll_handle.offset();
+        scroll_offset.y = -(row_offset_y - self.input_bounds.size.height.half()).max(px(0.));
+        self.update_scroll_offset(Some(scroll_offset), cx);
+    }
+}
+
+/// Split the line into the blocks of the continuous non-whitespace characters with same color.
+fn line_blocks(line: &str, color_at: impl Fn(usize) -> Hsla) -> Vec<(Range<usize>, Hsla)> {
+    let mut blocks: Vec<(Range<usize>, Hsla)> = vec![];
+    let mut column = 0;
+    for (ix, c) in line.char_indices() {
+        if column >= MAX_COLUMNS {
+            break;
+        }
+
+        let width = if c == '\t' { TAB_COLUMNS } else { 1 };
+        if !c.is_whitespace() {
+            let color = color_at(ix);
+            match blocks.last_mut() {
+                Some((range, last_color)) if range.end == column && *last_color == color => {
+                    range.end += width;
+                }
+                _ => blocks.push((column..column + width, color)),
+            }
+        }
+        column += width;
+    }
+    blocks
+}
+
+/// A downscaled overview of the text, click to jump to the position.
+pub(super) struct MinimapElement {
+    state: Entity<InputState>,
+}
+
+impl MinimapElement {
+    pub(super) fn new(state: Entity<InputState>) -> Self {
+        Self { state }
+    }
+}
+
+impl IntoElement for MinimapElement {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for MinimapElement {
+    type RequestLayoutState = ();
+    type PrepaintState = Rc<Vec<Vec<(Range<usize>, Hsla)>>>;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&gpui::InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        style.position = Position::Absolute;
+        style.inset.top = px(0.).into();
+        style.inset.right = px(0.).into();
+        style.size.width = MINIMAP_WIDTH.into();
+        style.size.height = relative(1.).into();
+
+        (window.request_layout(style, [], cx), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&gpui::InspectorElementId>,
+        _: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        self.state.update(cx, |state, cx| state.minimap_lines(cx))
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&gpui::InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        lines: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let state = self.state.read(cx);
+        let Some(last_layout) = state.last_layout.as_ref() else {
+            return;
+        };
+
+        // Scroll the minimap with the same ratio as the input, if it is taller than the bounds.
+        let total_height = lines.len() as f32 * LINE_HEIGHT;
+        let max_scroll = (state.scroll_size.height - state.input_bounds.size.height).max(px(1.));
+        let ratio = (-state.scroll_handle.offset().y / max_scroll).clamp(0., 1.);
+        let scroll_y = ((total_height - bounds.size.height) * ratio).max(px(0.));
+        let first_row = (scroll_y / LINE_HEIGHT).floor() as usize;
+        let top = bounds.top() - (scroll_y - first_row as f32 * LINE_HEIGHT);
+
+        window.paint_quad(fill(bounds, cx.theme().background));
+
+        // Paint the viewport band
+        let visible_range = &last_layout.visible_range;
+        let band_top = top + (visible_range.start as f32 - first_row as f32) * LINE_HEIGHT;
+        window.paint_quad(fill(
+            Bounds::new(
+                point(bounds.left(), band_top),
+                size(bounds.size.width, visible_range.len() as f32 * LINE_HEIGHT),
+            ),
+            cx.theme().scrollbar_thumb.opacity(0.3),
+        ));
+
+        let visible_rows = (bounds.size.height / LINE_HEIGHT).ceil() as usize + 1;
+        for (ix, blocks) in lines.iter().skip(first_row).take(visible_rows).enumerate() {
+            let y = top + ix as f32 * LINE_HEIGHT;
+            for (columns, color) in blocks {
+                window.paint_quad(fill(
+                    Bounds::new(
+                        point(bounds.left() + columns.start as f32 * CHAR_WIDTH, y),
+                        size(columns.len() as f32 * CHAR_WIDTH, LINE_HEIGHT),
+                    ),
+                    *color,
+                ));
+            }
+        }
+
+        window.on_mouse_event({
+            let state = self.state.clone();
+            move |event: &MouseDownEvent, phase, _, cx| {
+                if phase != DispatchPhase::Bubble || !bounds.contains(&event.position) {
+                    return;
+                }
+
+                let row = first_row + ((event.position.y - top) / LINE_HEIGHT).floor() as usize;
+                state.update(cx, |state, cx| state.scroll_to_row_center(row, cx));
+                cx.stop_propagation();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::hsla;
+
+    use super::{line_blocks, MAX_COLUMNS};
+
+    #[test]
+    fn test_line_blocks() {
+        let red = hsla(0., 1., 0.5, 1.);
+        let blue = hsla(0.6, 1., 0.5, 1.);
+        let color_at = |ix: usize| if ix < 3 { red } else { blue };
+
+        assert_eq!(
+            line_blocks("let a = 1;", color_at),
+            vec![(0..3, red), (4..5, blue), (6..7, blue), (8..10, blue)]
+        );
+        assert_eq!(line_blocks("\tfoo", |_| red), vec![(4..7, red)]);
+        assert_eq!(line_blocks("   ", |_| red), vec![]);
+
+        let long_line = "x".repeat(MAX_COLUMNS * 2);
+        assert_eq!(
+            line_blocks(&long_line, |_| red),
+            vec![(0..MAX_COLUMNS, red)]
+        );
+    }
+}
@@ -1,24 +1,40 @@
+mod auto_close;
 mod blink_cursor;
+mod bracket;
 mod change;
 mod clear_button;
 mod cursor;
+mod diff_hunk;
 mod element;
+mod file_drop;
+mod folding;
+mod ghost_text;
+mod gutter;
+mod indent_guide;
 mod lsp;
 mod mask_pattern;
+mod metrics;
+mod minimap;
 mod mode;
+mod multi_cursor;
 mod number_input;
 mod otp_input;
 mod popovers;
 mod rope_ext;
 mod search;
+mod snippet;
 mod state;
+mod text_diff;
 mod text_input;
 mod text_wrapper;
 
 pub(crate) use clear_button::*;
 pub use cursor::*;
+pub use diff_hunk::{DiffHunk, DiffKind};
+pub use gutter::GutterDecoration;
 pub use lsp::*;
 pub use mask_pattern::MaskPattern;
+pub use metrics::RenderMetrics;
 pub use mode::TabSize;
 pub use number_input::{NumberInput, NumberInputEvent, StepAction};
 pub use otp_input::*;
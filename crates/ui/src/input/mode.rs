@@ -7,7 +7,7 @@ use tree_sitter::{InputEdit, Point};
 
 use crate::highlighter::DiagnosticSet;
 use crate::highlighter::SyntaxHighlighter;
-use crate::input::{CodeActionProvider, CompletionProvider};
+use crate::input::{CodeActionProvider, CompletionProvider, GoToDefinitionProvider};
 
 use super::text_wrapper::TextWrapper;
 
@@ -61,6 +61,7 @@ pub enum InputMode {
         diagnostics: DiagnosticSet,
         completion_provider: Option<Rc<dyn CompletionProvider>>,
         code_action_providers: Vec<Rc<dyn CodeActionProvider>>,
+        go_to_definition_provider: Option<Rc<dyn GoToDefinitionProvider>>,
     },
 }
 
@@ -76,6 +77,14 @@ impl InputMode {
         matches!(self, InputMode::CodeEditor { .. })
     }
 
+    /// Returns the highlighter language of the code editor.
+    pub(super) fn language(&self) -> Option<SharedString> {
+        match self {
+            InputMode::CodeEditor { language, .. } => Some(language.clone()),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub(super) fn is_auto_grow(&self) -> bool {
         matches!(self, InputMode::AutoGrow { .. })
@@ -163,6 +172,7 @@ impl InputMode {
         }
     }
 
+    /// Update the highlighter with the edit, returns true if the highlighter has been updated.
     pub(super) fn update_highlighter(
         &mut self,
         selected_range: &Range<usize>,
@@ -170,7 +180,7 @@ impl InputMode {
         new_text: &str,
         force: bool,
         cx: &mut App,
-    ) {
+    ) -> bool {
         match &self {
             InputMode::CodeEditor {
                 language,
@@ -178,7 +188,7 @@ impl InputMode {
                 ..
             } => {
                 if !force && highlighter.borrow().is_some() {
-                    return;
+                    return false;
                 }
 
                 let mut highlighter = highlighter.borrow_mut();
@@ -188,7 +198,7 @@ impl InputMode {
                 }
 
                 let Some(highlighter) = highlighter.as_mut() else {
-                    return;
+                    return false;
                 };
 
                 // When full text changed, the selected_range may be out of bound (The before version).
@@ -222,8 +232,21 @@ impl InputMode {
                 };
 
                 highlighter.update(Some(edit), text);
+                true
             }
-            _ => {}
+            _ => false,
+        }
+    }
+
+    /// Get the foldable line ranges from the highlighter, only for [`InputMode::CodeEditor`].
+    pub(super) fn foldable_ranges(&self, text: &Rope) -> Vec<Range<usize>> {
+        match self {
+            InputMode::CodeEditor { highlighter, .. } => highlighter
+                .borrow()
+                .as_ref()
+                .map(|highlighter| highlighter.foldable_ranges(text))
+                .unwrap_or_default(),
+            _ => vec![],
         }
     }
 
@@ -261,6 +284,16 @@ impl InputMode {
             _ => vec![],
         }
     }
+
+    pub(super) fn go_to_definition_provider(&self) -> Option<&Rc<dyn GoToDefinitionProvider>> {
+        match self {
+            InputMode::CodeEditor {
+                go_to_definition_provider,
+                ..
+            } => go_to_definition_provider.as_ref(),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,334 @@
+use gpui::{ClipboardItem, Context, Window};
+use rope::Rope;
+
+use super::{change::Change, InputEvent, InputState, SelectNextOccurrence, Selection};
+
+impl InputState {
+    /// Returns true if there are more cursors than the primary one.
+    pub(super) fn has_additional_cursors(&self) -> bool {
+        !self.additional_cursors.is_empty()
+    }
+
+    /// Get the selections of all cursors, sorted by offset and with the overlapping ones merged.
+    pub(super) fn cursor_selections(&self) -> Vec<Selection> {
+        merge_selections(
+            std::iter::once(self.selected_range).chain(self.additional_cursors.iter().copied()),
+        )
+    }
+
+    /// Add a cursor at the offset, the current cursor is kept as an additional cursor.
+    ///
+    /// If there is already an additional cursor at the offset, it will be removed.
+    pub(super) fn add_cursor(&mut self, offset: usize, _: &mut Window, cx: &mut Context<Self>) {
+        let offset = offset.min(self.text.len());
+        let cursor = Selection::new(offset, offset);
+
+        if let Some(ix) = self.additional_cursors.iter().position(|c| *c == cursor) {
+            self.additional_cursors.remove(ix);
+        } else if self.selected_range != cursor {
+            self.additional_cursors.push(self.selected_range);
+            self.selected_range = cursor;
+            self.selection_reversed = false;
+        }
+
+        self.pause_blink_cursor(cx);
+        cx.notify();
+    }
+
+    /// Select the next occurrence of the selected text, and add a cursor for it.
+    ///
+    /// If nothing is selected, the word at the cursor will be selected first.
+    pub(super) fn select_next_occurrence(
+        &mut self,
+        _: &SelectNextOccurrence,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode.is_multi_line() {
+            cx.propagate();
+            return;
+        }
+
+        if self.selected_range.is_empty() {
+            self.select_word(self.cursor(), window, cx);
+            self.selected_word_range = None;
+            return;
+        }
+
+        let query = self.selected_text().to_string();
+        let selections = self.cursor_selections();
+        let Some(next) = next_occurrence(
+            &self.text.to_string(),
+            &query,
+            self.selected_range.end,
+            &selections,
+        ) else {
+            return;
+        };
+
+        self.additional_cursors.push(self.selected_range);
+        self.selected_range = next;
+        self.selection_reversed = false;
+        self.scroll_to(next.end, cx);
+        self.pause_blink_cursor(cx);
+        cx.notify();
+    }
+
+    /// Delete the selected text of all cursors, or the character before (or after) the empty ones.
+    pub(super) fn delete_in_cursors(
+        &mut self,
+        backward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let selections = self.cursor_selections().into_iter().map(|selection| {
+            if !selection.is_empty() {
+                selection
+            } else if backward {
+                Selection::new(self.previous_boundary(selection.start), selection.end)
+            } else {
+                Selection::new(selection.start, self.next_boundary(selection.end))
+            }
+        });
+        let selections = merge_selections(selections);
+        self.replace_text_in_cursors(selections, "", window, cx);
+    }
+
+    /// Write the selected text of all cursors to the clipboard, one line per cursor.
+    pub(super) fn copy_cursors(&self, cx: &mut Context<Self>) -> bool {
+        let selected_text = self
+            .cursor_selections()
+            .into_iter()
+            .filter(|selection| !selection.is_empty())
+            .map(|selection| self.text.slice(selection.into()).to_string())
+            .collect::<Vec<_>>();
+        if selected_text.is_empty() {
+            return false;
+        }
+
+        cx.write_to_clipboard(ClipboardItem::new_string(selected_text.join("\n")));
+        true
+    }
+
+    /// Replace the `selections` with `new_text` as one change, so it can be undone at once.
+    pub(super) fn replace_text_in_cursors(
+        &mut self,
+        selections: Vec<Selection>,
+        new_text: &str,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.disabled || self.read_only || selections.is_empty() {
+            return;
+        }
+
+        self.pause_blink_cursor(cx);
+
+        let old_len = self.text.len();
+        let (text, changes, cursors) = edit_selections(&self.text, &selections, new_text);
+        if !self.is_within_max_chars(self.text.chars_count(), text.chars_count())
+            || !self.is_valid_input(&text.to_string(), cx)
+        {
+            return;
+        }
+
+        self.text = text;
+        if !self.history.ignore {
+            self.history.push_all(changes);
+        }
+        if let Some(diagnostics) = self.mode.diagnostics_mut() {
+            diagnostics.reset(&self.text)
+        }
+        self.text_wrapper.update(&self.text, false, cx);
+        let full_text = self.text.to_string();
+        self.mode
+            .update_highlighter(&(0..old_len), &self.text, &full_text, true, cx);
+
+        let mut cursors = cursors.into_iter();
+        if let Some(primary) = cursors.next() {
+            self.selected_range = primary;
+        }
+        self.additional_cursors = cursors.collect();
+        self.selection_reversed = false;
+        self.ime_marked_range.take();
+        self.update_fold_ranges(cx);
+        self.exit_snippet(cx);
+        self.update_preferred_column();
+        self.update_scroll_offset(None, cx);
+        self.update_search(cx);
+        self.mode.update_auto_grow(&self.text_wrapper);
+        cx.emit(InputEvent::Change);
+        cx.notify();
+    }
+}
+
+/// Sort the selections by offset, and merge the overlapping ones.
+fn merge_selections(selections: impl IntoIterator<Item = Selection>) -> Vec<Selection> {
+    let mut selections = selections.into_iter().collect::<Vec<_>>();
+    selections.sort_by_key(|selection| (selection.start, selection.end));
+
+    let mut merged: Vec<Selection> = Vec::with_capacity(selections.len());
+    for selection in selections {
+        match merged.last_mut() {
+            Some(last) if selection.start < last.end || selection == *last => {
+                last.end = last.end.max(selection.end);
+            }
+            _ => merged.push(selection),
+        }
+    }
+    merged
+}
+
+/// Replace each of the sorted `selections` in the `text` with `new_text`.
+///
+/// Returns the new text, the changes and the new cursors. The range of each change is
+/// shifted by the edits before it, so undoing the changes in reverse order restores the text.
+fn edit_selections(
+    text: &Rope,
+    selections: &[Selection],
+    new_text: &str,
+) -> (Rope, Vec<Change>, Vec<Selection>) {
+    let mut text = text.clone();
+    let mut changes = Vec::with_capacity(selections.len());
+    let mut cursors = Vec::with_capacity(selections.len());
+    let mut delta: isize = 0;
+
+    for selection in selections {
+        let start = (selection.start as isize + delta) as usize;
+        let end = (selection.end as isize + delta) as usize;
+        let old_text = text.slice(start..end).to_string();
+        text.replace(start..end, new_text);
+
+        let offset = start + new_text.len();
+        changes.push(Change::new(start..end, &old_text, start..offset, new_text));
+        cursors.push(Selection::new(offset, offset));
+        delta += new_text.len() as isize - selection.len() as isize;
+    }
+
+    (text, changes, cursors)
+}
+
+/// Find the next occurrence of `query` starting from `offset`, wrapping around to the start.
+///
+/// Occurrences that overlap with one of the `selections` are skipped.
+fn next_occurrence(
+    text: &str,
+    query: &str,
+    offset: usize,
+    selections: &[Selection],
+) -> Option<Selection> {
+    if query.is_empty() {
+        return None;
+    }
+
+    let occurrences = || {
+        text.match_indices(query)
+            .map(|(ix, _)| Selection::new(ix, ix + query.len()))
+    };
+
+    occurrences()
+        .filter(|occurrence| occurrence.start >= offset)
+        .chain(occurrences().filter(|occurrence| occurrence.start < offset))
+        .find(|occurrence| {
+            !selections
+                .iter()
+                .any(|s| s.start < occurrence.end && occurrence.start < s.end)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use rope::Rope;
+
+    use super::{edit_selections, merge_selections, next_occurrence};
+    use crate::input::Selection;
+
+    #[test]
+    fn test_merge_selections() {
+        let selections = merge_selections([
+            Selection::new(10, 12),
+            Selection::new(2, 2),
+            Selection::new(4, 8),
+            Selection::new(6, 11),
+            Selection::new(2, 2),
+            Selection::new(12, 12),
+        ]);
+        assert_eq!(
+            selections,
+            vec![
+                Selection::new(2, 2),
+                Selection::new(4, 12),
+                Selection::new(12, 12),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edit_selections() {
+        let text = Rope::from("let a = 1;\nlet b = 2;\nlet c = 3;");
+        let selections = [
+            Selection::new(4, 5),
+            Selection::new(15, 16),
+            Selection::new(26, 27),
+        ];
+
+        let (new_text, changes, cursors) = edit_selections(&text, &selections, "value");
+        assert_eq!(
+            new_text.to_string(),
+            "let value = 1;\nlet value = 2;\nlet value = 3;"
+        );
+        assert_eq!(
+            cursors,
+            vec![
+                Selection::new(9, 9),
+                Selection::new(24, 24),
+                Selection::new(39, 39),
+            ]
+        );
+        assert_eq!(changes[1].old_range, Selection::new(19, 20));
+        assert_eq!(changes[1].old_text, "b");
+        assert_eq!(changes[1].new_range, Selection::new(19, 24));
+
+        // Undo in reverse order restores the text.
+        let mut undo_text = new_text.clone();
+        for change in changes.iter().rev() {
+            undo_text.replace(change.new_range.into(), &change.old_text);
+        }
+        assert_eq!(undo_text, text);
+
+        // Delete with empty cursors
+        let (new_text, _, cursors) = edit_selections(
+            &Rope::from("abc"),
+            &[Selection::new(0, 1), Selection::new(2, 3)],
+            "",
+        );
+        assert_eq!(new_text.to_string(), "b");
+        assert_eq!(cursors, vec![Selection::new(0, 0), Selection::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        let text = "foo bar foo baz foo";
+        let selections = [Selection::new(8, 11)];
+        assert_eq!(
+            next_occurrence(text, "foo", 11, &selections),
+            Some(Selection::new(16, 19))
+        );
+
+        // Wrap around to the start
+        let selections = [Selection::new(8, 11), Selection::new(16, 19)];
+        assert_eq!(
+            next_occurrence(text, "foo", 19, &selections),
+            Some(Selection::new(0, 3))
+        );
+
+        let selections = [
+            Selection::new(0, 3),
+            Selection::new(8, 11),
+            Selection::new(16, 19),
+        ];
+        assert_eq!(next_occurrence(text, "foo", 3, &selections), None);
+        assert_eq!(next_occurrence(text, "", 0, &[]), None);
+        assert_eq!(next_occurrence(text, "qux", 0, &[]), None);
+    }
+}
@@ -6,7 +6,7 @@ use gpui::{
     EventEmitter, HighlightStyle, InteractiveElement as _, IntoElement, ParentElement, Pixels,
     Point, Render, RenderOnce, SharedString, Stateful, Styled, StyledText, Subscription, Window,
 };
-use lsp_types::CompletionItem;
+use lsp_types::{CompletionItem, InsertTextFormat};
 
 const MAX_MENU_WIDTH: Pixels = px(320.);
 const MAX_MENU_HEIGHT: Pixels = px(480.);
@@ -232,17 +232,22 @@ impl CompletionMenu {
             .as_deref()
             .unwrap_or(&item.label)
             .to_string();
+        let is_snippet = item.insert_text_format == Some(InsertTextFormat::SNIPPET);
         let state = self.state.clone();
 
         cx.spawn_in(window, async move |_, cx| {
             state.update_in(cx, |state, window, cx| {
                 state.completion_inserting = true;
-                state.replace_text_in_range(
-                    Some(state.range_to_utf16(&range)),
-                    &insert_text,
-                    window,
-                    cx,
-                );
+                if is_snippet {
+                    state.insert_snippet(range, &insert_text, window, cx);
+                } else {
+                    state.replace_text_in_range(
+                        Some(state.range_to_utf16(&range)),
+                        &insert_text,
+                        window,
+                        cx,
+                    );
+                }
                 state.completion_inserting = false;
                 // FIXME: Input not get the focus
                 state.focus(window, cx);
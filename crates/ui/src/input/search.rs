@@ -1,11 +1,12 @@
 use aho_corasick::AhoCorasick;
+use regex::{Regex, RegexBuilder};
 use rust_i18n::t;
 use std::{ops::Range, rc::Rc};
 
 use gpui::{
     actions, div, prelude::FluentBuilder as _, App, AppContext as _, Context, Empty, Entity,
     EntityInputHandler, FocusHandle, Focusable, Half, InteractiveElement as _, IntoElement,
-    KeyBinding, ParentElement as _, Render, Styled, Subscription, Window,
+    KeyBinding, ParentElement as _, Render, SharedString, Styled, Subscription, Task, Window,
 };
 use rope::Rope;
 
@@ -13,7 +14,10 @@ use crate::{
     actions::SelectPrev,
     button::{Button, ButtonVariants},
     h_flex,
-    input::{Enter, Escape, IndentInline, InputEvent, InputState, RopeExt, Search, TextInput},
+    input::{
+        Enter, Escape, IndentInline, InputEvent, InputState, RopeExt, Search, TextInput,
+        ToggleReplace,
+    },
     v_flex, ActiveTheme, IconName, Selectable, Sizable,
 };
 
@@ -32,7 +36,17 @@ pub(super) fn init(cx: &mut App) {
 #[derive(Debug, Clone)]
 pub struct SearchMatcher {
     text: Rope,
+    /// The query string, kept to rebuild the query when a mode changes.
+    pattern: String,
     pub query: Option<AhoCorasick>,
+    /// The query as a regular expression, only set in regex mode.
+    pub regex: Option<Regex>,
+    /// Match the query as a regular expression instead of a literal string.
+    pub(super) regex_mode: bool,
+    /// Match the case of the query, the literal query only ignores the ASCII case.
+    pub(super) case_sensitive: bool,
+    /// Only match the whole words.
+    pub(super) whole_word: bool,
 
     pub(super) matched_ranges: Rc<Vec<Range<usize>>>,
     pub(super) current_match_ix: usize,
@@ -44,34 +58,92 @@ impl SearchMatcher {
     pub fn new() -> Self {
         Self {
             text: "".into(),
+            pattern: String::new(),
             query: None,
+            regex: None,
+            regex_mode: false,
+            case_sensitive: false,
+            whole_word: false,
             matched_ranges: Rc::new(Vec::new()),
             current_match_ix: 0,
             replacing: false,
         }
     }
 
+    /// Set true to match the query as a regular expression, default is false.
+    ///
+    /// Only the full match ranges are used, capture groups are ignored.
+    pub fn regex_mode(mut self, regex_mode: bool) -> Self {
+        self.regex_mode = regex_mode;
+        self
+    }
+
+    /// Set true to match the case of the query, default is false.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Set true to only match the whole words, default is false.
+    ///
+    /// A match is a whole word if the characters around it are not word characters (alphanumeric or `_`).
+    pub fn whole_word(mut self, whole_word: bool) -> Self {
+        self.whole_word = whole_word;
+        self
+    }
+
+    /// Update the case sensitive mode and re-match.
+    pub fn set_case_sensitive(&mut self, case_sensitive: bool) {
+        self.case_sensitive = case_sensitive;
+        self.rebuild_query();
+        self.update_matches();
+    }
+
+    /// Update the whole word mode and re-match.
+    pub fn set_whole_word(&mut self, whole_word: bool) {
+        self.whole_word = whole_word;
+        self.update_matches();
+    }
+
     /// Update source text and re-match
     pub(crate) fn update(&mut self, text: &Rope) {
+        if self.set_text(text) {
+            self.update_matches();
+        }
+    }
+
+    /// Update source text without re-matching, returns true if the text changed.
+    fn set_text(&mut self, text: &Rope) -> bool {
         if self.text.eq(text) {
-            return;
+            return false;
         }
 
         self.text = text.clone();
-        self.update_matches();
+        true
     }
 
     fn update_matches(&mut self) {
         let mut new_ranges = Vec::new();
-        if let Some(query) = &self.query {
+        if let Some(regex) = &self.regex {
+            new_ranges = find_regex_matches(regex, &self.text.to_string(), self.whole_word);
+        } else if let Some(query) = &self.query {
             let matches = query.stream_find_iter(self.text.bytes_in_range(0..self.text.len()));
 
             for query_match in matches.into_iter() {
                 let query_match = query_match.expect("query match for select all action");
                 new_ranges.push(query_match.range());
             }
+
+            if self.whole_word {
+                let text = self.text.to_string();
+                new_ranges.retain(|range| is_whole_word(&text, range));
+            }
         }
-        self.matched_ranges = Rc::new(new_ranges);
+        self.set_matched_ranges(new_ranges);
+    }
+
+    fn set_matched_ranges(&mut self, ranges: Vec<Range<usize>>) {
+        self.matched_ranges = Rc::new(ranges);
         if !self.replacing {
             self.current_match_ix = 0;
             self.replacing = false;
@@ -79,18 +151,40 @@ impl SearchMatcher {
     }
 
     /// Update the search query and reset the current match index.
-    pub fn update_query(&mut self, query: &str, case_insensitive: bool) {
-        if query.len() > 0 {
+    pub fn update_query(&mut self, query: &str) {
+        self.set_query(query);
+        self.update_matches();
+    }
+
+    /// Update the search query without re-matching.
+    fn set_query(&mut self, query: &str) {
+        self.pattern = query.to_string();
+        self.rebuild_query();
+    }
+
+    /// Build the query from the pattern with the current modes.
+    ///
+    /// In regex mode, an invalid pattern has no matches.
+    fn rebuild_query(&mut self) {
+        self.query = None;
+        self.regex = None;
+        if self.pattern.is_empty() {
+            return;
+        }
+
+        if self.regex_mode {
+            self.regex = RegexBuilder::new(&self.pattern)
+                .case_insensitive(!self.case_sensitive)
+                .build()
+                .ok();
+        } else {
             self.query = Some(
                 AhoCorasick::builder()
-                    .ascii_case_insensitive(case_insensitive)
-                    .build(&[query.to_string()])
+                    .ascii_case_insensitive(!self.case_sensitive)
+                    .build(&[self.pattern.as_str()])
                     .expect("failed to build AhoCorasick query in SearchMatcher"),
             );
-        } else {
-            self.query = None;
         }
-        self.update_matches();
     }
 
     /// Returns the number of matches found.
@@ -100,9 +194,56 @@ impl SearchMatcher {
         self.matched_ranges.len()
     }
 
+    /// Returns the match count label, e.g. "2 of 5", empty if the query is empty.
+    fn count_label(&self) -> SharedString {
+        if self.pattern.is_empty() {
+            return SharedString::default();
+        }
+
+        if self.matched_ranges.is_empty() {
+            return t!("Input.No Results").into();
+        }
+
+        t!(
+            "Input.Match Count",
+            current = self.current_match_ix + 1,
+            total = self.matched_ranges.len()
+        )
+        .into()
+    }
+
     fn peek(&self) -> Option<Range<usize>> {
         self.matched_ranges.get(self.current_match_ix + 1).cloned()
     }
+
+    /// Replace all the matched ranges in the `source` with `replacement`.
+    ///
+    /// The ranges are replaced from the end, so the earlier ranges stay valid.
+    pub fn replace_all(&self, source: &Rope, replacement: &str) -> Rope {
+        let mut rope = source.clone();
+        for range in self.matched_ranges.iter().rev() {
+            rope.replace(range.clone(), replacement);
+        }
+        rope
+    }
+}
+
+/// Find the byte ranges of all the (non-overlapping) matches of the `regex` in the `text`.
+fn find_regex_matches(regex: &Regex, text: &str, whole_word: bool) -> Vec<Range<usize>> {
+    regex
+        .find_iter(text)
+        .filter(|m| !m.is_empty())
+        .map(|m| m.range())
+        .filter(|range| !whole_word || is_whole_word(text, range))
+        .collect()
+}
+
+/// Returns true if the characters before and after the `range` are not word characters.
+fn is_whole_word(text: &str, range: &Range<usize>) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let before = text[..range.start].chars().next_back();
+    let after = text[range.end..].chars().next();
+    !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
 }
 
 impl Iterator for SearchMatcher {
@@ -143,10 +284,13 @@ impl DoubleEndedIterator for SearchMatcher {
 pub(super) struct SearchPanel {
     text_state: Entity<InputState>,
     search_input: Entity<InputState>,
-    replace_input: Entity<InputState>,
-    case_insensitive: bool,
-    replace_mode: bool,
+    replace_text: Entity<InputState>,
+    show_replace: bool,
     matcher: SearchMatcher,
+    /// The match count shown after the search input, empty when the query is empty.
+    count_label: SharedString,
+    /// The regex matching running in the background.
+    _matching_task: Option<Task<()>>,
 
     open: bool,
     _subscriptions: Vec<Subscription>,
@@ -160,8 +304,8 @@ impl InputState {
         };
 
         let text = self.text.clone();
-        search_panel.update(cx, |this, _| {
-            this.matcher.update(&text);
+        search_panel.update(cx, |this, cx| {
+            this.update_text(&text, cx);
         });
     }
 
@@ -170,6 +314,30 @@ impl InputState {
         _: &Search,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        self.open_search_panel(false, window, cx);
+    }
+
+    pub(super) fn on_action_toggle_replace(
+        &mut self,
+        _: &ToggleReplace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.searchable {
+            // Let the search panel toggle the replace row when focus is on its inputs.
+            cx.propagate();
+            return;
+        }
+
+        self.open_search_panel(true, window, cx);
+    }
+
+    fn open_search_panel(
+        &mut self,
+        show_replace: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         if !self.searchable {
             return;
@@ -185,8 +353,12 @@ impl InputState {
         let selected_text = self.selected_text();
         search_panel.update(cx, |this, cx| {
             this.text_state = text_state;
-            this.matcher.update(&text);
+            this.update_text(&text, cx);
             this.show(&selected_text, window, cx);
+            if show_replace {
+                this.show_replace = true;
+                this.replace_text.read(cx).focus_handle.focus(window);
+            }
         });
         self.search_panel = Some(search_panel);
         cx.notify();
@@ -196,7 +368,7 @@ impl InputState {
 impl SearchPanel {
     pub fn new(text_state: Entity<InputState>, window: &mut Window, cx: &mut App) -> Entity<Self> {
         let search_input = cx.new(|cx| InputState::new(window, cx));
-        let replace_input = cx.new(|cx| InputState::new(window, cx));
+        let replace_text = cx.new(|cx| InputState::new(window, cx));
 
         cx.new(|cx| {
             let _subscriptions = vec![cx.subscribe(
@@ -206,8 +378,7 @@ impl SearchPanel {
                     match ev {
                         InputEvent::Change => {
                             let value = search_input.read(cx).value();
-                            this.matcher
-                                .update_query(value.as_str(), this.case_insensitive);
+                            this.update_query(value.as_str(), cx);
                         }
                         _ => {}
                     }
@@ -217,10 +388,11 @@ impl SearchPanel {
             Self {
                 text_state,
                 search_input,
-                replace_input,
-                case_insensitive: true,
-                replace_mode: false,
+                replace_text,
+                show_replace: false,
                 matcher: SearchMatcher::new(),
+                count_label: SharedString::default(),
+                _matching_task: None,
                 open: true,
                 _subscriptions,
             }
@@ -248,11 +420,82 @@ impl SearchPanel {
 
     fn update_search(&mut self, cx: &mut Context<Self>) {
         let query = self.search_input.read(cx).value();
-        self.matcher
-            .update_query(query.as_str(), self.case_insensitive);
+        self.update_query(query.as_str(), cx);
         self.update_text_selection(cx);
     }
 
+    fn update_query(&mut self, query: &str, cx: &mut Context<Self>) {
+        if self.matcher.regex_mode {
+            self.matcher.set_query(query);
+            self.update_matches_in_background(cx);
+        } else {
+            self.matcher.update_query(query);
+        }
+        self.update_count_label();
+    }
+
+    fn update_text(&mut self, text: &Rope, cx: &mut Context<Self>) {
+        if self.matcher.regex_mode {
+            if self.matcher.set_text(text) {
+                self.update_matches_in_background(cx);
+            }
+        } else {
+            self.matcher.update(text);
+        }
+        self.update_count_label();
+    }
+
+    fn update_count_label(&mut self) {
+        self.count_label = self.matcher.count_label();
+    }
+
+    /// Regex can be expensive on large text, so in regex mode the matching runs
+    /// in the background and the matched ranges are updated when it is done.
+    fn update_matches_in_background(&mut self, cx: &mut Context<Self>) {
+        let Some(regex) = self.matcher.regex.clone() else {
+            self._matching_task = None;
+            self.matcher.update_matches();
+            cx.notify();
+            return;
+        };
+
+        let text = self.matcher.text.clone();
+        let whole_word = self.matcher.whole_word;
+        let task = cx
+            .background_executor()
+            .spawn(async move { find_regex_matches(&regex, &text.to_string(), whole_word) });
+        self._matching_task = Some(cx.spawn(async move |this, cx| {
+            let ranges = task.await;
+            _ = this.update(cx, |this, cx| {
+                this.matcher.set_matched_ranges(ranges);
+                this.update_count_label();
+                this.update_text_selection(cx);
+                this.text_state.update(cx, |_, cx| cx.notify());
+                cx.notify();
+            });
+        }));
+    }
+
+    /// Set true to match the case of the query, the matches are updated immediately.
+    pub fn set_case_sensitive(&mut self, case_sensitive: bool, cx: &mut Context<Self>) {
+        self.matcher.case_sensitive = case_sensitive;
+        self.update_search(cx);
+        cx.notify();
+    }
+
+    /// Set true to only match the whole words, the matches are updated immediately.
+    pub fn set_whole_word(&mut self, whole_word: bool, cx: &mut Context<Self>) {
+        self.matcher.whole_word = whole_word;
+        self.update_search(cx);
+        cx.notify();
+    }
+
+    fn toggle_regex_mode(&mut self, cx: &mut Context<Self>) {
+        self.matcher.regex_mode = !self.matcher.regex_mode;
+        self.update_search(cx);
+        cx.notify();
+    }
+
     pub(super) fn hide(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.open = false;
         self.text_state.read(cx).focus_handle.focus(window);
@@ -271,6 +514,25 @@ impl SearchPanel {
         self.hide(window, cx);
     }
 
+    fn on_action_toggle_replace(
+        &mut self,
+        _: &ToggleReplace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_replace(window, cx);
+    }
+
+    fn toggle_replace(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.show_replace = !self.show_replace;
+        if self.show_replace {
+            self.replace_text.read(cx).focus_handle.focus(window);
+        } else {
+            self.search_input.read(cx).focus_handle.focus(window);
+        }
+        cx.notify();
+    }
+
     fn on_action_tab(&mut self, _: &IndentInline, window: &mut Window, cx: &mut Context<Self>) {
         self.text_state.focus_handle(cx).focus(window);
     }
@@ -297,17 +559,21 @@ impl SearchPanel {
 
     fn prev(&mut self, _: &mut Window, cx: &mut Context<Self>) {
         if let Some(range) = self.matcher.next_back() {
+            self.update_count_label();
             self.text_state.update(cx, |state, cx| {
                 state.scroll_to(range.start, cx);
             });
+            cx.notify();
         }
     }
 
     fn next(&mut self, _: &mut Window, cx: &mut Context<Self>) {
         if let Some(range) = self.matcher.next() {
+            self.update_count_label();
             self.text_state.update(cx, |state, cx| {
                 state.scroll_to(range.end, cx);
             });
+            cx.notify();
         }
     }
 
@@ -320,7 +586,7 @@ impl SearchPanel {
     }
 
     fn replace_next(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let new_text = self.replace_input.read(cx).value();
+        let new_text = self.replace_text.read(cx).value();
         self.matcher.replacing = true;
         if let Some(range) = self
             .matcher
@@ -342,6 +608,7 @@ impl SearchPanel {
                             window,
                             cx,
                         );
+                        cx.emit(InputEvent::TextReplaced(1));
                     });
                 })
             })
@@ -350,22 +617,19 @@ impl SearchPanel {
     }
 
     fn replace_all(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let new_text = self.replace_input.read(cx).value();
+        let new_text = self.replace_text.read(cx).value();
         self.matcher.replacing = true;
-        let ranges = self.matcher.matched_ranges.clone();
-        if ranges.is_empty() {
+        let count = self.matcher.matched_ranges.len();
+        if count == 0 {
             return;
         }
 
+        let matcher = self.matcher.clone();
         let text_state = self.text_state.clone();
         cx.spawn_in(window, async move |_, cx| {
             cx.update(|window, cx| {
                 text_state.update(cx, |state, cx| {
-                    // Replace from the end to avoid messing up the ranges.
-                    let mut rope = state.text.clone();
-                    for range in ranges.iter().rev() {
-                        rope.replace(range.clone(), new_text.as_str());
-                    }
+                    let rope = matcher.replace_all(&state.text, new_text.as_str());
                     state.replace_text_in_range(
                         Some(0..state.text.len()),
                         &rope.to_string(),
@@ -373,6 +637,7 @@ impl SearchPanel {
                         cx,
                     );
                     state.scroll_to(0, cx);
+                    cx.emit(InputEvent::TextReplaced(count));
                 });
             })
         })
@@ -401,6 +666,7 @@ impl Render for SearchPanel {
             .on_action(cx.listener(Self::on_action_next))
             .on_action(cx.listener(Self::on_action_escape))
             .on_action(cx.listener(Self::on_action_tab))
+            .on_action(cx.listener(Self::on_action_toggle_replace))
             .font_family(".SystemUIFont")
             .items_center()
             .py_2()
@@ -408,7 +674,8 @@ impl Render for SearchPanel {
             .w_full()
             .gap_1()
             .bg(cx.theme().popover)
-            .border_b_1()
+            .border_1()
+            .shadow_md()
             .rounded(cx.theme().radius.half())
             .border_color(cx.theme().border)
             .child(
@@ -420,17 +687,44 @@ impl Render for SearchPanel {
                             TextInput::new(&self.search_input)
                                 .focus_bordered(false)
                                 .suffix(
-                                    Button::new("case-insensitive")
-                                        .selected(!self.case_insensitive)
-                                        .xsmall()
-                                        .compact()
-                                        .ghost()
-                                        .icon(IconName::CaseSensitive)
-                                        .on_click(cx.listener(|this, _, _, cx| {
-                                            this.case_insensitive = !this.case_insensitive;
-                                            this.update_search(cx);
-                                            cx.notify();
-                                        })),
+                                    h_flex()
+                                        .gap_0p5()
+                                        .child(
+                                            Button::new("case-insensitive")
+                                                .selected(self.matcher.case_sensitive)
+                                                .xsmall()
+                                                .compact()
+                                                .ghost()
+                                                .icon(IconName::CaseSensitive)
+                                                .on_click(cx.listener(|this, _, _, cx| {
+                                                    let case_sensitive =
+                                                        !this.matcher.case_sensitive;
+                                                    this.set_case_sensitive(case_sensitive, cx);
+                                                })),
+                                        )
+                                        .child(
+                                            Button::new("whole-word")
+                                                .selected(self.matcher.whole_word)
+                                                .xsmall()
+                                                .compact()
+                                                .ghost()
+                                                .icon(IconName::WholeWord)
+                                                .on_click(cx.listener(|this, _, _, cx| {
+                                                    let whole_word = !this.matcher.whole_word;
+                                                    this.set_whole_word(whole_word, cx);
+                                                })),
+                                        )
+                                        .child(
+                                            Button::new("regex-mode")
+                                                .selected(self.matcher.regex_mode)
+                                                .xsmall()
+                                                .compact()
+                                                .ghost()
+                                                .label(".*")
+                                                .on_click(cx.listener(|this, _, _, cx| {
+                                                    this.toggle_regex_mode(cx);
+                                                })),
+                                        ),
                                 )
                                 .small()
                                 .w_full()
@@ -438,16 +732,29 @@ impl Render for SearchPanel {
                                 .shadow_none(),
                         ),
                     )
+                    .when(!self.count_label.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .flex_none()
+                                .text_xs()
+                                .map(|this| {
+                                    if self.matcher.matched_ranges.is_empty() {
+                                        this.text_color(cx.theme().danger)
+                                    } else {
+                                        this.text_color(cx.theme().muted_foreground)
+                                    }
+                                })
+                                .child(self.count_label.clone()),
+                        )
+                    })
                     .child(
                         Button::new("replace-mode")
                             .xsmall()
                             .ghost()
                             .icon(IconName::Replace)
-                            .selected(self.replace_mode)
+                            .selected(self.show_replace)
                             .on_click(cx.listener(|this, _, window, cx| {
-                                this.replace_mode = !this.replace_mode;
-                                this.replace_input.read(cx).focus_handle.focus(window);
-                                cx.notify();
+                                this.toggle_replace(window, cx);
                             })),
                     )
                     .child(
@@ -479,13 +786,13 @@ impl Render for SearchPanel {
                             })),
                     ),
             )
-            .when(self.replace_mode, |this| {
+            .when(self.show_replace, |this| {
                 this.child(
                     h_flex()
                         .w_full()
                         .gap_2()
                         .child(
-                            TextInput::new(&self.replace_input)
+                            TextInput::new(&self.replace_text)
                                 .focus_bordered(false)
                                 .small()
                                 .w_full()
@@ -521,7 +828,7 @@ mod tests {
     fn test_search() {
         let mut search = SearchMatcher::new();
         search.update(&Rope::from("Hello 世界 this is a Is test string."));
-        search.update_query("Is", true);
+        search.update_query("Is");
 
         assert_eq!(search.len(), 3);
         let mut matches = search.clone().into_iter();
@@ -539,9 +846,90 @@ mod tests {
         assert_eq!(matches.current_match_ix, 0);
         assert_eq!(matches.next_back(), Some(23..25));
 
-        search.update_query("IS", false);
+        search.set_case_sensitive(true);
+        search.update_query("IS");
         assert_eq!(search.len(), 0);
         assert_eq!(search.next(), None);
         assert_eq!(search.next_back(), None);
     }
+
+    #[test]
+    fn test_replace_all() {
+        let source = Rope::from("Hello 世界 this is a Is test string.");
+        let mut search = SearchMatcher::new();
+        search.update(&source);
+        search.update_query("is");
+
+        let replaced = search.replace_all(&source, "was");
+        assert_eq!(
+            replaced.to_string(),
+            "Hello 世界 thwas was a was test string."
+        );
+        // The source is not changed.
+        assert_eq!(source.to_string(), "Hello 世界 this is a Is test string.");
+
+        let replaced = search.replace_all(&source, "");
+        assert_eq!(replaced.to_string(), "Hello 世界 th  a  test string.");
+
+        search.update_query("nothing");
+        assert_eq!(search.replace_all(&source, "x"), source);
+    }
+
+    #[test]
+    fn test_count_label() {
+        let mut search = SearchMatcher::new();
+        search.update(&Rope::from("is this is"));
+        assert_eq!(search.count_label().to_string(), "");
+
+        search.update_query("is");
+        assert_eq!(search.count_label().to_string(), "1 of 3");
+        search.next();
+        assert_eq!(search.count_label().to_string(), "2 of 3");
+
+        search.update_query("nothing");
+        assert_eq!(search.count_label().to_string(), "No results");
+    }
+
+    #[test]
+    fn test_regex_search() {
+        let mut search = SearchMatcher::new().regex_mode(true);
+        search.update(&Rope::from("foo(1) bar(22) Foo(333)"));
+
+        search.update_query(r"foo\((\d+)\)");
+        assert!(search.query.is_none());
+        // Only the full matches, not the capture groups.
+        assert_eq!(search.matched_ranges.as_ref(), &vec![0..6, 15..23]);
+
+        search.set_case_sensitive(true);
+        search.update_query(r"(f)(o)o");
+        assert_eq!(search.matched_ranges.as_ref(), &vec![0..3]);
+
+        // Empty matches are skipped.
+        search.update_query(r"x*");
+        assert_eq!(search.len(), 0);
+
+        // Invalid pattern has no matches.
+        search.update_query(r"foo(");
+        assert!(search.regex.is_none());
+        assert_eq!(search.len(), 0);
+    }
+
+    #[test]
+    fn test_whole_word_search() {
+        let mut search = SearchMatcher::new().whole_word(true);
+        search.update(&Rope::from("is this_is Is is_ 世is (is)"));
+        search.update_query("is");
+        assert_eq!(search.matched_ranges.as_ref(), &vec![0..2, 11..13, 25..27]);
+
+        search.set_case_sensitive(true);
+        assert_eq!(search.matched_ranges.as_ref(), &vec![0..2, 25..27]);
+
+        search.set_whole_word(false);
+        assert_eq!(search.len(), 6);
+
+        let mut search = SearchMatcher::new().regex_mode(true).whole_word(true);
+        search.update(&Rope::from("foo1 foo22 xfoo3"));
+        search.update_query(r"foo\d+");
+        assert_eq!(search.matched_ranges.as_ref(), &vec![0..4, 5..10]);
+    }
 }
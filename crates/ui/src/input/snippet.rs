@@ -0,0 +1,318 @@
+use std::ops::Range;
+
+use gpui::{Context, Window};
+
+use super::{InputEvent, InputState};
+
+/// The tab stops of the inserted snippet, see [`InputState::apply_snippet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SnippetState {
+    /// The placeholder ranges in the order of `$1..$N`, the last one is the final
+    /// cursor position (`$0` or the end of the snippet).
+    ranges: Vec<Range<usize>>,
+    /// The index of the active placeholder in `ranges`.
+    active: usize,
+}
+
+impl SnippetState {
+    fn active_range(&self) -> &Range<usize> {
+        &self.ranges[self.active]
+    }
+
+    /// Shift the placeholder ranges by the edit of the `range` replaced with `new_len` bytes.
+    ///
+    /// Returns false if the edit is out of the active placeholder, then the snippet
+    /// should be exited.
+    fn edit(&mut self, range: &Range<usize>, new_len: usize) -> bool {
+        let active = self.active_range().clone();
+        if range.start < active.start || range.end > active.end {
+            return false;
+        }
+
+        let active_ix = self.active;
+        for (ix, placeholder) in self.ranges.iter_mut().enumerate() {
+            if ix == active_ix {
+                placeholder.end = placeholder.end - range.len() + new_len;
+            } else if placeholder.start >= active.end {
+                placeholder.start = placeholder.start - range.len() + new_len;
+                placeholder.end = placeholder.end - range.len() + new_len;
+            }
+        }
+        true
+    }
+}
+
+impl InputState {
+    /// Insert the snippet `template` at the cursor, replacing the selected text.
+    ///
+    /// The `$N` and `${N:default}` markers are the tab stops, press `tab` to move to the
+    /// next one in order, the `$0` is the final cursor position. The text of the active
+    /// placeholder is selected, and the [`InputEvent::SnippetExited`] is emitted when the
+    /// last placeholder is left.
+    pub fn apply_snippet(&mut self, template: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let range = self.selected_range.into();
+        self.insert_snippet(range, template, window, cx);
+    }
+
+    /// Returns true if the input is in snippet mode, see [`Self::apply_snippet`].
+    pub fn is_snippet_active(&self) -> bool {
+        self.snippet.is_some()
+    }
+
+    /// Replace the `range` with the expanded snippet `template`.
+    pub(super) fn insert_snippet(
+        &mut self,
+        range: Range<usize>,
+        template: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.exit_snippet(cx);
+
+        let (text, tab_stops) = parse_snippet(template);
+        self.replace_text_in_range(Some(self.range_to_utf16(&range)), &text, window, cx);
+        if self
+            .text
+            .slice(range.start..range.start + text.len())
+            .to_string()
+            != text
+        {
+            // The text has been rejected or changed by the validation or mask pattern.
+            return;
+        }
+
+        let ranges = tab_stops
+            .into_iter()
+            .map(|stop| range.start + stop.start..range.start + stop.end)
+            .collect::<Vec<_>>();
+        if ranges.len() > 1 {
+            self.snippet = Some(SnippetState { ranges, active: 0 });
+            self.select_snippet_placeholder(cx);
+        } else if let Some(range) = ranges.first() {
+            self.selected_range = range.clone().into();
+            self.update_preferred_column();
+        }
+    }
+
+    /// Move to the next (or previous if `backward`) placeholder of the snippet.
+    ///
+    /// Returns false if not in snippet mode.
+    pub(super) fn move_snippet_placeholder(
+        &mut self,
+        backward: bool,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(snippet) = self.snippet.as_mut() else {
+            return false;
+        };
+
+        if backward {
+            snippet.active = snippet.active.saturating_sub(1);
+        } else {
+            snippet.active += 1;
+        }
+        self.select_snippet_placeholder(cx);
+        true
+    }
+
+    /// Update the snippet placeholders by the edit, exit the snippet mode if the edit
+    /// is out of the active placeholder.
+    pub(super) fn shift_snippet(
+        &mut self,
+        range: &Range<usize>,
+        new_len: usize,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(snippet) = self.snippet.as_mut() else {
+            return;
+        };
+
+        if !snippet.edit(range, new_len) {
+            self.exit_snippet(cx);
+        }
+    }
+
+    /// Exit the snippet mode, returns false if not in snippet mode.
+    pub(super) fn exit_snippet(&mut self, cx: &mut Context<Self>) -> bool {
+        if self.snippet.take().is_none() {
+            return false;
+        }
+
+        cx.emit(InputEvent::SnippetExited);
+        cx.notify();
+        true
+    }
+
+    /// Select the active placeholder, or move the cursor to the final position and
+    /// exit the snippet mode if the active one is the last.
+    fn select_snippet_placeholder(&mut self, cx: &mut Context<Self>) {
+        let Some(snippet) = self.snippet.as_ref() else {
+            return;
+        };
+
+        let range = snippet.active_range().clone();
+        if snippet.active + 1 >= snippet.ranges.len() {
+            self.exit_snippet(cx);
+        }
+
+        self.selected_range = range.clone().into();
+        self.selection_reversed = false;
+        self.update_preferred_column();
+        self.scroll_to(range.end, cx);
+        cx.notify();
+    }
+}
+
+/// Expand the snippet template, returns the text and the tab stop ranges.
+///
+/// The tab stops are ordered by `$1..$N`, and the final cursor position (`$0` or
+/// the end of text) is always the last one. The later tab stops with an existing
+/// number are ignored, and `\$`, `\}`, `\\` are escaped.
+fn parse_snippet(template: &str) -> (String, Vec<Range<usize>>) {
+    let mut text = String::with_capacity(template.len());
+    let mut tab_stops: Vec<(usize, Range<usize>)> = vec![];
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.peek() {
+                Some(&next @ ('$' | '}' | '\\')) => {
+                    text.push(next);
+                    chars.next();
+                }
+                _ => text.push(c),
+            },
+            '$' => {
+                let braced = chars.peek() == Some(&'{');
+                let mut lookahead = chars.clone();
+                if braced {
+                    lookahead.next();
+                }
+
+                let mut digits = String::new();
+                while let Some(d) = lookahead.peek().filter(|d| d.is_ascii_digit()) {
+                    digits.push(*d);
+                    lookahead.next();
+                }
+                let Ok(number) = digits.parse::<usize>() else {
+                    text.push(c);
+                    continue;
+                };
+
+                let start = text.len();
+                if braced {
+                    match lookahead.next() {
+                        Some('}') => {}
+                        Some(':') => {
+                            let mut closed = false;
+                            let mut default = String::new();
+                            while let Some(d) = lookahead.next() {
+                                match d {
+                                    '\\' if matches!(lookahead.peek(), Some('$' | '}' | '\\')) => {
+                                        default.extend(lookahead.next());
+                                    }
+                                    '}' => {
+                                        closed = true;
+                                        break;
+                                    }
+                                    _ => default.push(d),
+                                }
+                            }
+                            if !closed {
+                                text.push(c);
+                                continue;
+                            }
+                            text.push_str(&default);
+                        }
+                        _ => {
+                            text.push(c);
+                            continue;
+                        }
+                    }
+                }
+
+                chars = lookahead;
+                if !tab_stops.iter().any(|(n, _)| *n == number) {
+                    tab_stops.push((number, start..text.len()));
+                }
+            }
+            _ => text.push(c),
+        }
+    }
+
+    let has_final = tab_stops.iter().any(|(number, _)| *number == 0);
+    // `$0` is the final tab stop, the `0` is sorted to the last.
+    tab_stops.sort_by_key(|(number, _)| number.wrapping_sub(1));
+    let mut ranges = tab_stops
+        .into_iter()
+        .map(|(_, range)| range)
+        .collect::<Vec<_>>();
+    if !has_final {
+        ranges.push(text.len()..text.len());
+    }
+
+    (text, ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_snippet, SnippetState};
+
+    #[test]
+    fn test_parse_snippet() {
+        assert_eq!(parse_snippet("hello"), ("hello".to_string(), vec![5..5]));
+        assert_eq!(
+            parse_snippet("fn ${1:name}(${2:args}) -> ${3:ReturnType} {}"),
+            (
+                "fn name(args) -> ReturnType {}".to_string(),
+                vec![3..7, 8..12, 17..27, 30..30]
+            )
+        );
+        assert_eq!(
+            parse_snippet("if $2 {\n    $0\n} else $1"),
+            (
+                "if  {\n    \n} else ".to_string(),
+                vec![18..18, 3..3, 10..10]
+            )
+        );
+        assert_eq!(
+            parse_snippet("${1:a} ${1:b} ${2}"),
+            ("a b ".to_string(), vec![0..1, 4..4, 4..4])
+        );
+        assert_eq!(
+            parse_snippet(r"\$1 $ ${x} ${1:\}}"),
+            ("$1 $ ${x} }".to_string(), vec![10..11, 11..11])
+        );
+        assert_eq!(
+            parse_snippet("${1:abc"),
+            ("${1:abc".to_string(), vec![7..7])
+        );
+    }
+
+    #[test]
+    fn test_snippet_edit() {
+        let mut snippet = SnippetState {
+            ranges: vec![3..7, 8..12, 30..30],
+            active: 0,
+        };
+        // Replace the `name` with `foo_bar`
+        assert!(snippet.edit(&(3..7), 7));
+        assert_eq!(snippet.ranges, vec![3..10, 11..15, 33..33]);
+        // Type at the end of the placeholder
+        assert!(snippet.edit(&(10..10), 1));
+        assert_eq!(snippet.ranges, vec![3..11, 12..16, 34..34]);
+        // Delete in the placeholder
+        assert!(snippet.edit(&(5..7), 0));
+        assert_eq!(snippet.ranges, vec![3..9, 10..14, 32..32]);
+        // Edit out of the active placeholder
+        assert!(!snippet.edit(&(10..11), 0));
+
+        // Type in the empty placeholder followed by another one
+        let mut snippet = SnippetState {
+            ranges: vec![1..1, 1..1, 4..4],
+            active: 0,
+        };
+        assert!(snippet.edit(&(1..1), 2));
+        assert_eq!(snippet.ranges, vec![1..3, 3..3, 6..6]);
+    }
+}
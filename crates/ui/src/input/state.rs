@@ -5,28 +5,36 @@
 use anyhow::Result;
 use gpui::{
     actions, div, point, prelude::FluentBuilder as _, px, Action, App, AppContext, Bounds,
-    ClipboardItem, Context, Entity, EntityInputHandler, EventEmitter, FocusHandle, Focusable, Half,
-    InteractiveElement as _, IntoElement, KeyBinding, KeyDownEvent, MouseButton, MouseDownEvent,
-    MouseMoveEvent, MouseUpEvent, ParentElement as _, Pixels, Point, Render, ScrollHandle,
-    ScrollWheelEvent, SharedString, Styled as _, Subscription, Task, UTF16Selection, Window,
-    WrappedLine,
+    ClipboardItem, Context, Entity, EntityInputHandler, EventEmitter, ExternalPaths, FocusHandle,
+    Focusable, Half, InteractiveElement as _, IntoElement, KeyBinding, KeyDownEvent, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, ParentElement as _, Pixels, Point, Render,
+    ScrollHandle, ScrollWheelEvent, SharedString, Styled as _, Subscription, Task, UTF16Selection,
+    Window, WrappedLine,
 };
 use rope::Rope;
 use serde::Deserialize;
 use smallvec::SmallVec;
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::ops::Range;
+use std::path::PathBuf;
 use std::rc::Rc;
 use sum_tree::Bias;
 use unicode_segmentation::*;
 
 use super::{
+    auto_close::default_auto_close_pairs,
     blink_cursor::BlinkCursor,
-    change::Change,
+    change::{redo_changes, undo_changes, Change},
+    diff_hunk::DiffHunk,
     element::TextElement,
+    gutter::GutterDecoration,
     mask_pattern::MaskPattern,
+    metrics::RenderMetrics,
+    minimap::MinimapCache,
     mode::{InputMode, TabSize},
     number_input,
+    snippet::SnippetState,
     text_wrapper::TextWrapper,
 };
 use crate::input::{
@@ -36,7 +44,7 @@ use crate::input::{
 };
 use crate::input::{RopeExt as _, Selection};
 use crate::{highlighter::DiagnosticSet, input::text_wrapper::LineItem};
-use crate::{history::History, scroll::ScrollbarState, Root};
+use crate::{history::History, scroll::ScrollbarState, ActiveTheme as _, Root};
 
 #[derive(Action, Clone, PartialEq, Eq, Deserialize)]
 #[action(namespace = input, no_json)]
@@ -91,19 +99,35 @@ actions!(
         MoveToNextWord,
         Escape,
         ToggleCodeActions,
+        GoToDefinition,
         Search,
+        ToggleReplace,
+        SelectNextOccurrence,
     ]
 );
 
 #[derive(Clone)]
 pub enum InputEvent {
     Change,
-    PressEnter { secondary: bool },
+    PressEnter {
+        secondary: bool,
+    },
     Focus,
     Blur,
+    /// The search panel replaced the matched text, with the number of replacements.
+    TextReplaced(usize),
+    /// The snippet mode has been exited, see [`InputState::apply_snippet`].
+    SnippetExited,
+    /// A file has been dropped from the OS into the code editor, emitted before its path is inserted.
+    FileDrop(PathBuf),
+    /// The definition of a symbol was found by the [`super::GoToDefinitionProvider`],
+    /// with the file and the byte offset in it, for the host to open it.
+    GoToDefinition(PathBuf, usize),
 }
 
 pub(super) const CONTEXT: &str = "Input";
+/// The interval to group the continuous typing into one undo step.
+const UNDO_GROUP_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
 
 pub fn init(cx: &mut App) {
     cx.bind_keys([
@@ -214,14 +238,25 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("ctrl-z", Undo, Some(CONTEXT)),
         #[cfg(not(target_os = "macos"))]
         KeyBinding::new("ctrl-y", Redo, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-z", Redo, Some(CONTEXT)),
         #[cfg(target_os = "macos")]
         KeyBinding::new("cmd-.", ToggleCodeActions, Some(CONTEXT)),
         #[cfg(not(target_os = "macos"))]
         KeyBinding::new("ctrl-.", ToggleCodeActions, Some(CONTEXT)),
+        KeyBinding::new("f12", GoToDefinition, Some(CONTEXT)),
         #[cfg(target_os = "macos")]
         KeyBinding::new("cmd-f", Search, Some(CONTEXT)),
         #[cfg(not(target_os = "macos"))]
         KeyBinding::new("ctrl-f", Search, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-alt-f", ToggleReplace, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-h", ToggleReplace, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-d", SelectNextOccurrence, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-d", SelectNextOccurrence, Some(CONTEXT)),
     ]);
 
     search::init(cx);
@@ -246,6 +281,16 @@ pub(super) struct LastLayout {
     pub(super) line_number_width: Pixels,
     /// The cursor position (top, left) in pixels.
     pub(super) cursor_bounds: Option<Bounds<Pixels>>,
+    /// The rows hidden by folds.
+    pub(super) hidden_rows: Rc<Vec<Range<usize>>>,
+}
+
+impl LastLayout {
+    /// Returns true if the line at the `ix` of the `lines` is hidden by a fold.
+    pub(super) fn is_hidden(&self, ix: usize) -> bool {
+        let row = self.visible_range.start + ix;
+        self.hidden_rows.iter().any(|rows| rows.contains(&row))
+    }
 }
 
 /// InputState to keep editing state of the [`super::TextInput`].
@@ -262,6 +307,17 @@ pub struct InputState {
     /// - "Hello 世界💝" = 16
     /// - "💝" = 4
     pub(super) selected_range: Selection,
+    /// The cursors added by alt-click or select next occurrence, besides the `selected_range`.
+    pub(super) additional_cursors: Vec<Selection>,
+    /// The foldable line ranges, see [`InputState::fold_ranges`].
+    pub(super) fold_ranges: Vec<Range<usize>>,
+    /// The start lines of the folded ranges.
+    pub(super) folded: HashSet<usize>,
+    pub(super) minimap_enabled: bool,
+    /// The cached minimap blocks, see [`MinimapCache`].
+    pub(super) minimap_cache: Option<MinimapCache>,
+    /// The tab stops of the inserted snippet, see [`InputState::apply_snippet`].
+    pub(super) snippet: Option<SnippetState>,
     pub(super) search_panel: Option<Entity<SearchPanel>>,
     pub(super) searchable: bool,
     /// Range for save the selected word, use to keep word range when drag move.
@@ -278,11 +334,28 @@ pub struct InputState {
     pub(super) last_selected_range: Option<Selection>,
     pub(super) selecting: bool,
     pub(super) disabled: bool,
+    /// The text can be selected and copied, but not edited by the user.
+    pub(super) read_only: bool,
     pub(super) masked: bool,
+    /// The character to display for each character of the text when masked.
+    pub(super) mask_character: char,
     pub(super) clean_on_escape: bool,
     pub(super) soft_wrap: bool,
+    pub(super) smart_wrap_threshold: Option<usize>,
     pub(super) pattern: Option<regex::Regex>,
     pub(super) validate: Option<Box<dyn Fn(&str, &mut Context<Self>) -> bool + 'static>>,
+    /// The max number of characters, set by [`super::TextInput::max_length`].
+    pub(super) max_chars: Option<usize>,
+    /// Paint the indent guides, set by [`super::TextInput::indent_guides`].
+    pub(super) indent_guides: bool,
+    /// The text painted after the cursor at the offset, see [`InputState::set_ghost_text`].
+    pub(super) ghost_text: Option<(usize, SharedString)>,
+    /// The pairs closed when typing the opening text, see [`InputState::with_auto_close`].
+    pub(super) auto_close_pairs: Vec<(String, String)>,
+    /// Record the [`RenderMetrics`], set by [`super::TextInput::with_metrics_enabled`].
+    pub(super) metrics_enabled: bool,
+    /// The timings of the last render, see [`InputState::render_metrics`].
+    pub(super) render_metrics: Option<RenderMetrics>,
     pub(crate) scroll_handle: ScrollHandle,
     pub(super) scroll_state: ScrollbarState,
     /// The size of the scrollable content.
@@ -291,6 +364,15 @@ pub struct InputState {
     /// The mask pattern for formatting the input text
     pub(crate) mask_pattern: MaskPattern,
     pub(super) placeholder: SharedString,
+    /// The multi-line placeholder, the first line is rendered as a title.
+    pub(super) placeholder_lines: Option<Vec<SharedString>>,
+
+    /// The gutter decorations by line (0-based).
+    pub(super) gutter_decorations: HashMap<usize, Vec<GutterDecoration>>,
+    /// The line and the index of the hovered gutter decoration that has a tooltip.
+    pub(super) hovered_gutter_decoration: Option<(usize, usize)>,
+    /// The diff hunks shown between the line numbers and the text.
+    pub(super) diff_hunks: Vec<DiffHunk>,
 
     /// Popover
     diagnostic_popover: Option<Entity<DiagnosticPopover>>,
@@ -318,7 +400,7 @@ impl InputState {
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         let focus_handle = cx.focus_handle();
         let blink_cursor = cx.new(|_| BlinkCursor::new());
-        let history = History::new().group_interval(std::time::Duration::from_secs(1));
+        let history = History::new().group_interval(UNDO_GROUP_INTERVAL);
 
         let _subscriptions = vec![
             // Observe the blink cursor to repaint the view when it changes.
@@ -351,6 +433,12 @@ impl InputState {
             blink_cursor,
             history,
             selected_range: Selection::default(),
+            additional_cursors: Vec::new(),
+            fold_ranges: Vec::new(),
+            folded: HashSet::new(),
+            minimap_enabled: false,
+            minimap_cache: None,
+            snippet: None,
             search_panel: None,
             searchable: false,
             selected_word_range: None,
@@ -359,12 +447,21 @@ impl InputState {
             input_bounds: Bounds::default(),
             selecting: false,
             disabled: false,
+            read_only: false,
             masked: false,
+            mask_character: '\u{2022}',
             clean_on_escape: false,
             soft_wrap: true,
+            smart_wrap_threshold: None,
             loading: false,
             pattern: None,
             validate: None,
+            max_chars: None,
+            indent_guides: false,
+            ghost_text: None,
+            auto_close_pairs: default_auto_close_pairs(),
+            metrics_enabled: false,
+            render_metrics: None,
             mode: InputMode::SingleLine,
             last_layout: None,
             last_bounds: None,
@@ -375,7 +472,11 @@ impl InputState {
             scroll_size: gpui::size(px(0.), px(0.)),
             preferred_column: None,
             placeholder: SharedString::default(),
+            placeholder_lines: None,
             mask_pattern: MaskPattern::default(),
+            gutter_decorations: HashMap::new(),
+            diff_hunks: Vec::new(),
+            hovered_gutter_decoration: None,
             diagnostic_popover: None,
             context_menu: None,
             completion_inserting: false,
@@ -435,6 +536,7 @@ impl InputState {
             diagnostics: DiagnosticSet::default(),
             code_action_providers: vec![],
             completion_provider: None,
+            go_to_definition_provider: None,
         };
         self.searchable = true;
         self
@@ -506,6 +608,35 @@ impl InputState {
         }
     }
 
+    /// Set the go-to-definition provider for the code editor mode, used by `F12` and
+    /// `secondary-click` (`cmd-click` on macOS, `ctrl-click` on others).
+    ///
+    /// Only for `InputMode::CodeEditor`.
+    pub fn set_go_to_definition_provider(
+        &mut self,
+        provider: Option<Rc<dyn super::GoToDefinitionProvider>>,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputMode::CodeEditor {
+            go_to_definition_provider,
+            ..
+        } = &mut self.mode
+        {
+            *go_to_definition_provider = provider;
+            cx.notify();
+        }
+    }
+
+    /// Set the maximum number of undo steps to keep, default is 1000.
+    ///
+    /// The changes undone together (e.g. a burst of typing) count as one step, 0 disables undo.
+    pub fn with_undo_depth(mut self, depth: usize) -> Self {
+        self.history = History::new()
+            .max_undo(depth)
+            .group_interval(UNDO_GROUP_INTERVAL);
+        self
+    }
+
     /// Set placeholder
     pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
         self.placeholder = placeholder.into();
@@ -612,8 +743,22 @@ impl InputState {
         cx.notify();
     }
 
+    /// Set a placeholder of multiple lines, e.g. a welcome message of an editor.
+    ///
+    /// The first line is rendered in bold as a title. This only applies to the multi-line
+    /// input, the single-line input keeps using the [`Self::set_placeholder`] text.
+    pub fn set_placeholder_multiline(
+        &mut self,
+        lines: Vec<SharedString>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.placeholder_lines = (!lines.is_empty()).then_some(lines);
+        cx.notify();
+    }
+
     /// Called after moving the cursor. Updates preferred_column if we know where the cursor now is.
-    fn update_preferred_column(&mut self) {
+    pub(super) fn update_preferred_column(&mut self) {
         let Some(last_layout) = &self.last_layout else {
             self.preferred_column = None;
             return;
@@ -626,8 +771,13 @@ impl InputState {
             return;
         };
 
-        let Some(pos) = line.position_for_index(point.column as usize, last_layout.line_height)
-        else {
+        // The masked text is displayed in a single line.
+        let display_column = if self.masked {
+            self.display_offset(self.cursor())
+        } else {
+            point.column as usize
+        };
+        let Some(pos) = line.position_for_index(display_column, last_layout.line_height) else {
             self.preferred_column = None;
             return;
         };
@@ -651,10 +801,16 @@ impl InputState {
             return (0, 0, None);
         };
         let line_height = last_layout.line_height;
+        let offset = self.display_offset(offset);
 
         let mut prev_lines_offset = last_layout.visible_range_offset.start;
         let mut y_offset = last_layout.visible_top;
         for (line_index, line) in last_layout.lines.iter().enumerate() {
+            if last_layout.is_hidden(line_index) {
+                prev_lines_offset += line.len() + 1;
+                continue;
+            }
+
             let local_offset = offset.saturating_sub(prev_lines_offset);
             if let Some(pos) = line.position_for_index(local_offset, line_height) {
                 let sub_line_index = (pos.y.0 / line_height.0) as usize;
@@ -684,6 +840,7 @@ impl InputState {
 
         let row = self.text.offset_to_point(offset).row;
         let new_row = row.saturating_add_signed(move_lines as i32);
+        let new_row = self.visible_row(new_row as usize, move_lines > 0) as u32;
         let line_start_offset = self.text.point_to_offset(rope::Point::new(new_row, 0));
 
         let mut new_offset = line_start_offset;
@@ -727,8 +884,11 @@ impl InputState {
     ) {
         self.history.ignore = true;
         let was_disabled = self.disabled;
+        let was_read_only = self.read_only;
+        self.read_only = false;
         self.replace_text(value, window, cx);
         self.disabled = was_disabled;
+        self.read_only = was_read_only;
         self.history.ignore = false;
         // Ensure cursor to start when set text
         if self.mode.is_single_line() {
@@ -792,6 +952,36 @@ impl InputState {
         self
     }
 
+    /// Set with read-only mode, the text can be selected and copied but not edited.
+    ///
+    /// The [`Self::set_value`] still works in read-only mode.
+    ///
+    /// See also: [`Self::set_read_only`], [`Self::is_read_only`].
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Set the read-only mode of the input field.
+    pub fn set_read_only(&mut self, read_only: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.read_only = read_only;
+        if read_only {
+            self.ime_marked_range.take();
+            self.exit_snippet(cx);
+        }
+        cx.notify();
+    }
+
+    /// Returns true if the input field is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns true if the text can be changed, it is neither disabled nor read-only.
+    pub(super) fn is_editable(&self) -> bool {
+        !self.disabled && !self.read_only
+    }
+
     /// Set with password masked state.
     pub fn masked(mut self, masked: bool) -> Self {
         self.masked = masked;
@@ -804,6 +994,54 @@ impl InputState {
         cx.notify();
     }
 
+    /// Toggle the password masked state, to show or hide the password.
+    pub fn toggle_masked(&mut self, cx: &mut Context<Self>) {
+        self.masked = !self.masked;
+        cx.notify();
+    }
+
+    /// Set the character to display for each character when masked, default is `•`.
+    pub fn with_mask_character(mut self, mask_character: char) -> Self {
+        self.mask_character = mask_character;
+        self
+    }
+
+    /// Set the character to display for each character when masked, default is `•`.
+    pub fn set_mask_character(&mut self, mask_character: char, cx: &mut Context<Self>) {
+        self.mask_character = mask_character;
+        cx.notify();
+    }
+
+    /// Convert the `offset` in the text to the offset in the displayed text.
+    ///
+    /// They are different when masked, because each character is displayed as the mask character.
+    pub(super) fn display_offset(&self, offset: usize) -> usize {
+        if !self.masked {
+            return offset;
+        }
+
+        let chars_count = self
+            .text
+            .slice(0..offset.min(self.text.len()))
+            .chars()
+            .count();
+        chars_count * self.mask_character.len_utf8()
+    }
+
+    /// Convert the `offset` in the displayed text to the offset in the text, see [`Self::display_offset`].
+    pub(super) fn text_offset(&self, display_offset: usize) -> usize {
+        if !self.masked {
+            return display_offset;
+        }
+
+        let chars_count = display_offset / self.mask_character.len_utf8();
+        self.text
+            .chars()
+            .take(chars_count)
+            .map(|c| c.len_utf8())
+            .sum()
+    }
+
     /// Set true to clear the input by pressing Escape key.
     pub fn clean_on_escape(mut self) -> Self {
         self.clean_on_escape = true;
@@ -838,6 +1076,24 @@ impl InputState {
         cx.notify();
     }
 
+    /// Set the smart wrap threshold (in grapheme clusters) for multi-line input, default is None.
+    ///
+    /// When set, only the lines longer than the threshold are soft wrapped, shorter lines
+    /// (like the lines of a code block in a Markdown editor) overflow horizontally instead.
+    ///
+    /// This only applies when `soft_wrap` is enabled, with `soft_wrap` disabled no line is wrapped.
+    pub fn set_smart_wrap_threshold(
+        &mut self,
+        threshold: Option<usize>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.smart_wrap_threshold = threshold;
+        self.text_wrapper.set_smart_wrap_threshold(threshold, cx);
+        self.mode.update_auto_grow(&self.text_wrapper);
+        cx.notify();
+    }
+
     /// Set the regular expression pattern of the input field.
     pub fn pattern(mut self, pattern: regex::Regex) -> Self {
         self.pattern = Some(pattern);
@@ -860,6 +1116,18 @@ impl InputState {
         self
     }
 
+    /// Returns the max number of characters, see [`super::TextInput::max_length`].
+    pub fn max_chars(&self) -> Option<usize> {
+        self.max_chars
+    }
+
+    /// Returns true if an edit from `old_chars` to `new_chars` characters is within the max length.
+    ///
+    /// The edits that don't add characters are always allowed.
+    pub(super) fn is_within_max_chars(&self, old_chars: usize, new_chars: usize) -> bool {
+        new_chars <= old_chars || new_chars <= self.max_chars.unwrap_or(usize::MAX)
+    }
+
     /// Set true to show indicator at the input right.
     pub fn set_loading(&mut self, loading: bool, _: &mut Window, cx: &mut Context<Self>) {
         self.loading = loading;
@@ -1286,6 +1554,10 @@ impl InputState {
     }
 
     pub(super) fn backspace(&mut self, _: &Backspace, window: &mut Window, cx: &mut Context<Self>) {
+        if self.has_additional_cursors() {
+            return self.delete_in_cursors(true, window, cx);
+        }
+
         if self.selected_range.is_empty() {
             self.select_to(self.previous_boundary(self.cursor()), window, cx)
         }
@@ -1294,6 +1566,10 @@ impl InputState {
     }
 
     pub(super) fn delete(&mut self, _: &Delete, window: &mut Window, cx: &mut Context<Self>) {
+        if self.has_additional_cursors() {
+            return self.delete_in_cursors(false, window, cx);
+        }
+
         if self.selected_range.is_empty() {
             self.select_to(self.next_boundary(self.cursor()), window, cx)
         }
@@ -1404,6 +1680,13 @@ impl InputState {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.move_snippet_placeholder(false, cx) {
+            return;
+        }
+        if self.accept_ghost_text(window, cx) {
+            return;
+        }
+
         self.indent(false, window, cx);
     }
 
@@ -1417,6 +1700,10 @@ impl InputState {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.move_snippet_placeholder(true, cx) {
+            return;
+        }
+
         self.outdent(false, window, cx);
     }
 
@@ -1433,6 +1720,9 @@ impl InputState {
         let Some(tab_size) = self.mode.tab_size() else {
             return;
         };
+        if !self.is_editable() {
+            return;
+        }
 
         let tab_indent = tab_size.to_string();
         let selected_range = self.selected_range;
@@ -1490,6 +1780,9 @@ impl InputState {
         let Some(tab_size) = self.mode.tab_size() else {
             return;
         };
+        if !self.is_editable() {
+            return;
+        }
 
         let tab_indent = tab_size.to_string();
         let selected_range = self.selected_range;
@@ -1571,6 +1864,16 @@ impl InputState {
             self.unmark_text(window, cx);
         }
 
+        if self.has_additional_cursors() {
+            self.additional_cursors.clear();
+            cx.notify();
+            return;
+        }
+
+        if self.exit_snippet(cx) {
+            return;
+        }
+
         if self.clean_on_escape {
             return self.clean(window, cx);
         }
@@ -1587,6 +1890,17 @@ impl InputState {
         self.handle_code_action_trigger(window, cx)
     }
 
+    pub(super) fn go_to_definition(
+        &mut self,
+        _: &GoToDefinition,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.handle_go_to_definition(self.cursor(), window, cx) {
+            cx.propagate();
+        }
+    }
+
     pub(super) fn on_mouse_down(
         &mut self,
         event: &MouseDownEvent,
@@ -1601,8 +1915,30 @@ impl InputState {
             }
         }
 
-        self.selecting = true;
+        if self.click_gutter_decoration(event.position, window, cx) {
+            return;
+        }
+
         let offset = self.index_for_mouse_position(event.position, window, cx);
+        if self.is_in_fold_gutter(event.position) {
+            let row = self.text.offset_to_point(offset).row as usize;
+            if self.toggle_fold_at(row, window, cx) {
+                return;
+            }
+        }
+
+        if event.modifiers.alt && self.mode.is_multi_line() {
+            self.add_cursor(offset, window, cx);
+            return;
+        }
+
+        if event.modifiers.secondary() && self.mode.go_to_definition_provider().is_some() {
+            self.move_to(offset, window, cx);
+            self.handle_go_to_definition(offset, window, cx);
+            return;
+        }
+
+        self.selecting = true;
         // Double click to select word
         if event.button == MouseButton::Left && event.click_count == 2 {
             self.select_word(offset, window, cx);
@@ -1632,6 +1968,8 @@ impl InputState {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.update_hovered_gutter_decoration(event.position, window, cx);
+
         if self.mode.is_code_editor() {
             // Show diagnostic popover on mouse move
             let offset = self.index_for_mouse_position(event.position, window, cx);
@@ -1678,7 +2016,11 @@ impl InputState {
         self.diagnostic_popover = None;
     }
 
-    fn update_scroll_offset(&mut self, offset: Option<Point<Pixels>>, cx: &mut Context<Self>) {
+    pub(super) fn update_scroll_offset(
+        &mut self,
+        offset: Option<Point<Pixels>>,
+        cx: &mut Context<Self>,
+    ) {
         let mut offset = offset.unwrap_or(self.scroll_handle.offset());
 
         let safe_y_range =
@@ -1737,6 +2079,11 @@ impl InputState {
     }
 
     pub(super) fn copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
+        if self.has_additional_cursors() {
+            self.copy_cursors(cx);
+            return;
+        }
+
         if self.selected_range.is_empty() {
             return;
         }
@@ -1746,6 +2093,17 @@ impl InputState {
     }
 
     pub(super) fn cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.is_editable() {
+            return;
+        }
+
+        if self.has_additional_cursors() {
+            if self.copy_cursors(cx) {
+                self.replace_text_in_range(None, "", window, cx);
+            }
+            return;
+        }
+
         if self.selected_range.is_empty() {
             return;
         }
@@ -1762,6 +2120,17 @@ impl InputState {
                 new_text = new_text.replace('\n', "");
             }
 
+            // Truncate the pasted text to fit the max length.
+            if let Some(max_chars) = self.max_chars {
+                if !self.has_additional_cursors() {
+                    let range: Range<usize> = self.selected_range.into();
+                    let selected_chars = self.text.slice(range).chars().count();
+                    let available = max_chars
+                        .saturating_sub(self.text.chars_count().saturating_sub(selected_chars));
+                    new_text = new_text.chars().take(available).collect();
+                }
+            }
+
             self.replace_text_in_range(None, &new_text, window, cx);
         }
     }
@@ -1775,28 +2144,43 @@ impl InputState {
 
         let new_range = range.start..range.start + new_text.len();
 
-        self.history
-            .push(Change::new(range.clone(), &old_text, new_range, new_text));
+        let change = Change::new(range.clone(), &old_text, new_range, new_text);
+        // Only group the continuous typing or deleting of single characters.
+        if self
+            .history
+            .undos()
+            .last()
+            .map_or(false, |last| change.can_group_with(last))
+        {
+            self.history.push(change);
+        } else {
+            self.history.push_separate(change);
+        }
     }
 
     pub(super) fn undo(&mut self, _: &Undo, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(changes) = undo_changes(&mut self.history, self.is_editable()) else {
+            return;
+        };
+
+        self.additional_cursors.clear();
         self.history.ignore = true;
-        if let Some(changes) = self.history.undo() {
-            for change in changes {
-                let range_utf16 = self.range_to_utf16(&change.new_range.into());
-                self.replace_text_in_range(Some(range_utf16), &change.old_text, window, cx);
-            }
+        for change in changes {
+            let range_utf16 = self.range_to_utf16(&change.new_range.into());
+            self.replace_text_in_range(Some(range_utf16), &change.old_text, window, cx);
         }
         self.history.ignore = false;
     }
 
     pub(super) fn redo(&mut self, _: &Redo, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(changes) = redo_changes(&mut self.history, self.is_editable()) else {
+            return;
+        };
+
         self.history.ignore = true;
-        if let Some(changes) = self.history.redo() {
-            for change in changes {
-                let range_utf16 = self.range_to_utf16(&change.old_range.into());
-                self.replace_text_in_range(Some(range_utf16), &change.new_text, window, cx);
-            }
+        for change in changes {
+            let range_utf16 = self.range_to_utf16(&change.old_range.into());
+            self.replace_text_in_range(Some(range_utf16), &change.new_text, window, cx);
         }
         self.history.ignore = false;
     }
@@ -1808,6 +2192,8 @@ impl InputState {
     /// Ensure the offset use self.next_boundary or self.previous_boundary to get the correct offset.
     fn move_to(&mut self, offset: usize, _: &mut Window, cx: &mut Context<Self>) {
         let offset = offset.clamp(0, self.text.len());
+        self.additional_cursors.clear();
+        self.reveal_offset(offset, cx);
         self.selected_range = (offset..offset).into();
         self.scroll_to(offset, cx);
         self.pause_blink_cursor(cx);
@@ -1831,7 +2217,7 @@ impl InputState {
         }
     }
 
-    fn index_for_mouse_position(
+    pub(super) fn index_for_mouse_position(
         &self,
         position: Point<Pixels>,
         _window: &Window,
@@ -1872,6 +2258,11 @@ impl InputState {
             .skip(last_layout.visible_range.start)
             .enumerate()
         {
+            if line.is_hidden() {
+                index += line.len() + 1;
+                continue;
+            }
+
             let line_origin = self.line_origin_with_y_offset(&mut y_offset, line, line_height);
             let pos = inner_position - line_origin;
 
@@ -1885,7 +2276,8 @@ impl InputState {
 
             // Return offset by use closest_index_for_x if is single line mode.
             if self.mode.is_single_line() {
-                return rendered_line.unwrapped_layout.closest_index_for_x(pos.x);
+                let index = rendered_line.unwrapped_layout.closest_index_for_x(pos.x);
+                return self.text_offset(index);
             }
 
             let index_result = rendered_line.closest_index_for_position(pos, line_height);
@@ -1952,6 +2344,8 @@ impl InputState {
     /// Ensure the offset use self.next_boundary or self.previous_boundary to get the correct offset.
     fn select_to(&mut self, offset: usize, _: &mut Window, cx: &mut Context<Self>) {
         let offset = offset.clamp(0, self.text.len());
+        self.additional_cursors.clear();
+        self.reveal_offset(offset, cx);
         if self.selection_reversed {
             self.selected_range.start = offset
         } else {
@@ -1983,7 +2377,12 @@ impl InputState {
     /// The offset is the UTF-8 offset.
     ///
     /// FIXME: When click on a non-word character, the word is not selected.
-    fn select_word(&mut self, offset: usize, window: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn select_word(
+        &mut self,
+        offset: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         #[inline(always)]
         fn is_word(c: char) -> bool {
             c.is_alphanumeric() || matches!(c, '_')
@@ -2079,7 +2478,7 @@ impl InputState {
         self.offset_from_utf16(range_utf16.start)..self.offset_from_utf16(range_utf16.end)
     }
 
-    fn previous_boundary(&self, offset: usize) -> usize {
+    pub(super) fn previous_boundary(&self, offset: usize) -> usize {
         let mut offset = self.text.clip_offset(offset.saturating_sub(1), Bias::Left);
         if let Some(ch) = self.text.char_at(offset) {
             if ch == '\r' {
@@ -2090,7 +2489,7 @@ impl InputState {
         offset
     }
 
-    fn next_boundary(&self, offset: usize) -> usize {
+    pub(super) fn next_boundary(&self, offset: usize) -> usize {
         let mut offset = self.text.clip_offset(offset + 1, Bias::Right);
         if let Some(ch) = self.text.char_at(offset) {
             if ch == '\r' {
@@ -2129,7 +2528,7 @@ impl InputState {
         cx.emit(InputEvent::Blur);
     }
 
-    fn pause_blink_cursor(&mut self, cx: &mut Context<Self>) {
+    pub(super) fn pause_blink_cursor(&mut self, cx: &mut Context<Self>) {
         self.blink_cursor.update(cx, |cursor, cx| {
             cursor.pause(cx);
         });
@@ -2165,7 +2564,7 @@ impl InputState {
         self.select_to(offset, window, cx);
     }
 
-    fn is_valid_input(&self, new_text: &str, cx: &mut Context<Self>) -> bool {
+    pub(super) fn is_valid_input(&self, new_text: &str, cx: &mut Context<Self>) -> bool {
         if new_text.is_empty() {
             return true;
         }
@@ -2292,10 +2691,22 @@ impl EntityInputHandler for InputState {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if self.disabled {
+        if self.disabled || self.read_only {
+            return;
+        }
+
+        // Only typing replaces the selected range, other edits pass the range.
+        if range_utf16.is_none() && self.auto_close(new_text, window, cx) {
             return;
         }
 
+        if range_utf16.is_some() {
+            // The explicit range is only for the primary cursor.
+            self.additional_cursors.clear();
+        } else if self.ime_marked_range.is_none() && self.has_additional_cursors() {
+            return self.replace_text_in_cursors(self.cursor_selections(), new_text, window, cx);
+        }
+
         self.pause_blink_cursor(cx);
 
         let range = range_utf16
@@ -2307,6 +2718,11 @@ impl EntityInputHandler for InputState {
         let old_text = self.text.clone();
         self.text.replace(range.clone(), new_text);
 
+        if !self.is_within_max_chars(old_text.chars_count(), self.text.chars_count()) {
+            self.text = old_text;
+            return;
+        }
+
         let pending_text = self.text.to_string();
         // Check if the new text is valid
         if !self.is_valid_input(&pending_text, cx) {
@@ -2332,6 +2748,11 @@ impl EntityInputHandler for InputState {
             .update_highlighter(&range, &self.text, &new_text, true, cx);
         self.selected_range = (new_offset..new_offset).into();
         self.ime_marked_range.take();
+        self.shift_folds(&old_text, &range, new_text);
+        self.shift_gutter_decorations(&old_text, &range, new_text);
+        self.shift_diff_hunks(&old_text, &range, new_text);
+        self.update_fold_ranges(cx);
+        self.shift_snippet(&range, new_text.len(), cx);
         self.update_preferred_column();
         self.update_scroll_offset(None, cx);
         self.update_search(cx);
@@ -2350,7 +2771,7 @@ impl EntityInputHandler for InputState {
         _: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if self.disabled {
+        if self.disabled || self.read_only {
             return;
         }
 
@@ -2389,6 +2810,11 @@ impl EntityInputHandler for InputState {
                 .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len())
                 .into();
         }
+        self.shift_folds(&old_text, &range, new_text);
+        self.shift_gutter_decorations(&old_text, &range, new_text);
+        self.shift_diff_hunks(&old_text, &range, new_text);
+        self.update_fold_ranges(cx);
+        self.shift_snippet(&range, new_text.len(), cx);
         self.mode.update_auto_grow(&self.text_wrapper);
         cx.emit(InputEvent::Change);
         cx.notify();
@@ -2406,6 +2832,7 @@ impl EntityInputHandler for InputState {
         let line_height = last_layout.line_height;
         let line_number_width = last_layout.line_number_width;
         let range = self.range_from_utf16(&range_utf16);
+        let range = self.display_offset(range.start)..self.display_offset(range.end);
 
         let mut start_origin = None;
         let mut end_origin = None;
@@ -2413,11 +2840,16 @@ impl EntityInputHandler for InputState {
         let mut y_offset = last_layout.visible_top;
         let mut index_offset = last_layout.visible_range_offset.start;
 
-        for line in last_layout.lines.iter() {
+        for (ix, line) in last_layout.lines.iter().enumerate() {
             if start_origin.is_some() && end_origin.is_some() {
                 break;
             }
 
+            if last_layout.is_hidden(ix) {
+                index_offset += line.len() + 1;
+                continue;
+            }
+
             if start_origin.is_none() {
                 if let Some(p) =
                     line.position_for_index(range.start.saturating_sub(index_offset), line_height)
@@ -2463,7 +2895,7 @@ impl EntityInputHandler for InputState {
 
         for line in last_layout.lines.iter() {
             if let Ok(utf8_index) = line.index_for_position(line_point, line_height) {
-                return Some(self.offset_to_utf16(offset + utf8_index));
+                return Some(self.offset_to_utf16(self.text_offset(offset + utf8_index)));
             }
         }
 
@@ -2480,8 +2912,12 @@ impl Focusable for InputState {
 impl Render for InputState {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.text_wrapper.update(&self.text, false, cx);
-        self.mode
-            .update_highlighter(&(0..0), &self.text, "", false, cx);
+        if self
+            .mode
+            .update_highlighter(&(0..0), &self.text, "", false, cx)
+        {
+            self.update_fold_ranges(cx);
+        }
 
         div()
             .id("input-state")
@@ -2489,7 +2925,12 @@ impl Render for InputState {
             .when(self.mode.is_multi_line(), |this| this.h_full())
             .flex_grow()
             .overflow_x_hidden()
+            .when(self.mode.is_code_editor(), |this| {
+                this.drag_over::<ExternalPaths>(|this, _, _, cx| this.bg(cx.theme().drop_target))
+                    .on_drop(cx.listener(Self::on_drop_paths))
+            })
             .child(TextElement::new(cx.entity().clone()).placeholder(self.placeholder.clone()))
+            .children(self.render_gutter_tooltip(cx))
             .children(self.diagnostic_popover.clone())
             .children(self.context_menu.as_ref().map(|menu| menu.render()))
     }
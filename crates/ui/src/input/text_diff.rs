@@ -0,0 +1,210 @@
+use std::ops::Range;
+
+use gpui::{Context, EntityInputHandler as _, Window};
+
+use super::{change::Change, InputState};
+
+impl InputState {
+    /// Update the text from `before` to `after` by only replacing the changed lines, e.g. to apply
+    /// the full file returned by a formatter.
+    ///
+    /// Unlike replacing the whole text, the cursor stays on the unchanged content and the edits
+    /// are undone in a single step.
+    ///
+    /// Returns `false` and keeps the text if it is not `before` anymore.
+    pub fn apply_text_diff(
+        &mut self,
+        before: &str,
+        after: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if self.disabled || self.read_only || self.text.to_string() != before {
+            return false;
+        }
+
+        let edits = line_diff(before, after);
+        if edits.is_empty() {
+            return true;
+        }
+
+        let selected_range = self.selected_range;
+        let mut changes = Vec::with_capacity(edits.len());
+        let ignore = self.history.ignore;
+        self.history.ignore = true;
+        // Apply from the end, so the ranges of the remaining edits stay valid.
+        for (range, new_text) in edits.iter().rev() {
+            let old_text = self.text.slice(range.clone()).to_string();
+            let range_utf16 = self.range_to_utf16(range);
+            self.replace_text_in_range(Some(range_utf16), new_text, window, cx);
+            changes.push(Change::new(
+                range.clone(),
+                &old_text,
+                range.start..range.start + new_text.len(),
+                new_text,
+            ));
+        }
+        self.history.ignore = ignore;
+        if !self.history.ignore {
+            self.history.push_all(changes);
+        }
+
+        let start = map_offset(&edits, selected_range.start);
+        let end = map_offset(&edits, selected_range.end);
+        self.selected_range = (start..end).into();
+        cx.notify();
+        true
+    }
+}
+
+/// Compute the edits that turn `before` into `after`, by a Myers diff of their lines.
+///
+/// Each edit replaces a byte range of `before` with the new text, the edits are ordered and
+/// don't overlap.
+pub(super) fn line_diff(before: &str, after: &str) -> Vec<(Range<usize>, String)> {
+    let old_lines = before.split_inclusive('\n').collect::<Vec<_>>();
+    let new_lines = after.split_inclusive('\n').collect::<Vec<_>>();
+
+    let mut line_offsets = Vec::with_capacity(old_lines.len() + 1);
+    let mut offset = 0;
+    line_offsets.push(offset);
+    for line in &old_lines {
+        offset += line.len();
+        line_offsets.push(offset);
+    }
+
+    let mut edits = vec![];
+    let (mut old_ix, mut new_ix) = (0, 0);
+    let matches = matching_lines(&old_lines, &new_lines);
+    for (old_match, new_match) in matches
+        .into_iter()
+        .chain(std::iter::once((old_lines.len(), new_lines.len())))
+    {
+        if old_ix < old_match || new_ix < new_match {
+            edits.push((
+                line_offsets[old_ix]..line_offsets[old_match],
+                new_lines[new_ix..new_match].concat(),
+            ));
+        }
+        old_ix = old_match + 1;
+        new_ix = new_match + 1;
+    }
+    edits
+}
+
+/// Returns the pairs of equal lines of the shortest edit script from `old` to `new`, in order.
+fn matching_lines(old: &[&str], new: &[&str]) -> Vec<(usize, usize)> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = n + m;
+    let ix = |k: isize| (k + max + 1) as usize;
+
+    // The furthest `x` reached on each diagonal `k = x - y`, kept for each edit distance `d`.
+    let mut v = vec![0isize; 2 * max as usize + 3];
+    let mut trace = vec![];
+    'outer: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[ix(k - 1)] < v[ix(k + 1)]) {
+                v[ix(k + 1)]
+            } else {
+                v[ix(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[ix(k)] = x;
+            if x >= n && y >= m {
+                break 'outer;
+            }
+        }
+    }
+
+    let mut matches = vec![];
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[ix(k - 1)] < v[ix(k + 1)]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[ix(prev_k)];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            matches.push((x as usize, y as usize));
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    matches.reverse();
+    matches
+}
+
+/// Map an `offset` of the text before the `edits` to the text after them.
+///
+/// An offset inside a replaced range moves to the end of its new text.
+fn map_offset(edits: &[(Range<usize>, String)], offset: usize) -> usize {
+    let mut delta = 0isize;
+    for (range, new_text) in edits {
+        if range.start >= offset && !(range.is_empty() && range.start == offset) {
+            break;
+        }
+        if offset < range.end {
+            return (range.start as isize + delta) as usize + new_text.len();
+        }
+        delta += new_text.len() as isize - range.len() as isize;
+    }
+    (offset as isize + delta) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{line_diff, map_offset};
+
+    fn apply(before: &str, edits: &[(std::ops::Range<usize>, String)]) -> String {
+        let mut text = before.to_string();
+        for (range, new_text) in edits.iter().rev() {
+            text.replace_range(range.clone(), new_text);
+        }
+        text
+    }
+
+    #[test]
+    fn test_line_diff() {
+        assert!(line_diff("a\nb\n", "a\nb\n").is_empty());
+
+        let before = "fn main() {\nlet a=1;\n    println!(\"{a}\");\n}\n";
+        let after = "fn main() {\n    let a = 1;\n    println!(\"{a}\");\n}\n";
+        let edits = line_diff(before, after);
+        assert_eq!(edits, vec![(12..21, "    let a = 1;\n".to_string())]);
+        assert_eq!(apply(before, &edits), after);
+
+        for (before, after) in [
+            ("", "a\nb"),
+            ("a\nb", ""),
+            ("a\nb\nc\nd", "b\nx\nd\ne"),
+            ("a\nb\nc", "c\nb\na"),
+            ("a\n\n\nb\n", "a\n\nb\n\n"),
+        ] {
+            assert_eq!(apply(before, &line_diff(before, after)), after);
+        }
+    }
+
+    #[test]
+    fn test_cursor_in_unchanged_content() {
+        let before = "use std::io;\nfn  main( ) {\n}\n\nfn helper() {}\n";
+        let after = "use std::io;\nfn main() {}\n\nfn helper() {}\n";
+        let edits = line_diff(before, after);
+
+        let cursor = before.find("helper").unwrap();
+        assert_eq!(map_offset(&edits, cursor), after.find("helper").unwrap());
+        assert_eq!(map_offset(&edits, 4), 4);
+        // Inside a changed line, the cursor moves to the end of the replacement.
+        assert_eq!(map_offset(&edits, 16), after.find("\n\n").unwrap() + 1);
+    }
+}
@@ -15,7 +15,29 @@ use crate::{v_flex, ActiveTheme};
 use crate::{IconName, Size};
 use crate::{Sizable, StyleSized};
 
-use super::InputState;
+use super::{InputState, RopeExt as _};
+
+/// The border style of the [`TextInput`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputBorder {
+    /// No border.
+    None,
+    /// The default border, with the focus ring when focused.
+    #[default]
+    Normal,
+    /// A muted border without the focus ring, for the read-only input.
+    ReadOnly,
+}
+
+impl From<bool> for InputBorder {
+    fn from(bordered: bool) -> Self {
+        if bordered {
+            Self::Normal
+        } else {
+            Self::None
+        }
+    }
+}
 
 #[derive(IntoElement)]
 pub struct TextInput {
@@ -29,7 +51,10 @@ pub struct TextInput {
     cleanable: bool,
     mask_toggle: bool,
     disabled: bool,
-    bordered: bool,
+    max_length: Option<usize>,
+    indent_guides: bool,
+    metrics_enabled: bool,
+    bordered: InputBorder,
     focus_bordered: bool,
 }
 
@@ -54,7 +79,10 @@ impl TextInput {
             cleanable: false,
             mask_toggle: false,
             disabled: false,
-            bordered: true,
+            max_length: None,
+            indent_guides: false,
+            metrics_enabled: false,
+            bordered: InputBorder::Normal,
             focus_bordered: true,
         }
     }
@@ -87,9 +115,11 @@ impl TextInput {
         self
     }
 
-    /// Set the bordered for the input, default: true
-    pub fn bordered(mut self, bordered: bool) -> Self {
-        self.bordered = bordered;
+    /// Set the border style for the input, default: [`InputBorder::Normal`]
+    ///
+    /// Accepts a `bool` for the [`InputBorder::Normal`] or [`InputBorder::None`].
+    pub fn bordered(mut self, bordered: impl Into<InputBorder>) -> Self {
+        self.bordered = bordered.into();
         self
     }
 
@@ -117,6 +147,32 @@ impl TextInput {
         self
     }
 
+    /// Set the max number of characters of the input, the typing beyond the limit is ignored
+    /// and the pasted text is truncated to fit.
+    ///
+    /// A character counter like `37/100` is shown below the input.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Set true to paint vertical lines at the indent levels of the lines (Multi-line only),
+    /// default is false.
+    ///
+    /// The guide of the innermost block around the cursor is highlighted.
+    pub fn indent_guides(mut self, indent_guides: bool) -> Self {
+        self.indent_guides = indent_guides;
+        self
+    }
+
+    /// Record the timings of each render, see [`InputState::render_metrics`].
+    ///
+    /// They are also recorded when the `GPUI_MEASUREMENTS` env is set.
+    pub fn with_metrics_enabled(mut self) -> Self {
+        self.metrics_enabled = true;
+        self
+    }
+
     fn render_toggle_mask_button(state: Entity<InputState>) -> impl IntoElement {
         Button::new("toggle-mask")
             .icon(IconName::Eye)
@@ -173,8 +229,18 @@ impl TextInput {
         const MIN_SCROLL_PADDING: Pixels = px(2.0);
 
         v_flex()
+            .relative()
             .size_full()
-            .children(state.search_panel.clone())
+            .children(state.search_panel.clone().map(|panel| {
+                // Overlay the panel, so showing the replace row does not resize the editor.
+                div()
+                    .absolute()
+                    .top_0()
+                    .right_0()
+                    .w(px(420.))
+                    .max_w_full()
+                    .child(panel)
+            }))
             .child(div().flex_1().child(input_state.clone()).map(|this| {
                 if let Some(last_layout) = state.last_layout.as_ref() {
                     let left = if last_layout.line_number_width.is_zero() {
@@ -189,7 +255,7 @@ impl TextInput {
                         height: state.scroll_size.height,
                     };
 
-                    let scrollbar = if !state.soft_wrap {
+                    let scrollbar = if !state.soft_wrap || state.smart_wrap_threshold.is_some() {
                         Scrollbar::both(&state.scroll_state, &state.scroll_handle)
                     } else {
                         Scrollbar::vertical(&state.scroll_state, &state.scroll_handle)
@@ -226,6 +292,9 @@ impl RenderOnce for TextInput {
         self.state.update(cx, |state, cx| {
             state.text_wrapper.set_font(font, font_size, cx);
             state.disabled = self.disabled;
+            state.max_chars = self.max_length;
+            state.indent_guides = self.indent_guides;
+            state.metrics_enabled = self.metrics_enabled;
         });
 
         let state = self.state.read(cx);
@@ -247,6 +316,16 @@ impl RenderOnce for TextInput {
         let show_clear_button =
             self.cleanable && !state.loading && state.text.len() > 0 && state.mode.is_single_line();
         let has_suffix = suffix.is_some() || state.loading || self.mask_toggle || show_clear_button;
+        let chars_counter = state.max_chars.map(|max_chars| {
+            let chars_count = state.text.chars_count();
+            // Highlight the counter when more than 90% of the limit is used.
+            let color = if chars_count * 10 > max_chars * 9 {
+                cx.theme().danger
+            } else {
+                cx.theme().muted_foreground
+            };
+            (format!("{}/{}", chars_count, max_chars), color)
+        });
 
         div()
             .id(("input", self.state.entity_id()))
@@ -254,6 +333,9 @@ impl RenderOnce for TextInput {
             .key_context(crate::input::CONTEXT)
             .track_focus(&state.focus_handle)
             .when(!state.disabled, |this| {
+                this.on_action(window.listener_for(&self.state, InputState::escape))
+            })
+            .when(!state.disabled && !state.read_only, |this| {
                 this.on_action(window.listener_for(&self.state, InputState::backspace))
                     .on_action(window.listener_for(&self.state, InputState::delete))
                     .on_action(
@@ -263,7 +345,6 @@ impl RenderOnce for TextInput {
                     .on_action(window.listener_for(&self.state, InputState::delete_previous_word))
                     .on_action(window.listener_for(&self.state, InputState::delete_next_word))
                     .on_action(window.listener_for(&self.state, InputState::enter))
-                    .on_action(window.listener_for(&self.state, InputState::escape))
                     .on_action(window.listener_for(&self.state, InputState::paste))
                     .on_action(window.listener_for(&self.state, InputState::cut))
                     .on_action(window.listener_for(&self.state, InputState::undo))
@@ -275,6 +356,7 @@ impl RenderOnce for TextInput {
                             .on_action(window.listener_for(&self.state, InputState::outdent_block))
                     })
                     .on_action(window.listener_for(&self.state, InputState::toggle_code_actions))
+                    .on_action(window.listener_for(&self.state, InputState::go_to_definition))
             })
             .on_action(window.listener_for(&self.state, InputState::left))
             .on_action(window.listener_for(&self.state, InputState::right))
@@ -304,6 +386,8 @@ impl RenderOnce for TextInput {
             .on_action(window.listener_for(&self.state, InputState::show_character_palette))
             .on_action(window.listener_for(&self.state, InputState::copy))
             .on_action(window.listener_for(&self.state, InputState::on_action_search))
+            .on_action(window.listener_for(&self.state, InputState::on_action_toggle_replace))
+            .on_action(window.listener_for(&self.state, InputState::select_next_occurrence))
             .on_key_down(window.listener_for(&self.state, InputState::on_key_down))
             .on_mouse_down(
                 MouseButton::Left,
@@ -330,7 +414,7 @@ impl RenderOnce for TextInput {
             .when(self.appearance, |this| {
                 this.bg(bg)
                     .rounded(cx.theme().radius)
-                    .when(self.bordered, |this| {
+                    .when(self.bordered == InputBorder::Normal, |this| {
                         this.border_color(cx.theme().input)
                             .border_1()
                             .when(cx.theme().shadow, |this| this.shadow_xs())
@@ -338,6 +422,9 @@ impl RenderOnce for TextInput {
                                 this.focused_border(cx)
                             })
                     })
+                    .when(self.bordered == InputBorder::ReadOnly, |this| {
+                        this.border_color(cx.theme().border).border_1()
+                    })
             })
             .items_center()
             .gap(gap_x)
@@ -382,5 +469,17 @@ impl RenderOnce for TextInput {
                         .children(suffix),
                 )
             })
+            .when_some(chars_counter, |this, (counter, color)| {
+                this.relative().child(
+                    div()
+                        .absolute()
+                        .top(relative(1.))
+                        .right_0()
+                        .mt_0p5()
+                        .text_xs()
+                        .text_color(color)
+                        .child(counter),
+                )
+            })
     }
 }
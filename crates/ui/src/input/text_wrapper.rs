@@ -1,7 +1,8 @@
 use std::ops::Range;
 
-use gpui::{App, Font, LineFragment, Pixels};
+use gpui::{px, App, Font, LineFragment, Pixels};
 use rope::Rope;
+use unicode_segmentation::UnicodeSegmentation as _;
 
 use crate::input::RopeExt as _;
 
@@ -16,6 +17,10 @@ pub(super) struct LineItem {
     /// like the `window.text_system().shape_text`. So, this value may not equal
     /// the actual rendered lines.
     wrapped_lines: Vec<Range<usize>>,
+    /// Whether this line is hidden by a fold.
+    hidden: bool,
+    /// Whether this line is soft wrapped, false if it is under the smart wrap threshold.
+    soft_wrap: bool,
 }
 
 impl LineItem {
@@ -31,8 +36,24 @@ impl LineItem {
         self.wrapped_lines.len()
     }
 
-    /// Get the height of this line item with given line height.
+    /// Returns true if this line is hidden by a fold.
+    #[inline]
+    pub(super) fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Returns true if this line is soft wrapped when it exceeds the wrap width.
+    #[inline]
+    pub(super) fn is_soft_wrapped(&self) -> bool {
+        self.soft_wrap
+    }
+
+    /// Get the height of this line item with given line height, the hidden line has no height.
     pub(super) fn height(&self, line_height: Pixels) -> Pixels {
+        if self.hidden {
+            return px(0.);
+        }
+
         self.lines_len() as f32 * line_height
     }
 }
@@ -48,8 +69,12 @@ pub(super) struct TextWrapper {
     font_size: Pixels,
     /// If is none, it means the text is not wrapped
     wrap_width: Option<Pixels>,
+    /// If is some, only the lines longer than this number of graphemes are wrapped.
+    smart_wrap_threshold: Option<usize>,
     /// The lines by split \n
     pub(super) lines: Vec<LineItem>,
+    /// The rows hidden by folds.
+    hidden_rows: Vec<Range<usize>>,
 }
 
 #[allow(unused)]
@@ -60,8 +85,10 @@ impl TextWrapper {
             font,
             font_size,
             wrap_width,
+            smart_wrap_threshold: None,
             soft_lines: 0,
             lines: Vec::new(),
+            hidden_rows: Vec::new(),
         }
     }
 
@@ -76,6 +103,36 @@ impl TextWrapper {
         self.soft_lines
     }
 
+    /// Returns true if the row is hidden by a fold.
+    #[inline]
+    pub(super) fn is_hidden(&self, row: usize) -> bool {
+        self.hidden_rows.iter().any(|rows| rows.contains(&row))
+    }
+
+    /// Get the rows hidden by folds.
+    #[inline]
+    pub(super) fn hidden_rows(&self) -> &[Range<usize>] {
+        &self.hidden_rows
+    }
+
+    /// Set the rows to hide, the hidden rows will have no height.
+    pub(super) fn set_hidden_rows(&mut self, hidden_rows: Vec<Range<usize>>) {
+        self.hidden_rows = hidden_rows;
+        self.apply_hidden_rows();
+    }
+
+    fn apply_hidden_rows(&mut self) {
+        for (row, line) in self.lines.iter_mut().enumerate() {
+            line.hidden = self.hidden_rows.iter().any(|rows| rows.contains(&row));
+        }
+        self.soft_lines = self
+            .lines
+            .iter()
+            .filter(|l| !l.hidden)
+            .map(|l| l.lines_len())
+            .sum();
+    }
+
     /// Get the line item by row index.
     #[inline]
     pub(super) fn line(&self, row: usize) -> Option<&LineItem> {
@@ -91,6 +148,15 @@ impl TextWrapper {
         self.update(&self.text.clone(), true, cx);
     }
 
+    pub(super) fn set_smart_wrap_threshold(&mut self, threshold: Option<usize>, cx: &mut App) {
+        if threshold == self.smart_wrap_threshold {
+            return;
+        }
+
+        self.smart_wrap_threshold = threshold;
+        self.update(&self.text.clone(), true, cx);
+    }
+
     pub(super) fn set_font(&mut self, font: Font, font_size: Pixels, cx: &mut App) {
         if self.font.eq(&font) && self.font_size == font_size {
             return;
@@ -119,9 +185,10 @@ impl TextWrapper {
             let line_str = line.to_string();
             let mut wrapped_lines = vec![];
             let mut prev_boundary_ix = 0;
+            let soft_wrap = exceeds_smart_wrap_threshold(&line_str, self.smart_wrap_threshold);
 
             // If wrap_width is Pixels::MAX, skip wrapping to disable word wrap
-            if let Some(wrap_width) = wrap_width {
+            if let Some(wrap_width) = wrap_width.filter(|_| soft_wrap) {
                 // Here only have wrapped line, if there is no wrap meet, the `line_wraps` result will empty.
                 for boundary in line_wrapper.wrap_line(&[LineFragment::text(&line_str)], wrap_width)
                 {
@@ -138,10 +205,37 @@ impl TextWrapper {
             self.lines.push(LineItem {
                 line: line.clone(),
                 wrapped_lines,
+                hidden: false,
+                soft_wrap,
             });
         }
 
         self.text = text.clone();
-        self.soft_lines = self.lines.iter().map(|l| l.lines_len()).sum();
+        self.apply_hidden_rows();
+    }
+}
+
+/// Returns true if the line is longer than the smart wrap threshold (in grapheme clusters),
+/// always true if there is no threshold.
+pub(super) fn exceeds_smart_wrap_threshold(line: &str, threshold: Option<usize>) -> bool {
+    match threshold {
+        Some(threshold) => line.graphemes(true).nth(threshold).is_some(),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::exceeds_smart_wrap_threshold;
+
+    #[test]
+    fn test_exceeds_smart_wrap_threshold() {
+        assert!(exceeds_smart_wrap_threshold("let a = 1;", None));
+        assert!(!exceeds_smart_wrap_threshold("", Some(0)));
+        assert!(!exceeds_smart_wrap_threshold("let a = 1;", Some(10)));
+        assert!(exceeds_smart_wrap_threshold("let a = 10;", Some(10)));
+        // Graphemes, not bytes or chars.
+        assert!(!exceeds_smart_wrap_threshold("👨‍👩‍👧e\u{301}", Some(2)));
+        assert!(exceeds_smart_wrap_threshold("👨‍👩‍👧e\u{301}!", Some(2)));
     }
 }
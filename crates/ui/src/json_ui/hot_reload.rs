@@ -79,11 +79,11 @@ impl HotReloadManager {
     }
 
     pub fn clear_dependencies(&mut self) {
-        self.dependencies.clear();
         if let Some(ref mut watcher) = self.watcher {
             for dep in &self.dependencies {
                 watcher.remove_file(dep);
             }
         }
+        self.dependencies.clear();
     }
 }
\ No newline at end of file
@@ -1,21 +1,28 @@
 use gpui::*;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 pub mod schema;
 pub mod parser;
 pub mod renderer;
 pub mod hot_reload;
+pub mod validation;
 
 pub use schema::*;
 pub use parser::*;
 pub use renderer::*;
 pub use hot_reload::*;
+pub use validation::*;
 
 pub struct JsonCanvas {
     root_path: PathBuf,
+    dependency_paths: Vec<PathBuf>,
     current_ui: Option<UiComponent>,
+    warnings: Vec<String>,
     hot_reload_manager: HotReloadManager,
     parser: UiParser,
+    /// Values of the props written as `"{{name}}"`
+    variables: Variables,
 }
 
 impl JsonCanvas {
@@ -25,20 +32,95 @@ impl JsonCanvas {
 
         Self {
             root_path: root_path.clone(),
+            dependency_paths: Vec::new(),
             current_ui: None,
+            warnings: Vec::new(),
             hot_reload_manager: HotReloadManager::new(),
             parser: UiParser::new(base_path),
+            variables: Variables::new(),
         }
     }
 
+    /// Set the variables bound to the props written as `"{{name}}"`.
+    pub fn with_variables(mut self, vars: HashMap<String, serde_json::Value>) -> Self {
+        self.variables = vars;
+        self
+    }
+
+    /// Update a variable, the canvas shows the new value on its next render.
+    pub fn update_variable(&mut self, name: &str, value: serde_json::Value) {
+        self.variables.insert(name.to_string(), value);
+    }
+
+    pub fn get_variables(&self) -> &HashMap<String, serde_json::Value> {
+        &self.variables
+    }
+
     pub fn load(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let ui = self.parser.parse_document(&self.root_path)?;
-        self.current_ui = Some(ui);
+        // A cached root is returned unresolved, so parse it again to find all dependencies.
+        self.parser.clear_cache();
+        self.parser.clear_resolved_paths();
+        let ui = self.parser.parse_document(&self.root_path);
+        self.update_dependencies();
+
+        self.set_ui(ui?);
         Ok(())
     }
 
+    fn set_ui(&mut self, ui: UiComponent) {
+        self.current_ui = Some(ui);
+        self.warnings = self.validate().iter().map(|error| error.to_string()).collect();
+        for warning in &self.warnings {
+            eprintln!("JSON UI validation warning: {}", warning);
+        }
+    }
+
+    /// Validate the loaded UI against the bundled UI schema.
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let Some(ui) = &self.current_ui else {
+            return Vec::new();
+        };
+
+        match serde_json::to_value(ui) {
+            Ok(value) => validate_component(&value),
+            Err(err) => vec![ValidationError {
+                path: String::new(),
+                message: err.to_string(),
+            }],
+        }
+    }
+
+    /// Validation warnings of the last loaded UI.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Files referenced by the root file through `$ref`, watched by hot reload as well.
+    pub fn dependencies(&self) -> &[PathBuf] {
+        &self.dependency_paths
+    }
+
+    fn update_dependencies(&mut self) {
+        // The root file is always resolved first.
+        let root_path = self.parser.resolved_paths().first().cloned();
+        let dependency_paths: Vec<PathBuf> = self.parser.resolved_paths()
+            .iter()
+            .filter(|path| Some(*path) != root_path.as_ref())
+            .cloned()
+            .collect();
+
+        self.hot_reload_manager.clear_dependencies();
+        for path in &dependency_paths {
+            self.hot_reload_manager.add_dependency(path.clone());
+        }
+        self.dependency_paths = dependency_paths;
+    }
+
     pub fn start_hot_reload(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         self.hot_reload_manager.start_watching(&self.root_path)?;
+        for path in &self.dependency_paths {
+            self.hot_reload_manager.add_dependency(path.clone());
+        }
         Ok(())
     }
 
@@ -69,7 +151,7 @@ impl JsonCanvas {
 
     pub fn load_from_string(&mut self, json_content: &str) -> Result<(), Box<dyn std::error::Error>> {
         let ui = self.parser.parse_from_string(json_content)?;
-        self.current_ui = Some(ui);
+        self.set_ui(ui);
         Ok(())
     }
 }
@@ -83,7 +165,7 @@ impl Render for JsonCanvas {
         }
 
         if let Some(ref ui) = self.current_ui {
-            UiRenderer::render_component(ui, cx)
+            UiRenderer::render_component(ui, &self.variables, cx)
         } else {
             div()
                 .p_4()
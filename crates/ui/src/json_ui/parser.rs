@@ -3,9 +3,13 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 
+/// Variables of a [`crate::json_ui::JsonCanvas`], bound to props written as `"{{name}}"`.
+pub type Variables = HashMap<String, serde_json::Value>;
+
 pub struct UiParser {
     base_path: PathBuf,
     cache: HashMap<PathBuf, UiDocument>,
+    resolved_paths: Vec<PathBuf>,
 }
 
 impl UiParser {
@@ -13,6 +17,7 @@ impl UiParser {
         Self {
             base_path: base_path.as_ref().to_path_buf(),
             cache: HashMap::new(),
+            resolved_paths: Vec::new(),
         }
     }
 
@@ -28,6 +33,10 @@ impl UiParser {
             self.base_path.join(path)
         };
 
+        if !self.resolved_paths.contains(&absolute_path) {
+            self.resolved_paths.push(absolute_path.clone());
+        }
+
         if let Some(cached) = self.cache.get(&absolute_path) {
             return Ok(cached.root.clone());
         }
@@ -149,6 +158,15 @@ impl UiParser {
         self.cache.clear();
     }
 
+    /// Files parsed since the last `clear_resolved_paths`, including the files referenced by `$ref`.
+    pub fn resolved_paths(&self) -> &[PathBuf] {
+        &self.resolved_paths
+    }
+
+    pub fn clear_resolved_paths(&mut self) {
+        self.resolved_paths.clear();
+    }
+
     pub fn parse_from_string(&mut self, content: &str) -> Result<UiComponent, Box<dyn std::error::Error>> {
         let document: UiDocument = serde_json::from_str(content)?;
         self.resolve_component(&document.root)
@@ -157,4 +175,80 @@ impl UiParser {
     pub fn remove_from_cache(&mut self, path: &Path) {
         self.cache.remove(path);
     }
-}
\ No newline at end of file
+
+    /// Replace the props written as `"{{name}}"` with the value of the variable, props of
+    /// unknown variables are kept as is.
+    pub fn bind_props(props: &HashMap<String, UiValue>, variables: &Variables) -> HashMap<String, UiValue> {
+        props
+            .iter()
+            .map(|(key, value)| (key.clone(), Self::bind_value(value, variables)))
+            .collect()
+    }
+
+    fn bind_value(value: &UiValue, variables: &Variables) -> UiValue {
+        match value {
+            UiValue::String(s) => bound_variable_name(s)
+                .and_then(|name| variables.get(name))
+                .map(ui_value_from_json)
+                .unwrap_or_else(|| value.clone()),
+            UiValue::Array(arr) => UiValue::Array(arr.iter().map(|v| Self::bind_value(v, variables)).collect()),
+            UiValue::Object(obj) => UiValue::Object(
+                obj.iter()
+                    .map(|(k, v)| (k.clone(), Self::bind_value(v, variables)))
+                    .collect(),
+            ),
+            _ => value.clone(),
+        }
+    }
+}
+
+/// Returns the variable name of a binding like `{{count}}`.
+fn bound_variable_name(s: &str) -> Option<&str> {
+    let name = s.strip_prefix("{{")?.strip_suffix("}}")?.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+fn ui_value_from_json(value: &serde_json::Value) -> UiValue {
+    match value {
+        serde_json::Value::Null => UiValue::String(String::new()),
+        serde_json::Value::Bool(b) => UiValue::Boolean(*b),
+        serde_json::Value::Number(n) => UiValue::Number(n.as_f64().unwrap_or_default()),
+        serde_json::Value::String(s) => UiValue::String(s.clone()),
+        serde_json::Value::Array(arr) => UiValue::Array(arr.iter().map(ui_value_from_json).collect()),
+        serde_json::Value::Object(obj) => UiValue::Object(
+            obj.iter()
+                .map(|(k, v)| (k.clone(), ui_value_from_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bind_props() {
+        let props: HashMap<String, UiValue> = serde_json::from_value(json!({
+            "content": "{{ title }}",
+            "width": "{{width}}",
+            "items": ["{{visible}}", "static"],
+            "color": "{{missing}}",
+            "label": "Hello {{title}}"
+        }))
+        .unwrap();
+        let variables = Variables::from([
+            ("title".to_string(), json!("Dashboard")),
+            ("width".to_string(), json!(320)),
+            ("visible".to_string(), json!(true)),
+        ]);
+
+        let bound = UiParser::bind_props(&props, &variables);
+        assert_eq!(bound["content"].as_string(), Some("Dashboard"));
+        assert_eq!(bound["width"].as_number(), Some(320.0));
+        assert!(matches!(&bound["items"], UiValue::Array(items) if matches!(items[0], UiValue::Boolean(true))));
+        assert_eq!(bound["color"].as_string(), Some("{{missing}}"));
+        assert_eq!(bound["label"].as_string(), Some("Hello {{title}}"));
+    }
+}
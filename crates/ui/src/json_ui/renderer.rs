@@ -1,4 +1,5 @@
 use crate::json_ui::schema::*;
+use crate::json_ui::parser::{UiParser, Variables};
 use crate::button::Button;
 use crate::{StyledExt, gray};
 use gpui::*;
@@ -7,8 +8,10 @@ use std::collections::HashMap;
 pub struct UiRenderer;
 
 impl UiRenderer {
-    pub fn render_component(component: &UiComponent, cx: &mut Context<crate::json_ui::JsonCanvas>) -> AnyElement {
-        Self::render_component_internal(component, &component.props, cx)
+    /// Render the component, string props like `"{{name}}"` are replaced by the `variables`.
+    pub fn render_component(component: &UiComponent, variables: &Variables, cx: &mut Context<crate::json_ui::JsonCanvas>) -> AnyElement {
+        let props = UiParser::bind_props(&component.props, variables);
+        Self::render_component_internal(component, &props, variables, cx)
     }
 
     pub fn render_component_generic<T>(component: &UiComponent, _cx: &mut Context<T>) -> AnyElement {
@@ -18,60 +21,61 @@ impl UiRenderer {
     fn render_component_internal(
         component: &UiComponent,
         props: &HashMap<String, UiValue>,
+        variables: &Variables,
         cx: &mut Context<crate::json_ui::JsonCanvas>
     ) -> AnyElement {
         match component.component_type.as_str() {
-            "div" => Self::render_div(component, props, cx).into_any_element(),
-            "h1" => Self::render_h1(component, props, cx).into_any_element(),
-            "h2" => Self::render_h2(component, props, cx).into_any_element(),
-            "h3" => Self::render_h3(component, props, cx).into_any_element(),
-            "button" => Self::render_button(component, props, cx).into_any_element(),
-            "input" => Self::render_input(component, props, cx).into_any_element(),
-            "text" => Self::render_text(component, props, cx).into_any_element(),
-            "flex" => Self::render_flex(component, props, cx).into_any_element(),
-            "column" => Self::render_column(component, props, cx).into_any_element(),
-            "row" => Self::render_row(component, props, cx).into_any_element(),
-            _ => Self::render_unknown(component, props, cx).into_any_element(),
+            "div" => Self::render_div(component, props, variables, cx).into_any_element(),
+            "h1" => Self::render_h1(component, props, variables, cx).into_any_element(),
+            "h2" => Self::render_h2(component, props, variables, cx).into_any_element(),
+            "h3" => Self::render_h3(component, props, variables, cx).into_any_element(),
+            "button" => Self::render_button(component, props, variables, cx).into_any_element(),
+            "input" => Self::render_input(component, props, variables, cx).into_any_element(),
+            "text" => Self::render_text(component, props, variables, cx).into_any_element(),
+            "flex" => Self::render_flex(component, props, variables, cx).into_any_element(),
+            "column" => Self::render_column(component, props, variables, cx).into_any_element(),
+            "row" => Self::render_row(component, props, variables, cx).into_any_element(),
+            _ => Self::render_unknown(component, props, variables, cx).into_any_element(),
         }
     }
 
-    fn render_div(component: &UiComponent, props: &HashMap<String, UiValue>, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_div(component: &UiComponent, props: &HashMap<String, UiValue>, variables: &Variables, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let mut element = div();
 
         element = Self::apply_common_props(element, props);
-        element = Self::apply_children(element, &component.children, cx);
+        element = Self::apply_children(element, &component.children, variables, cx);
 
         element
     }
 
-    fn render_h1(component: &UiComponent, props: &HashMap<String, UiValue>, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_h1(component: &UiComponent, props: &HashMap<String, UiValue>, variables: &Variables, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let mut element = div().text_xl().font_bold();
 
         element = Self::apply_common_props(element, props);
-        element = Self::apply_children(element, &component.children, cx);
+        element = Self::apply_children(element, &component.children, variables, cx);
 
         element
     }
 
-    fn render_h2(component: &UiComponent, props: &HashMap<String, UiValue>, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_h2(component: &UiComponent, props: &HashMap<String, UiValue>, variables: &Variables, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let mut element = div().text_lg().font_bold();
 
         element = Self::apply_common_props(element, props);
-        element = Self::apply_children(element, &component.children, cx);
+        element = Self::apply_children(element, &component.children, variables, cx);
 
         element
     }
 
-    fn render_h3(component: &UiComponent, props: &HashMap<String, UiValue>, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_h3(component: &UiComponent, props: &HashMap<String, UiValue>, variables: &Variables, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let mut element = div().text_base().font_bold();
 
         element = Self::apply_common_props(element, props);
-        element = Self::apply_children(element, &component.children, cx);
+        element = Self::apply_children(element, &component.children, variables, cx);
 
         element
     }
 
-    fn render_button(component: &UiComponent, _props: &HashMap<String, UiValue>, _cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_button(component: &UiComponent, _props: &HashMap<String, UiValue>, _variables: &Variables, _cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let text = component.children.iter()
             .filter_map(|child| match child {
                 UiChild::Text(text) => Some(text.as_str()),
@@ -83,7 +87,7 @@ impl UiRenderer {
         Button::new("json_button").label(text)
     }
 
-    fn render_input(_component: &UiComponent, props: &HashMap<String, UiValue>, _cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_input(_component: &UiComponent, props: &HashMap<String, UiValue>, _variables: &Variables, _cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let placeholder = props.get("placeholder")
             .and_then(|v| v.as_string())
             .unwrap_or("")
@@ -98,7 +102,7 @@ impl UiRenderer {
             .child(placeholder)
     }
 
-    fn render_text(component: &UiComponent, props: &HashMap<String, UiValue>, _cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_text(component: &UiComponent, props: &HashMap<String, UiValue>, _variables: &Variables, _cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let content = props.get("content")
             .and_then(|v| v.as_string())
             .map(|s| s.to_string())
@@ -117,7 +121,7 @@ impl UiRenderer {
         element
     }
 
-    fn render_flex(component: &UiComponent, props: &HashMap<String, UiValue>, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_flex(component: &UiComponent, props: &HashMap<String, UiValue>, variables: &Variables, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let mut element = div().flex();
 
         let direction = props.get("direction")
@@ -130,37 +134,37 @@ impl UiRenderer {
         };
 
         element = Self::apply_common_props(element, props);
-        element = Self::apply_children(element, &component.children, cx);
+        element = Self::apply_children(element, &component.children, variables, cx);
 
         element
     }
 
-    fn render_column(component: &UiComponent, props: &HashMap<String, UiValue>, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_column(component: &UiComponent, props: &HashMap<String, UiValue>, variables: &Variables, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let mut element = div().flex().flex_col();
 
         element = Self::apply_common_props(element, props);
-        element = Self::apply_children(element, &component.children, cx);
+        element = Self::apply_children(element, &component.children, variables, cx);
 
         element
     }
 
-    fn render_row(component: &UiComponent, props: &HashMap<String, UiValue>, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_row(component: &UiComponent, props: &HashMap<String, UiValue>, variables: &Variables, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let mut element = div().flex().flex_row();
 
         element = Self::apply_common_props(element, props);
-        element = Self::apply_children(element, &component.children, cx);
+        element = Self::apply_children(element, &component.children, variables, cx);
 
         element
     }
 
-    fn render_unknown(component: &UiComponent, _props: &HashMap<String, UiValue>, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
+    fn render_unknown(component: &UiComponent, _props: &HashMap<String, UiValue>, variables: &Variables, cx: &mut Context<crate::json_ui::JsonCanvas>) -> impl IntoElement {
         let mut element = div()
             .border_1()
             .border_color(gpui::red())
             .p_2()
             .child(format!("Unknown component: {}", component.component_type));
 
-        element = Self::apply_children(element, &component.children, cx);
+        element = Self::apply_children(element, &component.children, variables, cx);
         element
     }
 
@@ -213,12 +217,13 @@ impl UiRenderer {
     fn apply_children<E: ParentElement>(
         mut element: E,
         children: &[UiChild],
+        variables: &Variables,
         cx: &mut Context<crate::json_ui::JsonCanvas>
     ) -> E {
         for child in children {
             match child {
                 UiChild::Component(component) => {
-                    element = element.child(Self::render_component_internal(component, &component.props, cx));
+                    element = element.child(Self::render_component(component, variables, cx));
                 }
                 UiChild::Text(text) => {
                     element = element.child(text.clone());
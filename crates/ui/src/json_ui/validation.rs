@@ -0,0 +1,228 @@
+use serde_json::Value;
+use std::fmt;
+use std::sync::OnceLock;
+
+// A hand-written validator for the subset of JSON Schema draft-07 used by the bundled schema:
+// `$ref` to local definitions, `type`, `enum`, `required`, `properties`,
+// `additionalProperties`, `items` and `anyOf`.
+const UI_SCHEMA: &str = include_str!("schema/ui-schema.json");
+
+fn ui_schema() -> &'static Value {
+    static SCHEMA: OnceLock<Value> = OnceLock::new();
+    SCHEMA.get_or_init(|| serde_json::from_str(UI_SCHEMA).expect("invalid bundled ui-schema.json"))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// The JSON pointer of the invalid value, e.g. `/children/0/type`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() { "/" } else { &self.path };
+        write!(f, "{}: {}", path, self.message)
+    }
+}
+
+/// Validate a component against the bundled UI schema.
+pub fn validate_component(value: &Value) -> Vec<ValidationError> {
+    let root = ui_schema();
+    let mut errors = Vec::new();
+    validate(value, root, root, "", &mut errors);
+    errors
+}
+
+fn validate(value: &Value, schema: &Value, root: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let schema = resolve_ref(schema, root);
+
+    if let Some(expected) = schema.get("type") {
+        if !type_matches(value, expected) {
+            errors.push(ValidationError {
+                path: path.to_string(),
+                message: format!("expected {}, found {}", expected_types(expected), type_name(value)),
+            });
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|v| v.as_array()) {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+            errors.push(ValidationError {
+                path: path.to_string(),
+                message: format!("{} is not one of {}", value, allowed.join(", ")),
+            });
+        }
+    }
+
+    if let Some(branches) = schema.get("anyOf").and_then(|v| v.as_array()) {
+        validate_any_of(value, branches, root, path, errors);
+    }
+
+    if let Value::Object(object) = value {
+        if let Some(required) = schema.get("required").and_then(|v| v.as_array()) {
+            for key in required.iter().filter_map(|v| v.as_str()) {
+                if !object.contains_key(key) {
+                    errors.push(ValidationError {
+                        path: path.to_string(),
+                        message: format!("missing required property `{}`", key),
+                    });
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(|v| v.as_object());
+        for (key, child) in object {
+            let child_path = format!("{}/{}", path, escape_pointer(key));
+            match properties.and_then(|properties| properties.get(key)) {
+                Some(child_schema) => validate(child, child_schema, root, &child_path, errors),
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => errors.push(ValidationError {
+                        path: child_path,
+                        message: format!("unknown property `{}`", key),
+                    }),
+                    Some(additional) if additional.is_object() => {
+                        validate(child, additional, root, &child_path, errors)
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (ix, item) in items.iter().enumerate() {
+            validate(item, item_schema, root, &format!("{}/{}", path, ix), errors);
+        }
+    }
+}
+
+fn validate_any_of(value: &Value, branches: &[Value], root: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    // Only report the errors of the branches of the same type, a type mismatch is not helpful.
+    let candidates: Vec<&Value> = branches
+        .iter()
+        .map(|branch| resolve_ref(branch, root))
+        .filter(|branch| match branch.get("type") {
+            Some(expected) => type_matches(value, expected),
+            None => true,
+        })
+        .collect();
+
+    let Some(first) = candidates.first() else {
+        let expected: Vec<String> = branches
+            .iter()
+            .filter_map(|branch| resolve_ref(branch, root).get("type"))
+            .map(expected_types)
+            .collect();
+        errors.push(ValidationError {
+            path: path.to_string(),
+            message: format!("expected {}, found {}", expected.join(" or "), type_name(value)),
+        });
+        return;
+    };
+
+    let mut first_errors = Vec::new();
+    validate(value, first, root, path, &mut first_errors);
+    if first_errors.is_empty() {
+        return;
+    }
+
+    for branch in candidates.iter().skip(1) {
+        let mut branch_errors = Vec::new();
+        validate(value, branch, root, path, &mut branch_errors);
+        if branch_errors.is_empty() {
+            return;
+        }
+    }
+
+    errors.extend(first_errors);
+}
+
+fn resolve_ref<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    let Some(reference) = schema.get("$ref").and_then(|v| v.as_str()) else {
+        return schema;
+    };
+
+    let Some(pointer) = reference.strip_prefix('#') else {
+        return schema;
+    };
+
+    match root.pointer(pointer) {
+        Some(resolved) => resolve_ref(resolved, root),
+        None => schema,
+    }
+}
+
+fn type_matches(value: &Value, expected: &Value) -> bool {
+    match expected {
+        Value::String(name) => is_type(value, name),
+        Value::Array(names) => names.iter().filter_map(|v| v.as_str()).any(|name| is_type(value, name)),
+        _ => true,
+    }
+}
+
+fn is_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "number" => value.is_number(),
+        name => type_name(value) == name,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn expected_types(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join(" or "),
+        Value::String(name) => name.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_component() {
+        let valid = json!({
+            "type": "div",
+            "props": { "padding": 16, "direction": "column" },
+            "children": [
+                "Hello",
+                { "type": "button", "children": ["Click"] }
+            ]
+        });
+        assert_eq!(validate_component(&valid), vec![]);
+
+        let invalid = json!({
+            "type": "div",
+            "children": [
+                { "type": "buton", "props": { "width": "100" } }
+            ]
+        });
+        let errors: Vec<String> = validate_component(&invalid)
+            .iter()
+            .map(|error| error.to_string())
+            .collect();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|error| error.starts_with("/children/0/type: \"buton\" is not one of")));
+        assert!(errors.contains(&"/children/0/props/width: expected number, found string".to_string()));
+    }
+}
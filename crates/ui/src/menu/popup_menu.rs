@@ -206,6 +206,24 @@ impl PopupMenu {
         self
     }
 
+    /// Add Menu Item calling the `handler` on click, instead of dispatching an action
+    pub fn menu_with_handler(
+        mut self,
+        label: impl Into<SharedString>,
+        disabled: bool,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.menu_items.push(PopupMenuItem::Item {
+            icon: None,
+            label: label.into(),
+            disabled,
+            action: None,
+            is_link: false,
+            handler: Rc::new(handler),
+        });
+        self
+    }
+
     /// Add Menu to open link
     pub fn link(self, label: impl Into<SharedString>, href: impl Into<String>) -> Self {
         self.link_with_disabled(label, href, false)
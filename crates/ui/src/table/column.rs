@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use gpui::{
     div, prelude::FluentBuilder, px, Bounds, Context, Edges, Empty, EntityId, IntoElement,
     ParentElement as _, Pixels, Render, SharedString, Styled as _, TextAlign, Window,
@@ -18,6 +20,9 @@ pub struct Column {
     pub resizable: bool,
     pub movable: bool,
     pub selectable: bool,
+    pub visible: bool,
+    pub filterable: bool,
+    pub header_group: Option<SharedString>,
 }
 
 impl Default for Column {
@@ -33,6 +38,9 @@ impl Default for Column {
             resizable: true,
             movable: true,
             selectable: true,
+            visible: true,
+            filterable: false,
+            header_group: None,
         }
     }
 }
@@ -129,10 +137,51 @@ impl Column {
         self.selectable = selectable;
         self
     }
+
+    /// Set whether the column is visible, default is true.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Set whether the column shows a filter input below the header, default is false.
+    pub fn filterable(mut self, filterable: bool) -> Self {
+        self.filterable = filterable;
+        self
+    }
+
+    /// Set the header group of the column, default is None.
+    ///
+    /// Adjacent columns with the same header group are rendered under one spanning header,
+    /// and they are kept together when moving columns.
+    pub fn header_group(mut self, header_group: impl Into<SharedString>) -> Self {
+        self.header_group = Some(header_group.into());
+        self
+    }
 }
 
 impl FluentBuilder for Column {}
 
+/// A spanning header over a range of columns, rendered in a row above the column headers.
+///
+/// See [`Table::set_col_group_spans`](super::Table::set_col_group_spans).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColGroupSpan {
+    pub label: SharedString,
+    /// The indices of the columns under the header.
+    pub col_range: Range<usize>,
+}
+
+impl ColGroupSpan {
+    /// Create a new spanning header over the columns in `col_range`.
+    pub fn new(label: impl Into<SharedString>, col_range: Range<usize>) -> Self {
+        Self {
+            label: label.into(),
+            col_range,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColumnFixed {
     Left,
@@ -154,6 +203,14 @@ impl ColGroup {
     pub(crate) fn is_resizable(&self) -> bool {
         self.column.resizable
     }
+
+    pub(crate) fn is_visible(&self) -> bool {
+        self.column.visible
+    }
+
+    pub(crate) fn is_filterable(&self) -> bool {
+        self.column.visible && self.column.filterable
+    }
 }
 
 #[derive(Clone)]
@@ -175,6 +232,26 @@ pub enum ColumnSort {
     Descending,
 }
 
+/// The value type of a column, used by [`super::Table::export_to_json_schema`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ColumnType {
+    #[default]
+    String,
+    Number,
+    Boolean,
+}
+
+impl ColumnType {
+    /// Returns the JSON Schema type name.
+    pub(crate) fn json_type(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+        }
+    }
+}
+
 impl Render for DragColumn {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         div()
@@ -200,3 +277,11 @@ impl Render for ResizeColumn {
         Empty
     }
 }
+
+#[derive(Clone)]
+pub(crate) struct ResizeColGroupSpan(pub (EntityId, usize));
+impl Render for ResizeColGroupSpan {
+    fn render(&mut self, _window: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use gpui::{App, SharedString, Window};
+
+use crate::popup_menu::PopupMenu;
+
+/// An item of the row context menu set by [`super::Table::set_context_menu_items`].
+#[derive(Clone)]
+pub struct ContextMenuItem {
+    pub label: SharedString,
+    pub enabled: bool,
+    /// Called with the index of the right-clicked row.
+    pub action: Arc<dyn Fn(usize, &mut Window, &mut App) + Send + Sync>,
+    separator: bool,
+}
+
+impl ContextMenuItem {
+    /// Create an enabled item calling the `action` with the row index on click.
+    pub fn new(
+        label: impl Into<SharedString>,
+        action: impl Fn(usize, &mut Window, &mut App) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            enabled: true,
+            action: Arc::new(action),
+            separator: false,
+        }
+    }
+
+    /// Create a separator between the items.
+    pub fn separator() -> Self {
+        Self {
+            label: SharedString::default(),
+            enabled: false,
+            action: Arc::new(|_, _, _| {}),
+            separator: true,
+        }
+    }
+
+    /// Set the item enabled, default is true.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Returns true if the item is a separator.
+    pub fn is_separator(&self) -> bool {
+        self.separator
+    }
+
+    /// Add the item for the row at `row_ix` to the `menu`.
+    pub(super) fn add_to(&self, row_ix: usize, menu: PopupMenu) -> PopupMenu {
+        if self.separator {
+            return menu.separator();
+        }
+
+        let action = self.action.clone();
+        menu.menu_with_handler(self.label.clone(), !self.enabled, move |window, cx| {
+            action(row_ix, window, cx)
+        })
+    }
+}
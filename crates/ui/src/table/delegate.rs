@@ -1,15 +1,15 @@
 use std::ops::Range;
 
 use gpui::{
-    div, App, Context, Div, InteractiveElement as _, IntoElement, ParentElement as _, Stateful,
-    Styled as _, Window,
+    div, App, Context, Div, InteractiveElement as _, IntoElement, ParentElement as _, Pixels,
+    SharedString, Stateful, Styled as _, Window,
 };
 
 use crate::{
     h_flex,
     popup_menu::PopupMenu,
-    table::{loading::Loading, Column, ColumnSort, Table},
-    ActiveTheme as _, Icon, IconName, Size,
+    table::{empty::TableEmpty, loading::Loading, Column, ColumnSort, ColumnType, Table},
+    Size,
 };
 
 #[allow(unused)]
@@ -34,6 +34,23 @@ pub trait TableDelegate: Sized + 'static {
     ) {
     }
 
+    /// Perform sort by multiple columns, the first column has the highest priority.
+    ///
+    /// This is called when a column header is shift-clicked, or by [`Table::sort_by_multiple_columns`].
+    /// An empty `sort_state` means the rows should be in the default order.
+    ///
+    /// Default to call [`TableDelegate::perform_sort`] with the first column only.
+    fn perform_multi_sort(
+        &mut self,
+        sort_state: &[(usize, ColumnSort)],
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) {
+        if let Some(&(col_ix, sort)) = sort_state.first() {
+            self.perform_sort(col_ix, sort, window, cx);
+        }
+    }
+
     /// Render the header cell at the given column index, default to the column name.
     fn render_th(
         &self,
@@ -56,7 +73,52 @@ pub trait TableDelegate: Sized + 'static {
         h_flex().id(("row", row_ix))
     }
 
+    /// Return the number of rows at the top that stay fixed while the body scrolls, default is 0.
+    fn pinned_rows_count(&self, cx: &App) -> usize {
+        0
+    }
+
+    /// Return true if the rows are grouped by [`TableDelegate::group_for_row`], default is false.
+    ///
+    /// The groups are computed again when the rows count changes, after [`Table::delegate_mut`]
+    /// or [`Table::refresh`].
+    fn has_row_groups(&self, cx: &App) -> bool {
+        false
+    }
+
+    /// Return the group key of the row at the given index, default is `None` (no grouping).
+    ///
+    /// Consecutive rows with the same key are rendered under one collapsible group header,
+    /// only called when [`TableDelegate::has_row_groups`] returns true.
+    fn group_for_row(&self, row_ix: usize, cx: &App) -> Option<SharedString> {
+        None
+    }
+
+    /// Render the content of the group header, default to the group key.
+    fn render_group_header(
+        &self,
+        group: &SharedString,
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) -> impl IntoElement {
+        div().child(group.clone())
+    }
+
+    /// Render the content of the spanning header of a column header group, default to the group name.
+    ///
+    /// See [`Column::header_group`].
+    fn render_header_group(
+        &self,
+        group: &SharedString,
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) -> impl IntoElement {
+        div().child(group.clone())
+    }
+
     /// Render the context menu for the row at the given row index.
+    ///
+    /// Not called when the items are set with [`super::Table::set_context_menu_items`].
     fn context_menu(&self, row_ix: usize, menu: PopupMenu, window: &Window, cx: &App) -> PopupMenu {
         menu
     }
@@ -70,6 +132,72 @@ pub trait TableDelegate: Sized + 'static {
         cx: &mut Context<Table<Self>>,
     ) -> impl IntoElement;
 
+    /// Return the plain text value of the cell for exporting, default is `None`.
+    ///
+    /// Implement this to support [`Table::export_to_csv`] and [`Table::export_to_clipboard`].
+    fn export_cell(&self, row_ix: usize, col_ix: usize, cx: &App) -> Option<String> {
+        None
+    }
+
+    /// Return the header text of the column for exporting, default to the column name.
+    fn export_header(&self, col_ix: usize, cx: &App) -> String {
+        self.column(col_ix, cx).name.to_string()
+    }
+
+    /// Return the width needed to show the content of the cell, including its paddings, default
+    /// is `None`.
+    ///
+    /// Implement this to support [`Table::auto_fit_columns`].
+    fn measure_cell(&self, row_ix: usize, col_ix: usize, cx: &App) -> Option<Pixels> {
+        None
+    }
+
+    /// Return the width needed to show the header of the column, default is `None`.
+    fn measure_header(&self, col_ix: usize, cx: &App) -> Option<Pixels> {
+        None
+    }
+
+    /// Return the value type of the column, used by [`Table::export_to_json_schema`], default is
+    /// [`ColumnType::String`].
+    fn column_type(&self, col_ix: usize, cx: &App) -> ColumnType {
+        ColumnType::String
+    }
+
+    /// Return true if the cell can be edited inline (double-click or `F2`), default is false.
+    fn can_edit_cell(&self, row_ix: usize, col_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Return the value the inline editor of the cell starts with, default to
+    /// [`TableDelegate::export_cell`].
+    fn edit_value(&self, row_ix: usize, col_ix: usize, cx: &App) -> Option<String> {
+        self.export_cell(row_ix, col_ix, cx)
+    }
+
+    /// Apply the value of a committed inline cell edit, only called when the value was changed.
+    fn commit_edit(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        new_value: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) {
+    }
+
+    /// Filter the data by the query of the column filter input.
+    ///
+    /// This is called when the filter text of a [`Column::filterable`] column changes,
+    /// the delegate is responsible for filtering its data source.
+    fn filter_column(
+        &mut self,
+        col_ix: usize,
+        query: &str,
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) {
+    }
+
     /// Move the column at the given `col_ix` to insert before the column at the given `to_ix`.
     fn move_column(
         &mut self,
@@ -80,14 +208,42 @@ pub trait TableDelegate: Sized + 'static {
     ) {
     }
 
+    /// Move the row at the given `row_ix` to the given `to_ix`, see [`Table::row_movable`].
+    ///
+    /// The `to_ix` is the index of the row after the move.
+    fn move_row(
+        &mut self,
+        row_ix: usize,
+        to_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) {
+    }
+
     /// Return a Element to show when table is empty.
+    ///
+    /// The default shows a centered icon with the message and action set with [`Table::empty_icon`],
+    /// [`Table::empty_message`] and [`Table::empty_action`], they are ignored when this is implemented.
     fn render_empty(&self, window: &mut Window, cx: &mut Context<Table<Self>>) -> impl IntoElement {
-        h_flex()
-            .size_full()
-            .justify_center()
-            .text_color(cx.theme().muted_foreground.opacity(0.6))
-            .child(Icon::new(IconName::Inbox).size_12())
-            .into_any_element()
+        TableEmpty::new(cx.weak_entity())
+    }
+
+    /// Render the summary row fixed at the bottom of the table body, default is `None`.
+    ///
+    /// The row scrolls horizontally with the body, so lay out its cells with the column widths,
+    /// and keep them in sync with [`crate::table::TableEvent::ColumnWidthsChanged`] if the
+    /// columns are resizable.
+    fn render_summary_row(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) -> Option<impl IntoElement> {
+        None::<Div>
+    }
+
+    /// Return the height of the summary row, default is `None` to use the table row height.
+    fn summary_row_height(&self) -> Option<Pixels> {
+        None
     }
 
     /// Return true to show the loading view.
@@ -123,6 +279,23 @@ pub trait TableDelegate: Sized + 'static {
         20
     }
 
+    /// Return the total number of rows of all pages in [`PaginationMode::Pages`] mode.
+    ///
+    /// [`PaginationMode::Pages`]: super::PaginationMode::Pages
+    ///
+    /// Default is `None`, then the pagination bar only shows the prev/next buttons.
+    fn total_rows_count(&self, cx: &App) -> Option<usize> {
+        None
+    }
+
+    /// Load the rows of the page at the given zero-based index in [`PaginationMode::Pages`] mode.
+    ///
+    /// [`PaginationMode::Pages`]: super::PaginationMode::Pages
+    ///
+    /// After this, [`TableDelegate::rows_count`] and [`TableDelegate::render_td`] should
+    /// refer to the rows of the new page.
+    fn go_to_page(&mut self, page: usize, window: &mut Window, cx: &mut Context<Table<Self>>) {}
+
     /// Load more data when the table is scrolled to the bottom.
     ///
     /// This will performed in a background task.
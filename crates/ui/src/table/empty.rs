@@ -0,0 +1,79 @@
+use std::rc::Rc;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, App, IntoElement, ParentElement as _, RenderOnce,
+    SharedString, Styled as _, WeakEntity, Window,
+};
+
+use crate::{button::Button, v_flex, ActiveTheme as _, Icon, IconName, Sizable as _};
+
+use super::{Table, TableDelegate};
+
+/// The content of the default empty state, set with [`Table::empty_icon`],
+/// [`Table::empty_message`] and [`Table::empty_action`].
+#[derive(Clone)]
+pub(crate) struct EmptyState {
+    pub(crate) icon: IconName,
+    pub(crate) message: Option<SharedString>,
+    pub(crate) action: Option<(SharedString, Rc<dyn Fn(&mut Window, &mut App)>)>,
+}
+
+impl Default for EmptyState {
+    fn default() -> Self {
+        Self {
+            icon: IconName::Inbox,
+            message: None,
+            action: None,
+        }
+    }
+}
+
+/// The default [`TableDelegate::render_empty`], a centered icon with the optional message and action.
+#[derive(IntoElement)]
+pub(crate) struct TableEmpty<D: TableDelegate> {
+    table: WeakEntity<Table<D>>,
+}
+
+impl<D: TableDelegate> TableEmpty<D> {
+    pub(crate) fn new(table: WeakEntity<Table<D>>) -> Self {
+        Self { table }
+    }
+}
+
+impl<D: TableDelegate> RenderOnce for TableEmpty<D> {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = self
+            .table
+            .upgrade()
+            .map(|table| table.read(cx).empty_state.clone())
+            .unwrap_or_default();
+
+        v_flex()
+            .size_full()
+            .justify_center()
+            .items_center()
+            .gap_2()
+            .child(
+                Icon::new(state.icon)
+                    .size_12()
+                    .text_color(cx.theme().muted_foreground.opacity(0.6)),
+            )
+            .when_some(state.message, |this, message| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(message),
+                )
+            })
+            .when_some(state.action, |this, (label, handler)| {
+                this.child(
+                    Button::new("empty-action")
+                        .small()
+                        .outline()
+                        .label(label)
+                        .on_click(move |_, window, cx| handler(window, cx)),
+                )
+            })
+    }
+}
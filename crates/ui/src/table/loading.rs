@@ -1,5 +1,16 @@
+use std::time::{Duration, Instant};
+
 use crate::{h_flex, skeleton::Skeleton, v_flex, ActiveTheme, Size};
-use gpui::{prelude::FluentBuilder as _, IntoElement, ParentElement as _, RenderOnce, Styled};
+use gpui::{
+    canvas, fill, linear_color_stop, linear_gradient, point, prelude::FluentBuilder as _, px,
+    Bounds, ContentMask, Hsla, IntoElement, ParentElement as _, PathBuilder, Pixels, RenderOnce,
+    Styled, Window,
+};
+
+/// The duration of one shimmer sweep across the skeleton.
+const SHIMMER_DURATION: Duration = Duration::from_millis(1500);
+/// The width of the shimmer highlight.
+const SHIMMER_WIDTH: Pixels = px(240.);
 
 #[derive(IntoElement)]
 pub struct Loading {
@@ -87,3 +98,118 @@ impl RenderOnce for Loading {
             .child(LoadingRow::row().size(self.size))
     }
 }
+
+/// The built-in loading skeleton of the table, see [`super::Table::skeleton`].
+///
+/// Renders placeholder bars with the current column widths, with a shimmer sweeping over them.
+#[derive(IntoElement)]
+pub(crate) struct TableSkeleton {
+    col_widths: Vec<Pixels>,
+    rows_count: usize,
+    size: Size,
+    row_height: Pixels,
+    started_at: Instant,
+}
+
+impl TableSkeleton {
+    pub(crate) fn new(
+        col_widths: Vec<Pixels>,
+        rows_count: usize,
+        size: Size,
+        row_height: Pixels,
+        started_at: Instant,
+    ) -> Self {
+        Self {
+            col_widths,
+            rows_count,
+            size,
+            row_height,
+            started_at,
+        }
+    }
+}
+
+impl RenderOnce for TableSkeleton {
+    fn render(self, _: &mut Window, cx: &mut gpui::App) -> impl IntoElement {
+        let head_bg = cx.theme().table_head;
+        let color = cx.theme().skeleton;
+        let highlight = cx.theme().background.opacity(0.6);
+
+        canvas(
+            |_, _, _| {},
+            move |bounds, _, window, _| {
+                let row_height = self.row_height;
+                let paddings = self.size.table_cell_padding();
+                let bar_height = row_height * 0.5;
+
+                let progress = (self.started_at.elapsed().as_secs_f32()
+                    / SHIMMER_DURATION.as_secs_f32())
+                .fract();
+                let shimmer_left =
+                    bounds.left() - SHIMMER_WIDTH + (bounds.size.width + SHIMMER_WIDTH) * progress;
+
+                window.paint_quad(fill(
+                    Bounds::new(bounds.origin, gpui::size(bounds.size.width, row_height)),
+                    head_bg,
+                ));
+
+                for row_ix in 0..self.rows_count {
+                    let top = bounds.top() + row_height * row_ix as f32;
+                    let color = if row_ix == 0 {
+                        color.opacity(0.5)
+                    } else {
+                        color
+                    };
+
+                    let mut left = bounds.left();
+                    for width in &self.col_widths {
+                        let bar = Bounds::new(
+                            point(left + paddings.left, top + (row_height - bar_height) / 2.),
+                            gpui::size(*width - paddings.left - paddings.right, bar_height),
+                        );
+                        left += *width;
+                        if bar.size.width <= px(0.) {
+                            continue;
+                        }
+
+                        window.paint_quad(fill(bar, color).corner_radii(px(4.)));
+                        window.with_content_mask(Some(ContentMask { bounds: bar }), |window| {
+                            paint_shimmer(shimmer_left, bar, highlight, window);
+                        });
+                    }
+                }
+
+                window.request_animation_frame();
+            },
+        )
+        .size_full()
+    }
+}
+
+/// Paint the shimmer highlight starting at `left`, fading in and out horizontally.
+fn paint_shimmer(left: Pixels, bounds: Bounds<Pixels>, highlight: Hsla, window: &mut Window) {
+    let half_width = SHIMMER_WIDTH / 2.;
+    let transparent = highlight.opacity(0.);
+
+    for (ix, (from, to)) in [(transparent, highlight), (highlight, transparent)]
+        .into_iter()
+        .enumerate()
+    {
+        let left = left + half_width * ix as f32;
+        let right = left + half_width;
+
+        let mut builder = PathBuilder::fill();
+        builder.move_to(point(left, bounds.top()));
+        builder.line_to(point(right, bounds.top()));
+        builder.line_to(point(right, bounds.bottom()));
+        builder.line_to(point(left, bounds.bottom()));
+        builder.close();
+
+        if let Ok(path) = builder.build() {
+            window.paint_path(
+                path,
+                linear_gradient(90., linear_color_stop(from, 0.), linear_color_stop(to, 1.)),
+            );
+        }
+    }
+}
@@ -1,30 +1,64 @@
-use std::{ops::Range, rc::Rc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
 use crate::{
     actions::{Cancel, SelectNext, SelectPrev},
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
     context_menu::ContextMenuExt,
     h_flex,
+    input::{InputEvent, InputState, TextInput},
     popup_menu::PopupMenu,
     scroll::{self, ScrollableMask, Scrollbar, ScrollbarState},
-    v_flex, ActiveTheme, Icon, IconName, Sizable, Size, StyleSized as _, StyledExt,
-    VirtualListScrollHandle,
+    v_flex, ActiveTheme, Disableable as _, Icon, IconName, Selectable as _, Sizable, Size,
+    StyleSized as _, StyledExt, VirtualListScrollHandle,
 };
 use gpui::{
-    actions, canvas, div, prelude::FluentBuilder, px, uniform_list, App, AppContext, Axis, Bounds,
-    Context, Div, DragMoveEvent, Edges, EventEmitter, FocusHandle, Focusable, InteractiveElement,
-    IntoElement, KeyBinding, ListSizingBehavior, MouseButton, MouseDownEvent, ParentElement,
-    Pixels, Point, Render, ScrollStrategy, ScrollWheelEvent, SharedString,
-    StatefulInteractiveElement as _, Styled, Task, UniformListScrollHandle, Window,
+    actions, anchored, canvas, deferred, div, prelude::FluentBuilder, px, uniform_list, Action,
+    AnyElement, App, AppContext, Axis, Bounds, ClickEvent, ClipboardItem, Context, DismissEvent,
+    Div, DragMoveEvent, Edges, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, KeyBinding, ListSizingBehavior, MouseButton, MouseDownEvent,
+    ParentElement, Pixels, Point, Render, ScrollStrategy, ScrollWheelEvent, SharedString,
+    StatefulInteractiveElement as _, Styled, Subscription, Task, UniformListScrollHandle, Window,
 };
+use indexmap::IndexSet;
+use rust_i18n::t;
 
 mod column;
+mod context_menu;
 mod delegate;
+mod empty;
 mod loading;
 
+use empty::EmptyState;
+use loading::TableSkeleton;
+
 pub use column::*;
+pub use context_menu::ContextMenuItem;
 pub use delegate::*;
 
-actions!(table, [SelectPrevColumn, SelectNextColumn]);
+actions!(
+    table,
+    [
+        SelectPrevColumn,
+        SelectNextColumn,
+        ExportCsv,
+        CopyToClipboard,
+        ShowColumnChooser,
+        EditNextCell,
+        StartCellEdit,
+        StopCellEdit
+    ]
+);
+
+/// Hide the column at the given index.
+#[derive(Action, Debug, Clone, Copy, PartialEq, Eq)]
+#[action(namespace = table, no_json)]
+pub struct HideColumn(pub usize);
 
 pub fn init(cx: &mut App) {
     let context = Some("Table");
@@ -34,6 +68,17 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("down", SelectNext, context),
         KeyBinding::new("left", SelectPrevColumn, context),
         KeyBinding::new("right", SelectNextColumn, context),
+        KeyBinding::new("f2", StartCellEdit, context),
+        KeyBinding::new("tab", EditNextCell, Some("Table > Input")),
+        KeyBinding::new("escape", StopCellEdit, Some("Table > Input")),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-shift-c", ExportCsv, context),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-c", ExportCsv, context),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-c", CopyToClipboard, context),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-c", CopyToClipboard, context),
     ]);
 }
 
@@ -47,11 +92,50 @@ enum SelectionState {
 pub enum TableEvent {
     /// Single click or move to selected row.
     SelectRow(usize),
+    /// The selected rows changed in multi-select mode, in selection order.
+    SelectRows(Vec<usize>),
     /// Double click on the row.
     DoubleClickedRow(usize),
     SelectColumn(usize),
     ColumnWidthsChanged(Vec<Pixels>),
     MoveColumn(usize, usize),
+    /// A row was dragged to a new position, with the row index before and after the move.
+    MoveRow(usize, usize),
+    /// The user requested to export the table data, use [`Table::export_to_csv`] to get the content.
+    ExportRequested,
+    /// An inline cell edit changed the value, with the row, column and the new value.
+    CellEditCommitted(usize, usize, SharedString),
+    /// The filter text of the column changed, with the column index and the query.
+    FilterChanged(usize, SharedString),
+    /// A pinned row was clicked, see [`TableDelegate::pinned_rows_count`].
+    PinnedRowClicked(usize),
+    /// A group header was clicked, with the group key and whether it is now expanded.
+    GroupToggled(SharedString, bool),
+    /// The user requested to choose the visible columns, use [`Table::set_column_visible`] to apply.
+    ShowColumnChooser,
+    /// The current page changed in [`PaginationMode::Pages`] mode, with the zero-based page index.
+    PageChanged(usize),
+    /// The table data exported by [`Table::export_to_json_in_background`].
+    ExportedJson(serde_json::Value),
+}
+
+/// How the table loads rows beyond the first screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PaginationMode {
+    /// Scroll to the bottom to call [`TableDelegate::load_more`], this is the default.
+    #[default]
+    Infinite,
+    /// Show one page of rows at a time, with a pagination bar below the table body.
+    ///
+    /// The delegate only provides the rows of the current page, see [`TableDelegate::go_to_page`].
+    Pages { page_size: usize },
+}
+
+/// A row in the rendered list, either a group header or a delegate row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DisplayRow {
+    GroupHeader(SharedString),
+    Row(usize),
 }
 
 /// The visible range of the rows and columns.
@@ -75,6 +159,33 @@ impl VisibleRangeState {
     }
 }
 
+#[derive(Clone)]
+pub(crate) struct DragRow {
+    pub(crate) entity_id: EntityId,
+    pub(crate) row_ix: usize,
+}
+
+impl Render for DragRow {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_4()
+            .py_1()
+            .bg(cx.theme().table_head)
+            .text_color(cx.theme().muted_foreground)
+            .opacity(0.9)
+            .border_1()
+            .border_color(cx.theme().border)
+            .shadow_md()
+            .child(format!("Row {}", self.row_ix + 1))
+    }
+}
+
+/// The width limits of a column when resizing.
+const MIN_COL_WIDTH: Pixels = px(10.0);
+const MAX_COL_WIDTH: Pixels = px(1200.0);
+/// The number of rows measured by [`Table::auto_fit_columns`].
+const AUTO_FIT_SAMPLE_ROWS: usize = 50;
+
 pub struct Table<D: TableDelegate> {
     focus_handle: FocusHandle,
     delegate: D,
@@ -84,6 +195,10 @@ pub struct Table<D: TableDelegate> {
     fixed_head_cols_bounds: Bounds<Pixels>,
 
     col_groups: Vec<ColGroup>,
+    /// The spanning headers above the columns, see [`Table::set_col_group_spans`].
+    col_group_spans: Vec<ColGroupSpan>,
+    /// The sorted columns by priority, see [`Table::sort_by_multiple_columns`].
+    sort_state: Vec<(usize, ColumnSort)>,
 
     /// Whether the table can loop selection, default is true.
     ///
@@ -93,14 +208,24 @@ pub struct Table<D: TableDelegate> {
     pub col_selectable: bool,
     /// Whether the table can select row.
     pub row_selectable: bool,
+    /// Whether the table can select multiple rows with shift-click and ctrl/cmd-click.
+    pub multi_select: bool,
+    /// Whether to show a checkbox column before the first column to select rows.
+    pub checkbox_column: bool,
     /// Whether the table can sort.
     pub sortable: bool,
     /// Whether the table can resize columns.
     pub col_resizable: bool,
     /// Whether the table can move columns.
     pub col_movable: bool,
+    /// Whether the table can move rows by drag and drop.
+    pub row_movable: bool,
     /// Enable/disable fixed columns feature.
     pub col_fixed: bool,
+    /// The pagination mode, default is [`PaginationMode::Infinite`].
+    pub pagination: PaginationMode,
+    /// Whether to show the built-in skeleton when loading, instead of [`TableDelegate::render_loading`].
+    pub skeleton: bool,
 
     pub vertical_scroll_handle: UniformListScrollHandle,
     pub vertical_scroll_state: ScrollbarState,
@@ -109,12 +234,50 @@ pub struct Table<D: TableDelegate> {
 
     scrollbar_visible: Edges<bool>,
     selected_row: Option<usize>,
+    /// The selected rows in multi-select mode, in selection order.
+    selected_rows: IndexSet<usize>,
+    /// The row that shift-click extends the selection from.
+    anchor_row: Option<usize>,
     selection_state: SelectionState,
     right_clicked_row: Option<usize>,
+    right_clicked_col: Option<usize>,
     selected_col: Option<usize>,
+    /// The row context menu items, replacing [`TableDelegate::context_menu`] when set.
+    context_menu_items: Option<Vec<ContextMenuItem>>,
+    /// The row context menu opened by [`Table::show_context_menu`].
+    keyboard_context_menu: Option<(Entity<PopupMenu>, Subscription)>,
+    /// The bounds of the right-clicked row, to anchor the keyboard context menu.
+    right_clicked_row_bounds: Bounds<Pixels>,
+
+    /// The collapsed row groups, see [`TableDelegate::group_for_row`].
+    collapsed_groups: HashSet<SharedString>,
+    /// The number of rows pinned at the top, updated on each render.
+    pinned_rows_count: usize,
+    /// The height of the summary row, `None` if there is no summary row, updated on each render.
+    summary_row_height: Option<Pixels>,
+    /// The zero-based current page in [`PaginationMode::Pages`] mode.
+    current_page: usize,
+    /// The rendered rows when the rows are grouped, empty when there are no groups.
+    display_rows: Vec<DisplayRow>,
+    /// The index in `display_rows` of each delegate row, `None` if the row is collapsed.
+    row_display_ixs: Vec<Option<usize>>,
+    /// The rows count and pinned rows count the `display_rows` were built for, `None` to rebuild them.
+    display_rows_key: Option<(usize, usize)>,
+
+    /// The cell being edited inline, with its row, column and input state.
+    editing_cell: Option<(usize, usize, Entity<InputState>, SharedString)>,
+    _editing_subscription: Option<Subscription>,
+
+    /// The filter inputs of the filterable columns, keyed by column index.
+    filter_inputs: HashMap<usize, Entity<InputState>>,
+    /// The current filter query of each column.
+    filters: HashMap<usize, SharedString>,
+    _filter_subscriptions: Vec<Subscription>,
 
     /// The column index that is being resized.
     resizing_col: Option<usize>,
+    /// The insertion index of the row being dragged, between `0` and the rows count.
+    row_drop_ix: Option<usize>,
 
     /// Set stripe style of the table.
     stripe: bool,
@@ -122,8 +285,16 @@ pub struct Table<D: TableDelegate> {
     border: bool,
     /// The cell size of the table.
     size: Size,
+    /// The height of the rows and the header, overrides the height of the `size`.
+    custom_row_height: Option<Pixels>,
+    /// The content of the default [`TableDelegate::render_empty`].
+    empty_state: EmptyState,
     /// The visible range of the rows and columns.
     visible_range: VisibleRangeState,
+    /// The time the delegate started loading, to animate the skeleton shimmer.
+    loading_started_at: Option<Instant>,
+    /// The time the delegate finished loading, to fade out the skeleton.
+    loaded_at: Option<Instant>,
 
     _measure: Vec<Duration>,
     _load_more_task: Task<()>,
@@ -138,29 +309,59 @@ where
             focus_handle: cx.focus_handle(),
             delegate,
             col_groups: Vec::new(),
+            col_group_spans: Vec::new(),
+            sort_state: Vec::new(),
             horizontal_scroll_handle: VirtualListScrollHandle::new(),
             vertical_scroll_handle: UniformListScrollHandle::new(),
             vertical_scroll_state: ScrollbarState::default(),
             horizontal_scroll_state: ScrollbarState::default(),
             selection_state: SelectionState::Row,
             selected_row: None,
+            selected_rows: IndexSet::new(),
+            anchor_row: None,
             right_clicked_row: None,
+            right_clicked_col: None,
             selected_col: None,
+            context_menu_items: None,
+            keyboard_context_menu: None,
+            right_clicked_row_bounds: Bounds::default(),
+            editing_cell: None,
+            _editing_subscription: None,
+            filter_inputs: HashMap::new(),
+            filters: HashMap::new(),
+            _filter_subscriptions: Vec::new(),
             resizing_col: None,
+            row_drop_ix: None,
+            collapsed_groups: HashSet::new(),
+            pinned_rows_count: 0,
+            summary_row_height: None,
+            current_page: 0,
+            display_rows: Vec::new(),
+            display_rows_key: None,
+            row_display_ixs: Vec::new(),
             bounds: Bounds::default(),
             fixed_head_cols_bounds: Bounds::default(),
             stripe: false,
             border: true,
             size: Size::default(),
+            custom_row_height: None,
+            empty_state: EmptyState::default(),
             scrollbar_visible: Edges::all(true),
             visible_range: VisibleRangeState::default(),
+            loading_started_at: None,
+            loaded_at: None,
             loop_selection: true,
             col_selectable: true,
             row_selectable: true,
+            multi_select: false,
+            checkbox_column: false,
             sortable: true,
             col_movable: true,
+            row_movable: false,
             col_resizable: true,
             col_fixed: true,
+            pagination: PaginationMode::default(),
+            skeleton: true,
             _load_more_task: Task::ready(()),
             _measure: Vec::new(),
         };
@@ -174,6 +375,7 @@ where
     }
 
     pub fn delegate_mut(&mut self) -> &mut D {
+        self.display_rows_key = None;
         &mut self.delegate
     }
 
@@ -206,6 +408,14 @@ where
         self
     }
 
+    /// Set to enable/disable row movable by drag and drop, default to false.
+    ///
+    /// The rows are moved by [`TableDelegate::move_row`].
+    pub fn row_movable(mut self, row_movable: bool) -> Self {
+        self.row_movable = row_movable;
+        self
+    }
+
     /// Set to enable/disable column resizable, default to true.
     pub fn col_resizable(mut self, col_resizable: bool) -> Self {
         self.col_resizable = col_resizable;
@@ -224,15 +434,96 @@ where
         self
     }
 
+    /// Set to enable/disable multiple row selection, default false
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    /// Set to show a checkbox column to select rows, default false
+    ///
+    /// The checkbox column is managed by the table and always rendered first, it is not
+    /// sortable, resizable or movable. Enabling it also enables [`Table::multi_select`].
+    pub fn with_checkbox_column(mut self, checkbox_column: bool) -> Self {
+        self.checkbox_column = checkbox_column;
+        if checkbox_column {
+            self.multi_select = true;
+        }
+        self
+    }
+
     /// Set to enable/disable column selectable, default true
     pub fn col_selectable(mut self, col_selectable: bool) -> Self {
         self.col_selectable = col_selectable;
         self
     }
 
-    /// Set the size to the table.
+    /// Set the pagination mode, default is [`PaginationMode::Infinite`].
+    pub fn pagination(mut self, pagination: PaginationMode) -> Self {
+        self.pagination = pagination;
+        self
+    }
+
+    /// Set to show the built-in skeleton with shimmer rows when loading, default true
+    ///
+    /// Set to false to render [`TableDelegate::render_loading`] instead.
+    pub fn skeleton(mut self, skeleton: bool) -> Self {
+        self.skeleton = skeleton;
+        self
+    }
+
+    /// Set the pagination mode, and go back to the first page.
+    pub fn set_pagination(&mut self, pagination: PaginationMode, cx: &mut Context<Self>) {
+        self.pagination = pagination;
+        self.current_page = 0;
+        cx.notify();
+    }
+
+    /// Returns the zero-based current page, always 0 in [`PaginationMode::Infinite`] mode.
+    pub fn current_page(&self) -> usize {
+        self.current_page
+    }
+
+    /// Returns the number of pages, or `None` if it is unknown.
+    ///
+    /// This requires [`TableDelegate::total_rows_count`] in [`PaginationMode::Pages`] mode.
+    pub fn pages_count(&self, cx: &App) -> Option<usize> {
+        let PaginationMode::Pages { page_size } = self.pagination else {
+            return None;
+        };
+        let total_rows_count = self.delegate.total_rows_count(cx)?;
+        Some(total_rows_count.div_ceil(page_size.max(1)).max(1))
+    }
+
+    /// Go to the page at the given zero-based index, and call [`TableDelegate::go_to_page`].
+    ///
+    /// The selection is cleared, because the row indexes are relative to the page.
+    pub fn go_to_page(&mut self, page: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if !matches!(self.pagination, PaginationMode::Pages { .. }) {
+            return;
+        }
+
+        let page = match self.pages_count(cx) {
+            Some(pages_count) => page.min(pages_count - 1),
+            None => page,
+        };
+        if page == self.current_page {
+            return;
+        }
+
+        self.current_page = page;
+        self.clear_selection(cx);
+        self.delegate_mut().go_to_page(page, window, cx);
+        self.vertical_scroll_handle
+            .scroll_to_item(0, ScrollStrategy::Top);
+        cx.emit(TableEvent::PageChanged(page));
+        cx.notify();
+    }
+
+    /// Set the size to the table, this clears the custom row height.
     pub fn set_size(&mut self, size: Size, cx: &mut Context<Self>) {
         self.size = size;
+        self.custom_row_height = None;
         cx.notify();
     }
 
@@ -241,6 +532,53 @@ where
         self.size
     }
 
+    /// Set a uniform height of the rows and the header instead of the height of the size,
+    /// e.g. for a compact table in a panel.
+    pub fn with_row_height(mut self, height: Pixels) -> Self {
+        self.custom_row_height = Some(height);
+        self
+    }
+
+    /// Set a uniform height of the rows and the header, see [`Self::with_row_height`].
+    pub fn set_row_height(&mut self, height: Pixels, cx: &mut Context<Self>) {
+        self.custom_row_height = Some(height);
+        cx.notify();
+    }
+
+    /// Get the height of the rows of the table.
+    pub fn row_height(&self) -> Pixels {
+        self.custom_row_height
+            .unwrap_or(self.size.table_row_height())
+    }
+
+    /// Set the icon of the default empty state, default is [`IconName::Inbox`].
+    ///
+    /// Ignored when the delegate implements [`TableDelegate::render_empty`].
+    pub fn empty_icon(mut self, icon: IconName) -> Self {
+        self.empty_state.icon = icon;
+        self
+    }
+
+    /// Set the message shown below the icon of the default empty state.
+    ///
+    /// Ignored when the delegate implements [`TableDelegate::render_empty`].
+    pub fn empty_message(mut self, message: impl Into<SharedString>) -> Self {
+        self.empty_state.message = Some(message.into());
+        self
+    }
+
+    /// Add a button below the message of the default empty state, e.g. to create the first row.
+    ///
+    /// Ignored when the delegate implements [`TableDelegate::render_empty`].
+    pub fn empty_action(
+        mut self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.empty_state.action = Some((label.into(), Rc::new(handler)));
+        self
+    }
+
     /// Set scrollbar visibility.
     pub fn scrollbar_visible(mut self, vertical: bool, horizontal: bool) -> Self {
         self.scrollbar_visible = Edges {
@@ -253,6 +591,7 @@ where
 
     /// When we update columns or rows, we need to refresh the table.
     pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        self.display_rows_key = None;
         self.prepare_col_groups(cx);
     }
 
@@ -267,6 +606,15 @@ where
                 }
             })
             .collect();
+        self.sort_state = self
+            .col_groups
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, col_group)| match col_group.column.sort {
+                Some(ColumnSort::Default) | None => None,
+                Some(sort) => Some((ix, sort)),
+            })
+            .collect();
         cx.notify();
     }
 
@@ -281,199 +629,1031 @@ where
             .count()
     }
 
-    /// Scroll to the row at the given index.
-    pub fn scroll_to_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
-        self.vertical_scroll_handle
-            .scroll_to_item(row_ix, ScrollStrategy::Top);
-        cx.notify();
+    /// Returns the current widths of all columns, including hidden columns.
+    pub fn column_widths(&self) -> Vec<Pixels> {
+        self.col_groups.iter().map(|g| g.width).collect()
     }
 
-    // Scroll to the column at the given index.
-    pub fn scroll_to_col(&mut self, col_ix: usize, cx: &mut Context<Self>) {
-        let col_ix = col_ix.saturating_sub(self.fixed_left_cols_count());
-
-        self.horizontal_scroll_handle
-            .scroll_to_item(col_ix, ScrollStrategy::Top);
+    /// Restore column widths saved from [`Table::column_widths`].
+    ///
+    /// Widths are applied by index, extra widths or missing columns are ignored, so saved
+    /// widths remain usable after columns are added or removed at the end.
+    ///
+    /// To persist widths across sessions, serialize [`Table::column_widths`] (e.g. with
+    /// `serde_json`) when receiving [`TableEvent::ColumnWidthsChanged`], and call this method
+    /// with the deserialized widths after creating the table.
+    pub fn restore_column_widths(&mut self, widths: &[Pixels], cx: &mut Context<Self>) {
+        apply_column_widths(&mut self.col_groups, widths);
         cx.notify();
     }
 
-    /// Returns the selected row index.
-    pub fn selected_row(&self) -> Option<usize> {
-        self.selected_row
-    }
-
-    /// Sets the selected row to the given index.
-    pub fn set_selected_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
-        self.selection_state = SelectionState::Row;
-        self.right_clicked_row = None;
-        self.selected_row = Some(row_ix);
-        if let Some(row_ix) = self.selected_row {
-            self.vertical_scroll_handle
-                .scroll_to_item(row_ix, ScrollStrategy::Top);
-        }
-        cx.emit(TableEvent::SelectRow(row_ix));
-        cx.notify();
+    /// Returns true if the column at the given index is visible.
+    pub fn is_column_visible(&self, col_ix: usize) -> bool {
+        self.col_groups
+            .get(col_ix)
+            .map(|col_group| col_group.is_visible())
+            .unwrap_or(false)
     }
 
-    /// Returns the selected column index.
-    pub fn selected_col(&self) -> Option<usize> {
-        self.selected_col
-    }
+    /// Show or hide the column at the given index.
+    ///
+    /// Hidden columns keep their index, they are only skipped when rendering.
+    pub fn set_column_visible(&mut self, col_ix: usize, visible: bool, cx: &mut Context<Self>) {
+        let Some(col_group) = self.col_groups.get_mut(col_ix) else {
+            return;
+        };
 
-    /// Sets the selected col to the given index.
-    pub fn set_selected_col(&mut self, col_ix: usize, cx: &mut Context<Self>) {
-        self.selection_state = SelectionState::Column;
-        self.selected_col = Some(col_ix);
-        if let Some(col_ix) = self.selected_col {
-            self.scroll_to_col(col_ix, cx);
+        col_group.column.visible = visible;
+        if !visible && self.selected_col == Some(col_ix) {
+            self.selected_col = None;
         }
-        cx.emit(TableEvent::SelectColumn(col_ix));
         cx.notify();
     }
 
-    /// Clear the selection of the table.
-    pub fn clear_selection(&mut self, cx: &mut Context<Self>) {
-        self.selection_state = SelectionState::Row;
-        self.selected_row = None;
-        self.selected_col = None;
+    /// Set the spanning headers rendered in a row above the column headers,
+    /// an empty `Vec` removes the row.
+    ///
+    /// The spans are rendered instead of the [`Column::header_group`] headers. Resizing a
+    /// spanning header distributes the width change among its columns proportionally.
+    pub fn set_col_group_spans(&mut self, spans: Vec<ColGroupSpan>, cx: &mut Context<Self>) {
+        self.col_group_spans = spans;
         cx.notify();
     }
 
-    /// Returns the visible range of the rows and columns.
-    pub fn visible_range(&self) -> &VisibleRangeState {
-        &self.visible_range
+    /// Scroll to the row at the given index.
+    pub fn scroll_to_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        self.scroll_to_row_with_strategy(row_ix, ScrollStrategy::Top, cx);
     }
 
-    fn on_row_click(
+    /// Scroll to the row at the given index, use `ScrollStrategy::Center` to center the row.
+    pub fn scroll_to_row_with_strategy(
         &mut self,
-        ev: &MouseDownEvent,
         row_ix: usize,
-        _: &mut Window,
+        strategy: ScrollStrategy,
         cx: &mut Context<Self>,
     ) {
-        if ev.button == MouseButton::Right {
-            self.right_clicked_row = Some(row_ix);
-        } else {
-            self.set_selected_row(row_ix, cx);
+        self.scroll_row_into_view(row_ix, strategy);
+        cx.notify();
+    }
 
-            if ev.click_count == 2 {
-                cx.emit(TableEvent::DoubleClickedRow(row_ix));
-            }
+    fn scroll_row_into_view(&mut self, row_ix: usize, strategy: ScrollStrategy) {
+        if let Some(display_ix) = self.display_ix_for_row(row_ix) {
+            self.vertical_scroll_handle
+                .scroll_to_item(display_ix, strategy);
         }
     }
 
-    fn on_col_head_click(&mut self, col_ix: usize, _: &mut Window, cx: &mut Context<Self>) {
-        if !self.col_selectable {
-            return;
-        }
+    /// Returns true if the row group with the given key is collapsed.
+    pub fn is_group_collapsed(&self, group: &SharedString) -> bool {
+        self.collapsed_groups.contains(group)
+    }
 
-        let Some(col_group) = self.col_groups.get(col_ix) else {
-            return;
+    /// Collapse or expand the row group with the given key.
+    pub fn toggle_group(&mut self, group: SharedString, cx: &mut Context<Self>) {
+        let expanded = if self.collapsed_groups.remove(&group) {
+            true
+        } else {
+            self.collapsed_groups.insert(group.clone());
+            false
         };
+        self.display_rows_key = None;
 
-        if !col_group.column.selectable {
+        cx.emit(TableEvent::GroupToggled(group, expanded));
+        cx.notify();
+    }
+
+    /// Rebuild the group header rows from [`TableDelegate::group_for_row`], if the rows changed.
+    fn prepare_display_rows(&mut self, rows_count: usize, cx: &App) {
+        let key = (rows_count, self.pinned_rows_count);
+        if self.display_rows_key == Some(key) {
+            return;
+        }
+        self.display_rows_key = Some(key);
+        self.display_rows.clear();
+        self.row_display_ixs.clear();
+        if !self.delegate.has_row_groups(cx) {
             return;
         }
 
-        self.set_selected_col(col_ix, cx)
-    }
+        let mut current_group: Option<SharedString> = None;
+        let mut has_groups = false;
+        for row_ix in self.pinned_rows_count..rows_count {
+            let group = self.delegate.group_for_row(row_ix, cx);
+            if group.is_some() && group != current_group {
+                self.display_rows
+                    .push(DisplayRow::GroupHeader(group.clone().unwrap()));
+                has_groups = true;
+            }
 
-    fn has_selection(&self) -> bool {
-        self.selected_row.is_some() || self.selected_col.is_some()
-    }
+            let collapsed = group
+                .as_ref()
+                .map(|group| self.collapsed_groups.contains(group))
+                .unwrap_or(false);
+            if collapsed {
+                self.row_display_ixs.push(None);
+            } else {
+                self.row_display_ixs.push(Some(self.display_rows.len()));
+                self.display_rows.push(DisplayRow::Row(row_ix));
+            }
+            current_group = group;
+        }
 
-    fn action_cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
-        if self.has_selection() {
-            self.clear_selection(cx);
-            return;
+        if !has_groups {
+            self.display_rows.clear();
+            self.row_display_ixs.clear();
         }
-        cx.propagate();
     }
 
-    fn action_select_prev(&mut self, _: &SelectPrev, _: &mut Window, cx: &mut Context<Self>) {
-        let rows_count = self.delegate.rows_count(cx);
-        if rows_count < 1 {
-            return;
+    /// Returns the number of rows rendered in the scrollable body, including group headers.
+    fn display_rows_count(&self, rows_count: usize) -> usize {
+        if self.display_rows.is_empty() {
+            rows_count.saturating_sub(self.pinned_rows_count)
+        } else {
+            self.display_rows.len()
         }
+    }
 
-        let mut selected_row = self.selected_row.unwrap_or(0);
-        if selected_row > 0 {
-            selected_row = selected_row.saturating_sub(1);
-        } else {
-            if self.loop_selection {
-                selected_row = rows_count.saturating_sub(1);
-            }
+    /// Returns the rendered row at the given list index, the index may be past the end for fake rows.
+    fn display_row(&self, ix: usize, rows_count: usize) -> DisplayRow {
+        if self.display_rows.is_empty() {
+            return DisplayRow::Row(ix + self.pinned_rows_count);
         }
 
-        self.set_selected_row(selected_row, cx);
+        match self.display_rows.get(ix) {
+            Some(row) => row.clone(),
+            None => DisplayRow::Row(rows_count + ix - self.display_rows.len()),
+        }
     }
 
-    fn action_select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
-        let rows_count = self.delegate.rows_count(cx);
-        if rows_count < 1 {
-            return;
+    /// Returns the list index of the given row, or of its group header when collapsed.
+    ///
+    /// Returns `None` for pinned rows, they are not part of the scrollable body.
+    fn display_ix_for_row(&self, row_ix: usize) -> Option<usize> {
+        let row_ix = row_ix.checked_sub(self.pinned_rows_count)?;
+        if self.display_rows.is_empty() {
+            return Some(row_ix);
         }
 
-        let selected_row = match self.selected_row {
-            Some(selected_row) if selected_row < rows_count.saturating_sub(1) => selected_row + 1,
-            Some(selected_row) => {
-                if self.loop_selection {
-                    0
-                } else {
-                    selected_row
-                }
-            }
-            _ => 0,
-        };
+        Some(
+            (0..=row_ix.min(self.row_display_ixs.len().saturating_sub(1)))
+                .rev()
+                .find_map(|ix| self.row_display_ixs.get(ix).copied().flatten())
+                .unwrap_or(0),
+        )
+    }
 
-        self.set_selected_row(selected_row, cx);
+    /// Returns true if the row is hidden in a collapsed group.
+    fn is_row_collapsed(&self, row_ix: usize) -> bool {
+        let Some(ix) = row_ix.checked_sub(self.pinned_rows_count) else {
+            return false;
+        };
+        matches!(self.row_display_ixs.get(ix), Some(None))
     }
 
-    fn action_select_prev_col(
-        &mut self,
-        _: &SelectPrevColumn,
-        _: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        let mut selected_col = self.selected_col.unwrap_or(0);
-        let columns_count = self.delegate.columns_count(cx);
-        if selected_col > 0 {
-            selected_col = selected_col.saturating_sub(1);
-        } else {
-            if self.loop_selection {
-                selected_col = columns_count.saturating_sub(1);
+    /// Step from the given row over the rows hidden in collapsed groups.
+    fn skip_collapsed_rows(&self, row_ix: usize, forward: bool, rows_count: usize) -> usize {
+        let mut ix = row_ix;
+        for _ in 0..rows_count {
+            if !self.is_row_collapsed(ix) {
+                return ix;
             }
+            ix = if forward {
+                (ix + 1) % rows_count
+            } else {
+                (ix + rows_count - 1) % rows_count
+            };
         }
-        self.set_selected_col(selected_col, cx);
+        row_ix
     }
 
-    fn action_select_next_col(
+    // Scroll to the column at the given index.
+    pub fn scroll_to_col(&mut self, col_ix: usize, cx: &mut Context<Self>) {
+        self.scroll_to_col_with_strategy(col_ix, ScrollStrategy::Top, cx);
+    }
+
+    /// Scroll to the column at the given index, use `ScrollStrategy::Center` to center the column.
+    pub fn scroll_to_col_with_strategy(
         &mut self,
-        _: &SelectNextColumn,
-        _: &mut Window,
+        col_ix: usize,
+        strategy: ScrollStrategy,
         cx: &mut Context<Self>,
     ) {
-        let mut selected_col = self.selected_col.unwrap_or(0);
-        if selected_col < self.delegate.columns_count(cx).saturating_sub(1) {
-            selected_col += 1;
-        } else {
-            if self.loop_selection {
-                selected_col = 0;
-            }
-        }
+        self.scroll_col_into_view(col_ix, strategy);
+        cx.notify();
+    }
 
-        self.set_selected_col(selected_col, cx);
+    fn scroll_col_into_view(&mut self, col_ix: usize, strategy: ScrollStrategy) {
+        let col_ix = col_ix.saturating_sub(self.fixed_left_cols_count());
+
+        self.horizontal_scroll_handle
+            .scroll_to_item(col_ix, strategy);
     }
 
-    /// Scroll table when mouse position is near the edge of the table bounds.
-    fn scroll_table_by_col_resizing(
+    /// Scroll to make the cell at the given row and column visible.
+    ///
+    /// Both scroll handles are updated first, then the table is notified on the next frame,
+    /// so the vertical and horizontal scrolling are applied in the same render.
+    pub fn ensure_visible(
         &mut self,
-        mouse_position: Point<Pixels>,
-        col_group: &ColGroup,
+        row_ix: usize,
+        col_ix: usize,
+        strategy: ScrollStrategy,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
-        // Do nothing if pos out of the table bounds right for avoid scroll to the right.
-        if mouse_position.x > self.bounds.right() {
-            return;
+        self.scroll_row_into_view(row_ix, strategy);
+        self.scroll_col_into_view(col_ix, strategy);
+        cx.on_next_frame(window, |_, _, cx| cx.notify());
+    }
+
+    /// Returns the selected row index.
+    pub fn selected_row(&self) -> Option<usize> {
+        self.selected_row
+    }
+
+    /// Sets the selected row to the given index.
+    ///
+    /// In multi-select mode, this replaces the selected rows with the given row.
+    pub fn set_selected_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        if self.multi_select {
+            self.selected_rows.clear();
+            self.selected_rows.insert(row_ix);
+            self.anchor_row = Some(row_ix);
+            cx.emit(TableEvent::SelectRows(vec![row_ix]));
+        }
+        self.move_cursor(row_ix, cx);
+    }
+
+    /// Returns the selected rows in selection order.
+    ///
+    /// When multi-select is disabled, this contains at most the selected row.
+    pub fn selected_rows(&self) -> Vec<usize> {
+        if self.multi_select {
+            self.selected_rows.iter().copied().collect()
+        } else {
+            self.selected_row.into_iter().collect()
+        }
+    }
+
+    /// Sets the selected rows, the last row becomes the cursor row.
+    ///
+    /// When multi-select is disabled, only the last row is selected.
+    pub fn set_selected_rows(
+        &mut self,
+        rows: impl IntoIterator<Item = usize>,
+        cx: &mut Context<Self>,
+    ) {
+        let rows: IndexSet<usize> = rows.into_iter().collect();
+        let Some(last) = rows.last().copied() else {
+            self.clear_selection(cx);
+            return;
+        };
+
+        if !self.multi_select {
+            self.set_selected_row(last, cx);
+            return;
+        }
+
+        self.selected_rows = rows;
+        self.anchor_row = Some(last);
+        cx.emit(TableEvent::SelectRows(self.selected_rows()));
+        self.move_cursor(last, cx);
+    }
+
+    /// Move the cursor row to the given index, without changing the multi-selection.
+    fn move_cursor(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        self.selection_state = SelectionState::Row;
+        self.right_clicked_row = None;
+        self.selected_row = Some(row_ix);
+        if let Some(display_ix) = self.display_ix_for_row(row_ix) {
+            self.vertical_scroll_handle
+                .scroll_to_item(display_ix, ScrollStrategy::Top);
+        }
+        cx.emit(TableEvent::SelectRow(row_ix));
+        cx.notify();
+    }
+
+    /// Extend the selection from the anchor row to the given row.
+    fn extend_selection_to(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        let anchor = self.anchor_row.or(self.selected_row).unwrap_or(row_ix);
+        self.selected_rows = selection_range(anchor, row_ix);
+        self.anchor_row = Some(anchor);
+        cx.emit(TableEvent::SelectRows(self.selected_rows()));
+        self.move_cursor(row_ix, cx);
+    }
+
+    /// Toggle the selection of the given row, keeping other selected rows.
+    fn toggle_row_selection(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        if !self.selected_rows.shift_remove(&row_ix) {
+            self.selected_rows.insert(row_ix);
+        }
+        self.anchor_row = Some(row_ix);
+        cx.emit(TableEvent::SelectRows(self.selected_rows()));
+        self.move_cursor(row_ix, cx);
+    }
+
+    /// Select all rows in multi-select mode, keeping the cursor row.
+    pub fn select_all_rows(&mut self, cx: &mut Context<Self>) {
+        if !self.multi_select {
+            return;
+        }
+
+        let rows_count = self.delegate.rows_count(cx);
+        self.selection_state = SelectionState::Row;
+        self.selected_rows = (0..rows_count).collect();
+        cx.emit(TableEvent::SelectRows(self.selected_rows()));
+        cx.notify();
+    }
+
+    /// Returns the (checked, indeterminate) state of the checkbox column head.
+    fn head_checkbox_state(&self, rows_count: usize) -> (bool, bool) {
+        let selected_count = self
+            .selected_rows
+            .iter()
+            .filter(|row_ix| **row_ix < rows_count)
+            .count();
+
+        let checked = rows_count > 0 && selected_count == rows_count;
+        (checked, selected_count > 0 && !checked)
+    }
+
+    fn on_head_checkbox_click(&mut self, cx: &mut Context<Self>) {
+        let rows_count = self.delegate.rows_count(cx);
+        let (checked, _) = self.head_checkbox_state(rows_count);
+        if checked {
+            self.anchor_row = None;
+            self.selected_rows.clear();
+            cx.emit(TableEvent::SelectRows(vec![]));
+            cx.notify();
+        } else {
+            self.select_all_rows(cx);
+        }
+    }
+
+    fn is_row_selected(&self, row_ix: usize) -> bool {
+        if self.multi_select {
+            self.selected_rows.contains(&row_ix)
+        } else {
+            self.selected_row == Some(row_ix)
+        }
+    }
+
+    /// Returns the selected column index.
+    pub fn selected_col(&self) -> Option<usize> {
+        self.selected_col
+    }
+
+    /// Sets the selected col to the given index.
+    pub fn set_selected_col(&mut self, col_ix: usize, cx: &mut Context<Self>) {
+        self.selection_state = SelectionState::Column;
+        self.selected_col = Some(col_ix);
+        if let Some(col_ix) = self.selected_col {
+            self.scroll_to_col(col_ix, cx);
+        }
+        cx.emit(TableEvent::SelectColumn(col_ix));
+        cx.notify();
+    }
+
+    /// Clear the selection of the table.
+    pub fn clear_selection(&mut self, cx: &mut Context<Self>) {
+        self.selection_state = SelectionState::Row;
+        self.selected_row = None;
+        self.selected_col = None;
+        self.anchor_row = None;
+        if !self.selected_rows.is_empty() {
+            self.selected_rows.clear();
+            cx.emit(TableEvent::SelectRows(vec![]));
+        }
+        cx.notify();
+    }
+
+    /// Export the table data as CSV, using the given separator (e.g. `,` or `\t` for TSV).
+    ///
+    /// The first line contains the headers returned by [`TableDelegate::export_header`], followed by
+    /// one line per row with the values returned by [`TableDelegate::export_cell`], cells returning
+    /// `None` are left empty.
+    pub fn export_to_csv(&self, separator: char, cx: &App) -> String {
+        let (out, _) = self.export_rows(0..self.delegate.rows_count(cx), separator, cx);
+        out
+    }
+
+    /// Export the table data as a JSON array, with one object per row keyed by the column names.
+    ///
+    /// The values returned by [`TableDelegate::export_cell`] are converted to numbers and booleans
+    /// when they parse as one, cells returning `None` are `null`.
+    pub fn export_to_json(&self, cx: &App) -> serde_json::Value {
+        let (names, rows) = self.export_cells(cx);
+        rows_to_json(&names, rows)
+    }
+
+    /// Export the table data as JSON like [`Table::export_to_json`], the conversion runs in the
+    /// background and the result is emitted as [`TableEvent::ExportedJson`].
+    pub fn export_to_json_in_background(&self, cx: &mut Context<Self>) {
+        let (names, rows) = self.export_cells(cx);
+        let task = cx
+            .background_executor()
+            .spawn(async move { rows_to_json(&names, rows) });
+        cx.spawn(async move |this, cx| {
+            let value = task.await;
+            _ = this.update(cx, |_, cx| cx.emit(TableEvent::ExportedJson(value)));
+        })
+        .detach();
+    }
+
+    /// Returns a JSON Schema describing the rows of [`Table::export_to_json`], with the column
+    /// types returned by [`TableDelegate::column_type`].
+    pub fn export_to_json_schema(&self, cx: &App) -> serde_json::Value {
+        let mut properties = serde_json::Map::new();
+        for col_ix in self.export_col_ixs() {
+            let column_type = self.delegate.column_type(col_ix, cx);
+            properties.insert(
+                self.col_groups[col_ix].column.name.to_string(),
+                serde_json::json!({ "type": [column_type.json_type(), "null"] }),
+            );
+        }
+
+        serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": properties,
+            },
+        })
+    }
+
+    /// Returns the names of the visible columns and the exported cells of all the rows.
+    fn export_cells(&self, cx: &App) -> (Vec<String>, Vec<Vec<Option<String>>>) {
+        let col_ixs = self.export_col_ixs();
+        let names = col_ixs
+            .iter()
+            .map(|&col_ix| self.col_groups[col_ix].column.name.to_string())
+            .collect();
+        let rows = (0..self.delegate.rows_count(cx))
+            .map(|row_ix| {
+                col_ixs
+                    .iter()
+                    .map(|&col_ix| self.delegate.export_cell(row_ix, col_ix, cx))
+                    .collect()
+            })
+            .collect();
+
+        (names, rows)
+    }
+
+    fn export_col_ixs(&self) -> Vec<usize> {
+        (0..self.col_groups.len())
+            .filter(|&col_ix| self.is_column_visible(col_ix))
+            .collect()
+    }
+
+    /// Copy the selected rows to the clipboard as TSV, or all visible rows if no row is selected.
+    ///
+    /// Does nothing if [`TableDelegate::export_cell`] returns `None` for all the cells.
+    pub fn export_to_clipboard(&self, cx: &mut App) {
+        let mut row_ixs = self.selected_rows();
+        if row_ixs.is_empty() {
+            row_ixs = (0..self.delegate.rows_count(cx))
+                .filter(|&row_ix| !self.is_row_collapsed(row_ix))
+                .collect();
+        } else {
+            row_ixs.sort_unstable();
+        }
+
+        let (out, has_value) = self.export_rows(row_ixs, '\t', cx);
+        if has_value {
+            cx.write_to_clipboard(ClipboardItem::new_string(out));
+        }
+    }
+
+    /// Export the given rows of the visible columns with a header line, and whether any cell has a value.
+    fn export_rows(
+        &self,
+        row_ixs: impl IntoIterator<Item = usize>,
+        separator: char,
+        cx: &App,
+    ) -> (String, bool) {
+        let mut out = String::new();
+        let mut has_value = false;
+
+        let col_ixs = self.export_col_ixs();
+
+        let header = col_ixs
+            .iter()
+            .map(|&col_ix| Some(self.delegate.export_header(col_ix, cx)));
+        out.push_str(&csv_line(header, separator));
+
+        for row_ix in row_ixs {
+            let cells = col_ixs
+                .iter()
+                .map(|&col_ix| self.delegate.export_cell(row_ix, col_ix, cx))
+                .inspect(|value| has_value |= value.is_some());
+            out.push_str(&csv_line(cells, separator));
+        }
+
+        (out, has_value)
+    }
+
+    /// Returns the filter query of the column at the given index.
+    pub fn filter(&self, col_ix: usize) -> Option<&SharedString> {
+        self.filters.get(&col_ix)
+    }
+
+    /// Set the filter query of the column at the given index, and call [`TableDelegate::filter_column`].
+    pub fn set_filter(
+        &mut self,
+        col_ix: usize,
+        query: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let query: SharedString = query.into();
+        if let Some(state) = self.filter_inputs.get(&col_ix) {
+            state.update(cx, |state, cx| state.set_value(query.clone(), window, cx));
+        }
+        self.apply_filter(col_ix, query, window, cx);
+    }
+
+    fn apply_filter(
+        &mut self,
+        col_ix: usize,
+        query: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous = self.filters.get(&col_ix).cloned().unwrap_or_default();
+        if previous == query {
+            return;
+        }
+
+        if query.is_empty() {
+            self.filters.remove(&col_ix);
+        } else {
+            self.filters.insert(col_ix, query.clone());
+        }
+        self.delegate_mut()
+            .filter_column(col_ix, &query, window, cx);
+        cx.emit(TableEvent::FilterChanged(col_ix, query));
+        cx.notify();
+    }
+
+    fn has_filter_row(&self) -> bool {
+        self.col_groups
+            .iter()
+            .any(|col_group| col_group.is_filterable())
+    }
+
+    /// The height of the table head, including the filter row.
+    fn head_height(&self) -> Pixels {
+        let mut rows = 1.;
+        if self.has_filter_row() {
+            rows += 1.;
+        }
+        if self.has_header_groups() {
+            rows += 1.;
+        }
+        self.row_height() * rows
+    }
+
+    /// Returns true if any column has a header group, see [`Column::header_group`],
+    /// or spanning headers are set with [`Table::set_col_group_spans`].
+    fn has_header_groups(&self) -> bool {
+        !self.col_group_spans.is_empty()
+            || self
+                .col_groups
+                .iter()
+                .any(|col_group| col_group.column.header_group.is_some())
+    }
+
+    /// Create the filter inputs for the filterable columns that don't have one yet.
+    fn ensure_filter_inputs(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for col_ix in 0..self.col_groups.len() {
+            if !self.col_groups[col_ix].is_filterable() || self.filter_inputs.contains_key(&col_ix)
+            {
+                continue;
+            }
+
+            let query = self.filters.get(&col_ix).cloned().unwrap_or_default();
+            let state = cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder(t!("Table.Filter"))
+                    .default_value(query)
+            });
+            self._filter_subscriptions.push(cx.subscribe_in(
+                &state,
+                window,
+                move |table, state, ev: &InputEvent, window, cx| {
+                    if let InputEvent::Change = ev {
+                        let query = state.read(cx).value();
+                        table.apply_filter(col_ix, query, window, cx);
+                    }
+                },
+            ));
+            self.filter_inputs.insert(col_ix, state);
+        }
+    }
+
+    /// Returns the row and column of the cell being edited inline.
+    pub fn editing_cell(&self) -> Option<(usize, usize)> {
+        self.editing_cell
+            .as_ref()
+            .map(|(row_ix, col_ix, _, _)| (*row_ix, *col_ix))
+    }
+
+    /// Start editing the cell at the given position, if [`TableDelegate::can_edit_cell`] allows it.
+    ///
+    /// The editor is seeded with the value from [`TableDelegate::edit_value`].
+    /// Returns false if the cell is not editable.
+    pub fn start_edit(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if !self.is_column_visible(col_ix) || !self.delegate.can_edit_cell(row_ix, col_ix, cx) {
+            return false;
+        }
+
+        self.commit_edit(window, cx);
+
+        let value = SharedString::from(
+            self.delegate
+                .edit_value(row_ix, col_ix, cx)
+                .unwrap_or_default(),
+        );
+        let state = cx.new(|cx| InputState::new(window, cx).default_value(value.clone()));
+        state.update(cx, |state, cx| state.focus(window, cx));
+
+        self._editing_subscription = Some(cx.subscribe_in(
+            &state,
+            window,
+            |table, _, ev: &InputEvent, window, cx| match ev {
+                InputEvent::PressEnter { .. } => table.edit_next_row(window, cx),
+                // Keep the focus on the control that took it
+                InputEvent::Blur => table.finish_edit(false, window, cx),
+                _ => {}
+            },
+        ));
+        self.editing_cell = Some((row_ix, col_ix, state, value));
+        self.selected_row = Some(row_ix);
+        self.selected_col = Some(col_ix);
+        self.ensure_visible(row_ix, col_ix, ScrollStrategy::Top, window, cx);
+        cx.notify();
+        true
+    }
+
+    /// Commit the current inline edit and start editing the same column in the next row.
+    fn edit_next_row(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((row_ix, col_ix)) = self.editing_cell() else {
+            return;
+        };
+
+        self.commit_edit(window, cx);
+        if row_ix + 1 < self.delegate.rows_count(cx) {
+            self.start_edit(row_ix + 1, col_ix, window, cx);
+        }
+    }
+
+    /// Commit the current inline edit to the delegate and focus the table.
+    ///
+    /// The delegate is not called if the value was not changed.
+    pub fn commit_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.finish_edit(true, window, cx);
+    }
+
+    fn finish_edit(&mut self, focus: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((row_ix, col_ix, state, initial_value)) = self.editing_cell.take() else {
+            return;
+        };
+        self._editing_subscription = None;
+
+        let value = state.read(cx).value();
+        if value != initial_value {
+            self.delegate_mut()
+                .commit_edit(row_ix, col_ix, value.clone(), window, cx);
+            cx.emit(TableEvent::CellEditCommitted(row_ix, col_ix, value));
+        }
+        if focus {
+            window.focus(&self.focus_handle);
+        }
+        cx.notify();
+    }
+
+    /// Cancel the current inline edit without committing.
+    pub fn cancel_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_cell.take().is_none() {
+            return;
+        }
+        self._editing_subscription = None;
+
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    /// Set the items of the row context menu, replacing [`TableDelegate::context_menu`].
+    ///
+    /// The action of an item is called with the index of the right-clicked row.
+    pub fn set_context_menu_items(&mut self, items: Vec<ContextMenuItem>, cx: &mut Context<Self>) {
+        self.context_menu_items = Some(items);
+        cx.notify();
+    }
+
+    /// Open the context menu of the row at `row_ix` below the row, e.g. from a key binding.
+    pub fn show_context_menu(
+        &mut self,
+        row_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if row_ix >= self.delegate.rows_count(cx) {
+            return;
+        }
+
+        let view = cx.entity();
+        let menu = PopupMenu::build(window, cx, move |menu, window, cx| {
+            view.read(cx)
+                .build_row_context_menu(row_ix, menu, window, cx)
+        });
+        if menu.read(cx).is_empty() {
+            return;
+        }
+
+        let subscription = cx.subscribe_in(&menu, window, |table, _, _: &DismissEvent, _, cx| {
+            table.keyboard_context_menu = None;
+            table.right_clicked_row = None;
+            cx.notify();
+        });
+        menu.focus_handle(cx).focus(window);
+        self.right_clicked_row = Some(row_ix);
+        self.right_clicked_col = None;
+        self.right_clicked_row_bounds = Bounds::default();
+        self.keyboard_context_menu = Some((menu, subscription));
+        self.scroll_row_into_view(row_ix, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn build_row_context_menu(
+        &self,
+        row_ix: usize,
+        menu: PopupMenu,
+        window: &Window,
+        cx: &App,
+    ) -> PopupMenu {
+        match &self.context_menu_items {
+            Some(items) => items
+                .iter()
+                .fold(menu, |menu, item| item.add_to(row_ix, menu)),
+            None => self.delegate.context_menu(row_ix, menu, window, cx),
+        }
+    }
+
+    /// Returns the visible range of the rows and columns.
+    pub fn visible_range(&self) -> &VisibleRangeState {
+        &self.visible_range
+    }
+
+    fn on_row_click(
+        &mut self,
+        ev: &MouseDownEvent,
+        row_ix: usize,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if ev.button == MouseButton::Right {
+            self.right_clicked_row = Some(row_ix);
+            self.right_clicked_col = None;
+        } else if self.multi_select && ev.modifiers.shift {
+            self.extend_selection_to(row_ix, cx);
+        } else if self.multi_select && ev.modifiers.secondary() {
+            self.toggle_row_selection(row_ix, cx);
+        } else {
+            self.set_selected_row(row_ix, cx);
+
+            if ev.click_count == 2 {
+                cx.emit(TableEvent::DoubleClickedRow(row_ix));
+            }
+        }
+
+        if row_ix < self.pinned_rows_count {
+            cx.emit(TableEvent::PinnedRowClicked(row_ix));
+        }
+    }
+
+    fn on_col_head_click(&mut self, col_ix: usize, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.col_selectable {
+            return;
+        }
+
+        let Some(col_group) = self.col_groups.get(col_ix) else {
+            return;
+        };
+
+        if !col_group.column.selectable {
+            return;
+        }
+
+        self.set_selected_col(col_ix, cx)
+    }
+
+    fn on_col_head_right_click(
+        &mut self,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.right_clicked_row = None;
+        self.right_clicked_col = Some(col_ix);
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn action_hide_column(&mut self, action: &HideColumn, _: &mut Window, cx: &mut Context<Self>) {
+        self.right_clicked_col = None;
+        self.set_column_visible(action.0, false, cx);
+    }
+
+    fn action_show_column_chooser(
+        &mut self,
+        _: &ShowColumnChooser,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.right_clicked_col = None;
+        cx.emit(TableEvent::ShowColumnChooser);
+    }
+
+    fn has_selection(&self) -> bool {
+        self.selected_row.is_some() || self.selected_col.is_some() || !self.selected_rows.is_empty()
+    }
+
+    fn action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_cell.is_some() {
+            self.cancel_edit(window, cx);
+            return;
+        }
+
+        if self.has_selection() {
+            self.clear_selection(cx);
+            return;
+        }
+        cx.propagate();
+    }
+
+    fn action_edit_next_cell(
+        &mut self,
+        _: &EditNextCell,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((row_ix, col_ix)) = self.editing_cell() else {
+            cx.propagate();
+            return;
+        };
+
+        self.commit_edit(window, cx);
+        for next_col_ix in (col_ix + 1)..self.col_groups.len() {
+            if self.start_edit(row_ix, next_col_ix, window, cx) {
+                break;
+            }
+        }
+    }
+
+    fn action_start_cell_edit(
+        &mut self,
+        _: &StartCellEdit,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(row_ix), Some(col_ix)) = (self.selected_row, self.selected_col) else {
+            cx.propagate();
+            return;
+        };
+
+        self.start_edit(row_ix, col_ix, window, cx);
+    }
+
+    fn action_stop_cell_edit(
+        &mut self,
+        _: &StopCellEdit,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.editing_cell.is_none() {
+            cx.propagate();
+            return;
+        }
+
+        self.cancel_edit(window, cx);
+    }
+
+    fn action_export_csv(&mut self, _: &ExportCsv, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(TableEvent::ExportRequested);
+    }
+
+    fn action_copy_to_clipboard(
+        &mut self,
+        _: &CopyToClipboard,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.export_to_clipboard(cx);
+    }
+
+    fn action_select_prev(&mut self, _: &SelectPrev, _: &mut Window, cx: &mut Context<Self>) {
+        let rows_count = self.delegate.rows_count(cx);
+        if rows_count < 1 {
+            return;
+        }
+
+        let mut selected_row = self.selected_row.unwrap_or(0);
+        if selected_row > 0 {
+            selected_row = selected_row.saturating_sub(1);
+        } else {
+            if self.loop_selection {
+                selected_row = rows_count.saturating_sub(1);
+            }
+        }
+        let selected_row = self.skip_collapsed_rows(selected_row, false, rows_count);
+
+        if self.multi_select && !self.selected_rows.is_empty() {
+            self.move_cursor(selected_row, cx);
+        } else {
+            self.set_selected_row(selected_row, cx);
+        }
+    }
+
+    fn action_select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let rows_count = self.delegate.rows_count(cx);
+        if rows_count < 1 {
+            return;
+        }
+
+        let selected_row = match self.selected_row {
+            Some(selected_row) if selected_row < rows_count.saturating_sub(1) => selected_row + 1,
+            Some(selected_row) => {
+                if self.loop_selection {
+                    0
+                } else {
+                    selected_row
+                }
+            }
+            _ => 0,
+        };
+        let selected_row = self.skip_collapsed_rows(selected_row, true, rows_count);
+
+        if self.multi_select && !self.selected_rows.is_empty() {
+            self.move_cursor(selected_row, cx);
+        } else {
+            self.set_selected_row(selected_row, cx);
+        }
+    }
+
+    fn action_select_prev_col(
+        &mut self,
+        _: &SelectPrevColumn,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut selected_col = self.selected_col.unwrap_or(0);
+        let columns_count = self.delegate.columns_count(cx);
+        if selected_col > 0 {
+            selected_col = selected_col.saturating_sub(1);
+        } else {
+            if self.loop_selection {
+                selected_col = columns_count.saturating_sub(1);
+            }
+        }
+        self.set_selected_col(selected_col, cx);
+    }
+
+    fn action_select_next_col(
+        &mut self,
+        _: &SelectNextColumn,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut selected_col = self.selected_col.unwrap_or(0);
+        if selected_col < self.delegate.columns_count(cx).saturating_sub(1) {
+            selected_col += 1;
+        } else {
+            if self.loop_selection {
+                selected_col = 0;
+            }
+        }
+
+        self.set_selected_col(selected_col, cx);
+    }
+
+    /// Scroll table when mouse position is near the edge of the table bounds.
+    fn scroll_table_by_col_resizing(
+        &mut self,
+        mouse_position: Point<Pixels>,
+        col_group: &ColGroup,
+    ) {
+        // Do nothing if pos out of the table bounds right for avoid scroll to the right.
+        if mouse_position.x > self.bounds.right() {
+            return;
         }
 
         let mut offset = self.horizontal_scroll_handle.offset();
@@ -499,8 +1679,6 @@ where
             return;
         }
 
-        const MIN_WIDTH: Pixels = px(10.0);
-        const MAX_WIDTH: Pixels = px(1200.0);
         let Some(col_group) = self.col_groups.get_mut(ix) else {
             return;
         };
@@ -512,7 +1690,7 @@ where
 
         let old_width = col_group.width;
         let new_width = size;
-        if new_width < MIN_WIDTH {
+        if new_width < MIN_COL_WIDTH {
             return;
         }
         let changed_width = new_width - old_width;
@@ -520,12 +1698,142 @@ where
         if changed_width > px(-1.0) && changed_width < px(1.0) {
             return;
         }
-        col_group.width = new_width.min(MAX_WIDTH);
+        col_group.width = new_width.min(MAX_COL_WIDTH);
+
+        cx.notify();
+    }
+
+    /// Resize the columns of the spanning header at `span_ix` to fit the new `size`,
+    /// the resizable columns keep their proportions.
+    fn resize_col_group_span(
+        &mut self,
+        span_ix: usize,
+        size: Pixels,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.col_resizable {
+            return;
+        }
+        let Some(span) = self.col_group_spans.get(span_ix) else {
+            return;
+        };
+
+        let cols = span.col_range.start.min(self.col_groups.len())
+            ..span.col_range.end.min(self.col_groups.len());
+        let mut resizable_cols = Vec::new();
+        let mut fixed_width = px(0.);
+        for col_ix in cols {
+            let col_group = &self.col_groups[col_ix];
+            if !col_group.is_visible() {
+                continue;
+            }
+            if col_group.is_resizable() {
+                resizable_cols.push(col_ix);
+            } else {
+                fixed_width += col_group.width;
+            }
+        }
+
+        let widths = resizable_cols
+            .iter()
+            .map(|col_ix| self.col_groups[*col_ix].width)
+            .collect::<Vec<_>>();
+        let old_width = widths.iter().fold(px(0.), |acc, width| acc + *width);
+        let changed_width = size.floor() - fixed_width - old_width;
+        // If change size is less than 1px, do nothing.
+        if changed_width > px(-1.0) && changed_width < px(1.0) {
+            return;
+        }
+        let Some(new_widths) = distribute_width(&widths, size.floor() - fixed_width) else {
+            return;
+        };
+
+        for (col_ix, width) in resizable_cols.into_iter().zip(new_widths) {
+            self.col_groups[col_ix].width = width;
+        }
+        cx.notify();
+    }
+
+    /// Emit the new widths when a column resizing drag ends.
+    fn finish_col_resizing(&mut self, cx: &mut Context<Self>) {
+        if self.resizing_col.is_none() {
+            return;
+        }
+
+        self.resizing_col = None;
+        self.emit_column_widths(cx);
+    }
 
+    fn emit_column_widths(&mut self, cx: &mut Context<Self>) {
+        let new_widths = self
+            .col_groups
+            .iter()
+            .filter(|g| g.is_visible())
+            .map(|g| g.width)
+            .collect();
+        cx.emit(TableEvent::ColumnWidthsChanged(new_widths));
         cx.notify();
     }
 
-    fn perform_sort(&mut self, col_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+    /// Resize the resizable columns to fit their content, e.g. after creating the table or
+    /// changing its data.
+    ///
+    /// The header and the first 50 visible rows are measured with
+    /// [`TableDelegate::measure_header`] and [`TableDelegate::measure_cell`], the columns without
+    /// any measurement keep their width.
+    pub fn auto_fit_columns(&mut self, cx: &mut Context<Self>) {
+        let mut changed = false;
+        for col_ix in 0..self.col_groups.len() {
+            changed |= self.fit_column_width(col_ix, cx);
+        }
+        if changed {
+            self.emit_column_widths(cx);
+        }
+    }
+
+    /// Resize the column at `col_ix` to fit its content, like double-clicking its resize handle.
+    pub fn auto_fit_column(&mut self, col_ix: usize, cx: &mut Context<Self>) {
+        if self.fit_column_width(col_ix, cx) {
+            self.emit_column_widths(cx);
+        }
+    }
+
+    /// Returns true if the width of the column changed.
+    fn fit_column_width(&mut self, col_ix: usize, cx: &App) -> bool {
+        let Some(col_group) = self.col_groups.get(col_ix) else {
+            return false;
+        };
+        if !col_group.is_visible() || !col_group.is_resizable() {
+            return false;
+        }
+
+        let rows_count = self.delegate.rows_count(cx);
+        let start = self.visible_range.rows.start.min(rows_count);
+        let rows = start..(start + AUTO_FIT_SAMPLE_ROWS).min(rows_count);
+        let widths = std::iter::once(self.delegate.measure_header(col_ix, cx))
+            .chain(rows.map(|row_ix| self.delegate.measure_cell(row_ix, col_ix, cx)));
+        let Some(width) = fit_width(widths) else {
+            return false;
+        };
+
+        let col_group = &mut self.col_groups[col_ix];
+        if col_group.width == width {
+            return false;
+        }
+        col_group.width = width;
+        true
+    }
+
+    /// Toggle the sort of the column, `additive` (shift-click) adds the column to the sorted
+    /// columns instead of replacing them.
+    fn perform_sort(
+        &mut self,
+        col_ix: usize,
+        additive: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         if !self.sortable {
             return;
         }
@@ -542,21 +1850,68 @@ where
             ColumnSort::Default => ColumnSort::Descending,
         };
 
-        for (ix, col_group) in self.col_groups.iter_mut().enumerate() {
-            if ix == col_ix {
-                col_group.column.sort = Some(sort);
-            } else {
-                if col_group.column.sort.is_some() {
-                    col_group.column.sort = Some(ColumnSort::Default);
-                }
-            }
+        let sort_state = next_sort_state(&self.sort_state, col_ix, sort, additive);
+        self.set_sort_state(sort_state);
+
+        // Clearing the last sorted column restores the default order like a plain click
+        if additive && !self.sort_state.is_empty() {
+            let sort_state = self.sort_state.clone();
+            self.delegate_mut()
+                .perform_multi_sort(&sort_state, window, cx);
+        } else {
+            self.delegate_mut().perform_sort(col_ix, sort, window, cx);
         }
 
-        self.delegate_mut().perform_sort(col_ix, sort, window, cx);
+        cx.notify();
+    }
+
+    /// Sort the rows by multiple columns, the first column has the highest priority.
+    ///
+    /// The columns that are not sortable or with [`ColumnSort::Default`] are ignored,
+    /// this calls [`TableDelegate::perform_multi_sort`] to sort the rows.
+    pub fn sort_by_multiple_columns(
+        &mut self,
+        sort_state: Vec<(usize, ColumnSort)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let sort_state = sort_state
+            .into_iter()
+            .filter(|(col_ix, sort)| {
+                *sort != ColumnSort::Default
+                    && self
+                        .col_groups
+                        .get(*col_ix)
+                        .map_or(false, |g| g.column.sort.is_some())
+            })
+            .collect::<Vec<_>>();
+        self.set_sort_state(sort_state);
 
+        let sort_state = self.sort_state.clone();
+        self.delegate_mut()
+            .perform_multi_sort(&sort_state, window, cx);
         cx.notify();
     }
 
+    /// Returns the sorted columns by priority, the first column has the highest priority.
+    pub fn sort_state(&self) -> &[(usize, ColumnSort)] {
+        &self.sort_state
+    }
+
+    /// Set the sort state and update the sort icons of the columns.
+    fn set_sort_state(&mut self, sort_state: Vec<(usize, ColumnSort)>) {
+        for (ix, col_group) in self.col_groups.iter_mut().enumerate() {
+            if col_group.column.sort.is_some() {
+                let sort = sort_state
+                    .iter()
+                    .find(|(col_ix, _)| *col_ix == ix)
+                    .map_or(ColumnSort::Default, |(_, sort)| *sort);
+                col_group.column.sort = Some(sort);
+            }
+        }
+        self.sort_state = sort_state;
+    }
+
     fn move_column(
         &mut self,
         col_ix: usize,
@@ -568,12 +1923,97 @@ where
             return;
         }
 
+        // Keep the columns of each header group together.
+        let mut header_groups = self
+            .col_groups
+            .iter()
+            .map(|col_group| col_group.column.header_group.clone())
+            .collect::<Vec<_>>();
+        let header_group = header_groups.remove(col_ix);
+        header_groups.insert(to_ix, header_group);
+        if !is_header_groups_contiguous(&header_groups) {
+            return;
+        }
+
         self.delegate.move_column(col_ix, to_ix, window, cx);
         let col_group = self.col_groups.remove(col_ix);
         self.col_groups.insert(to_ix, col_group);
+        for (ix, _) in self.sort_state.iter_mut() {
+            *ix = moved_index(*ix, col_ix, to_ix);
+        }
+
+        // Filter inputs are bound to the column index, recreate them on next render.
+        self.filters = self
+            .filters
+            .drain()
+            .map(|(ix, query)| {
+                let ix = if ix == col_ix {
+                    to_ix
+                } else if col_ix < ix && ix <= to_ix {
+                    ix - 1
+                } else if to_ix <= ix && ix < col_ix {
+                    ix + 1
+                } else {
+                    ix
+                };
+                (ix, query)
+            })
+            .collect();
+        self.filter_inputs.clear();
+        self._filter_subscriptions.clear();
+
+        cx.emit(TableEvent::MoveColumn(col_ix, to_ix));
+        cx.notify();
+    }
+
+    fn on_row_drag_move(
+        &mut self,
+        e: &DragMoveEvent<DragRow>,
+        row_ix: usize,
+        cx: &mut Context<Self>,
+    ) {
+        if e.drag(cx).entity_id != cx.entity_id() || !e.bounds.contains(&e.event.position) {
+            return;
+        }
+
+        let drop_ix = if e.event.position.y < e.bounds.center().y {
+            row_ix
+        } else {
+            row_ix + 1
+        };
+        if self.row_drop_ix != Some(drop_ix) {
+            self.row_drop_ix = Some(drop_ix);
+            cx.notify();
+        }
+    }
+
+    fn move_row(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(drop_ix) = self.row_drop_ix.take() else {
+            return;
+        };
+        cx.notify();
+
+        // The drop index is between rows, the rows after the dragged row shift up by one.
+        let to_ix = if drop_ix > row_ix {
+            drop_ix - 1
+        } else {
+            drop_ix
+        };
+        if to_ix == row_ix {
+            return;
+        }
+
+        self.delegate_mut().move_row(row_ix, to_ix, window, cx);
 
-        cx.emit(TableEvent::MoveColumn(col_ix, to_ix));
-        cx.notify();
+        self.selected_row = self.selected_row.map(|ix| moved_index(ix, row_ix, to_ix));
+        self.anchor_row = self.anchor_row.map(|ix| moved_index(ix, row_ix, to_ix));
+        self.selected_rows = self
+            .selected_rows
+            .iter()
+            .map(|ix| moved_index(*ix, row_ix, to_ix))
+            .collect();
+
+        cx.emit(TableEvent::MoveRow(row_ix, to_ix));
     }
 
     /// Dispatch delegate's `load_more` method when the visible range is near the end.
@@ -584,6 +2024,10 @@ where
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.pagination != PaginationMode::Infinite {
+            return;
+        }
+
         let threshold = self.delegate.load_more_threshold();
         // Securely handle subtract logic to prevent attempt to subtract with overflow
         if visible_end >= rows_count.saturating_sub(threshold) {
@@ -593,7 +2037,7 @@ where
 
             self._load_more_task = cx.spawn_in(window, async move |view, window| {
                 _ = view.update_in(window, |view, window, cx| {
-                    view.delegate.load_more(window, cx);
+                    view.delegate_mut().load_more(window, cx);
                 });
             });
         }
@@ -634,6 +2078,10 @@ where
             return div();
         };
 
+        if !col_group.is_visible() {
+            return div();
+        }
+
         let col_width = col_group.width;
         let col_padding = col_group.column.paddings;
 
@@ -685,9 +2133,9 @@ where
             div()
                 .occlude()
                 .absolute()
-                .top(self.size.table_row_height())
+                .top(self.body_top())
                 .right_0()
-                .bottom_0()
+                .bottom(self.body_bottom())
                 .w(scroll::WIDTH)
                 .on_scroll_wheel(cx.listener(|_, _: &ScrollWheelEvent, _, cx| {
                     cx.notify();
@@ -706,9 +2154,9 @@ where
         div()
             .occlude()
             .absolute()
-            .left(self.fixed_head_cols_bounds.size.width)
+            .left(self.checkbox_column_width() + self.fixed_head_cols_bounds.size.width)
             .right_0()
-            .bottom_0()
+            .bottom(self.body_bottom())
             .h(scroll::WIDTH)
             .on_scroll_wheel(cx.listener(|_, _: &ScrollWheelEvent, _, cx| {
                 cx.notify();
@@ -799,17 +2247,101 @@ where
             })
             .on_mouse_up_out(
                 MouseButton::Left,
-                cx.listener(|view, _, _, cx| {
-                    if view.resizing_col.is_none() {
+                cx.listener(|view, _, _, cx| view.finish_col_resizing(cx)),
+            )
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |view, ev: &MouseDownEvent, _, cx| {
+                    if ev.click_count == 2 {
+                        cx.stop_propagation();
+                        view.auto_fit_column(ix, cx);
+                    }
+                }),
+            )
+            .into_any_element()
+    }
+
+    /// Render the resize handle on the right edge of the spanning header at `span_ix`.
+    fn render_col_group_span_resize_handle(
+        &self,
+        span_ix: usize,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        const HANDLE_SIZE: Pixels = px(2.);
+
+        let resizable = self.col_resizable
+            && self.col_group_spans.get(span_ix).is_some_and(|span| {
+                self.col_groups
+                    .get(span.col_range.clone())
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|col_group| col_group.is_visible() && col_group.is_resizable())
+            });
+        if !resizable {
+            return div().into_any_element();
+        }
+
+        let group_id = SharedString::from(format!("col-group-span-resize-handle:{}", span_ix));
+
+        h_flex()
+            .id(("col-group-span-resize-handle", span_ix))
+            .group(group_id.clone())
+            .occlude()
+            .cursor_col_resize()
+            .absolute()
+            .top_0()
+            .right_0()
+            .h_full()
+            .w(HANDLE_SIZE)
+            .justify_end()
+            .items_center()
+            .child(
+                div()
+                    .h_full()
+                    .group_hover(group_id, |this| this.bg(cx.theme().border))
+                    .w(px(1.)),
+            )
+            .on_drag_move(cx.listener(
+                move |view, e: &DragMoveEvent<ResizeColGroupSpan>, window, cx| {
+                    let ResizeColGroupSpan((entity_id, span_ix)) = e.drag(cx);
+                    if cx.entity_id() != *entity_id {
                         return;
                     }
 
-                    view.resizing_col = None;
+                    let span_ix = *span_ix;
+                    let Some(span) = view.col_group_spans.get(span_ix) else {
+                        return;
+                    };
+                    let Some(first_col) = view
+                        .col_groups
+                        .get(span.col_range.clone())
+                        .unwrap_or_default()
+                        .iter()
+                        .find(|col_group| col_group.is_visible())
+                        .cloned()
+                    else {
+                        return;
+                    };
 
-                    let new_widths = view.col_groups.iter().map(|g| g.width).collect();
-                    cx.emit(TableEvent::ColumnWidthsChanged(new_widths));
-                    cx.notify();
-                }),
+                    view.resizing_col = Some(span.col_range.start);
+                    view.resize_col_group_span(
+                        span_ix,
+                        e.event.position.x - HANDLE_SIZE - first_col.bounds.left(),
+                        window,
+                        cx,
+                    );
+                },
+            ))
+            .on_drag(
+                ResizeColGroupSpan((cx.entity_id(), span_ix)),
+                |drag, _, _, cx| {
+                    cx.stop_propagation();
+                    cx.new(|_| drag.clone())
+                },
+            )
+            .on_mouse_up_out(
+                MouseButton::Left,
+                cx.listener(|view, _, _, cx| view.finish_col_resizing(cx)),
             )
             .into_any_element()
     }
@@ -834,9 +2366,18 @@ where
             ColumnSort::Descending => (IconName::SortDescending, true),
             ColumnSort::Default => (IconName::ChevronsUpDown, false),
         };
+        // Show the priority of the column when sorting by multiple columns.
+        let priority = if self.sort_state.len() > 1 {
+            self.sort_state
+                .iter()
+                .position(|(ix, _)| *ix == col_ix)
+                .map(|ix| ix + 1)
+        } else {
+            None
+        };
 
         Some(
-            div()
+            h_flex()
                 .id(("icon-sort", col_ix))
                 .p(px(2.))
                 .rounded(cx.theme().radius / 2.)
@@ -846,14 +2387,23 @@ where
                 })
                 .hover(|this| this.bg(cx.theme().secondary).opacity(7.))
                 .active(|this| this.bg(cx.theme().secondary_active).opacity(1.))
-                .on_click(
-                    cx.listener(move |table, _, window, cx| table.perform_sort(col_ix, window, cx)),
-                )
+                .on_click(cx.listener(move |table, ev: &ClickEvent, window, cx| {
+                    table.perform_sort(col_ix, ev.modifiers().shift, window, cx)
+                }))
                 .child(
                     Icon::new(icon)
                         .size_3()
                         .text_color(cx.theme().secondary_foreground),
-                ),
+                )
+                .when_some(priority, |this, priority| {
+                    this.child(
+                        div()
+                            .text_size(px(9.))
+                            .line_height(px(9.))
+                            .text_color(cx.theme().secondary_foreground)
+                            .child(priority.to_string()),
+                    )
+                }),
         )
     }
 
@@ -861,15 +2411,72 @@ where
     /// The children must be one by one items.
     /// Because the horizontal scroll handle will use the child_item_bounds to
     /// calculate the item position for itself's `scroll_to_item` method.
-    fn render_th(
-        &self,
-        col_ix: usize,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) -> impl IntoElement {
+    /// The width of the checkbox column, zero when it is disabled.
+    fn checkbox_column_width(&self) -> Pixels {
+        if self.checkbox_column {
+            self.row_height()
+        } else {
+            px(0.)
+        }
+    }
+
+    /// Render an empty cell with the width of the checkbox column.
+    fn render_checkbox_col_wrap(&self) -> Div {
+        h_flex()
+            .h_full()
+            .w(self.checkbox_column_width())
+            .flex_shrink_0()
+            .justify_center()
+            .items_center()
+    }
+
+    fn render_head_checkbox(&self, rows_count: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let (checked, indeterminate) = self.head_checkbox_state(rows_count);
+
+        self.render_checkbox_col_wrap()
+            .id("col-header-checkbox")
+            .bg(cx.theme().table_head)
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|table, _, _, cx| {
+                    cx.stop_propagation();
+                    table.on_head_checkbox_click(cx);
+                }),
+            )
+            .child(
+                Checkbox::new("table-checkbox-all")
+                    .checked(checked)
+                    .indeterminate(indeterminate)
+                    .with_size(self.size),
+            )
+    }
+
+    fn render_row_checkbox(&self, row_ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        self.render_checkbox_col_wrap()
+            .id(("row-checkbox", row_ix))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |table, _, _, cx| {
+                    // Keep other selected rows, instead of the row click to replace them.
+                    cx.stop_propagation();
+                    table.toggle_row_selection(row_ix, cx);
+                }),
+            )
+            .child(
+                Checkbox::new(("table-checkbox", row_ix))
+                    .checked(self.selected_rows.contains(&row_ix))
+                    .with_size(self.size),
+            )
+    }
+
+    fn render_th(&self, col_ix: usize, window: &mut Window, cx: &mut Context<Self>) -> AnyElement {
         let entity_id = cx.entity_id();
         let col_group = self.col_groups.get(col_ix).expect("BUG: invalid col index");
 
+        if !col_group.is_visible() {
+            return div().into_any_element();
+        }
+
         let movable = self.col_movable && col_group.column.movable;
         let paddings = col_group.column.paddings;
         let name = col_group.column.name.clone();
@@ -885,6 +2492,12 @@ where
                             this.on_col_head_click(col_ix, window, cx);
                         }),
                     )
+                    .on_mouse_down(
+                        MouseButton::Right,
+                        cx.listener(move |this, _, window, cx| {
+                            this.on_col_head_right_click(col_ix, window, cx);
+                        }),
+                    )
                     .child(
                         h_flex()
                             .size_full()
@@ -944,16 +2557,94 @@ where
                 .absolute()
                 .size_full()
             })
+            .into_any_element()
+    }
+
+    /// Render the spanning headers of the header groups of the given columns,
+    /// or the spans set with [`Table::set_col_group_spans`].
+    fn render_header_group_row(
+        &self,
+        cols: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let col_groups = &self.col_groups[cols.clone()];
+        let width = col_groups
+            .iter()
+            .filter(|col_group| col_group.is_visible())
+            .fold(px(0.), |acc, col_group| acc + col_group.width);
+
+        let border_color = cx.theme().border;
+        let active_color = cx.theme().table_active;
+        let header_cell = |left: Pixels, width: Pixels| {
+            h_flex()
+                .absolute()
+                .top_0()
+                .left(left)
+                .w(width)
+                .h_full()
+                .justify_center()
+                .overflow_hidden()
+                .whitespace_nowrap()
+                .table_cell_size(self.size)
+                .border_b_1()
+                .border_r_1()
+                .border_color(border_color)
+        };
+
+        let headers = if self.col_group_spans.is_empty() {
+            header_group_spans(col_groups)
+                .into_iter()
+                .map(|(group, left, width)| {
+                    header_cell(left, width)
+                        .child(self.delegate.render_header_group(&group, window, cx))
+                        .into_any_element()
+                })
+                .collect::<Vec<_>>()
+        } else {
+            let selected_col = self
+                .selected_col
+                .filter(|_| self.selection_state == SelectionState::Column);
+
+            col_group_span_bounds(&self.col_group_spans, cols.clone(), &self.col_groups)
+                .into_iter()
+                .map(|(span_ix, left, width)| {
+                    let span = &self.col_group_spans[span_ix];
+                    let selected =
+                        selected_col.is_some_and(|col_ix| span.col_range.contains(&col_ix));
+                    // The resize handle is on the right edge, in the part of the span that has it.
+                    let has_right_edge = span.col_range.end <= cols.end;
+
+                    header_cell(left, width)
+                        .when(selected, |this| this.bg(active_color))
+                        .child(self.delegate.render_header_group(&span.label, window, cx))
+                        .when(has_right_edge, |this| {
+                            this.child(self.render_col_group_span_resize_handle(span_ix, cx))
+                        })
+                        .into_any_element()
+                })
+                .collect()
+        };
+
+        div()
+            .relative()
+            .flex_shrink_0()
+            .w(width)
+            .h(self.row_height())
+            .children(headers)
     }
 
     fn render_table_head(
         &mut self,
+        rows_count: usize,
         left_columns_count: usize,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
         let view = cx.entity().clone();
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
+        let has_header_groups = self.has_header_groups();
+        let row_height = self.row_height();
 
         // Reset fixed head columns bounds, if no fixed columns are present
         if left_columns_count == 0 {
@@ -962,25 +2653,41 @@ where
 
         h_flex()
             .w_full()
-            .h(self.size.table_row_height())
+            .h(if has_header_groups {
+                row_height * 2.
+            } else {
+                row_height
+            })
             .flex_shrink_0()
             .border_b_1()
             .border_color(cx.theme().border)
             .text_color(cx.theme().table_head_foreground)
+            .when(self.checkbox_column, |this| {
+                this.child(self.render_head_checkbox(rows_count, cx))
+            })
             .when(left_columns_count > 0, |this| {
                 let view = view.clone();
                 // Render left fixed columns
                 this.child(
-                    h_flex()
+                    v_flex()
                         .relative()
                         .h_full()
                         .bg(cx.theme().table_head)
-                        .children(
-                            self.col_groups
-                                .iter()
-                                .filter(|col| col.column.fixed == Some(ColumnFixed::Left))
-                                .enumerate()
-                                .map(|(col_ix, _)| self.render_th(col_ix, window, cx)),
+                        .when(has_header_groups, |this| {
+                            this.child(self.render_header_group_row(
+                                0..left_columns_count,
+                                window,
+                                cx,
+                            ))
+                        })
+                        .child(
+                            h_flex().h(row_height).children(
+                                self.col_groups
+                                    .iter()
+                                    .filter(|col| col.column.fixed == Some(ColumnFixed::Left))
+                                    .enumerate()
+                                    .map(|(col_ix, _)| self.render_th(col_ix, window, cx)),
+                            ),
                         )
                         .child(
                             // Fixed columns border
@@ -1007,28 +2714,290 @@ where
                 )
             })
             .child(
-                // Columns
-                h_flex()
-                    .id("table-head")
-                    .size_full()
-                    .overflow_scroll()
-                    .relative()
-                    .track_scroll(&horizontal_scroll_handle)
-                    .bg(cx.theme().table_head)
-                    .child(
-                        h_flex()
-                            .relative()
-                            .children(
-                                self.col_groups
-                                    .iter()
-                                    .skip(left_columns_count)
-                                    .enumerate()
-                                    .map(|(col_ix, _)| {
-                                        self.render_th(left_columns_count + col_ix, window, cx)
-                                    }),
-                            )
-                            .child(self.delegate.render_last_empty_col(window, cx)),
+                // Columns
+                h_flex()
+                    .id("table-head")
+                    .size_full()
+                    .overflow_scroll()
+                    .relative()
+                    .track_scroll(&horizontal_scroll_handle)
+                    .bg(cx.theme().table_head)
+                    .child(
+                        v_flex()
+                            .relative()
+                            .h_full()
+                            .when(has_header_groups, |this| {
+                                this.child(self.render_header_group_row(
+                                    left_columns_count..self.col_groups.len(),
+                                    window,
+                                    cx,
+                                ))
+                            })
+                            .child(
+                                h_flex()
+                                    .relative()
+                                    .h(row_height)
+                                    .children(
+                                        self.col_groups
+                                            .iter()
+                                            .skip(left_columns_count)
+                                            .enumerate()
+                                            .map(|(col_ix, _)| {
+                                                self.render_th(
+                                                    left_columns_count + col_ix,
+                                                    window,
+                                                    cx,
+                                                )
+                                            }),
+                                    )
+                                    .child(self.delegate.render_last_empty_col(window, cx)),
+                            ),
+                    ),
+            )
+    }
+
+    fn render_filter_cell(
+        &self,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let filterable = self
+            .col_groups
+            .get(col_ix)
+            .map(|col_group| col_group.is_filterable())
+            .unwrap_or(false);
+
+        self.render_cell(col_ix, window, cx)
+            .when(filterable, |this| {
+                this.children(
+                    self.filter_inputs
+                        .get(&col_ix)
+                        .map(|state| TextInput::new(state).appearance(false).with_size(self.size)),
+                )
+            })
+    }
+
+    /// The sizes of the scrollable columns, hidden columns have zero size.
+    fn col_sizes(&self, left_columns_count: usize) -> Rc<Vec<gpui::Size<Pixels>>> {
+        Rc::new(
+            self.col_groups
+                .iter()
+                .skip(left_columns_count)
+                .map(|col| {
+                    if col.is_visible() {
+                        col.bounds.size
+                    } else {
+                        gpui::Size::default()
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Render the rows pinned at the top, above the scrollable body.
+    fn render_pinned_rows(
+        &mut self,
+        rows_count: usize,
+        left_columns_count: usize,
+        columns_count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let pinned_rows_count = self.pinned_rows_count.min(rows_count);
+        if pinned_rows_count == 0 {
+            return None;
+        }
+
+        let col_sizes = self.col_sizes(left_columns_count);
+        let rows = (0..pinned_rows_count)
+            .map(|row_ix| {
+                self.render_table_row(
+                    row_ix,
+                    rows_count,
+                    left_columns_count,
+                    col_sizes.clone(),
+                    columns_count,
+                    0,
+                    window,
+                    cx,
+                )
+                .into_any_element()
+            })
+            .collect::<Vec<_>>();
+
+        Some(
+            v_flex()
+                .w_full()
+                .flex_shrink_0()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .children(rows),
+        )
+    }
+
+    /// The top offset of the scrollable body, below the head and the pinned rows.
+    fn body_top(&self) -> Pixels {
+        self.head_height() + self.row_height() * self.pinned_rows_count as f32
+    }
+
+    /// The bottom offset of the scrollable body, above the summary row and the pagination bar.
+    fn body_bottom(&self) -> Pixels {
+        let summary_row_height = self.summary_row_height.unwrap_or_default();
+        match self.pagination {
+            PaginationMode::Infinite => summary_row_height,
+            PaginationMode::Pages { .. } => summary_row_height + self.row_height(),
+        }
+    }
+
+    /// Render the summary row below the table body, see [`TableDelegate::render_summary_row`].
+    fn render_summary_row(
+        &self,
+        summary_row: AnyElement,
+        height: Pixels,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let offset_x = self.horizontal_scroll_handle.offset().x;
+
+        h_flex()
+            .w_full()
+            .h(height)
+            .flex_shrink_0()
+            .overflow_hidden()
+            .border_t_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().table_head)
+            .text_color(cx.theme().table_head_foreground)
+            .when(self.checkbox_column, |this| {
+                this.child(self.render_checkbox_col_wrap())
+            })
+            .child(
+                h_flex().h_full().flex_1().overflow_hidden().child(
+                    h_flex()
+                        .h_full()
+                        .relative()
+                        .left(offset_x)
+                        .child(summary_row),
+                ),
+            )
+    }
+
+    /// Render the prev/next and page number buttons below the table body.
+    fn render_pagination_bar(
+        &self,
+        page_size: usize,
+        rows_count: usize,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let current_page = self.current_page;
+        let pages_count = self.pages_count(cx);
+        let has_next = match pages_count {
+            Some(pages_count) => current_page + 1 < pages_count,
+            None => rows_count >= page_size,
+        };
+
+        h_flex()
+            .w_full()
+            .h(self.row_height())
+            .flex_shrink_0()
+            .px_2()
+            .gap_1()
+            .justify_end()
+            .border_t_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().table_head)
+            .text_color(cx.theme().table_head_foreground)
+            .child(
+                Button::new("prev-page")
+                    .ghost()
+                    .icon(IconName::ChevronLeft)
+                    .with_size(self.size)
+                    .disabled(current_page == 0)
+                    .on_click(cx.listener(move |table, _, window, cx| {
+                        table.go_to_page(current_page.saturating_sub(1), window, cx)
+                    })),
+            )
+            .map(|this| match pages_count {
+                Some(pages_count) => this.children(
+                    visible_pages(current_page, pages_count)
+                        .into_iter()
+                        .map(|page| match page {
+                            Some(page) => Button::new(("page", page))
+                                .ghost()
+                                .label((page + 1).to_string())
+                                .with_size(self.size)
+                                .selected(page == current_page)
+                                .on_click(cx.listener(move |table, _, window, cx| {
+                                    table.go_to_page(page, window, cx)
+                                }))
+                                .into_any_element(),
+                            None => div()
+                                .px_1()
+                                .text_color(cx.theme().muted_foreground)
+                                .child("…")
+                                .into_any_element(),
+                        }),
+                ),
+                None => this.child(
+                    div()
+                        .px_2()
+                        .table_cell_size(self.size)
+                        .child(format!("Page {}", current_page + 1)),
+                ),
+            })
+            .child(
+                Button::new("next-page")
+                    .ghost()
+                    .icon(IconName::ChevronRight)
+                    .with_size(self.size)
+                    .disabled(!has_next)
+                    .on_click(cx.listener(move |table, _, window, cx| {
+                        table.go_to_page(current_page + 1, window, cx)
+                    })),
+            )
+    }
+
+    /// Render the filter inputs row below the table head.
+    fn render_filter_row(
+        &self,
+        left_columns_count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let offset_x = self.horizontal_scroll_handle.offset().x;
+
+        h_flex()
+            .w_full()
+            .h(self.row_height())
+            .flex_shrink_0()
+            .overflow_hidden()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().table_head)
+            .when(self.checkbox_column, |this| {
+                this.child(self.render_checkbox_col_wrap())
+            })
+            .when(left_columns_count > 0, |this| {
+                this.child(
+                    h_flex()
+                        .h_full()
+                        .flex_shrink_0()
+                        .border_r_1()
+                        .border_color(cx.theme().border)
+                        .children(
+                            (0..left_columns_count)
+                                .map(|col_ix| self.render_filter_cell(col_ix, window, cx)),
+                        ),
+                )
+            })
+            .child(
+                h_flex().h_full().flex_1().overflow_hidden().child(
+                    h_flex().relative().left(offset_x).children(
+                        (left_columns_count..self.col_groups.len())
+                            .map(|col_ix| self.render_filter_cell(col_ix, window, cx)),
                     ),
+                ),
             )
     }
 
@@ -1046,7 +3015,8 @@ where
     ) -> impl IntoElement {
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
         let is_stripe_row = self.stripe && row_ix % 2 != 0;
-        let is_selected = self.selected_row == Some(row_ix);
+        let is_selected = self.is_row_selected(row_ix);
+        let is_cursor = self.selected_row == Some(row_ix);
         let view = cx.entity().clone();
 
         if row_ix < rows_count {
@@ -1066,22 +3036,32 @@ where
 
             let mut tr = self.delegate.render_tr(row_ix, window, cx);
             let style = tr.style().clone();
+            let entity_id = cx.entity_id();
+            let drop_line = match self.row_drop_ix {
+                Some(drop_ix) if drop_ix == row_ix => Some(true),
+                Some(drop_ix) if drop_ix == row_ix + 1 && is_last_row => Some(false),
+                _ => None,
+            };
 
             tr.h_flex()
+                .id(("table-row", row_ix))
                 .w_full()
-                .h(self.size.table_row_height())
+                .h(self.row_height())
                 .when(need_render_border, |this| {
                     this.border_b_1().border_color(cx.theme().table_row_border)
                 })
                 .when(is_stripe_row, |this| this.bg(cx.theme().table_even))
                 .refine_style(&style)
                 .hover(|this| {
-                    if is_selected || self.right_clicked_row == Some(row_ix) {
+                    if is_selected || is_cursor || self.right_clicked_row == Some(row_ix) {
                         this
                     } else {
                         this.bg(cx.theme().table_hover)
                     }
                 })
+                .when(self.checkbox_column, |this| {
+                    this.child(self.render_row_checkbox(row_ix, cx))
+                })
                 .when(left_columns_count > 0, |this| {
                     // Left fixed columns
                     this.child(
@@ -1092,11 +3072,12 @@ where
                                 let mut items = Vec::with_capacity(left_columns_count);
 
                                 (0..left_columns_count).for_each(|col_ix| {
-                                    items.push(self.render_col_wrap(col_ix, window, cx).child(
-                                        self.render_cell(col_ix, window, cx).child(
-                                            self.measure_render_td(row_ix, col_ix, window, cx),
-                                        ),
-                                    ));
+                                    if !self.is_column_visible(col_ix) {
+                                        items.push(div());
+                                        return;
+                                    }
+
+                                    items.push(self.render_td_cell(row_ix, col_ix, window, cx));
                                 });
 
                                 items
@@ -1142,16 +3123,14 @@ where
 
                                         visible_range.for_each(|col_ix| {
                                             let col_ix = col_ix + left_columns_count;
-                                            let el =
-                                                table.render_col_wrap(col_ix, window, cx).child(
-                                                    table.render_cell(col_ix, window, cx).child(
-                                                        table.measure_render_td(
-                                                            row_ix, col_ix, window, cx,
-                                                        ),
-                                                    ),
-                                                );
-
-                                            items.push(el);
+                                            if !table.is_column_visible(col_ix) {
+                                                items.push(div());
+                                                return;
+                                            }
+
+                                            items.push(
+                                                table.render_td_cell(row_ix, col_ix, window, cx),
+                                            );
                                         });
 
                                         items
@@ -1163,36 +3142,53 @@ where
                         .child(self.delegate.render_last_empty_col(window, cx)),
                 )
                 // Row selected style
-                .when_some(self.selected_row, |this, _| {
-                    this.when(
-                        is_selected && self.selection_state == SelectionState::Row,
-                        |this| {
-                            this.border_color(gpui::transparent_white()).child(
-                                div()
-                                    .top(if row_ix == 0 { px(0.) } else { px(-1.) })
-                                    .left(px(0.))
-                                    .right(px(0.))
-                                    .bottom(px(-1.))
-                                    .absolute()
-                                    .bg(cx.theme().table_active)
-                                    .border_1()
-                                    .border_color(cx.theme().table_active_border),
-                            )
-                        },
-                    )
+                .when(self.selection_state == SelectionState::Row, |this| {
+                    this.when(is_selected || (self.multi_select && is_cursor), |this| {
+                        this.border_color(gpui::transparent_white()).child(
+                            div()
+                                .top(if row_ix == 0 { px(0.) } else { px(-1.) })
+                                .left(px(0.))
+                                .right(px(0.))
+                                .bottom(px(-1.))
+                                .absolute()
+                                .when(is_selected, |this| this.bg(cx.theme().table_active))
+                                .border_1()
+                                .border_color(cx.theme().table_active_border),
+                        )
+                    })
                 })
                 // Row right click row style
                 .when(self.right_clicked_row == Some(row_ix), |this| {
-                    this.border_color(gpui::transparent_white()).child(
-                        div()
-                            .top(if row_ix == 0 { px(0.) } else { px(-1.) })
-                            .left(px(0.))
-                            .right(px(0.))
-                            .bottom(px(-1.))
-                            .absolute()
-                            .border_1()
-                            .border_color(cx.theme().selection),
-                    )
+                    this.border_color(gpui::transparent_white())
+                        .child(
+                            div()
+                                .top(if row_ix == 0 { px(0.) } else { px(-1.) })
+                                .left(px(0.))
+                                .right(px(0.))
+                                .bottom(px(-1.))
+                                .absolute()
+                                .border_1()
+                                .border_color(cx.theme().selection),
+                        )
+                        // Save the row bounds to anchor the keyboard context menu.
+                        .when(self.keyboard_context_menu.is_some(), |this| {
+                            let view = cx.entity().clone();
+                            this.child(
+                                canvas(
+                                    move |bounds, _, cx| {
+                                        view.update(cx, |r, cx| {
+                                            if r.right_clicked_row_bounds != bounds {
+                                                r.right_clicked_row_bounds = bounds;
+                                                cx.notify();
+                                            }
+                                        })
+                                    },
+                                    |_, _, _, _| {},
+                                )
+                                .absolute()
+                                .size_full(),
+                            )
+                        })
                 })
                 .on_mouse_down(
                     MouseButton::Left,
@@ -1206,6 +3202,40 @@ where
                         this.on_row_click(ev, row_ix, window, cx);
                     }),
                 )
+                .when(self.row_movable, |this| {
+                    this.on_drag(DragRow { entity_id, row_ix }, |drag, _, _, cx| {
+                        cx.stop_propagation();
+                        cx.new(|_| drag.clone())
+                    })
+                    .on_drag_move(
+                        cx.listener(move |table, e: &DragMoveEvent<DragRow>, _, cx| {
+                            table.on_row_drag_move(e, row_ix, cx);
+                        }),
+                    )
+                    .on_drop(cx.listener(
+                        move |table, drag: &DragRow, window, cx| {
+                            if drag.entity_id != cx.entity_id() {
+                                return;
+                            }
+
+                            table.move_row(drag.row_ix, window, cx);
+                        },
+                    ))
+                })
+                // Row insertion line while dragging a row
+                .when_some(drop_line, |this, at_top| {
+                    this.child(
+                        div()
+                            .absolute()
+                            .left_0()
+                            .right_0()
+                            .h(px(2.))
+                            .when(at_top, |this| this.top(px(-1.)))
+                            .when(!at_top, |this| this.bottom(px(-1.)))
+                            .bg(cx.theme().drag_border),
+                    )
+                })
+                .into_any_element()
         } else {
             // Render fake rows to fill the rest table space
             self.delegate
@@ -1216,20 +3246,76 @@ where
                 .border_t_1()
                 .border_color(cx.theme().table_row_border)
                 .when(is_stripe_row, |this| this.bg(cx.theme().table_even))
+                .when(self.checkbox_column, |this| {
+                    this.child(self.render_checkbox_col_wrap())
+                })
                 .children((0..columns_count).map(|col_ix| {
                     h_flex()
                         .left(horizontal_scroll_handle.offset().x)
                         .child(self.render_cell(col_ix, window, cx))
                 }))
                 .child(self.delegate.render_last_empty_col(window, cx))
+                .into_any_element()
+        }
+    }
+
+    fn render_group_header_row(
+        &self,
+        group: &SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let collapsed = self.collapsed_groups.contains(group);
+        let icon = if collapsed {
+            IconName::ChevronRight
+        } else {
+            IconName::ChevronDown
+        };
+
+        h_flex()
+            .id(SharedString::from(format!("group-header:{}", group)))
+            .w_full()
+            .h(self.row_height())
+            .gap_1()
+            .px_2()
+            .items_center()
+            .flex_shrink_0()
+            .bg(cx.theme().table_head)
+            .text_color(cx.theme().table_head_foreground)
+            .border_b_1()
+            .border_color(cx.theme().table_row_border)
+            .cursor_pointer()
+            .child(Icon::new(icon).size_3())
+            .child(self.delegate.render_group_header(group, window, cx))
+            .on_click(cx.listener({
+                let group = group.clone();
+                move |table, _, _, cx| table.toggle_group(group.clone(), cx)
+            }))
+            .into_any_element()
+    }
+
+    /// Returns the group of the first visible row, to render as a sticky header.
+    fn sticky_group(&self) -> Option<SharedString> {
+        let start = self.visible_range.rows.start;
+        if self.display_rows.is_empty() || start == 0 {
+            return None;
         }
+
+        self.display_rows
+            .get(..=start.min(self.display_rows.len() - 1))?
+            .iter()
+            .rev()
+            .find_map(|row| match row {
+                DisplayRow::GroupHeader(group) => Some(group.clone()),
+                DisplayRow::Row(_) => None,
+            })
     }
 
     /// Calculate the extra rows needed to fill the table empty space when `stripe` is true.
     fn calculate_extra_rows_needed(&self, rows_count: usize) -> usize {
         let mut extra_rows_needed = 0;
 
-        let row_height = self.size.table_row_height();
+        let row_height = self.row_height();
         let total_height = self
             .vertical_scroll_handle
             .0
@@ -1249,6 +3335,111 @@ where
         extra_rows_needed
     }
 
+    /// Render the cell at the given position, or the inline editor when the cell is being edited.
+    fn render_td_cell(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        let editing_state = self
+            .editing_cell
+            .as_ref()
+            .filter(|(r, c, _, _)| *r == row_ix && *c == col_ix)
+            .map(|(_, _, state, _)| state.clone());
+
+        let cell = self.render_cell(col_ix, window, cx);
+        let cell = match editing_state {
+            Some(state) => cell.child(
+                TextInput::new(&state)
+                    .appearance(false)
+                    .with_size(self.size),
+            ),
+            None => cell
+                .child(self.measure_render_td(row_ix, col_ix, window, cx))
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |table, ev: &MouseDownEvent, window, cx| {
+                        if ev.click_count == 2 {
+                            table.start_edit(row_ix, col_ix, window, cx);
+                        }
+                    }),
+                ),
+        };
+
+        self.render_col_wrap(col_ix, window, cx).child(cell)
+    }
+
+    /// Track the loading state, returns the opacity of the skeleton, `None` to hide it.
+    fn update_skeleton_state(&mut self, loading: bool) -> Option<f32> {
+        const FADE_OUT_DURATION: Duration = Duration::from_millis(200);
+
+        if loading {
+            self.loading_started_at.get_or_insert_with(Instant::now);
+            self.loaded_at = None;
+            return self.skeleton.then_some(1.);
+        }
+
+        if self.loading_started_at.is_some() && self.loaded_at.is_none() {
+            self.loaded_at = Some(Instant::now());
+        }
+
+        let loaded_at = self.loaded_at?;
+        let progress = loaded_at.elapsed().as_secs_f32() / FADE_OUT_DURATION.as_secs_f32();
+        if progress >= 1. || !self.skeleton {
+            self.loading_started_at = None;
+            self.loaded_at = None;
+            return None;
+        }
+
+        Some(1. - progress)
+    }
+
+    /// Render the skeleton with the current column widths, only the rows visible in the table
+    /// height are painted, an empty table is filled.
+    fn render_skeleton(
+        &self,
+        rows_count: usize,
+        opacity: f32,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let row_height = self.row_height();
+        let visible_rows_count = (self.bounds.size.height / row_height).ceil() as usize;
+        let rows_count = if rows_count == 0 {
+            visible_rows_count
+        } else {
+            rows_count.min(visible_rows_count)
+        };
+        let col_widths = self
+            .checkbox_column
+            .then(|| self.checkbox_column_width())
+            .into_iter()
+            .chain(
+                self.col_groups
+                    .iter()
+                    .filter(|col_group| col_group.is_visible())
+                    .map(|col_group| col_group.width),
+            )
+            .collect();
+
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .overflow_hidden()
+            .bg(cx.theme().table)
+            .opacity(opacity)
+            .child(TableSkeleton::new(
+                col_widths,
+                rows_count,
+                self.size,
+                self.row_height(),
+                self.loading_started_at.unwrap_or_else(Instant::now),
+            ))
+    }
+
     #[inline]
     fn measure_render_td(
         &mut self,
@@ -1293,12 +3484,263 @@ where
     }
 }
 
+/// Returns the header group name, left offset and width of each run of adjacent columns
+/// with the same header group, hidden columns have zero width.
+fn header_group_spans(col_groups: &[ColGroup]) -> Vec<(SharedString, Pixels, Pixels)> {
+    let mut spans: Vec<(SharedString, Pixels, Pixels)> = Vec::new();
+    let mut prev_group: Option<&SharedString> = None;
+    let mut left = px(0.);
+
+    for col_group in col_groups {
+        let width = if col_group.is_visible() {
+            col_group.width
+        } else {
+            px(0.)
+        };
+
+        let group = col_group.column.header_group.as_ref();
+        match (group, spans.last_mut()) {
+            (Some(group), Some(last)) if prev_group == Some(group) => last.2 += width,
+            (Some(group), _) => spans.push((group.clone(), left, width)),
+            (None, _) => {}
+        }
+
+        prev_group = group;
+        left += width;
+    }
+
+    spans
+}
+
+/// Returns the index, left offset and width of the part of each spanning header within the `cols`
+/// of `col_groups`, hidden columns have zero width and spans without a visible column are skipped.
+fn col_group_span_bounds(
+    spans: &[ColGroupSpan],
+    cols: Range<usize>,
+    col_groups: &[ColGroup],
+) -> Vec<(usize, Pixels, Pixels)> {
+    let width_of = |range: Range<usize>| {
+        col_groups[range]
+            .iter()
+            .filter(|col_group| col_group.is_visible())
+            .fold(px(0.), |acc, col_group| acc + col_group.width)
+    };
+
+    spans
+        .iter()
+        .enumerate()
+        .filter_map(|(span_ix, span)| {
+            let start = span.col_range.start.max(cols.start);
+            let end = span.col_range.end.min(cols.end).min(col_groups.len());
+            if start >= end {
+                return None;
+            }
+
+            let width = width_of(start..end);
+            (width > px(0.)).then(|| (span_ix, width_of(cols.start..start), width))
+        })
+        .collect()
+}
+
+/// Returns the widest of the measured `widths` between the min and max column width, or `None`
+/// if nothing was measured.
+fn fit_width(widths: impl IntoIterator<Item = Option<Pixels>>) -> Option<Pixels> {
+    widths
+        .into_iter()
+        .flatten()
+        .reduce(|a, b| a.max(b))
+        .map(|width| width.ceil().max(MIN_COL_WIDTH).min(MAX_COL_WIDTH))
+}
+
+/// Scale the `widths` proportionally to add up to `total`, each width stays between the
+/// min and max column width.
+///
+/// Returns `None` if there are no widths or `total` can't fit the min width of each column.
+fn distribute_width(widths: &[Pixels], total: Pixels) -> Option<Vec<Pixels>> {
+    let old_total = widths.iter().fold(px(0.), |acc, width| acc + *width);
+    if widths.is_empty() || old_total <= px(0.) || total < MIN_COL_WIDTH * widths.len() as f32 {
+        return None;
+    }
+
+    let ratio = total / old_total;
+    Some(
+        widths
+            .iter()
+            .map(|width| {
+                (*width * ratio)
+                    .floor()
+                    .max(MIN_COL_WIDTH)
+                    .min(MAX_COL_WIDTH)
+            })
+            .collect(),
+    )
+}
+
+/// Returns true if the columns of each header group are adjacent.
+fn is_header_groups_contiguous(header_groups: &[Option<SharedString>]) -> bool {
+    let mut seen = HashSet::new();
+    let mut prev_group = None;
+
+    for group in header_groups.iter().map(Option::as_ref) {
+        if let Some(group) = group {
+            if prev_group != Some(group) && !seen.insert(group) {
+                return false;
+            }
+        }
+        prev_group = group;
+    }
+
+    true
+}
+
+/// Returns the sort state after the sort of the column is changed to `sort`.
+///
+/// Not `additive`, the column replaces the sorted columns. Otherwise the column is updated in
+/// place or added with the lowest priority, and removed when `sort` is [`ColumnSort::Default`].
+fn next_sort_state(
+    sort_state: &[(usize, ColumnSort)],
+    col_ix: usize,
+    sort: ColumnSort,
+    additive: bool,
+) -> Vec<(usize, ColumnSort)> {
+    let mut sort_state = if additive {
+        sort_state.to_vec()
+    } else {
+        vec![]
+    };
+
+    match sort_state.iter().position(|(ix, _)| *ix == col_ix) {
+        Some(pos) if sort == ColumnSort::Default => {
+            sort_state.remove(pos);
+        }
+        Some(pos) => sort_state[pos].1 = sort,
+        None if sort != ColumnSort::Default => sort_state.push((col_ix, sort)),
+        None => {}
+    }
+    sort_state
+}
+
+/// Returns the index of the row (or column) at `ix` after moving the row at `from` to `to`.
+fn moved_index(ix: usize, from: usize, to: usize) -> usize {
+    if ix == from {
+        to
+    } else if from < ix && ix <= to {
+        ix - 1
+    } else if to <= ix && ix < from {
+        ix + 1
+    } else {
+        ix
+    }
+}
+
+/// Returns the pages to show in the pagination bar, `None` is an ellipsis.
+///
+/// The first, last and the pages around the current page are always shown.
+fn visible_pages(current_page: usize, pages_count: usize) -> Vec<Option<usize>> {
+    const MAX_PAGES: usize = 7;
+    if pages_count <= MAX_PAGES {
+        return (0..pages_count).map(Some).collect();
+    }
+
+    let last = pages_count - 1;
+    let start = current_page.saturating_sub(1).max(1);
+    let end = (current_page + 1).clamp(start, last - 1);
+
+    let mut pages = vec![Some(0)];
+    if start > 1 {
+        pages.push(None);
+    }
+    pages.extend((start..=end).map(Some));
+    if end < last - 1 {
+        pages.push(None);
+    }
+    pages.push(Some(last));
+    pages
+}
+
+/// Apply the widths by index to the columns that exist.
+fn apply_column_widths(col_groups: &mut [ColGroup], widths: &[Pixels]) {
+    for (col_group, width) in col_groups.iter_mut().zip(widths) {
+        col_group.width = *width;
+    }
+}
+
+/// Returns the rows from the `anchor` to the `row_ix`, in the order they were selected.
+fn selection_range(anchor: usize, row_ix: usize) -> IndexSet<usize> {
+    if anchor <= row_ix {
+        (anchor..=row_ix).collect()
+    } else {
+        (row_ix..=anchor).rev().collect()
+    }
+}
+
+/// Quote a CSV field when it contains the separator, quotes or line breaks.
+fn escape_csv_field(value: &str, separator: char) -> String {
+    if value.contains(separator) || value.contains(['"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Join the fields into a CSV line ending with a line break, `None` fields are left empty.
+fn csv_line(fields: impl IntoIterator<Item = Option<String>>, separator: char) -> String {
+    let mut line = fields
+        .into_iter()
+        .map(|field| {
+            field
+                .map(|value| escape_csv_field(&value, separator))
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(&separator.to_string());
+    line.push('\n');
+    line
+}
+
+/// Convert the exported cells to JSON objects keyed by the column `names`.
+fn rows_to_json(names: &[String], rows: Vec<Vec<Option<String>>>) -> serde_json::Value {
+    rows.into_iter()
+        .map(|cells| {
+            names
+                .iter()
+                .cloned()
+                .zip(cells.iter().map(|cell| match cell {
+                    Some(value) => json_cell_value(value),
+                    None => serde_json::Value::Null,
+                }))
+                .collect::<serde_json::Map<_, _>>()
+                .into()
+        })
+        .collect::<Vec<serde_json::Value>>()
+        .into()
+}
+
+/// Convert an exported cell to a JSON number or boolean when it parses as one, or a string.
+fn json_cell_value(value: &str) -> serde_json::Value {
+    match value {
+        "true" => return true.into(),
+        "false" => return false.into(),
+        _ => {}
+    }
+
+    if let Ok(number) = value.parse::<i64>() {
+        return number.into();
+    }
+    value
+        .parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+        .map_or_else(|| value.into(), serde_json::Value::Number)
+}
+
 impl<D> Sizable for Table<D>
 where
     D: TableDelegate,
 {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
         self.size = size.into();
+        self.custom_row_height = None;
         self
     }
 }
@@ -1318,6 +3760,9 @@ where
 {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.measure(window, cx);
+        if !cx.has_active_drag() {
+            self.row_drop_ix = None;
+        }
 
         let view = cx.entity().clone();
         let vertical_scroll_handle = self.vertical_scroll_handle.clone();
@@ -1329,14 +3774,35 @@ where
             .filter(|col| self.col_fixed && col.column.fixed == Some(ColumnFixed::Left))
             .count();
         let rows_count = self.delegate.rows_count(cx);
+        self.pinned_rows_count = self.delegate.pinned_rows_count(cx).min(rows_count);
+        self.prepare_display_rows(rows_count, cx);
+        let has_filter_row = self.has_filter_row();
+        if has_filter_row {
+            self.ensure_filter_inputs(window, cx);
+        }
+        let display_rows_count = self.display_rows_count(rows_count);
         let loading = self.delegate.loading(cx);
-        let extra_rows_count = self.calculate_extra_rows_needed(rows_count);
+        let skeleton_opacity = self.update_skeleton_state(loading);
+        let extra_rows_count = self.calculate_extra_rows_needed(display_rows_count);
         let render_rows_count = if self.stripe {
-            rows_count + extra_rows_count
+            display_rows_count + extra_rows_count
         } else {
-            rows_count
+            display_rows_count
         };
 
+        let summary_row = self
+            .delegate
+            .render_summary_row(window, cx)
+            .map(|summary_row| summary_row.into_any_element());
+        self.summary_row_height = summary_row.as_ref().map(|_| {
+            self.delegate
+                .summary_row_height()
+                .unwrap_or(self.row_height())
+        });
+
+        let pinned_rows =
+            self.render_pinned_rows(rows_count, left_columns_count, columns_count, window, cx);
+
         let inner_table = v_flex()
             .key_context("Table")
             .id("table")
@@ -1346,16 +3812,30 @@ where
             .on_action(cx.listener(Self::action_select_prev))
             .on_action(cx.listener(Self::action_select_next_col))
             .on_action(cx.listener(Self::action_select_prev_col))
+            .on_action(cx.listener(Self::action_export_csv))
+            .on_action(cx.listener(Self::action_copy_to_clipboard))
+            .on_action(cx.listener(Self::action_edit_next_cell))
+            .on_action(cx.listener(Self::action_start_cell_edit))
+            .on_action(cx.listener(Self::action_stop_cell_edit))
+            .on_action(cx.listener(Self::action_hide_column))
+            .on_action(cx.listener(Self::action_show_column_chooser))
             .size_full()
             .overflow_hidden()
-            .child(self.render_table_head(left_columns_count, window, cx))
+            .child(self.render_table_head(rows_count, left_columns_count, window, cx))
+            .when(has_filter_row, |this| {
+                this.child(self.render_filter_row(left_columns_count, window, cx))
+            })
+            .children(pinned_rows)
             .context_menu({
                 let view = view.clone();
                 move |this, window: &mut Window, cx: &mut Context<PopupMenu>| {
-                    if let Some(row_ix) = view.read(cx).right_clicked_row {
+                    if let Some(col_ix) = view.read(cx).right_clicked_col {
+                        this.menu(t!("Table.Hide Column"), Box::new(HideColumn(col_ix)))
+                            .separator()
+                            .menu(t!("Table.Choose Columns"), Box::new(ShowColumnChooser))
+                    } else if let Some(row_ix) = view.read(cx).right_clicked_row {
                         view.read(cx)
-                            .delegate
-                            .context_menu(row_ix, this, window, cx)
+                            .build_row_context_menu(row_ix, this, window, cx)
                     } else {
                         this
                     }
@@ -1378,18 +3858,11 @@ where
                                     move |table, visible_range: Range<usize>, window, cx| {
                                         // We must calculate the col sizes here, because the col sizes
                                         // need render_th first, then that method will set the bounds of each col.
-                                        let col_sizes: Rc<Vec<gpui::Size<Pixels>>> = Rc::new(
-                                            table
-                                                .col_groups
-                                                .iter()
-                                                .skip(left_columns_count)
-                                                .map(|col| col.bounds.size)
-                                                .collect(),
-                                        );
+                                        let col_sizes = table.col_sizes(left_columns_count);
 
                                         table.load_more_if_need(
                                             rows_count,
-                                            visible_range.end,
+                                            visible_range.end + table.pinned_rows_count,
                                             window,
                                             cx,
                                         );
@@ -1400,14 +3873,15 @@ where
                                             cx,
                                         );
 
-                                        if visible_range.end > rows_count {
-                                            table.scroll_to_row(
+                                        if visible_range.end > display_rows_count {
+                                            table.vertical_scroll_handle.scroll_to_item(
                                                 std::cmp::min(
                                                     visible_range.start,
-                                                    rows_count.saturating_sub(1),
+                                                    display_rows_count.saturating_sub(1),
                                                 ),
-                                                cx,
+                                                ScrollStrategy::Top,
                                             );
+                                            cx.notify();
                                         }
 
                                         let mut items = Vec::with_capacity(
@@ -1415,18 +3889,31 @@ where
                                         );
 
                                         // Render fake rows to fill the table
-                                        visible_range.for_each(|row_ix| {
-                                            // Render real rows for available data
-                                            items.push(table.render_table_row(
-                                                row_ix,
-                                                rows_count,
-                                                left_columns_count,
-                                                col_sizes.clone(),
-                                                columns_count,
-                                                extra_rows_count,
-                                                window,
-                                                cx,
-                                            ));
+                                        visible_range.for_each(|ix| {
+                                            match table.display_row(ix, rows_count) {
+                                                DisplayRow::GroupHeader(group) => {
+                                                    items.push(table.render_group_header_row(
+                                                        &group, window, cx,
+                                                    ));
+                                                }
+                                                DisplayRow::Row(row_ix) => {
+                                                    // Render real rows for available data
+                                                    items.push(
+                                                        table
+                                                            .render_table_row(
+                                                                row_ix,
+                                                                rows_count,
+                                                                left_columns_count,
+                                                                col_sizes.clone(),
+                                                                columns_count,
+                                                                extra_rows_count,
+                                                                window,
+                                                                cx,
+                                                            )
+                                                            .into_any_element(),
+                                                    );
+                                                }
+                                            }
                                         });
 
                                         items
@@ -1441,6 +3928,18 @@ where
                         ),
                     )
                 }
+            })
+            .when_some(
+                summary_row.zip(self.summary_row_height),
+                |this, (summary_row, height)| {
+                    this.child(self.render_summary_row(summary_row, height, cx))
+                },
+            )
+            .map(|this| match self.pagination {
+                PaginationMode::Pages { page_size } => {
+                    this.child(self.render_pagination_bar(page_size, rows_count, window, cx))
+                }
+                PaginationMode::Infinite => this,
             });
 
         let view = cx.entity().clone();
@@ -1452,27 +3951,60 @@ where
                     .border_color(cx.theme().border)
             })
             .bg(cx.theme().table)
-            .when(loading, |this| {
+            .when(loading && !self.skeleton, |this| {
                 this.child(self.delegate().render_loading(self.size, window, cx))
             })
             .when(!loading, |this| {
                 this.child(inner_table)
+                    .when_some(self.sticky_group(), |this, group| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .top(self.body_top())
+                                .left_0()
+                                .right_0()
+                                .child(self.render_group_header_row(&group, window, cx)),
+                        )
+                    })
                     .child(ScrollableMask::new(
                         cx.entity().entity_id(),
                         Axis::Horizontal,
                         &horizontal_scroll_handle,
                     ))
-                    .when(self.right_clicked_row.is_some(), |this| {
-                        this.on_mouse_down_out(cx.listener(|this, _, _, cx| {
-                            this.right_clicked_row = None;
-                            cx.notify();
-                        }))
-                    })
+                    .when(
+                        self.right_clicked_row.is_some() || self.right_clicked_col.is_some(),
+                        |this| {
+                            this.on_mouse_down_out(cx.listener(|this, _, _, cx| {
+                                this.right_clicked_row = None;
+                                this.right_clicked_col = None;
+                                cx.notify();
+                            }))
+                        },
+                    )
+            })
+            .when_some(skeleton_opacity, |this, opacity| {
+                this.child(self.render_skeleton(render_rows_count, opacity, cx))
             })
             .child(canvas(
                 move |bounds, _, cx| view.update(cx, |r, _| r.bounds = bounds),
                 |_, _, _, _| {},
             ))
+            .when_some(
+                self.keyboard_context_menu
+                    .as_ref()
+                    .filter(|_| self.right_clicked_row_bounds.size.height > px(0.)),
+                |this, (menu, _)| {
+                    this.child(
+                        deferred(
+                            anchored()
+                                .position(self.right_clicked_row_bounds.bottom_left())
+                                .snap_to_window_with_margin(px(8.))
+                                .child(div().occlude().child(menu.clone())),
+                        )
+                        .with_priority(1),
+                    )
+                },
+            )
             .when(!window.is_inspector_picking(cx), |this| {
                 this.child(
                     div()
@@ -1489,3 +4021,261 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{px, Bounds, Pixels};
+
+    use super::*;
+
+    fn col_groups(count: usize) -> Vec<ColGroup> {
+        (0..count)
+            .map(|ix| ColGroup {
+                column: Column::new(format!("col{}", ix), format!("Column {}", ix)),
+                width: px(100.),
+                bounds: Bounds::default(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_visible_pages() {
+        assert_eq!(visible_pages(0, 0), vec![]);
+        assert_eq!(
+            visible_pages(2, 5),
+            vec![Some(0), Some(1), Some(2), Some(3), Some(4)]
+        );
+        assert_eq!(visible_pages(0, 20), vec![Some(0), Some(1), None, Some(19)]);
+        assert_eq!(
+            visible_pages(10, 20),
+            vec![Some(0), None, Some(9), Some(10), Some(11), None, Some(19)]
+        );
+        assert_eq!(
+            visible_pages(19, 20),
+            vec![Some(0), None, Some(18), Some(19)]
+        );
+        assert_eq!(
+            visible_pages(2, 20),
+            vec![Some(0), Some(1), Some(2), Some(3), None, Some(19)]
+        );
+    }
+
+    #[test]
+    fn test_header_group_spans() {
+        let mut groups = col_groups(5);
+        groups[0].column.header_group = Some("Q1".into());
+        groups[1].column.header_group = Some("Q1".into());
+        groups[2].column.header_group = Some("Q2".into());
+        groups[2].column.visible = false;
+        groups[3].column.header_group = Some("Q2".into());
+
+        assert_eq!(
+            header_group_spans(&groups),
+            vec![
+                ("Q1".into(), px(0.), px(200.)),
+                ("Q2".into(), px(200.), px(100.)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_col_group_span_bounds() {
+        let mut groups = col_groups(5);
+        groups[3].column.visible = false;
+        let spans = vec![
+            ColGroupSpan::new("Name", 0..2),
+            ColGroupSpan::new("Hidden", 3..4),
+            ColGroupSpan::new("Stats", 1..5),
+        ];
+
+        assert_eq!(
+            col_group_span_bounds(&spans, 0..5, &groups),
+            vec![(0, px(0.), px(200.)), (2, px(100.), px(300.))]
+        );
+        // The scrollable columns after 2 fixed columns.
+        assert_eq!(
+            col_group_span_bounds(&spans, 2..5, &groups),
+            vec![(2, px(0.), px(200.))]
+        );
+    }
+
+    #[test]
+    fn test_distribute_width() {
+        assert_eq!(
+            distribute_width(&[px(100.), px(300.)], px(600.)),
+            Some(vec![px(150.), px(450.)])
+        );
+        assert_eq!(
+            distribute_width(&[px(100.), px(300.)], px(200.)),
+            Some(vec![px(50.), px(150.)])
+        );
+        assert_eq!(distribute_width(&[px(100.), px(300.)], px(15.)), None);
+        assert_eq!(distribute_width(&[], px(100.)), None);
+    }
+
+    #[test]
+    fn test_fit_width() {
+        assert_eq!(
+            fit_width([Some(px(80.)), None, Some(px(120.4)), Some(px(60.))]),
+            Some(px(121.))
+        );
+        assert_eq!(fit_width([Some(px(2.))]), Some(MIN_COL_WIDTH));
+        assert_eq!(fit_width([Some(px(5000.))]), Some(MAX_COL_WIDTH));
+        assert_eq!(fit_width([None, None]), None);
+    }
+
+    #[test]
+    fn test_is_header_groups_contiguous() {
+        let q1 = Some(SharedString::from("Q1"));
+        let q2 = Some(SharedString::from("Q2"));
+
+        assert!(is_header_groups_contiguous(&[]));
+        assert!(is_header_groups_contiguous(&[
+            None,
+            q1.clone(),
+            q1.clone(),
+            q2.clone(),
+            None
+        ]));
+        assert!(!is_header_groups_contiguous(&[
+            q1.clone(),
+            None,
+            q1.clone()
+        ]));
+        assert!(!is_header_groups_contiguous(&[
+            q1.clone(),
+            q2.clone(),
+            q1.clone()
+        ]));
+    }
+
+    #[test]
+    fn test_next_sort_state() {
+        use ColumnSort::*;
+
+        assert_eq!(
+            next_sort_state(&[], 1, Descending, false),
+            vec![(1, Descending)]
+        );
+        assert_eq!(
+            next_sort_state(&[(1, Descending)], 3, Descending, false),
+            vec![(3, Descending)]
+        );
+
+        // Shift-click adds the column with the lowest priority, and updates it in place.
+        let sort_state = next_sort_state(&[(1, Descending)], 3, Descending, true);
+        assert_eq!(sort_state, vec![(1, Descending), (3, Descending)]);
+        let sort_state = next_sort_state(&sort_state, 1, Ascending, true);
+        assert_eq!(sort_state, vec![(1, Ascending), (3, Descending)]);
+        let sort_state = next_sort_state(&sort_state, 1, Default, true);
+        assert_eq!(sort_state, vec![(3, Descending)]);
+        assert_eq!(next_sort_state(&sort_state, 2, Default, false), vec![]);
+    }
+
+    #[test]
+    fn test_moved_index() {
+        let moved = |from, to| {
+            (0..5)
+                .map(|ix| moved_index(ix, from, to))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(moved(1, 3), vec![0, 3, 1, 2, 4]);
+        assert_eq!(moved(3, 1), vec![0, 2, 3, 1, 4]);
+        assert_eq!(moved(0, 4), vec![4, 0, 1, 2, 3]);
+        assert_eq!(moved(2, 2), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_column_widths_round_trip() {
+        let saved = vec![px(120.), px(80.5), px(300.)];
+        let json = serde_json::to_string(&saved).unwrap();
+        let restored: Vec<Pixels> = serde_json::from_str(&json).unwrap();
+
+        let mut groups = col_groups(3);
+        apply_column_widths(&mut groups, &restored);
+        assert_eq!(groups.iter().map(|g| g.width).collect::<Vec<_>>(), saved);
+    }
+
+    #[test]
+    fn test_restore_column_widths_with_changed_columns() {
+        let mut groups = col_groups(2);
+        apply_column_widths(&mut groups, &[px(50.), px(60.), px(70.)]);
+        assert_eq!(
+            groups.iter().map(|g| g.width).collect::<Vec<_>>(),
+            vec![px(50.), px(60.)]
+        );
+
+        let mut groups = col_groups(3);
+        apply_column_widths(&mut groups, &[px(50.)]);
+        assert_eq!(
+            groups.iter().map(|g| g.width).collect::<Vec<_>>(),
+            vec![px(50.), px(100.), px(100.)]
+        );
+    }
+
+    #[test]
+    fn test_rows_to_json() {
+        let names = vec!["Name".to_string(), "Age".to_string(), "Active".to_string()];
+        let rows = vec![
+            vec![Some("Ada".into()), Some("36".into()), Some("true".into())],
+            vec![Some("1.5".into()), None, Some("yes".into())],
+        ];
+        assert_eq!(
+            rows_to_json(&names, rows),
+            serde_json::json!([
+                { "Name": "Ada", "Age": 36, "Active": true },
+                { "Name": 1.5, "Age": null, "Active": "yes" },
+            ])
+        );
+        assert_eq!(json_cell_value("NaN"), serde_json::json!("NaN"));
+    }
+
+    #[test]
+    fn test_escape_csv_field() {
+        assert_eq!(escape_csv_field("plain", ','), "plain");
+        assert_eq!(escape_csv_field("", ','), "");
+        assert_eq!(escape_csv_field("a,b", ','), "\"a,b\"");
+        assert_eq!(escape_csv_field("say \"hi\"", ','), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv_field("line\nbreak", ','), "\"line\nbreak\"");
+        assert_eq!(escape_csv_field("cr\rlf", ','), "\"cr\rlf\"");
+        // TSV only quotes tabs, not commas.
+        assert_eq!(escape_csv_field("a,b", '\t'), "a,b");
+        assert_eq!(escape_csv_field("a\tb", '\t'), "\"a\tb\"");
+        assert_eq!(escape_csv_field("a\tb", ','), "a\tb");
+    }
+
+    #[test]
+    fn test_csv_line() {
+        assert_eq!(
+            csv_line(
+                [
+                    Some("Ada".into()),
+                    None,
+                    Some("1,5".into()),
+                    Some("".into())
+                ],
+                ','
+            ),
+            "Ada,,\"1,5\",\n"
+        );
+        assert_eq!(
+            csv_line([Some("a\tb".into()), Some("c,d".into())], '\t'),
+            "\"a\tb\"\tc,d\n"
+        );
+    }
+
+    #[test]
+    fn test_selection_range() {
+        let rows = |anchor, row_ix| {
+            selection_range(anchor, row_ix)
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(rows(2, 5), vec![2, 3, 4, 5]);
+        // Extending upwards keeps the anchor first and the cursor row last.
+        assert_eq!(rows(5, 2), vec![5, 4, 3, 2]);
+        assert_eq!(rows(3, 3), vec![3]);
+        assert_eq!(selection_range(5, 2).last(), Some(&2));
+    }
+}
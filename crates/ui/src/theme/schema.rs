@@ -75,6 +75,9 @@ pub struct ThemeConfigColors {
     /// Title text color for GroupBox.
     #[serde(rename = "group_box.title.foreground")]
     pub group_box_title_foreground: Option<SharedString>,
+    /// Background color of the matching brackets in the code editor.
+    #[serde(rename = "bracket_match.background")]
+    pub bracket_match: Option<SharedString>,
     /// Input caret color (Blinking cursor).
     #[serde(rename = "caret")]
     pub caret: Option<SharedString>,
@@ -515,6 +518,10 @@ impl ThemeColor {
                 )
         );
         apply_color!(group_box_foreground, fallback = self.secondary_foreground);
+        apply_color!(
+            bracket_match,
+            fallback = self.background.blend(self.primary.opacity(0.2))
+        );
         apply_color!(caret, fallback = self.primary);
         apply_color!(chart_1, fallback = self.blue.lighten(0.4));
         apply_color!(chart_2, fallback = self.blue.lighten(0.2));
@@ -20,6 +20,8 @@ pub struct ThemeColor {
     pub background: Hsla,
     /// Default border color
     pub border: Hsla,
+    /// Background color of the matching brackets in the code editor.
+    pub bracket_match: Hsla,
     /// Background color for GroupBox.
     pub group_box: Hsla,
     /// Text color for GroupBox.
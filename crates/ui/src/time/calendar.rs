@@ -1,31 +1,55 @@
 use std::{borrow::Cow, rc::Rc};
 
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{Datelike, Local, Locale, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
 use gpui::{
-    prelude::FluentBuilder as _, px, relative, App, ClickEvent, Context, ElementId, Empty, Entity,
-    EventEmitter, FocusHandle, InteractiveElement, IntoElement, ParentElement, Render, RenderOnce,
-    SharedString, StatefulInteractiveElement, StyleRefinement, Styled, Window,
+    div, prelude::FluentBuilder as _, px, relative, App, AppContext as _, ClickEvent, Context,
+    ElementId, Empty, Entity, EventEmitter, FocusHandle, Hsla, InteractiveElement, IntoElement,
+    ParentElement, Render, RenderOnce, SharedString, StatefulInteractiveElement, StyleRefinement,
+    Styled, Subscription, Window,
 };
 use rust_i18n::t;
 
 use crate::{
     button::{Button, ButtonVariants as _},
-    h_flex, v_flex, ActiveTheme, Disableable as _, IconName, Selectable, Sizable, Size,
-    StyledExt as _,
+    h_flex,
+    input::{InputEvent, InputState, NumberInput, NumberInputEvent, StepAction},
+    tooltip::Tooltip,
+    v_flex, ActiveTheme, Disableable as _, IconName, Selectable, Sizable, Size, StyledExt as _,
 };
 
-use super::utils::days_in_month;
+use super::utils::{days_in_month, iso_week_number};
 
 pub enum CalendarEvent {
-    /// The user selected a date.
-    Selected(Date),
+    /// The user selected a date, or changed the time when the time picker is shown.
+    Selected(SelectedDate),
+}
+
+/// The selection of the calendar emitted by [`CalendarEvent::Selected`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectedDate {
+    /// The selected date, when the time picker is not shown.
+    DateOnly(Date),
+    /// The start and end of the selection with the picked time, equal for a single date.
+    DateTime(NaiveDateTime, NaiveDateTime),
+}
+
+impl SelectedDate {
+    /// Returns the start date time of the selection, the date only selection uses midnight.
+    pub fn start(&self) -> Option<NaiveDateTime> {
+        match self {
+            Self::DateOnly(date) => date.start().map(|date| date.and_time(NaiveTime::default())),
+            Self::DateTime(start, _) => Some(*start),
+        }
+    }
 }
 
 /// The date of the calendar.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Date {
     Single(Option<NaiveDate>),
     Range(Option<NaiveDate>, Option<NaiveDate>),
+    /// Any number of dates, sorted in ascending order.
+    Multi(Vec<NaiveDate>),
 }
 
 impl std::fmt::Display for Date {
@@ -37,6 +61,11 @@ impl std::fmt::Display for Date {
             Self::Range(None, None) => write!(f, "nil"),
             Self::Range(Some(start), None) => write!(f, "{} - nil", start),
             Self::Range(None, Some(end)) => write!(f, "nil - {}", end),
+            Self::Multi(dates) if dates.is_empty() => write!(f, "nil"),
+            Self::Multi(dates) => {
+                let dates = dates.iter().map(|d| d.to_string()).collect::<Vec<_>>();
+                write!(f, "{}", dates.join(", "))
+            }
         }
     }
 }
@@ -53,12 +82,21 @@ impl From<(NaiveDate, NaiveDate)> for Date {
     }
 }
 
+impl From<Vec<NaiveDate>> for Date {
+    fn from(mut dates: Vec<NaiveDate>) -> Self {
+        dates.sort();
+        dates.dedup();
+        Self::Multi(dates)
+    }
+}
+
 impl Date {
     fn is_active(&self, v: &NaiveDate) -> bool {
         let v = *v;
         match self {
             Self::Single(d) => Some(v) == *d,
             Self::Range(start, end) => Some(v) == *start || Some(v) == *end,
+            Self::Multi(dates) => dates.contains(&v),
         }
     }
 
@@ -66,6 +104,22 @@ impl Date {
         matches!(self, Self::Single(_))
     }
 
+    fn is_multi(&self) -> bool {
+        matches!(self, Self::Multi(_))
+    }
+
+    /// Add the date to a `Multi` date, or remove it if already selected.
+    fn toggle(&mut self, date: NaiveDate) {
+        if let Self::Multi(dates) = self {
+            match dates.binary_search(&date) {
+                Ok(ix) => {
+                    dates.remove(ix);
+                }
+                Err(ix) => dates.insert(ix, date),
+            }
+        }
+    }
+
     fn is_in_range(&self, v: &NaiveDate) -> bool {
         let v = *v;
         match self {
@@ -80,6 +134,19 @@ impl Date {
                     false
                 }
             }
+            Self::Multi(_) => false,
+            _ => false,
+        }
+    }
+
+    /// Check if the date is in the range that a click on `hover` would select.
+    ///
+    /// Only a range with a start but no end date has a preview.
+    fn is_in_preview_range(&self, hover: Option<NaiveDate>, v: &NaiveDate) -> bool {
+        match (self, hover) {
+            (Self::Range(Some(start), None), Some(hover)) if hover >= *start => {
+                v >= start && *v <= hover
+            }
             _ => false,
         }
     }
@@ -87,6 +154,7 @@ impl Date {
     pub fn is_some(&self) -> bool {
         match self {
             Self::Single(Some(_)) | Self::Range(Some(_), _) => true,
+            Self::Multi(dates) => !dates.is_empty(),
             _ => false,
         }
     }
@@ -96,6 +164,7 @@ impl Date {
         match self {
             Self::Range(Some(_), Some(_)) => true,
             Self::Single(Some(_)) => true,
+            Self::Multi(dates) => !dates.is_empty(),
             _ => false,
         }
     }
@@ -104,6 +173,7 @@ impl Date {
         match self {
             Self::Single(Some(date)) => Some(*date),
             Self::Range(Some(start), _) => Some(*start),
+            Self::Multi(dates) => dates.first().copied(),
             _ => None,
         }
     }
@@ -111,6 +181,7 @@ impl Date {
     pub fn end(&self) -> Option<NaiveDate> {
         match self {
             Self::Range(_, Some(end)) => Some(*end),
+            Self::Multi(dates) => dates.last().copied(),
             _ => None,
         }
     }
@@ -122,9 +193,28 @@ impl Date {
             Self::Range(Some(start), Some(end)) => {
                 Some(format!("{} - {}", start.format(format), end.format(format)).into())
             }
+            Self::Multi(dates) if !dates.is_empty() => {
+                Some(self.format_multi(format).join(", ").into())
+            }
             _ => None,
         }
     }
+
+    /// Return each selected date as a formatted string.
+    ///
+    /// For `Single` and `Range`, this returns the dates that are set.
+    pub fn format_multi(&self, format: &str) -> Vec<SharedString> {
+        let dates = match self {
+            Self::Single(date) => date.iter().copied().collect::<Vec<_>>(),
+            Self::Range(start, end) => start.iter().chain(end.iter()).copied().collect(),
+            Self::Multi(dates) => dates.clone(),
+        };
+
+        dates
+            .iter()
+            .map(|date| date.format(format).to_string().into())
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -187,6 +277,18 @@ pub enum Matcher {
     /// }));
     /// Will match first 5 days of each month
     Custom(Box<dyn Fn(&NaiveDate) -> bool + Send + Sync>),
+    /// Match the days that are matched by all of the matchers.
+    ///
+    /// Matcher::And(vec![Matcher::DayOfWeek(vec![0, 6]), Matcher::range(from, to)])
+    /// Will match the weekends between `from` and `to`.
+    And(Vec<Matcher>),
+    /// Match the days that are matched by any of the matchers.
+    ///
+    /// Matcher::Or(vec![Matcher::DayOfWeek(vec![0, 6]), Matcher::interval(Some(today), None)])
+    /// Will match the weekends and the days before today.
+    Or(Vec<Matcher>),
+    /// Match the days that are not matched by the inner matcher.
+    Not(Box<Matcher>),
 }
 
 impl From<Vec<u32>> for Matcher {
@@ -204,7 +306,26 @@ where
     }
 }
 
+impl std::ops::Not for Matcher {
+    type Output = Matcher;
+
+    /// Invert the matcher, wrapping it in [`Matcher::Not`].
+    fn not(self) -> Self::Output {
+        Matcher::Not(Box::new(self))
+    }
+}
+
 impl Matcher {
+    /// Create a matcher that matches when all of the `matchers` match.
+    pub fn and(matchers: impl IntoIterator<Item = impl Into<Matcher>>) -> Self {
+        Matcher::And(matchers.into_iter().map(Into::into).collect())
+    }
+
+    /// Create a matcher that matches when any of the `matchers` match.
+    pub fn or(matchers: impl IntoIterator<Item = impl Into<Matcher>>) -> Self {
+        Matcher::Or(matchers.into_iter().map(Into::into).collect())
+    }
+
     pub fn interval(before: Option<NaiveDate>, after: Option<NaiveDate>) -> Self {
         Matcher::Interval(IntervalMatcher { before, after })
     }
@@ -227,13 +348,20 @@ impl Matcher {
                 !from_check && !to_check
             }
             Matcher::Custom(f) => f(date),
+            Matcher::And(matchers) => matchers.iter().all(|matcher| matcher.matched(date)),
+            Matcher::Or(matchers) => matchers.iter().any(|matcher| matcher.matched(date)),
+            Matcher::Not(matcher) => !matcher.matched(date),
         }
     }
 
     pub fn date_matched(&self, date: &Date) -> bool {
         match date {
             Date::Single(Some(date)) => self.matched(date),
-            Date::Range(Some(start), Some(end)) => self.matched(start) || self.matched(end),
+            Date::Range(Some(start), Some(end)) => start
+                .iter_days()
+                .take_while(|date| date <= end)
+                .any(|date| self.matched(&date)),
+            Date::Multi(dates) => dates.iter().any(|date| self.matched(date)),
             _ => false,
         }
     }
@@ -246,6 +374,48 @@ impl Matcher {
     }
 }
 
+/// The max number of marker dots of a day, more markers are collapsed into a `+`.
+const MAX_MARKER_DOTS: usize = 3;
+
+/// A colored dot under a day of the calendar, e.g. to show the events of the day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarMarker {
+    pub date: NaiveDate,
+    pub color: Hsla,
+    /// The text to show when the day is hovered.
+    pub tooltip: Option<SharedString>,
+}
+
+impl CalendarMarker {
+    pub fn new(date: NaiveDate, color: impl Into<Hsla>) -> Self {
+        Self {
+            date,
+            color: color.into(),
+            tooltip: None,
+        }
+    }
+
+    /// Set the tooltip to show when the day is hovered.
+    pub fn tooltip(mut self, tooltip: impl Into<SharedString>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+}
+
+/// Join the tooltips of the markers of a day, one per line.
+fn markers_tooltip(markers: &[&CalendarMarker]) -> Option<SharedString> {
+    let tooltips = markers
+        .iter()
+        .filter_map(|marker| marker.tooltip.as_ref())
+        .map(|tooltip| tooltip.as_ref())
+        .collect::<Vec<_>>();
+    if tooltips.is_empty() {
+        return None;
+    }
+
+    Some(tooltips.join("\n").into())
+}
+
 #[derive(IntoElement)]
 pub struct Calendar {
     id: ElementId,
@@ -254,6 +424,8 @@ pub struct Calendar {
     style: StyleRefinement,
     /// Number of the months view to show.
     number_of_months: usize,
+    show_week_numbers: Option<bool>,
+    first_day_of_week: Option<Weekday>,
 }
 
 /// Use to store the state of the calendar.
@@ -266,9 +438,80 @@ pub struct CalendarState {
     years: Vec<Vec<i32>>,
     year_page: i32,
     today: NaiveDate,
+    /// Animate the month transitions of the navigation, default is false.
+    ///
+    /// Reserved for the animation support, the view currently switches immediately.
+    animate_navigation: bool,
     /// Number of the months view to show.
     number_of_months: usize,
+    /// Show the ISO week number column, default is false.
+    show_week_numbers: bool,
+    /// The day under the mouse, used to preview the range before the end date is clicked.
+    hover_date: Option<NaiveDate>,
+    /// The first day of the week, default is Sunday.
+    first_day_of_week: Weekday,
+    /// The locale of the weekday and month names, the app translations are used when `None`.
+    locale: Option<Locale>,
     pub(crate) disabled_matcher: Option<Rc<Matcher>>,
+    /// The marker dots of the days, they don't affect whether a day is selectable.
+    event_markers: Vec<CalendarMarker>,
+    /// The time of the selection, used when the time picker is shown.
+    time: Option<NaiveTime>,
+    show_time_picker: bool,
+    show_seconds: bool,
+    time_inputs: Option<TimeInputs>,
+    _time_subscriptions: Vec<Subscription>,
+}
+
+/// The inputs of the time picker section.
+struct TimeInputs {
+    hour: Entity<InputState>,
+    minute: Entity<InputState>,
+    second: Entity<InputState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeField {
+    Hour,
+    Minute,
+    Second,
+}
+
+impl TimeField {
+    fn max(&self) -> u32 {
+        match self {
+            Self::Hour => 23,
+            Self::Minute | Self::Second => 59,
+        }
+    }
+
+    fn get(&self, time: &NaiveTime) -> u32 {
+        match self {
+            Self::Hour => time.hour(),
+            Self::Minute => time.minute(),
+            Self::Second => time.second(),
+        }
+    }
+
+    fn set(&self, time: &NaiveTime, value: u32) -> Option<NaiveTime> {
+        match self {
+            Self::Hour => time.with_hour(value),
+            Self::Minute => time.with_minute(value),
+            Self::Second => time.with_second(value),
+        }
+    }
+
+    /// Step the value of the field, wrapping around at the bounds.
+    fn step(&self, time: &NaiveTime, action: StepAction) -> Option<NaiveTime> {
+        let value = self.get(time);
+        let value = match action {
+            StepAction::Increment if value >= self.max() => 0,
+            StepAction::Increment => value + 1,
+            StepAction::Decrement if value == 0 => self.max(),
+            StepAction::Decrement => value - 1,
+        };
+        self.set(time, value)
+    }
 }
 
 impl CalendarState {
@@ -283,12 +526,30 @@ impl CalendarState {
             years: vec![],
             year_page: 0,
             today,
+            animate_navigation: false,
             number_of_months: 1,
+            show_week_numbers: false,
+            hover_date: None,
+            first_day_of_week: Weekday::Sun,
+            locale: None,
             disabled_matcher: None,
+            event_markers: Vec::new(),
+            time: None,
+            show_time_picker: false,
+            show_seconds: false,
+            time_inputs: None,
+            _time_subscriptions: Vec::new(),
         }
         .year_range((today.year() - 50, today.year() + 50))
     }
 
+    /// Create a calendar state to select multiple dates.
+    pub fn new_multi(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut this = Self::new(window, cx);
+        this.date = Date::Multi(vec![]);
+        this
+    }
+
     /// Set the disabled matcher of the calendar state.
     pub fn disabled_matcher(mut self, matcher: impl Into<Matcher>) -> Self {
         self.disabled_matcher = Some(Rc::new(matcher.into()));
@@ -302,17 +563,57 @@ impl CalendarState {
         &mut self,
         disabled: impl Into<Matcher>,
         _: &mut Window,
-        _: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) {
         self.disabled_matcher = Some(Rc::new(disabled.into()));
+        cx.notify();
+    }
+
+    /// Set the marker dots of the days, e.g. to show the events of the days.
+    pub fn with_event_markers(mut self, markers: Vec<CalendarMarker>) -> Self {
+        self.event_markers = markers;
+        self
+    }
+
+    /// Set the marker dots of the days, replacing the existing markers.
+    pub fn set_event_markers(&mut self, markers: Vec<CalendarMarker>, cx: &mut Context<Self>) {
+        self.event_markers = markers;
+        cx.notify();
+    }
+
+    /// Get the marker dots of the days.
+    pub fn event_markers(&self) -> &[CalendarMarker] {
+        &self.event_markers
+    }
+
+    fn markers_of(&self, date: &NaiveDate) -> Vec<&CalendarMarker> {
+        self.event_markers
+            .iter()
+            .filter(|marker| marker.date == *date)
+            .collect()
     }
 
     /// Set the date of the calendar.
     ///
     /// When you set a range date, the mode will be automatically set to `Mode::Range`.
+    ///
+    /// In multi mode, setting a single date toggles it in the selected dates.
     pub fn set_date(&mut self, date: impl Into<Date>, _: &mut Window, cx: &mut Context<Self>) {
         let date = date.into();
 
+        if let (Date::Multi(_), Date::Single(Some(day))) = (&self.date, &date) {
+            let day = *day;
+            let invalid = self
+                .disabled_matcher
+                .as_ref()
+                .map_or(false, |matcher| matcher.matched(&day));
+            if !invalid {
+                self.date.toggle(day);
+                cx.notify();
+            }
+            return;
+        }
+
         let invalid = self
             .disabled_matcher
             .as_ref()
@@ -324,13 +625,8 @@ impl CalendarState {
 
         self.date = date;
         match self.date {
-            Date::Single(Some(date)) => {
-                self.current_month = date.month() as u8;
-                self.current_year = date.year();
-            }
-            Date::Range(Some(start), _) => {
-                self.current_month = start.month() as u8;
-                self.current_year = start.year();
+            Date::Single(Some(date)) | Date::Range(Some(date), _) => {
+                self.set_view_month(date.year(), date.month() as u8, cx);
             }
             _ => {}
         }
@@ -338,9 +634,190 @@ impl CalendarState {
         cx.notify()
     }
 
+    /// Show the month containing the `date`, without changing the selected date.
+    pub fn go_to_date(&mut self, date: NaiveDate, cx: &mut Context<Self>) {
+        self.set_view_month(date.year(), date.month() as u8, cx);
+    }
+
+    /// Show the month of today, without changing the selected date.
+    pub fn go_to_today(&mut self, cx: &mut Context<Self>) {
+        self.today = Local::now().naive_local().date();
+        self.go_to_date(self.today, cx);
+    }
+
+    /// Show the `month` (1-12) of the `year`.
+    ///
+    /// The year page follows the year when it is in the year range.
+    fn set_view_month(&mut self, year: i32, month: u8, cx: &mut Context<Self>) {
+        self.current_year = year;
+        self.current_month = month;
+        if let Some(page) = self.years.iter().position(|years| years.contains(&year)) {
+            self.year_page = page as i32;
+        }
+        cx.notify();
+    }
+
     /// Get the date of the calendar.
     pub fn date(&self) -> Date {
+        self.date.clone()
+    }
+
+    /// Get the time of the calendar.
+    pub fn time(&self) -> Option<NaiveTime> {
+        self.time
+    }
+
+    /// Set the time of the calendar, and update the time picker inputs.
+    pub fn set_time(
+        &mut self,
+        time: Option<NaiveTime>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.time = time;
+        self.sync_time_inputs(window, cx);
+        cx.notify();
+    }
+
+    /// Set the date and time of the calendar.
+    pub fn set_datetime(
+        &mut self,
+        datetime: NaiveDateTime,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_date(datetime.date(), window, cx);
+        self.set_time(Some(datetime.time()), window, cx);
+    }
+
+    /// Get the start date with the selected time, time defaults to midnight.
+    pub fn selected_datetime(&self) -> Option<NaiveDateTime> {
         self.date
+            .start()
+            .map(|date| date.and_time(self.time.unwrap_or_default()))
+    }
+
+    /// Get the current selection, including the time when the time picker is shown.
+    pub fn selected(&self) -> SelectedDate {
+        if !self.show_time_picker {
+            return SelectedDate::DateOnly(self.date.clone());
+        }
+
+        let time = self.time.unwrap_or_default();
+        match (self.date.start(), self.date.end().or(self.date.start())) {
+            (Some(start), Some(end)) => {
+                SelectedDate::DateTime(start.and_time(time), end.and_time(time))
+            }
+            _ => SelectedDate::DateOnly(self.date.clone()),
+        }
+    }
+
+    /// Show the time picker below the days, to select a [`NaiveDateTime`].
+    pub fn set_show_time_picker(
+        &mut self,
+        show_time_picker: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_time_picker = show_time_picker;
+        if show_time_picker && self.time_inputs.is_none() {
+            let time = self.time.unwrap_or_default();
+            self.time_inputs = Some(TimeInputs {
+                hour: self.new_time_input(TimeField::Hour, &time, window, cx),
+                minute: self.new_time_input(TimeField::Minute, &time, window, cx),
+                second: self.new_time_input(TimeField::Second, &time, window, cx),
+            });
+        }
+        cx.notify();
+    }
+
+    /// Show the seconds input in the time picker, default is false.
+    pub fn set_show_seconds(&mut self, show_seconds: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.show_seconds = show_seconds;
+        cx.notify();
+    }
+
+    fn new_time_input(
+        &mut self,
+        field: TimeField,
+        time: &NaiveTime,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<InputState> {
+        let value = format!("{:02}", field.get(time));
+        let state = cx.new(|cx| InputState::new(window, cx).default_value(value));
+
+        self._time_subscriptions.push(cx.subscribe_in(
+            &state,
+            window,
+            |this, _, ev: &InputEvent, window, cx| match ev {
+                InputEvent::PressEnter { .. } | InputEvent::Blur => {
+                    this.commit_time_inputs(window, cx);
+                }
+                _ => {}
+            },
+        ));
+        self._time_subscriptions.push(cx.subscribe_in(
+            &state,
+            window,
+            move |this, _, ev: &NumberInputEvent, window, cx| {
+                let NumberInputEvent::Step(action) = ev;
+                let time = this.time.unwrap_or_default();
+                if let Some(time) = field.step(&time, *action) {
+                    this.update_time(time, window, cx);
+                }
+            },
+        ));
+
+        state
+    }
+
+    /// Parse the time picker inputs, and apply the time if it changed.
+    fn commit_time_inputs(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(inputs) = self.time_inputs.as_ref() else {
+            return;
+        };
+
+        let parse = |state: &Entity<InputState>, field: TimeField| {
+            state
+                .read(cx)
+                .value()
+                .trim()
+                .parse::<u32>()
+                .map(|value| value.min(field.max()))
+                .unwrap_or(0)
+        };
+        let hour = parse(&inputs.hour, TimeField::Hour);
+        let minute = parse(&inputs.minute, TimeField::Minute);
+        let second = parse(&inputs.second, TimeField::Second);
+
+        match NaiveTime::from_hms_opt(hour, minute, second) {
+            Some(time) if Some(time) != self.time => self.update_time(time, window, cx),
+            _ => self.sync_time_inputs(window, cx),
+        }
+    }
+
+    fn update_time(&mut self, time: NaiveTime, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_time(Some(time), window, cx);
+        if self.date.is_complete() {
+            cx.emit(CalendarEvent::Selected(self.selected()));
+        }
+    }
+
+    fn sync_time_inputs(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(inputs) = self.time_inputs.as_ref() else {
+            return;
+        };
+
+        let time = self.time.unwrap_or_default();
+        for (state, field) in [
+            (&inputs.hour, TimeField::Hour),
+            (&inputs.minute, TimeField::Minute),
+            (&inputs.second, TimeField::Second),
+        ] {
+            let value = format!("{:02}", field.get(&time));
+            state.update(cx, |state, cx| state.set_value(value, window, cx));
+        }
     }
 
     // pub fn set_size(&mut self, size: Size, _: &mut Window, cx: &mut Context<Self>) {
@@ -348,6 +825,59 @@ impl CalendarState {
     //     cx.notify();
     // }
 
+    /// Show the ISO week number as the first column of the days, default is false.
+    pub fn set_show_week_numbers(
+        &mut self,
+        show_week_numbers: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_week_numbers = show_week_numbers;
+        cx.notify();
+    }
+
+    /// Animate the month transitions of the navigation, default is false.
+    pub fn set_animate_navigation(
+        &mut self,
+        animate_navigation: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.animate_navigation = animate_navigation;
+        cx.notify();
+    }
+
+    /// Returns whether the month transitions of the navigation are animated.
+    pub fn animate_navigation(&self) -> bool {
+        self.animate_navigation
+    }
+
+    fn set_hover_date(&mut self, hover_date: Option<NaiveDate>, cx: &mut Context<Self>) {
+        if self.hover_date != hover_date {
+            self.hover_date = hover_date;
+            cx.notify();
+        }
+    }
+
+    /// Format the weekday and month names with the `locale`, e.g. `de_DE`.
+    ///
+    /// The app translations are still used if the locale is not supported.
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = Locale::try_from(locale).ok();
+        self
+    }
+
+    /// Return the abbreviated name of the `weekday`, shown in the header of the days.
+    pub fn weekday_abbreviation(&self, weekday: Weekday) -> SharedString {
+        weekday_abbreviation(weekday, self.locale)
+    }
+
+    /// Set the first day of the week, default is Sunday.
+    pub fn set_first_day_of_week(&mut self, day: Weekday, _: &mut Window, cx: &mut Context<Self>) {
+        self.first_day_of_week = day;
+        cx.notify();
+    }
+
     pub fn set_number_of_months(
         &mut self,
         number_of_months: usize,
@@ -395,7 +925,11 @@ impl CalendarState {
     fn days(&self) -> Vec<Vec<NaiveDate>> {
         (0..self.number_of_months)
             .flat_map(|offset| {
-                days_in_month(self.current_year, self.current_month as u32 + offset as u32)
+                days_in_month(
+                    self.current_year,
+                    self.current_month as u32 + offset as u32,
+                    self.first_day_of_week,
+                )
             })
             .collect()
     }
@@ -427,51 +961,24 @@ impl CalendarState {
     }
 
     fn prev_month(&mut self, _: &ClickEvent, _: &mut Window, cx: &mut Context<Self>) {
-        self.current_month = if self.current_month == 1 {
-            12
+        if self.current_month == 1 {
+            self.set_view_month(self.current_year - 1, 12, cx);
         } else {
-            self.current_month - 1
-        };
-        self.current_year = if self.current_month == 12 {
-            self.current_year - 1
-        } else {
-            self.current_year
-        };
-        cx.notify()
+            self.set_view_month(self.current_year, self.current_month - 1, cx);
+        }
     }
 
     fn next_month(&mut self, _: &ClickEvent, _: &mut Window, cx: &mut Context<Self>) {
-        self.current_month = if self.current_month == 12 {
-            1
+        if self.current_month == 12 {
+            self.set_view_month(self.current_year + 1, 1, cx);
         } else {
-            self.current_month + 1
-        };
-        self.current_year = if self.current_month == 1 {
-            self.current_year + 1
-        } else {
-            self.current_year
-        };
-        cx.notify()
+            self.set_view_month(self.current_year, self.current_month + 1, cx);
+        }
     }
 
     fn month_name(&self, offset_month: usize) -> SharedString {
         let (_, month) = self.offset_year_month(offset_month);
-        match month {
-            1 => t!("Calendar.month.January"),
-            2 => t!("Calendar.month.February"),
-            3 => t!("Calendar.month.March"),
-            4 => t!("Calendar.month.April"),
-            5 => t!("Calendar.month.May"),
-            6 => t!("Calendar.month.June"),
-            7 => t!("Calendar.month.July"),
-            8 => t!("Calendar.month.August"),
-            9 => t!("Calendar.month.September"),
-            10 => t!("Calendar.month.October"),
-            11 => t!("Calendar.month.November"),
-            12 => t!("Calendar.month.December"),
-            _ => Cow::Borrowed(""),
-        }
-        .into()
+        month_name(month, self.locale)
     }
 
     fn year_name(&self, offset_month: usize) -> SharedString {
@@ -485,26 +992,59 @@ impl CalendarState {
     }
 
     fn months(&self) -> Vec<SharedString> {
-        [
-            t!("Calendar.month.January"),
-            t!("Calendar.month.February"),
-            t!("Calendar.month.March"),
-            t!("Calendar.month.April"),
-            t!("Calendar.month.May"),
-            t!("Calendar.month.June"),
-            t!("Calendar.month.July"),
-            t!("Calendar.month.August"),
-            t!("Calendar.month.September"),
-            t!("Calendar.month.October"),
-            t!("Calendar.month.November"),
-            t!("Calendar.month.December"),
-        ]
-        .iter()
-        .map(|s| s.clone().into())
-        .collect()
+        (1..=12)
+            .map(|month| month_name(month, self.locale))
+            .collect()
     }
 }
 
+/// Return the name of the `month` (1-12), formatted with the `locale` or translated.
+fn month_name(month: u8, locale: Option<Locale>) -> SharedString {
+    if let Some(locale) = locale {
+        if let Some(date) = NaiveDate::from_ymd_opt(2000, month as u32, 1) {
+            return date.format_localized("%B", locale).to_string().into();
+        }
+    }
+
+    match month {
+        1 => t!("Calendar.month.January"),
+        2 => t!("Calendar.month.February"),
+        3 => t!("Calendar.month.March"),
+        4 => t!("Calendar.month.April"),
+        5 => t!("Calendar.month.May"),
+        6 => t!("Calendar.month.June"),
+        7 => t!("Calendar.month.July"),
+        8 => t!("Calendar.month.August"),
+        9 => t!("Calendar.month.September"),
+        10 => t!("Calendar.month.October"),
+        11 => t!("Calendar.month.November"),
+        12 => t!("Calendar.month.December"),
+        _ => Cow::Borrowed(""),
+    }
+    .into()
+}
+
+/// Return the abbreviated name of the `weekday`, formatted with the `locale` or translated.
+fn weekday_abbreviation(weekday: Weekday, locale: Option<Locale>) -> SharedString {
+    if let Some(locale) = locale {
+        // 2023-01-01 is a Sunday.
+        let date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()
+            + chrono::Days::new(weekday.num_days_from_sunday() as u64);
+        return date.format_localized("%a", locale).to_string().into();
+    }
+
+    match weekday {
+        Weekday::Sun => t!("Calendar.week.0"),
+        Weekday::Mon => t!("Calendar.week.1"),
+        Weekday::Tue => t!("Calendar.week.2"),
+        Weekday::Wed => t!("Calendar.week.3"),
+        Weekday::Thu => t!("Calendar.week.4"),
+        Weekday::Fri => t!("Calendar.week.5"),
+        Weekday::Sat => t!("Calendar.week.6"),
+    }
+    .into()
+}
+
 impl Render for CalendarState {
     fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
         Empty
@@ -519,6 +1059,8 @@ impl Calendar {
             state: state.clone(),
             style: StyleRefinement::default(),
             number_of_months: 1,
+            show_week_numbers: None,
+            first_day_of_week: None,
         }
     }
 
@@ -528,6 +1070,18 @@ impl Calendar {
         self
     }
 
+    /// Show the ISO week number as the first column of the days, default is false.
+    pub fn show_week_numbers(mut self, show_week_numbers: bool) -> Self {
+        self.show_week_numbers = Some(show_week_numbers);
+        self
+    }
+
+    /// Set the first day of the week, default is Sunday.
+    pub fn first_day_of_week(mut self, day: Weekday) -> Self {
+        self.first_day_of_week = Some(day);
+        self
+    }
+
     fn render_day(
         &self,
         d: &NaiveDate,
@@ -540,7 +1094,8 @@ impl Calendar {
         let day = d.day();
         let is_current_month = d.month() == month;
         let is_active = state.date.is_active(d);
-        let is_in_range = state.date.is_in_range(d);
+        let is_in_range =
+            state.date.is_in_range(d) || state.date.is_in_preview_range(state.hover_date, d);
 
         let date = *d;
         let is_today = *d == state.today;
@@ -550,6 +1105,14 @@ impl Calendar {
             .map_or(false, |disabled| disabled.matched(&date));
 
         let date_id: SharedString = format!("{}_{}", date.format("%Y-%m-%d"), offset_month).into();
+        let markers = state.markers_of(&date);
+        let tooltip = markers_tooltip(&markers);
+        let marker_colors = markers
+            .iter()
+            .take(MAX_MARKER_DOTS)
+            .map(|marker| marker.color)
+            .collect::<Vec<_>>();
+        let collapsed = markers.len() > MAX_MARKER_DOTS;
 
         self.item_button(
             date_id,
@@ -564,13 +1127,43 @@ impl Calendar {
         .when(is_today && !is_active, |this| {
             this.border_1().border_color(cx.theme().border)
         }) // Add border for today
+        .when(!marker_colors.is_empty(), |this| {
+            this.relative().child(
+                h_flex()
+                    .absolute()
+                    .bottom(px(2.))
+                    .gap(px(2.))
+                    .items_center()
+                    .opacity(if is_current_month { 1. } else { 0.5 })
+                    .children(
+                        marker_colors
+                            .into_iter()
+                            .map(|color| div().flex_none().size(px(4.)).rounded_full().bg(color)),
+                    )
+                    .when(collapsed, |this| {
+                        this.child(div().text_size(px(8.)).line_height(px(8.)).child("+"))
+                    }),
+            )
+        })
+        .when_some(tooltip, |this, tooltip| {
+            this.tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx))
+        })
         .when(!disabled, |this| {
-            this.on_click(window.listener_for(
+            this.on_hover(
+                window.listener_for(&self.state, move |view, hovered: &bool, _, cx| {
+                    if *hovered {
+                        view.set_hover_date(Some(date), cx);
+                    } else if view.hover_date == Some(date) {
+                        view.set_hover_date(None, cx);
+                    }
+                }),
+            )
+            .on_click(window.listener_for(
                 &self.state,
                 move |view, _: &ClickEvent, window, cx| {
-                    if view.date.is_single() {
+                    if view.date.is_single() || view.date.is_multi() {
                         view.set_date(date, window, cx);
-                        cx.emit(CalendarEvent::Selected(view.date()));
+                        cx.emit(CalendarEvent::Selected(view.selected()));
                     } else {
                         let start = view.date.start();
                         let end = view.date.end();
@@ -592,7 +1185,7 @@ impl Calendar {
                         }
 
                         if view.date.is_complete() {
-                            cx.emit(CalendarEvent::Selected(view.date()));
+                            cx.emit(CalendarEvent::Selected(view.selected()));
                         }
                     }
                 },
@@ -721,7 +1314,7 @@ impl Calendar {
         disabled: bool,
         _: &mut Window,
         cx: &mut App,
-    ) -> impl IntoElement + Styled + StatefulInteractiveElement {
+    ) -> impl IntoElement + Styled + ParentElement + StatefulInteractiveElement {
         h_flex()
             .id(id.into())
             .map(|this| match self.size {
@@ -760,15 +1353,11 @@ impl Calendar {
 
     fn render_days(&self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let state = self.state.read(cx);
-        let weeks = [
-            t!("Calendar.week.0"),
-            t!("Calendar.week.1"),
-            t!("Calendar.week.2"),
-            t!("Calendar.week.3"),
-            t!("Calendar.week.4"),
-            t!("Calendar.week.5"),
-            t!("Calendar.week.6"),
-        ];
+        let show_week_numbers = state.show_week_numbers;
+        let weeks = std::iter::successors(Some(state.first_day_of_week), |day| Some(day.succ()))
+            .take(7)
+            .map(|day| state.weekday_abbreviation(day))
+            .collect::<Vec<_>>();
 
         h_flex()
             .map(|this| match self.size {
@@ -786,22 +1375,70 @@ impl Calendar {
                         v_flex()
                             .gap_0p5()
                             .child(
-                                h_flex().gap_0p5().justify_between().children(
-                                    weeks
-                                        .iter()
-                                        .map(|week| self.render_week(week.clone(), window, cx)),
-                                ),
+                                h_flex()
+                                    .gap_0p5()
+                                    .justify_between()
+                                    .when(show_week_numbers, |this| {
+                                        this.child(self.render_week("", window, cx))
+                                    })
+                                    .children(
+                                        weeks
+                                            .iter()
+                                            .map(|week| self.render_week(week.clone(), window, cx)),
+                                    ),
                             )
                             .children(days.iter().map(|week| {
-                                h_flex().gap_0p5().justify_between().children(
-                                    week.iter()
-                                        .map(|d| self.render_day(d, offset_month, window, cx)),
-                                )
+                                h_flex()
+                                    .gap_0p5()
+                                    .justify_between()
+                                    .when(show_week_numbers, |this| {
+                                        let week_number = iso_week_number(week)
+                                            .map(|n| n.to_string())
+                                            .unwrap_or_default();
+                                        this.child(self.render_week(week_number, window, cx))
+                                    })
+                                    .children(
+                                        week.iter()
+                                            .map(|d| self.render_day(d, offset_month, window, cx)),
+                                    )
                             }))
                     }),
             )
     }
 
+    fn render_time(&self, _: &mut Window, cx: &mut App) -> Option<impl IntoElement> {
+        let state = self.state.read(cx);
+        if !state.show_time_picker {
+            return None;
+        }
+        let inputs = state.time_inputs.as_ref()?;
+        let show_seconds = state.show_seconds;
+
+        let input = |state: &Entity<InputState>| {
+            div()
+                .w(px(96.))
+                .child(NumberInput::new(state).with_size(self.size))
+        };
+        let separator = || div().text_color(cx.theme().muted_foreground).child(":");
+
+        Some(
+            h_flex()
+                .mt_2()
+                .pt_2()
+                .gap_1()
+                .justify_center()
+                .items_center()
+                .border_t_1()
+                .border_color(cx.theme().border)
+                .child(input(&inputs.hour))
+                .child(separator())
+                .child(input(&inputs.minute))
+                .when(show_seconds, |this| {
+                    this.child(separator()).child(input(&inputs.second))
+                }),
+        )
+    }
+
     fn render_week(
         &self,
         week: impl Into<SharedString>,
@@ -934,13 +1571,28 @@ impl RenderOnce for Calendar {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let view_mode = self.state.read(cx).view_mode;
         let number_of_months = self.number_of_months;
+        let show_week_numbers = self.show_week_numbers;
+        let first_day_of_week = self.first_day_of_week;
         self.state.update(cx, |state, _| {
             state.number_of_months = number_of_months;
+            if let Some(show_week_numbers) = show_week_numbers {
+                state.show_week_numbers = show_week_numbers;
+            }
+            if let Some(first_day_of_week) = first_day_of_week {
+                state.first_day_of_week = first_day_of_week;
+            }
         });
 
         v_flex()
             .id(self.id.clone())
             .track_focus(&self.state.read(cx).focus_handle)
+            .on_hover(
+                window.listener_for(&self.state, |view, hovered: &bool, _, cx| {
+                    if !*hovered {
+                        view.set_hover_date(None, cx);
+                    }
+                }),
+            )
             .border_1()
             .border_color(cx.theme().border)
             .rounded(cx.theme().radius_lg)
@@ -952,6 +1604,7 @@ impl RenderOnce for Calendar {
                 v_flex()
                     .when(view_mode.is_day(), |this| {
                         this.child(self.render_days(window, cx))
+                            .children(self.render_time(window, cx))
                     })
                     .when(view_mode.is_month(), |this| {
                         this.child(self.render_months(window, cx))
@@ -965,9 +1618,14 @@ impl RenderOnce for Calendar {
 
 #[cfg(test)]
 mod tests {
-    use chrono::NaiveDate;
+    use chrono::{NaiveDate, NaiveTime};
+    use gpui::SharedString;
 
-    use super::Date;
+    use super::{
+        markers_tooltip, month_name, weekday_abbreviation, CalendarMarker, Date, Matcher,
+        SelectedDate, TimeField,
+    };
+    use crate::input::StepAction;
 
     #[test]
     fn test_date_to_string() {
@@ -991,5 +1649,190 @@ mod tests {
 
         let date = Date::Range(None, None);
         assert_eq!(date.to_string(), "nil");
+
+        let date = Date::Multi(vec![]);
+        assert_eq!(date.to_string(), "nil");
+
+        let date = Date::Multi(vec![
+            NaiveDate::from_ymd_opt(2024, 8, 3).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 8, 9).unwrap(),
+        ]);
+        assert_eq!(date.to_string(), "2024-08-03, 2024-08-09");
+    }
+
+    #[test]
+    fn test_markers_tooltip() {
+        let date = NaiveDate::from_ymd_opt(2024, 8, 3).unwrap();
+        let meeting = CalendarMarker::new(date, gpui::red()).tooltip("Meeting");
+        let plain = CalendarMarker::new(date, gpui::blue());
+        let review = CalendarMarker::new(date, gpui::green()).tooltip("Review");
+
+        assert_eq!(markers_tooltip(&[]), None);
+        assert_eq!(markers_tooltip(&[&plain]), None);
+        assert_eq!(
+            markers_tooltip(&[&meeting, &plain, &review]),
+            Some(SharedString::from("Meeting\nReview"))
+        );
+    }
+
+    #[test]
+    fn test_multi_date_toggle() {
+        let d1 = NaiveDate::from_ymd_opt(2024, 8, 9).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2024, 8, 3).unwrap();
+
+        let mut date = Date::Multi(vec![]);
+        date.toggle(d1);
+        date.toggle(d2);
+        assert_eq!(date, Date::Multi(vec![d2, d1]));
+        assert!(date.is_active(&d1));
+        assert!(!date.is_in_range(&d1));
+        assert_eq!(date.start(), Some(d2));
+        assert_eq!(date.end(), Some(d1));
+
+        date.toggle(d1);
+        assert_eq!(date, Date::Multi(vec![d2]));
+        assert!(!date.is_active(&d1));
+    }
+
+    #[test]
+    fn test_multi_date_format() {
+        let date = Date::from(vec![
+            NaiveDate::from_ymd_opt(2024, 8, 9).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 8, 3).unwrap(),
+        ]);
+        assert_eq!(
+            date.format_multi("%m/%d"),
+            vec![SharedString::from("08/03"), SharedString::from("08/09")]
+        );
+        assert_eq!(date.format("%m/%d"), Some("08/03, 08/09".into()));
+        assert_eq!(Date::Multi(vec![]).format("%m/%d"), None);
+    }
+
+    #[test]
+    fn test_time_field_step_wraps() {
+        let time = NaiveTime::from_hms_opt(23, 59, 0).unwrap();
+        assert_eq!(
+            TimeField::Hour.step(&time, StepAction::Increment),
+            NaiveTime::from_hms_opt(0, 59, 0)
+        );
+        assert_eq!(
+            TimeField::Minute.step(&time, StepAction::Increment),
+            NaiveTime::from_hms_opt(23, 0, 0)
+        );
+        assert_eq!(
+            TimeField::Second.step(&time, StepAction::Decrement),
+            NaiveTime::from_hms_opt(23, 59, 59)
+        );
+        assert_eq!(
+            TimeField::Hour.step(&time, StepAction::Decrement),
+            NaiveTime::from_hms_opt(22, 59, 0)
+        );
+    }
+
+    #[test]
+    fn test_selected_date_start() {
+        let date = NaiveDate::from_ymd_opt(2024, 8, 3).unwrap();
+        let time = NaiveTime::from_hms_opt(9, 30, 0).unwrap();
+
+        let selected = SelectedDate::DateOnly(Date::Single(Some(date)));
+        assert_eq!(selected.start(), Some(date.and_time(NaiveTime::default())));
+
+        let selected = SelectedDate::DateTime(date.and_time(time), date.and_time(time));
+        assert_eq!(selected.start(), Some(date.and_time(time)));
+
+        assert_eq!(SelectedDate::DateOnly(Date::Single(None)).start(), None);
+    }
+
+    #[test]
+    fn test_matcher_composition() {
+        let date = |day| NaiveDate::from_ymd_opt(2024, 8, day).unwrap();
+        let weekends = || Matcher::DayOfWeek(vec![0, 6]);
+        let before_10th = || Matcher::interval(Some(date(10)), None);
+
+        // 2024-08-03 is a Saturday.
+        let and = Matcher::and([weekends(), before_10th()]);
+        assert!(and.matched(&date(3)));
+        assert!(!and.matched(&date(5)));
+        assert!(!and.matched(&date(17)));
+
+        let or = Matcher::or([weekends(), before_10th()]);
+        assert!(or.matched(&date(5)));
+        assert!(or.matched(&date(17)));
+        assert!(!or.matched(&date(12)));
+
+        let not = !weekends();
+        assert!(!not.matched(&date(3)));
+        assert!(not.matched(&date(5)));
+
+        // Weekdays after the 10th, except the 20th.
+        let nested = Matcher::and([
+            !Matcher::or([weekends(), before_10th()]),
+            !Matcher::range(Some(date(20)), Some(date(20))),
+        ]);
+        assert!(nested.matched(&date(12)));
+        assert!(!nested.matched(&date(20)));
+        assert!(!nested.matched(&date(17)));
+        assert!(!nested.matched(&date(5)));
+
+        assert!(Matcher::And(vec![]).matched(&date(1)));
+        assert!(!Matcher::Or(vec![]).matched(&date(1)));
+    }
+
+    #[test]
+    fn test_matcher_date_range() {
+        let date = |day| NaiveDate::from_ymd_opt(2024, 8, day).unwrap();
+        let weekends = Matcher::DayOfWeek(vec![0, 6]);
+
+        // 2024-08-05 to 2024-08-09 is Monday to Friday.
+        assert!(!weekends.date_matched(&Date::Range(Some(date(5)), Some(date(9)))));
+        // The range spans the weekend, but both ends are weekdays.
+        assert!(weekends.date_matched(&Date::Range(Some(date(2)), Some(date(5)))));
+        assert!(!weekends.date_matched(&Date::Range(Some(date(5)), None)));
+    }
+
+    #[test]
+    fn test_range_hover_preview() {
+        let date = |day| NaiveDate::from_ymd_opt(2024, 8, day).unwrap();
+        let range = Date::Range(Some(date(5)), None);
+
+        assert!(range.is_in_preview_range(Some(date(9)), &date(5)));
+        assert!(range.is_in_preview_range(Some(date(9)), &date(7)));
+        assert!(range.is_in_preview_range(Some(date(9)), &date(9)));
+        assert!(!range.is_in_preview_range(Some(date(9)), &date(10)));
+        assert!(!range.is_in_preview_range(Some(date(9)), &date(4)));
+        // Hovering before the start would restart the range, so there is no preview.
+        assert!(!range.is_in_preview_range(Some(date(3)), &date(4)));
+        assert!(!range.is_in_preview_range(None, &date(5)));
+
+        // The preview does not commit the end date.
+        assert_eq!(range, Date::Range(Some(date(5)), None));
+        assert!(!range.is_complete());
+        assert!(!range.is_in_range(&date(7)));
+
+        let complete = Date::Range(Some(date(5)), Some(date(6)));
+        assert!(!complete.is_in_preview_range(Some(date(9)), &date(8)));
+        assert!(!Date::Single(Some(date(5))).is_in_preview_range(Some(date(9)), &date(7)));
+    }
+
+    #[test]
+    fn test_localized_names() {
+        use chrono::{Locale, Weekday};
+
+        let en = Locale::try_from("en_US").ok();
+        assert_eq!(weekday_abbreviation(Weekday::Sun, en).to_string(), "Sun");
+        assert_eq!(weekday_abbreviation(Weekday::Wed, en).to_string(), "Wed");
+        assert_eq!(month_name(1, en).to_string(), "January");
+
+        let de = Locale::try_from("de_DE").ok();
+        assert_eq!(weekday_abbreviation(Weekday::Mon, de).to_string(), "Mo");
+        assert_eq!(weekday_abbreviation(Weekday::Sat, de).to_string(), "Sa");
+        assert_eq!(month_name(3, de).to_string(), "März");
+
+        let ja = Locale::try_from("ja_JP").ok();
+        assert_eq!(weekday_abbreviation(Weekday::Sun, ja).to_string(), "日");
+        assert_eq!(weekday_abbreviation(Weekday::Fri, ja).to_string(), "金");
+        assert_eq!(month_name(12, ja).to_string(), "12月");
+
+        assert!(Locale::try_from("xx_XX").is_err());
     }
 }
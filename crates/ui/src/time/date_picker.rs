@@ -98,16 +98,17 @@ impl DatePickerState {
 
         let calendar = cx.new(|cx| {
             let mut this = CalendarState::new(window, cx);
-            this.set_date(date, window, cx);
+            this.set_date(date.clone(), window, cx);
             this
         });
 
         let _subscriptions = vec![cx.subscribe_in(
             &calendar,
             window,
-            |this, _, ev: &CalendarEvent, window, cx| match ev {
-                CalendarEvent::Selected(date) => {
-                    this.update_date(*date, true, window, cx);
+            |this, calendar, ev: &CalendarEvent, window, cx| match ev {
+                CalendarEvent::Selected(_) => {
+                    let date = calendar.read(cx).date();
+                    this.update_date(date, true, window, cx);
                     this.focus_handle.focus(window);
                 }
             },
@@ -139,7 +140,7 @@ impl DatePickerState {
 
     /// Get the date of the date picker.
     pub fn date(&self) -> Date {
-        self.date
+        self.date.clone()
     }
 
     /// Set the date of the date picker.
@@ -148,9 +149,9 @@ impl DatePickerState {
     }
 
     fn update_date(&mut self, date: Date, emit: bool, window: &mut Window, cx: &mut Context<Self>) {
-        self.date = date;
+        self.date = date.clone();
         self.calendar.update(cx, |view, cx| {
-            view.set_date(date, window, cx);
+            view.set_date(date.clone(), window, cx);
         });
         self.open = false;
         if emit {
@@ -210,6 +211,9 @@ impl DatePickerState {
             Date::Range(_, _) => {
                 self.update_date(Date::Range(None, None), true, window, cx);
             }
+            Date::Multi(_) => {
+                self.update_date(Date::Multi(vec![]), true, window, cx);
+            }
         }
     }
 
@@ -1,4 +1,4 @@
-use chrono::{Datelike, Duration, NaiveDate};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
 
 trait NaiveDateExt {
     fn days_in_month(&self) -> i32;
@@ -28,7 +28,7 @@ impl NaiveDateExt for chrono::NaiveDate {
     }
 }
 
-pub(crate) fn days_in_month(year: i32, month: u32) -> Vec<Vec<NaiveDate>> {
+pub(crate) fn days_in_month(year: i32, month: u32, first_day: Weekday) -> Vec<Vec<NaiveDate>> {
     let mut year = year;
     let mut month = month;
     if month > 12 {
@@ -42,7 +42,9 @@ pub(crate) fn days_in_month(year: i32, month: u32) -> Vec<Vec<NaiveDate>> {
 
     let date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
     let num_days = date.days_in_month();
-    let start_weekday = date.weekday().num_days_from_sunday();
+    // The number of leading days from the previous month, relative to the first day of the week.
+    let start_weekday =
+        (date.weekday().num_days_from_sunday() + 7 - first_day.num_days_from_sunday()) % 7;
 
     // Get the days in the month, 2023-02 will returns
     // "29|30|31| 1| 2| 3| 4",
@@ -85,11 +87,18 @@ pub(crate) fn days_in_month(year: i32, month: u32) -> Vec<Vec<NaiveDate>> {
     days
 }
 
+/// Get the ISO week number of a week row, using the Monday of the row.
+pub(crate) fn iso_week_number(week: &[NaiveDate]) -> Option<u32> {
+    week.iter()
+        .find(|d| d.weekday() == Weekday::Mon)
+        .map(|d| d.iso_week().week())
+}
+
 #[cfg(test)]
 mod tests {
-    use chrono::{Datelike, NaiveDate};
+    use chrono::{Datelike, NaiveDate, Weekday};
 
-    use super::{days_in_month, NaiveDateExt};
+    use super::{days_in_month, iso_week_number, NaiveDateExt};
 
     #[test]
     fn test_days_in_month() {
@@ -115,7 +124,7 @@ mod tests {
     fn test_days() {
         #[track_caller]
         fn assert_case(date: NaiveDate, expected: Vec<&str>) {
-            let out = days_in_month(date.year(), date.month())
+            let out = days_in_month(date.year(), date.month(), Weekday::Sun)
                 .iter()
                 .map(|week| {
                     week.iter()
@@ -178,4 +187,68 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_iso_week_number() {
+        #[track_caller]
+        fn assert_weeks(year: i32, month: u32, expected: Vec<u32>) {
+            let weeks = days_in_month(year, month, Weekday::Sun)
+                .iter()
+                .map(|week| iso_week_number(week).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(weeks, expected);
+        }
+
+        // 2024-12-30 is the Monday of ISO week 1 of 2025.
+        assert_weeks(2025, 1, vec![1, 2, 3, 4, 5]);
+        assert_weeks(2024, 12, vec![49, 50, 51, 52, 1]);
+        // 2021-01-04 is the first Monday of 2021, 2020 has 53 ISO weeks.
+        assert_weeks(2021, 1, vec![53, 1, 2, 3, 4]);
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2024, 12, 30)
+                .unwrap()
+                .iso_week()
+                .year(),
+            2025
+        );
+        assert_eq!(iso_week_number(&[]), None);
+    }
+
+    #[test]
+    fn test_days_with_first_day_of_week() {
+        // 2024-08-01 is a Thursday.
+        let first_dates = [
+            (Weekday::Sun, "2024-07-28"),
+            (Weekday::Mon, "2024-07-29"),
+            (Weekday::Tue, "2024-07-30"),
+            (Weekday::Wed, "2024-07-31"),
+            (Weekday::Thu, "2024-08-01"),
+            (Weekday::Fri, "2024-07-26"),
+            (Weekday::Sat, "2024-07-27"),
+        ];
+
+        for (first_day, first_date) in first_dates {
+            let days = days_in_month(2024, 8, first_day);
+            assert_eq!(days.len(), 5);
+            assert_eq!(
+                days[0][0].format("%Y-%m-%d").to_string(),
+                first_date,
+                "first day {:?}",
+                first_day
+            );
+            for week in &days {
+                assert_eq!(week.len(), 7);
+                assert_eq!(week[0].weekday(), first_day);
+            }
+            assert!(days[0].contains(&NaiveDate::from_ymd_opt(2024, 8, 1).unwrap()));
+        }
+
+        assert_eq!(
+            days_in_month(2024, 8, Weekday::Mon)[4]
+                .iter()
+                .map(|d| d.day())
+                .collect::<Vec<_>>(),
+            vec![26, 27, 28, 29, 30, 31, 1]
+        );
+    }
 }
@@ -2,12 +2,22 @@ use gpui::{
     canvas, div, App, AppContext, Bounds, ContentMask, DismissEvent, EventEmitter,
     FocusHandle, Focusable, InteractiveElement, IntoElement,
     ParentElement as _, Pixels, Render, RenderImage, Size, Styled as _, Window, Corners, px,
-    Context, PaintQuad, Point, BorderStyle, Entity, WeakEntity,
+    Context, PaintQuad, Point, BorderStyle, Entity, WeakEntity, KeyDownEvent, KeyUpEvent,
+    Keystroke, Modifiers, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    ScrollDelta, ScrollWheelEvent,
 };
-use std::sync::{Arc, Mutex, mpsc, atomic::{AtomicBool, Ordering}};
+use std::cell::UnsafeCell;
+use std::sync::{Arc, Mutex, mpsc, atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering}};
 use std::collections::VecDeque;
+use std::error::Error;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
+mod primitives;
+
+pub use primitives::GLYPH_SIZE;
+
 /// Performance metrics for the viewport
 #[derive(Debug, Clone, Default)]
 pub struct ViewportMetrics {
@@ -19,6 +29,14 @@ pub struct ViewportMetrics {
     pub buffer_swaps: u64,
     pub texture_updates: u64,
     pub dropped_frames: u64,
+    /// The render thread stopped continuous rendering because the content is static
+    pub is_idle: bool,
+    /// How long the render thread has been idle, zero when it's not idle
+    pub idle_duration: Duration,
+    /// A recording started with [`Viewport::start_recording`] is in progress
+    pub is_recording: bool,
+    /// The frames written by the current or the last recording
+    pub recorded_frames: u64,
 }
 
 /// A trait for render engines that can render to a GPU texture
@@ -43,6 +61,59 @@ pub trait RenderEngine: Send + Sync + 'static {
 
     /// Set a callback that the render engine can use to trigger GPUI redraws
     fn set_notify_callback(&mut self, _callback: Box<dyn Fn() + Send + Sync>) {}
+
+    /// Called on the render thread for mouse input over the viewport
+    fn on_mouse_event(&mut self, _event: ViewportMouseEvent) {}
+
+    /// Called on the render thread for keyboard input while the viewport is focused
+    fn on_key_event(&mut self, _event: ViewportKeyEvent) {}
+}
+
+/// Mouse input forwarded to the [`RenderEngine`], positions are relative to the viewport's top-left.
+///
+/// With [`Viewport::set_target_size`], positions are in render pixels relative to the image's top-left.
+#[derive(Debug, Clone)]
+pub enum ViewportMouseEvent {
+    Down {
+        button: MouseButton,
+        position: Point<Pixels>,
+        modifiers: Modifiers,
+        click_count: usize,
+    },
+    Up {
+        button: MouseButton,
+        position: Point<Pixels>,
+        modifiers: Modifiers,
+    },
+    Move {
+        position: Point<Pixels>,
+        pressed_button: Option<MouseButton>,
+        modifiers: Modifiers,
+    },
+    Scroll {
+        position: Point<Pixels>,
+        delta: ScrollDelta,
+        modifiers: Modifiers,
+    },
+}
+
+/// Keyboard input forwarded to the [`RenderEngine`]
+#[derive(Debug, Clone)]
+pub enum ViewportKeyEvent {
+    Down {
+        keystroke: Keystroke,
+        is_held: bool,
+    },
+    Up {
+        keystroke: Keystroke,
+    },
+}
+
+/// Input sent to the render thread, separate from [`RenderCommand`] so input doesn't block rendering
+#[derive(Debug)]
+enum ViewportInput {
+    Mouse(ViewportMouseEvent),
+    Key(ViewportKeyEvent),
 }
 
 /// Render engine errors
@@ -74,23 +145,36 @@ pub enum FramebufferFormat {
     Rgb8,
     Bgra8,
     Bgr8,
+    /// HDR pixels stored in [`Framebuffer::hdr_buffer`], tonemapped to RGBA8 for display
+    F32Rgba,
 }
 
 impl FramebufferFormat {
     pub fn bytes_per_pixel(&self) -> u32 {
         match self {
+            FramebufferFormat::F32Rgba => 16,
             FramebufferFormat::Rgba8 | FramebufferFormat::Bgra8 => 4,
             FramebufferFormat::Rgb8 | FramebufferFormat::Bgr8 => 3,
         }
     }
 }
 
+/// Map a linear HDR channel to `0..=255` with Reinhard tonemapping
+fn reinhard_to_u8(value: f32) -> u8 {
+    let value = value.max(0.0);
+    let mapped = if value.is_finite() { value / (1.0 + value) } else { 1.0 };
+    (mapped * 255.0).round() as u8
+}
+
 /// A high-performance zero-copy framebuffer that can be rendered to
 pub struct Framebuffer {
     pub width: u32,
     pub height: u32,
     pub format: FramebufferFormat,
+    /// Pixels of the 8-bit formats, empty for [`FramebufferFormat::F32Rgba`]
     pub buffer: Vec<u8>,
+    /// Pixels of [`FramebufferFormat::F32Rgba`] in row-major order, empty for the 8-bit formats
+    pub hdr_buffer: Vec<[f32; 4]>,
     pub pitch: u32, // bytes per row
     dirty_rect: Option<Bounds<Pixels>>,
     generation: u64,
@@ -100,13 +184,14 @@ impl Framebuffer {
     pub fn new(width: u32, height: u32, format: FramebufferFormat) -> Self {
         let bytes_per_pixel = format.bytes_per_pixel();
         let pitch = width * bytes_per_pixel;
-        let buffer_size = (pitch * height) as usize;
+        let (buffer, hdr_buffer) = Self::allocate(width, height, format);
 
         Self {
             width,
             height,
             format,
-            buffer: vec![0; buffer_size],
+            buffer,
+            hdr_buffer,
             pitch,
             dirty_rect: Some(Bounds {
                 origin: Point { x: px(0.0), y: px(0.0) },
@@ -124,9 +209,11 @@ impl Framebuffer {
         self.width = width;
         self.height = height;
         self.pitch = width * self.format.bytes_per_pixel();
-        let buffer_size = (self.pitch * height) as usize;
-
-        self.buffer.resize(buffer_size, 0);
+        if self.format == FramebufferFormat::F32Rgba {
+            self.hdr_buffer.resize((width * height) as usize, [0.0; 4]);
+        } else {
+            self.buffer.resize((self.pitch * height) as usize, 0);
+        }
         self.dirty_rect = Some(Bounds {
             origin: Point { x: px(0.0), y: px(0.0) },
             size: Size { width: px(width as f32), height: px(height as f32) }
@@ -134,6 +221,23 @@ impl Framebuffer {
         self.generation += 1;
     }
 
+    fn allocate(width: u32, height: u32, format: FramebufferFormat) -> (Vec<u8>, Vec<[f32; 4]>) {
+        if format == FramebufferFormat::F32Rgba {
+            (Vec::new(), vec![[0.0; 4]; (width * height) as usize])
+        } else {
+            (vec![0; (width * format.bytes_per_pixel() * height) as usize], Vec::new())
+        }
+    }
+
+    /// Get the HDR pixel at `x`, `y` for writing.
+    ///
+    /// Panics if the format is not [`FramebufferFormat::F32Rgba`] or the position is out of bounds.
+    pub fn pixel_mut_f32(&mut self, x: u32, y: u32) -> &mut [f32; 4] {
+        assert_eq!(self.format, FramebufferFormat::F32Rgba, "pixel_mut_f32 requires the F32Rgba format");
+        assert!(x < self.width && y < self.height, "pixel ({}, {}) is out of bounds", x, y);
+        &mut self.hdr_buffer[(y * self.width + x) as usize]
+    }
+
     pub fn mark_dirty(&mut self, rect: Option<Bounds<Pixels>>) {
         self.dirty_rect = rect.or(self.dirty_rect);
         self.generation += 1;
@@ -155,6 +259,83 @@ impl Framebuffer {
         self.generation
     }
 
+    /// Convert the pixels to RGBA8 into `out`, reusing its allocation
+    pub fn to_rgba8(&self, out: &mut Vec<u8>) {
+        let required_size = match self.format {
+            FramebufferFormat::Rgba8 | FramebufferFormat::Bgra8 => self.buffer.len(),
+            FramebufferFormat::Rgb8 | FramebufferFormat::Bgr8 => self.buffer.len() * 4 / 3,
+            FramebufferFormat::F32Rgba => self.hdr_buffer.len() * 4,
+        };
+
+        if out.len() != required_size {
+            out.resize(required_size, 0);
+        }
+
+        match self.format {
+            FramebufferFormat::Rgba8 => {
+                out.copy_from_slice(&self.buffer);
+            }
+            FramebufferFormat::Bgra8 => {
+                // Convert BGRA to RGBA
+                for (i, chunk) in self.buffer.chunks_exact(4).enumerate() {
+                    let offset = i * 4;
+                    out[offset] = chunk[2];     // R
+                    out[offset + 1] = chunk[1]; // G
+                    out[offset + 2] = chunk[0]; // B
+                    out[offset + 3] = chunk[3]; // A
+                }
+            }
+            FramebufferFormat::Rgb8 => {
+                // Convert RGB to RGBA
+                for (i, chunk) in self.buffer.chunks_exact(3).enumerate() {
+                    let offset = i * 4;
+                    out[offset] = chunk[0];     // R
+                    out[offset + 1] = chunk[1]; // G
+                    out[offset + 2] = chunk[2]; // B
+                    out[offset + 3] = 255;     // A
+                }
+            }
+            FramebufferFormat::Bgr8 => {
+                // Convert BGR to RGBA
+                for (i, chunk) in self.buffer.chunks_exact(3).enumerate() {
+                    let offset = i * 4;
+                    out[offset] = chunk[2];     // R
+                    out[offset + 1] = chunk[1]; // G
+                    out[offset + 2] = chunk[0]; // B
+                    out[offset + 3] = 255;     // A
+                }
+            }
+            FramebufferFormat::F32Rgba => {
+                // Tonemap HDR to RGBA
+                for (i, pixel) in self.hdr_buffer.iter().enumerate() {
+                    let offset = i * 4;
+                    out[offset] = reinhard_to_u8(pixel[0]);     // R
+                    out[offset + 1] = reinhard_to_u8(pixel[1]); // G
+                    out[offset + 2] = reinhard_to_u8(pixel[2]); // B
+                    out[offset + 3] = (pixel[3].clamp(0.0, 1.0) * 255.0).round() as u8; // A
+                }
+            }
+        }
+    }
+
+    /// Encode the pixels as a PNG image
+    pub fn to_png(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut rgba = Vec::new();
+        self.to_rgba8(&mut rgba);
+        let image = image::RgbaImage::from_raw(self.width, self.height, rgba)
+            .ok_or("framebuffer size does not match its dimensions")?;
+
+        let mut png = Vec::new();
+        image.write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)?;
+        Ok(png)
+    }
+
+    /// Save the pixels to a PNG file
+    pub fn save_png(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        std::fs::write(path, self.to_png()?)?;
+        Ok(())
+    }
+
     pub fn clear(&mut self, color: [u8; 4]) {
         match self.format {
             FramebufferFormat::Rgba8 => {
@@ -179,12 +360,274 @@ impl Framebuffer {
                     chunk.copy_from_slice(&bgr);
                 }
             }
+            FramebufferFormat::F32Rgba => {
+                let pixel = color.map(|c| c as f32 / 255.0);
+                self.hdr_buffer.fill(pixel);
+            }
         }
         self.mark_dirty(Some(Bounds {
             origin: Point { x: px(0.0), y: px(0.0) },
             size: Size { width: px(self.width as f32), height: px(self.height as f32) }
         }));
     }
+
+    /// Copy the pixels of `src` into `dst_rect` of this framebuffer, converting the format if they differ.
+    ///
+    /// `src` is scaled to the size of `dst_rect` with nearest-neighbor sampling, the parts of
+    /// `dst_rect` outside of this framebuffer are skipped.
+    pub fn blit(&mut self, src: &Framebuffer, dst_rect: Bounds<Pixels>) {
+        let Some(region) = BlitRegion::new(self, src, dst_rect) else {
+            return;
+        };
+
+        if src.format == self.format && region.is_unscaled() {
+            // Same format and size, copy whole rows
+            let x = region.cols.start;
+            let len = region.cols.len();
+            for y in region.rows.clone() {
+                let (src_x, src_y) = (region.src_x(x), region.src_y(y));
+                if self.format == FramebufferFormat::F32Rgba {
+                    let dst_offset = (y * self.width + x) as usize;
+                    let src_offset = (src_y * src.width + src_x) as usize;
+                    self.hdr_buffer[dst_offset..dst_offset + len]
+                        .copy_from_slice(&src.hdr_buffer[src_offset..src_offset + len]);
+                } else {
+                    let bytes_per_pixel = self.format.bytes_per_pixel();
+                    let dst_offset = (y * self.pitch + x * bytes_per_pixel) as usize;
+                    let src_offset = (src_y * src.pitch + src_x * bytes_per_pixel) as usize;
+                    let len = len * bytes_per_pixel as usize;
+                    self.buffer[dst_offset..dst_offset + len]
+                        .copy_from_slice(&src.buffer[src_offset..src_offset + len]);
+                }
+            }
+        } else {
+            for y in region.rows.clone() {
+                let src_y = region.src_y(y);
+                for x in region.cols.clone() {
+                    let src_x = region.src_x(x);
+                    if self.format == FramebufferFormat::F32Rgba {
+                        self.hdr_buffer[(y * self.width + x) as usize] = src.pixel_rgba_f32(src_x, src_y);
+                    } else {
+                        self.set_pixel_rgba8(x, y, src.pixel_rgba8(src_x, src_y));
+                    }
+                }
+            }
+        }
+
+        self.mark_region_dirty(&region);
+    }
+
+    /// Blend the pixels of `src` over `dst_rect` of this framebuffer, like [`Framebuffer::blit`]
+    /// with the `alpha` opacity (`0.0..=1.0`).
+    ///
+    /// The source alpha is multiplied by `alpha` and composited with the pre-multiplied
+    /// "over" operator. The RGB formats have no alpha channel and are treated as opaque.
+    pub fn blit_with_alpha(&mut self, src: &Framebuffer, dst_rect: Bounds<Pixels>, alpha: f32) {
+        let alpha = alpha.clamp(0.0, 1.0);
+        if alpha == 0.0 {
+            return;
+        }
+        let Some(region) = BlitRegion::new(self, src, dst_rect) else {
+            return;
+        };
+
+        let hdr = self.format == FramebufferFormat::F32Rgba;
+        for y in region.rows.clone() {
+            let src_y = region.src_y(y);
+            for x in region.cols.clone() {
+                let src_x = region.src_x(x);
+                // Tonemap HDR sources when blending into an 8-bit framebuffer
+                let src_pixel = if hdr {
+                    src.pixel_rgba_f32(src_x, src_y)
+                } else {
+                    src.pixel_rgba8(src_x, src_y).map(|c| c as f32 / 255.0)
+                };
+                let blended = blend_over(src_pixel, self.pixel_rgba_f32(x, y), alpha);
+
+                if hdr {
+                    self.hdr_buffer[(y * self.width + x) as usize] = blended;
+                } else {
+                    self.set_pixel_rgba8(x, y, blended.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8));
+                }
+            }
+        }
+
+        self.mark_region_dirty(&region);
+    }
+
+    /// Get the pixel at `x`, `y` as RGBA8, HDR pixels are tonemapped like [`Framebuffer::to_rgba8`]
+    fn pixel_rgba8(&self, x: u32, y: u32) -> [u8; 4] {
+        if self.format == FramebufferFormat::F32Rgba {
+            let pixel = self.hdr_buffer[(y * self.width + x) as usize];
+            return [
+                reinhard_to_u8(pixel[0]),
+                reinhard_to_u8(pixel[1]),
+                reinhard_to_u8(pixel[2]),
+                (pixel[3].clamp(0.0, 1.0) * 255.0).round() as u8,
+            ];
+        }
+
+        let offset = (y * self.pitch + x * self.format.bytes_per_pixel()) as usize;
+        let p = &self.buffer[offset..];
+        match self.format {
+            FramebufferFormat::Rgba8 => [p[0], p[1], p[2], p[3]],
+            FramebufferFormat::Bgra8 => [p[2], p[1], p[0], p[3]],
+            FramebufferFormat::Rgb8 => [p[0], p[1], p[2], 255],
+            FramebufferFormat::Bgr8 => [p[2], p[1], p[0], 255],
+            FramebufferFormat::F32Rgba => unreachable!(),
+        }
+    }
+
+    /// Get the pixel at `x`, `y` as RGBA in `0.0..=1.0`, HDR pixels are returned as is
+    fn pixel_rgba_f32(&self, x: u32, y: u32) -> [f32; 4] {
+        if self.format == FramebufferFormat::F32Rgba {
+            self.hdr_buffer[(y * self.width + x) as usize]
+        } else {
+            self.pixel_rgba8(x, y).map(|c| c as f32 / 255.0)
+        }
+    }
+
+    /// Set the pixel at `x`, `y` from RGBA8, the RGB formats drop the alpha
+    fn set_pixel_rgba8(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        if self.format == FramebufferFormat::F32Rgba {
+            self.hdr_buffer[(y * self.width + x) as usize] = color.map(|c| c as f32 / 255.0);
+            return;
+        }
+
+        let offset = (y * self.pitch + x * self.format.bytes_per_pixel()) as usize;
+        let p = &mut self.buffer[offset..];
+        match self.format {
+            FramebufferFormat::Rgba8 => p[..4].copy_from_slice(&color),
+            FramebufferFormat::Bgra8 => p[..4].copy_from_slice(&[color[2], color[1], color[0], color[3]]),
+            FramebufferFormat::Rgb8 => p[..3].copy_from_slice(&color[0..3]),
+            FramebufferFormat::Bgr8 => p[..3].copy_from_slice(&[color[2], color[1], color[0]]),
+            FramebufferFormat::F32Rgba => unreachable!(),
+        }
+    }
+
+    /// Add the destination pixels of a blit to the dirty rect
+    fn mark_region_dirty(&mut self, region: &BlitRegion) {
+        let bounds = Bounds {
+            origin: Point { x: px(region.cols.start as f32), y: px(region.rows.start as f32) },
+            size: Size { width: px(region.cols.len() as f32), height: px(region.rows.len() as f32) }
+        };
+        let dirty_rect = self.dirty_rect.map_or(bounds, |dirty_rect| dirty_rect.union(&bounds));
+        self.mark_dirty(Some(dirty_rect));
+    }
+}
+
+/// The destination pixels of a blit clipped to the destination framebuffer,
+/// and their mapping to the source pixels
+struct BlitRegion {
+    /// Origin of the destination rect, may be outside of the destination
+    left: i64,
+    top: i64,
+    /// Size of the destination rect
+    width: u32,
+    height: u32,
+    src_width: u32,
+    src_height: u32,
+    /// The destination columns and rows to write
+    cols: std::ops::Range<u32>,
+    rows: std::ops::Range<u32>,
+}
+
+impl BlitRegion {
+    fn new(dst: &Framebuffer, src: &Framebuffer, dst_rect: Bounds<Pixels>) -> Option<Self> {
+        let left = dst_rect.origin.x.0.round() as i64;
+        let top = dst_rect.origin.y.0.round() as i64;
+        let width = dst_rect.size.width.0.round().max(0.0) as u32;
+        let height = dst_rect.size.height.0.round().max(0.0) as u32;
+        if width == 0 || height == 0 || src.width == 0 || src.height == 0 {
+            return None;
+        }
+
+        let clip = |start: i64, len: u32, max: u32| {
+            start.clamp(0, max as i64) as u32..(start + len as i64).clamp(0, max as i64) as u32
+        };
+        let cols = clip(left, width, dst.width);
+        let rows = clip(top, height, dst.height);
+        if cols.is_empty() || rows.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            left,
+            top,
+            width,
+            height,
+            src_width: src.width,
+            src_height: src.height,
+            cols,
+            rows,
+        })
+    }
+
+    fn is_unscaled(&self) -> bool {
+        self.width == self.src_width && self.height == self.src_height
+    }
+
+    /// The source column of the destination column `x`
+    fn src_x(&self, x: u32) -> u32 {
+        ((x as i64 - self.left) as u64 * self.src_width as u64 / self.width as u64) as u32
+    }
+
+    /// The source row of the destination row `y`
+    fn src_y(&self, y: u32) -> u32 {
+        ((y as i64 - self.top) as u64 * self.src_height as u64 / self.height as u64) as u32
+    }
+}
+
+/// Composite `src` over `dst` with the pre-multiplied "over" operator, the colors are not pre-multiplied
+fn blend_over(src: [f32; 4], dst: [f32; 4], alpha: f32) -> [f32; 4] {
+    let src_alpha = src[3] * alpha;
+    let dst_alpha = dst[3] * (1.0 - src_alpha);
+    let out_alpha = src_alpha + dst_alpha;
+    if out_alpha <= 0.0 {
+        return [0.0; 4];
+    }
+
+    let channel = |i: usize| (src[i] * src_alpha + dst[i] * dst_alpha) / out_alpha;
+    [channel(0), channel(1), channel(2), out_alpha]
+}
+
+/// How the rendered image is positioned within the viewport bounds when their sizes differ,
+/// see [`Viewport::set_target_size`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScaleMode {
+    /// Scale to fit within the bounds keeping the aspect ratio, the rest of the bounds is empty
+    Fit,
+    /// Scale to cover the bounds keeping the aspect ratio, the image is cropped
+    Fill,
+    /// Stretch to the bounds
+    #[default]
+    Stretch,
+}
+
+impl ScaleMode {
+    /// The bounds to paint an image of `image_size` in `bounds`, centered for [`ScaleMode::Fit`] and [`ScaleMode::Fill`]
+    pub fn image_bounds(&self, bounds: Bounds<Pixels>, image_size: Size<Pixels>) -> Bounds<Pixels> {
+        if image_size.width <= px(0.0) || image_size.height <= px(0.0) {
+            return bounds;
+        }
+
+        let scale_x = bounds.size.width / image_size.width;
+        let scale_y = bounds.size.height / image_size.height;
+        let scale = match self {
+            ScaleMode::Fit => scale_x.min(scale_y),
+            ScaleMode::Fill => scale_x.max(scale_y),
+            ScaleMode::Stretch => return bounds,
+        };
+
+        let size = Size { width: image_size.width * scale, height: image_size.height * scale };
+        Bounds {
+            origin: Point {
+                x: bounds.origin.x + (bounds.size.width - size.width) / 2.0,
+                y: bounds.origin.y + (bounds.size.height - size.height) / 2.0,
+            },
+            size,
+        }
+    }
 }
 
 /// Double-buffered framebuffer system for smooth updates
@@ -230,25 +673,440 @@ impl DoubleBuffer {
     }
 }
 
+/// Triple-buffered framebuffers, the render thread starts the next frame without waiting for
+/// the readers of the front buffer.
+///
+/// Lock-free: the render thread writes the buffer that is neither the published `front` nor
+/// read, and readers register in `readers` before touching the front buffer. A reader that
+/// sees `front` move after registering backs off and retries, so a buffer is never written
+/// while it is read.
+struct TripleBuffer {
+    buffers: [UnsafeCell<Framebuffer>; 3],
+    /// Index of the latest published frame
+    front: AtomicUsize,
+    /// Number of readers of each buffer
+    readers: [AtomicUsize; 3],
+    /// Set while a frame is rendered, keeps a single writer
+    writing: AtomicBool,
+    /// Size of the next frames, `width << 32 | height`, applied to the back buffer by `render`
+    size: AtomicU64,
+}
+
+// SAFETY: buffers are only written by the single writer of `render`, and only while they are
+// neither the front buffer nor read, see `render` and `with_front`.
+unsafe impl Sync for TripleBuffer {}
+
+impl TripleBuffer {
+    fn new(width: u32, height: u32, format: FramebufferFormat) -> Self {
+        Self {
+            buffers: std::array::from_fn(|_| UnsafeCell::new(Framebuffer::new(width, height, format))),
+            front: AtomicUsize::new(0),
+            readers: std::array::from_fn(|_| AtomicUsize::new(0)),
+            writing: AtomicBool::new(false),
+            size: AtomicU64::new(pack_size(width, height)),
+        }
+    }
+
+    /// Resize the next rendered frames, the front buffer keeps its size until it's replaced
+    fn resize(&self, width: u32, height: u32) {
+        self.size.store(pack_size(width, height), Ordering::Release);
+    }
+
+    /// Returns `None` if another frame is being rendered.
+    fn render<T, Err>(&self, f: impl FnOnce(&mut Framebuffer) -> Result<T, Err>) -> Option<Result<T, Err>> {
+        if self.writing.swap(true, Ordering::Acquire) {
+            return None;
+        }
+        let _writing = ResetOnDrop(&self.writing);
+
+        let back = loop {
+            let readers = std::array::from_fn(|ix| self.readers[ix].load(Ordering::SeqCst));
+            if let Some(back) = back_buffer_index(self.front.load(Ordering::SeqCst), readers) {
+                break back;
+            }
+            // Only reachable with readers on two buffers at once
+            std::thread::yield_now();
+        };
+
+        // SAFETY: `back` isn't the front buffer and has no readers. Readers only read the front
+        // buffer, which stays the same until this frame is published, and `writing` keeps
+        // other writers out.
+        let buffer = unsafe { &mut *self.buffers[back].get() };
+        let (width, height) = unpack_size(self.size.load(Ordering::Acquire));
+        buffer.resize(width, height);
+        let result = f(buffer);
+        if result.is_ok() {
+            self.front.store(back, Ordering::SeqCst);
+        }
+        Some(result)
+    }
+
+    fn with_front<R>(&self, f: impl FnOnce(&Framebuffer) -> R) -> Option<R> {
+        loop {
+            let front = self.front.load(Ordering::SeqCst);
+            self.readers[front].fetch_add(1, Ordering::SeqCst);
+            let _reading = ReleaseOnDrop(&self.readers[front]);
+            // The writer may have picked `front` as its back buffer before the registration
+            // if it was replaced meanwhile, retry with the new front buffer.
+            if self.front.load(Ordering::SeqCst) == front {
+                // SAFETY: the writer doesn't pick a registered buffer, nor the front buffer.
+                return Some(f(unsafe { &*self.buffers[front].get() }));
+            }
+        }
+    }
+}
+
+/// Clears a flag when dropped, also on unwinding
+struct ResetOnDrop<'a>(&'a AtomicBool);
+
+impl Drop for ResetOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Unregisters a reader when dropped, also on unwinding
+struct ReleaseOnDrop<'a>(&'a AtomicUsize);
+
+impl Drop for ReleaseOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// The first of the three buffers that is neither the `front` nor read, `None` if all are in use
+fn back_buffer_index(front: usize, readers: [usize; 3]) -> Option<usize> {
+    (0..3).find(|ix| *ix != front && readers[*ix] == 0)
+}
+
+fn pack_size(width: u32, height: u32) -> u64 {
+    (width as u64) << 32 | height as u64
+}
+
+fn unpack_size(size: u64) -> (u32, u32) {
+    ((size >> 32) as u32, size as u32)
+}
+
+/// How many framebuffers a [`Viewport`] renders to, see [`Viewport::new_with_buffering`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BufferingStrategy {
+    /// Two framebuffers behind a single lock, the render thread waits while the main thread
+    /// reads the front buffer
+    Double,
+    /// Three lock-free framebuffers, the render thread doesn't wait for the main thread
+    #[default]
+    Triple,
+}
+
+/// The framebuffers shared by the render thread and the main thread
+enum SwapChain {
+    Double(Mutex<DoubleBuffer>),
+    Triple(TripleBuffer),
+}
+
+impl SwapChain {
+    fn new(strategy: BufferingStrategy, width: u32, height: u32, format: FramebufferFormat) -> Self {
+        match strategy {
+            BufferingStrategy::Double => Self::Double(Mutex::new(DoubleBuffer::new(width, height, format))),
+            BufferingStrategy::Triple => Self::Triple(TripleBuffer::new(width, height, format)),
+        }
+    }
+
+    fn resize(&self, width: u32, height: u32) {
+        match self {
+            Self::Double(buffer) => {
+                if let Ok(mut buffer) = buffer.lock() {
+                    buffer.resize(width, height);
+                }
+            }
+            Self::Triple(buffer) => buffer.resize(width, height),
+        }
+    }
+
+    /// Render a frame to the back buffer with `f`, and publish it as the front buffer if it
+    /// succeeds. Returns `None` if the lock is poisoned.
+    fn render<T, Err>(&self, f: impl FnOnce(&mut Framebuffer) -> Result<T, Err>) -> Option<Result<T, Err>> {
+        match self {
+            Self::Double(buffer) => {
+                let mut buffer = buffer.lock().ok()?;
+                let result = f(buffer.get_back_buffer());
+                if result.is_ok() {
+                    buffer.swap();
+                }
+                Some(result)
+            }
+            Self::Triple(buffer) => buffer.render(f),
+        }
+    }
+
+    /// Read the latest published frame, returns `None` if the lock is poisoned
+    fn with_front<R>(&self, f: impl FnOnce(&Framebuffer) -> R) -> Option<R> {
+        match self {
+            Self::Double(buffer) => {
+                let buffer = buffer.lock().ok()?;
+                Some(f(buffer.get_front_buffer()))
+            }
+            Self::Triple(buffer) => buffer.with_front(f),
+        }
+    }
+}
+
 /// Commands sent to the render thread
 #[derive(Debug)]
 enum RenderCommand {
     Render,
     Resize(u32, u32),
+    SetTargetFps(f64),
+    SetIdleTimeout(Duration),
+    StartRecording { path: PathBuf, fps: u32, duration: Duration },
+    StopRecording,
     Shutdown,
 }
 
+const DEFAULT_TARGET_FPS: f64 = 60.0;
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Frame pacing state of the render thread
+struct RenderPacing {
+    target_fps: f64,
+    idle_after: Duration,
+    /// The last time a render was requested or the engine produced a dirty frame
+    last_activity: Instant,
+}
+
+impl RenderPacing {
+    fn new() -> Self {
+        Self {
+            target_fps: DEFAULT_TARGET_FPS,
+            idle_after: DEFAULT_IDLE_TIMEOUT,
+            last_activity: Instant::now(),
+        }
+    }
+
+    fn frame_interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.target_fps)
+    }
+
+    /// How long the thread has been idle, None if it's still active
+    fn idle_duration(&self) -> Option<Duration> {
+        self.last_activity.elapsed().checked_sub(self.idle_after)
+    }
+}
+
+/// A recording of the front buffer to a video file, owned by the render thread
+struct RecordingState {
+    path: PathBuf,
+    fps: u32,
+    duration: Duration,
+    started_at: Instant,
+    /// The timestamp of the last written frame, in frames since the start
+    last_pts: Option<i64>,
+    frames: u64,
+    /// Created with the size of the first frame
+    encoder: Option<VideoEncoder>,
+    rgba: Vec<u8>,
+}
+
+impl RecordingState {
+    fn new(path: PathBuf, fps: u32, duration: Duration) -> Self {
+        Self {
+            path,
+            fps,
+            duration,
+            started_at: Instant::now(),
+            last_pts: None,
+            frames: 0,
+            encoder: None,
+            rgba: Vec::new(),
+        }
+    }
+
+    /// Write the front buffer if a frame is due at the recording fps, returns true when the
+    /// duration is over.
+    ///
+    /// Frames are timestamped by their capture time, so an idle viewport holds its last frame
+    /// in the video. Frames of another size than the first one are skipped.
+    fn capture(&mut self, swap_chain: &SwapChain) -> Result<bool, Box<dyn Error>> {
+        let elapsed = self.started_at.elapsed();
+        if elapsed >= self.duration {
+            return Ok(true);
+        }
+        let pts = (elapsed.as_secs_f64() * self.fps as f64) as i64;
+        if self.last_pts.is_some_and(|last_pts| pts <= last_pts) {
+            return Ok(false);
+        }
+
+        let rgba = &mut self.rgba;
+        let (width, height) = swap_chain
+            .with_front(|front| {
+                front.to_rgba8(rgba);
+                (front.width, front.height)
+            })
+            .ok_or("framebuffer lock is poisoned")?;
+        if width == 0 || height == 0 {
+            return Ok(false);
+        }
+
+        let encoder = match self.encoder.take() {
+            Some(encoder) => encoder,
+            None => VideoEncoder::new(&self.path, width, height, self.fps)?,
+        };
+        let encoder = self.encoder.insert(encoder);
+        if encoder.size() != (width, height) {
+            return Ok(false);
+        }
+
+        encoder.write_frame(&self.rgba, pts)?;
+        self.last_pts = Some(pts);
+        self.frames += 1;
+        Ok(false)
+    }
+
+    /// Flush the encoder and finalize the file
+    fn finish(mut self) -> Result<(), Box<dyn Error>> {
+        match self.encoder.take() {
+            Some(encoder) => encoder.finish(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Encodes RGBA frames to a H.264 video, in the container of the file extension (e.g. MP4)
+#[cfg(feature = "recording")]
+struct VideoEncoder {
+    output: ffmpeg_next::format::context::Output,
+    encoder: ffmpeg_next::encoder::video::Encoder,
+    scaler: ffmpeg_next::software::scaling::Context,
+    stream_index: usize,
+    width: u32,
+    height: u32,
+    fps: i32,
+}
+
+#[cfg(feature = "recording")]
+impl VideoEncoder {
+    fn new(path: &Path, width: u32, height: u32, fps: u32) -> Result<Self, Box<dyn Error>> {
+        use ffmpeg_next::{codec, encoder, format, software::scaling};
+
+        ffmpeg_next::init()?;
+        let fps = fps as i32;
+        let mut output = format::output(&path)?;
+        let codec = encoder::find(codec::Id::H264).ok_or("H.264 encoder is not available")?;
+        let global_header = output.format().flags().contains(format::Flags::GLOBAL_HEADER);
+
+        // YUV 4:2:0 needs an even size
+        let (video_width, video_height) = (width & !1, height & !1);
+        let mut video = codec::context::Context::new_with_codec(codec).encoder().video()?;
+        video.set_width(video_width);
+        video.set_height(video_height);
+        video.set_format(format::Pixel::YUV420P);
+        video.set_time_base((1, fps));
+        video.set_frame_rate(Some((fps, 1)));
+        if global_header {
+            video.set_flags(codec::Flags::GLOBAL_HEADER);
+        }
+        let encoder = video.open_as(codec)?;
+
+        let stream_index = {
+            let mut stream = output.add_stream(codec)?;
+            stream.set_parameters(&encoder);
+            stream.index()
+        };
+        output.write_header()?;
+
+        let scaler = scaling::Context::get(
+            format::Pixel::RGBA,
+            width,
+            height,
+            format::Pixel::YUV420P,
+            video_width,
+            video_height,
+            scaling::Flags::BILINEAR,
+        )?;
+
+        Ok(Self { output, encoder, scaler, stream_index, width, height, fps })
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn write_frame(&mut self, rgba: &[u8], pts: i64) -> Result<(), Box<dyn Error>> {
+        use ffmpeg_next::{format::Pixel, frame};
+
+        let mut input = frame::Video::new(Pixel::RGBA, self.width, self.height);
+        let stride = input.stride(0);
+        let row_len = self.width as usize * 4;
+        let data = input.data_mut(0);
+        for (y, row) in rgba.chunks_exact(row_len).enumerate() {
+            data[y * stride..y * stride + row_len].copy_from_slice(row);
+        }
+
+        let mut frame = frame::Video::empty();
+        self.scaler.run(&input, &mut frame)?;
+        frame.set_pts(Some(pts));
+        self.encoder.send_frame(&frame)?;
+        self.write_packets()
+    }
+
+    fn write_packets(&mut self) -> Result<(), Box<dyn Error>> {
+        let stream_time_base = self
+            .output
+            .stream(self.stream_index)
+            .ok_or("video stream is missing")?
+            .time_base();
+        let mut packet = ffmpeg_next::Packet::empty();
+        while self.encoder.receive_packet(&mut packet).is_ok() {
+            packet.set_stream(self.stream_index);
+            packet.rescale_ts((1, self.fps), stream_time_base);
+            packet.write_interleaved(&mut self.output)?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<(), Box<dyn Error>> {
+        self.encoder.send_eof()?;
+        self.write_packets()?;
+        self.output.write_trailer()?;
+        Ok(())
+    }
+}
+
+/// Recording needs ffmpeg, which is only linked with the `recording` feature
+#[cfg(not(feature = "recording"))]
+struct VideoEncoder;
+
+#[cfg(not(feature = "recording"))]
+impl VideoEncoder {
+    fn new(_: &Path, _: u32, _: u32, _: u32) -> Result<Self, Box<dyn Error>> {
+        Err("recording a viewport requires the `recording` feature of gpui-component".into())
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (0, 0)
+    }
+
+    fn write_frame(&mut self, _: &[u8], _: i64) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
 
 /// High-performance viewport component with async rendering
 pub struct Viewport<E: RenderEngine> {
     focus_handle: FocusHandle,
     render_engine: Arc<Mutex<E>>,
-    double_buffer: Arc<Mutex<DoubleBuffer>>,
+    swap_chain: Arc<SwapChain>,
     visible: bool,
     bounds: Bounds<Pixels>,
 
     // Async rendering
     render_tx: mpsc::Sender<RenderCommand>,
+    input_tx: mpsc::Sender<ViewportInput>,
     _render_thread: std::thread::JoinHandle<()>,
 
     // Performance tracking
@@ -262,6 +1120,9 @@ pub struct Viewport<E: RenderEngine> {
     rgba_conversion_buffer: Vec<u8>,
     last_width: u32,
     last_height: u32,
+    /// Render at this size instead of the viewport bounds, see [`Viewport::set_target_size`]
+    target_render_size: Option<(u32, u32)>,
+    scale_mode: ScaleMode,
 
     // Debug flags
     debug_enabled: bool,
@@ -284,14 +1145,27 @@ impl<E: RenderEngine> Drop for Viewport<E> {
 
 impl<E: RenderEngine> Viewport<E> {
     pub fn new(render_engine: E, initial_width: u32, initial_height: u32, cx: &mut App) -> Self {
+        Self::new_with_buffering(render_engine, initial_width, initial_height, BufferingStrategy::default(), cx)
+    }
+
+    /// Create a viewport that renders to the framebuffers of the `buffering` strategy, the
+    /// default is [`BufferingStrategy::Triple`].
+    pub fn new_with_buffering(
+        render_engine: E,
+        initial_width: u32,
+        initial_height: u32,
+        buffering: BufferingStrategy,
+        cx: &mut App,
+    ) -> Self {
         let format = render_engine.preferred_format();
-        let double_buffer = Arc::new(Mutex::new(DoubleBuffer::new(initial_width, initial_height, format)));
+        let swap_chain = Arc::new(SwapChain::new(buffering, initial_width, initial_height, format));
         let render_engine = Arc::new(Mutex::new(render_engine));
         let metrics = Arc::new(Mutex::new(ViewportMetrics::default()));
         let frame_times = Arc::new(Mutex::new(VecDeque::with_capacity(60)));
 
         // Create render thread
         let (render_tx, render_rx) = mpsc::channel();
+        let (input_tx, input_rx) = mpsc::channel();
 
         // Initialize render engine
         if let Ok(mut engine) = render_engine.lock() {
@@ -300,7 +1174,7 @@ impl<E: RenderEngine> Viewport<E> {
             }
         }
         let engine_clone = render_engine.clone();
-        let buffer_clone = double_buffer.clone();
+        let buffer_clone = swap_chain.clone();
         let metrics_clone = metrics.clone();
         let frame_times_clone = frame_times.clone();
 
@@ -310,17 +1184,19 @@ impl<E: RenderEngine> Viewport<E> {
                 buffer_clone,
                 metrics_clone,
                 frame_times_clone,
-                render_rx
+                render_rx,
+                input_rx,
             );
         });
 
         Self {
             focus_handle: cx.focus_handle(),
             render_engine,
-            double_buffer,
+            swap_chain,
             visible: true,
             bounds: Bounds::default(),
             render_tx,
+            input_tx,
             _render_thread: render_thread,
             metrics,
             frame_times,
@@ -330,6 +1206,8 @@ impl<E: RenderEngine> Viewport<E> {
             rgba_conversion_buffer: Vec::new(),
             last_width: initial_width,
             last_height: initial_height,
+            target_render_size: None,
+            scale_mode: ScaleMode::default(),
             debug_enabled: cfg!(debug_assertions),
             entity: None,
         }
@@ -359,82 +1237,162 @@ impl<E: RenderEngine> Viewport<E> {
     /// Main render thread loop
     fn render_thread_main(
         render_engine: Arc<Mutex<E>>,
-        double_buffer: Arc<Mutex<DoubleBuffer>>,
+        swap_chain: Arc<SwapChain>,
         metrics: Arc<Mutex<ViewportMetrics>>,
         frame_times: Arc<Mutex<VecDeque<Instant>>>,
         render_rx: mpsc::Receiver<RenderCommand>,
+        input_rx: mpsc::Receiver<ViewportInput>,
     ) {
         let mut should_continue = true;
+        let mut pacing = RenderPacing::new();
+        let mut recording: Option<RecordingState> = None;
 
         while should_continue {
-            match render_rx.recv_timeout(Duration::from_millis(16)) { // ~60 FPS max
+            Self::dispatch_input(&render_engine, &input_rx);
+
+            let idle_duration = pacing.idle_duration();
+            if let Ok(mut metrics) = metrics.lock() {
+                metrics.is_idle = idle_duration.is_some();
+                metrics.idle_duration = idle_duration.unwrap_or_default();
+            }
+
+            match render_rx.recv_timeout(pacing.frame_interval()) {
                 Ok(command) => match command {
                     RenderCommand::Render => {
-                        Self::perform_render(&render_engine, &double_buffer, &metrics, &frame_times);
+                        pacing.last_activity = Instant::now();
+                        Self::perform_render(&render_engine, &swap_chain, &metrics, &frame_times);
                     }
-                    RenderCommand::Resize(width, height) => {
-                        if let Ok(mut buffer) = double_buffer.lock() {
-                            buffer.resize(width, height);
+                    RenderCommand::SetTargetFps(fps) => {
+                        pacing.target_fps = fps;
+                    }
+                    RenderCommand::SetIdleTimeout(duration) => {
+                        pacing.idle_after = duration;
+                        pacing.last_activity = Instant::now();
+                    }
+                    RenderCommand::StartRecording { path, fps, duration } => {
+                        Self::finish_recording(recording.take(), &metrics);
+                        recording = Some(RecordingState::new(path, fps, duration));
+                        if let Ok(mut metrics) = metrics.lock() {
+                            metrics.is_recording = true;
+                            metrics.recorded_frames = 0;
                         }
+                    }
+                    RenderCommand::StopRecording => {
+                        Self::finish_recording(recording.take(), &metrics);
+                    }
+                    RenderCommand::Resize(width, height) => {
+                        swap_chain.resize(width, height);
                         if let Ok(mut engine) = render_engine.lock() {
                             engine.on_resize(width, height);
                         }
                     }
                     RenderCommand::Shutdown => {
                         should_continue = false;
+                        Self::finish_recording(recording.take(), &metrics);
                         if let Ok(mut engine) = render_engine.lock() {
                             engine.cleanup();
                         }
                     }
                 },
                 Err(mpsc::RecvTimeoutError::Timeout) => {
-                    // Continue rendering at target framerate
-                    Self::perform_render(&render_engine, &double_buffer, &metrics, &frame_times);
+                    // Continue rendering at target framerate, until the content is static
+                    if idle_duration.is_none()
+                        && Self::perform_render(&render_engine, &swap_chain, &metrics, &frame_times)
+                    {
+                        pacing.last_activity = Instant::now();
+                    }
                 }
                 Err(mpsc::RecvTimeoutError::Disconnected) => {
                     should_continue = false;
+                    Self::finish_recording(recording.take(), &metrics);
                 }
             }
+
+            if let Some(state) = recording.as_mut() {
+                let finished = state.capture(&swap_chain).unwrap_or_else(|e| {
+                    eprintln!("[VIEWPORT] Recording error: {}", e);
+                    true
+                });
+                if let Ok(mut metrics) = metrics.lock() {
+                    metrics.recorded_frames = state.frames;
+                }
+                if finished {
+                    Self::finish_recording(recording.take(), &metrics);
+                }
+            }
+        }
+    }
+
+    fn finish_recording(recording: Option<RecordingState>, metrics: &Arc<Mutex<ViewportMetrics>>) {
+        let Some(recording) = recording else {
+            return;
+        };
+        let path = recording.path.clone();
+        if let Err(e) = recording.finish() {
+            eprintln!("[VIEWPORT] Failed to finish recording {}: {}", path.display(), e);
+        }
+        if let Ok(mut metrics) = metrics.lock() {
+            metrics.is_recording = false;
+        }
+    }
+
+    /// Deliver the pending input to the render engine before the next frame
+    fn dispatch_input(render_engine: &Arc<Mutex<E>>, input_rx: &mpsc::Receiver<ViewportInput>) {
+        let mut pending = input_rx.try_iter().peekable();
+        if pending.peek().is_none() {
+            return;
+        }
+
+        let Ok(mut engine) = render_engine.lock() else {
+            return;
+        };
+        for input in pending {
+            match input {
+                ViewportInput::Mouse(event) => engine.on_mouse_event(event),
+                ViewportInput::Key(event) => engine.on_key_event(event),
+            }
         }
     }
 
     fn perform_render(
         render_engine: &Arc<Mutex<E>>,
-        double_buffer: &Arc<Mutex<DoubleBuffer>>,
+        swap_chain: &SwapChain,
         metrics: &Arc<Mutex<ViewportMetrics>>,
         frame_times: &Arc<Mutex<VecDeque<Instant>>>,
-    ) {
+    ) -> bool {
         let start_time = Instant::now();
 
-        // Render to back buffer
+        // Render to back buffer, it becomes the front buffer when the render succeeds
         let render_result = {
-            let mut buffer_guard = match double_buffer.lock() {
-                Ok(guard) => guard,
-                Err(_) => return,
-            };
-
             let mut engine_guard = match render_engine.lock() {
                 Ok(guard) => guard,
-                Err(_) => return,
+                Err(_) => return false,
             };
 
-            let back_buffer = buffer_guard.get_back_buffer();
-            engine_guard.render(back_buffer)
+            let result = swap_chain.render(|back_buffer| {
+                let result = engine_guard.render(back_buffer);
+                let dirty = back_buffer.is_dirty();
+                back_buffer.clear_dirty();
+                result.map(|_| dirty)
+            });
+            match result {
+                Some(result) => result,
+                None => return false,
+            }
         };
 
-        if let Err(e) = render_result {
-            eprintln!("[VIEWPORT] Render error: {}", e);
-            return;
-        }
-
-        // Swap buffers
-        if let Ok(mut buffer_guard) = double_buffer.lock() {
-            buffer_guard.swap();
-        }
+        let dirty = match render_result {
+            Ok(dirty) => dirty,
+            Err(e) => {
+                eprintln!("[VIEWPORT] Render error: {}", e);
+                return false;
+            }
+        };
 
         // Update metrics
         let frame_time = start_time.elapsed();
         Self::update_metrics(metrics, frame_times, frame_time);
+        dirty
     }
 
     fn update_metrics(
@@ -502,6 +1460,151 @@ impl<E: RenderEngine> Viewport<E> {
         let _ = self.render_tx.send(RenderCommand::Render);
     }
 
+    /// Render at `width` x `height` instead of the viewport size, the image is scaled to the
+    /// bounds with the [`ScaleMode`], e.g. to render at a lower resolution for performance
+    pub fn set_target_size(&mut self, width: u32, height: u32) {
+        if self.target_render_size == Some((width, height)) {
+            return;
+        }
+
+        self.target_render_size = Some((width, height));
+        let _ = self.render_tx.send(RenderCommand::Resize(width, height));
+        self.texture_dirty = true;
+    }
+
+    /// Render at the viewport size again, the framebuffer is resized on the next layout
+    pub fn clear_target_size(&mut self) {
+        self.target_render_size = None;
+        self.texture_dirty = true;
+    }
+
+    pub fn target_size(&self) -> Option<(u32, u32)> {
+        self.target_render_size
+    }
+
+    /// Set how the image is positioned within the bounds, default [`ScaleMode::Stretch`]
+    pub fn set_scale_mode(&mut self, scale_mode: ScaleMode) {
+        self.scale_mode = scale_mode;
+    }
+
+    pub fn scale_mode(&self) -> ScaleMode {
+        self.scale_mode
+    }
+
+    /// The bounds the image is painted in, the viewport bounds unless a target size is set
+    fn image_bounds(&self, bounds: Bounds<Pixels>) -> Bounds<Pixels> {
+        let image_size = Size { width: px(self.last_width as f32), height: px(self.last_height as f32) };
+        self.scale_mode.image_bounds(bounds, image_size)
+    }
+
+    /// Convert a window position to the viewport's coordinate space,
+    /// or to the render pixels when a target size is set
+    fn local_position(&self, position: Point<Pixels>) -> Point<Pixels> {
+        let Some((width, height)) = self.target_render_size else {
+            return position - self.bounds.origin;
+        };
+
+        let image = self.image_bounds(self.bounds);
+        if image.size.width <= px(0.0) || image.size.height <= px(0.0) {
+            return position - self.bounds.origin;
+        }
+        let local = position - image.origin;
+        Point {
+            x: local.x * (width as f32 / image.size.width.0),
+            y: local.y * (height as f32 / image.size.height.0),
+        }
+    }
+
+    fn send_mouse_event(&self, event: ViewportMouseEvent) {
+        let _ = self.input_tx.send(ViewportInput::Mouse(event));
+    }
+
+    fn send_key_event(&self, event: ViewportKeyEvent) {
+        let _ = self.input_tx.send(ViewportInput::Key(event));
+    }
+
+    fn on_mouse_down(&mut self, event: &MouseDownEvent, window: &mut Window, _: &mut Context<Self>) {
+        window.focus(&self.focus_handle);
+        self.send_mouse_event(ViewportMouseEvent::Down {
+            button: event.button,
+            position: self.local_position(event.position),
+            modifiers: event.modifiers,
+            click_count: event.click_count,
+        });
+    }
+
+    fn on_mouse_up(&mut self, event: &MouseUpEvent, _: &mut Window, _: &mut Context<Self>) {
+        self.send_mouse_event(ViewportMouseEvent::Up {
+            button: event.button,
+            position: self.local_position(event.position),
+            modifiers: event.modifiers,
+        });
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, _: &mut Context<Self>) {
+        self.send_mouse_event(ViewportMouseEvent::Move {
+            position: self.local_position(event.position),
+            pressed_button: event.pressed_button,
+            modifiers: event.modifiers,
+        });
+    }
+
+    fn on_scroll_wheel(&mut self, event: &ScrollWheelEvent, _: &mut Window, _: &mut Context<Self>) {
+        self.send_mouse_event(ViewportMouseEvent::Scroll {
+            position: self.local_position(event.position),
+            delta: event.delta,
+            modifiers: event.modifiers,
+        });
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, _: &mut Context<Self>) {
+        self.send_key_event(ViewportKeyEvent::Down {
+            keystroke: event.keystroke.clone(),
+            is_held: event.is_held,
+        });
+    }
+
+    fn on_key_up(&mut self, event: &KeyUpEvent, _: &mut Window, _: &mut Context<Self>) {
+        self.send_key_event(ViewportKeyEvent::Up {
+            keystroke: event.keystroke.clone(),
+        });
+    }
+
+    /// Set the maximum frames per second of the render thread, default 60
+    pub fn set_target_fps(&self, fps: f64) {
+        if !fps.is_finite() || fps <= 0.0 {
+            return;
+        }
+        let _ = self.render_tx.send(RenderCommand::SetTargetFps(fps));
+    }
+
+    /// Stop continuous rendering after `duration` without render requests or dirty frames, default 2s
+    pub fn set_idle_timeout(&self, duration: Duration) {
+        let _ = self.render_tx.send(RenderCommand::SetIdleTimeout(duration));
+    }
+
+    /// Record the front buffer to a H.264 video file at `path` (e.g. `demo.mp4`), at `fps`
+    /// frames per second for `duration`, replacing a recording in progress.
+    ///
+    /// The frames are captured on the render thread after each swap. Requires the `recording`
+    /// feature, the progress is in [`ViewportMetrics::is_recording`] and
+    /// [`ViewportMetrics::recorded_frames`].
+    pub fn start_recording(&self, path: impl Into<PathBuf>, fps: u32, duration: Duration) {
+        if fps == 0 {
+            return;
+        }
+        let _ = self.render_tx.send(RenderCommand::StartRecording {
+            path: path.into(),
+            fps,
+            duration,
+        });
+    }
+
+    /// Stop the recording in progress and finalize the video file
+    pub fn stop_recording(&self) {
+        let _ = self.render_tx.send(RenderCommand::StopRecording);
+    }
+
     /// Get current performance metrics
     pub fn metrics(&self) -> ViewportMetrics {
         self.metrics.lock().map(|m| m.clone()).unwrap_or_default()
@@ -523,10 +1626,7 @@ impl<E: RenderEngine> Viewport<E> {
     /// Get a reference to the current framebuffer for reading (front buffer)
     /// Returns width, height, format, and generation without cloning the buffer
     pub fn current_framebuffer_info(&self) -> Option<(u32, u32, FramebufferFormat, u64)> {
-        self.double_buffer.lock().ok().map(|buffer| {
-            let front = buffer.get_front_buffer();
-            (front.width, front.height, front.format, front.generation)
-        })
+        self.swap_chain.with_front(|front| (front.width, front.height, front.format, front.generation))
     }
 
     /// Access the current framebuffer with a closure to avoid cloning
@@ -534,20 +1634,28 @@ impl<E: RenderEngine> Viewport<E> {
     where
         F: FnOnce(&Framebuffer) -> R,
     {
-        self.double_buffer.lock().ok().map(|buffer| {
-            let front = buffer.get_front_buffer();
-            f(front)
-        })
+        self.swap_chain.with_front(f)
     }
 
-    fn update_texture_if_needed(&mut self, _window: &mut Window) {
-        let buffer_guard = match self.double_buffer.lock() {
-            Ok(guard) => guard,
-            Err(_) => return,
-        };
+    /// Save the front framebuffer to a PNG file
+    pub fn capture_frame(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        std::fs::write(path, self.capture_frame_to_vec()?)?;
+        Ok(())
+    }
 
-        let front_buffer = buffer_guard.get_front_buffer();
+    /// Encode the front framebuffer as a PNG image in memory
+    pub fn capture_frame_to_vec(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.swap_chain
+            .with_front(|front| front.to_png())
+            .ok_or("framebuffer lock is poisoned")?
+    }
 
+    fn update_texture_if_needed(&mut self, _window: &mut Window) {
+        let swap_chain = self.swap_chain.clone();
+        swap_chain.with_front(|front_buffer| self.update_texture_from(front_buffer));
+    }
+
+    fn update_texture_from(&mut self, front_buffer: &Framebuffer) {
         // Check if texture needs updating
         let needs_update = self.current_texture.is_none()
             || self.texture_dirty
@@ -569,56 +1677,9 @@ impl<E: RenderEngine> Viewport<E> {
         }
 
 
-        // Reuse conversion buffer to avoid allocations
-        let required_size = match front_buffer.format {
-            FramebufferFormat::Rgba8 | FramebufferFormat::Bgra8 => front_buffer.buffer.len(),
-            FramebufferFormat::Rgb8 | FramebufferFormat::Bgr8 => front_buffer.buffer.len() * 4 / 3,
-        };
-
-        if self.rgba_conversion_buffer.len() != required_size {
-            self.rgba_conversion_buffer.resize(required_size, 0);
-        }
-
-        // Convert to RGBA8 format for GPUI using pre-allocated buffer
-        let rgba_buffer = match front_buffer.format {
-            FramebufferFormat::Rgba8 => {
-                self.rgba_conversion_buffer.copy_from_slice(&front_buffer.buffer);
-                &self.rgba_conversion_buffer
-            }
-            FramebufferFormat::Bgra8 => {
-                // Convert BGRA to RGBA in-place
-                for (i, chunk) in front_buffer.buffer.chunks_exact(4).enumerate() {
-                    let offset = i * 4;
-                    self.rgba_conversion_buffer[offset] = chunk[2];     // R
-                    self.rgba_conversion_buffer[offset + 1] = chunk[1]; // G
-                    self.rgba_conversion_buffer[offset + 2] = chunk[0]; // B
-                    self.rgba_conversion_buffer[offset + 3] = chunk[3]; // A
-                }
-                &self.rgba_conversion_buffer
-            }
-            FramebufferFormat::Rgb8 => {
-                // Convert RGB to RGBA
-                for (i, chunk) in front_buffer.buffer.chunks_exact(3).enumerate() {
-                    let offset = i * 4;
-                    self.rgba_conversion_buffer[offset] = chunk[0];     // R
-                    self.rgba_conversion_buffer[offset + 1] = chunk[1]; // G
-                    self.rgba_conversion_buffer[offset + 2] = chunk[2]; // B
-                    self.rgba_conversion_buffer[offset + 3] = 255;     // A
-                }
-                &self.rgba_conversion_buffer
-            }
-            FramebufferFormat::Bgr8 => {
-                // Convert BGR to RGBA
-                for (i, chunk) in front_buffer.buffer.chunks_exact(3).enumerate() {
-                    let offset = i * 4;
-                    self.rgba_conversion_buffer[offset] = chunk[2];     // R
-                    self.rgba_conversion_buffer[offset + 1] = chunk[1]; // G
-                    self.rgba_conversion_buffer[offset + 2] = chunk[0]; // B
-                    self.rgba_conversion_buffer[offset + 3] = 255;     // A
-                }
-                &self.rgba_conversion_buffer
-            }
-        };
+        // Convert to RGBA8 format for GPUI, reusing the conversion buffer to avoid allocations
+        front_buffer.to_rgba8(&mut self.rgba_conversion_buffer);
+        let rgba_buffer = &self.rgba_conversion_buffer;
 
         // Create image buffer from converted data
         if let Some(image_buffer) = image::ImageBuffer::from_vec(
@@ -663,21 +1724,30 @@ impl<E: RenderEngine> Render for Viewport<E> {
         div()
             .track_focus(&self.focus_handle)
             .size_full()
+            .on_any_mouse_down(cx.listener(Self::on_mouse_down))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up(MouseButton::Right, cx.listener(Self::on_mouse_up))
+            .on_mouse_up(MouseButton::Middle, cx.listener(Self::on_mouse_up))
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+            .on_key_down(cx.listener(Self::on_key_down))
+            .on_key_up(cx.listener(Self::on_key_up))
             .child({
                 let view_layout = cx.entity().clone();
                 let view_paint = cx.entity().clone();
                 canvas(
                     move |bounds, _, cx| {
                         view_layout.update(cx, |viewport, _| {
-                            let width = bounds.size.width.0 as u32;
-                            let height = bounds.size.height.0 as u32;
+                            let (width, height) = viewport.target_render_size.unwrap_or((
+                                bounds.size.width.0 as u32,
+                                bounds.size.height.0 as u32,
+                            ));
 
                             viewport.bounds = bounds;
 
                             // Resize if needed
-                            if let Ok(buffer) = viewport.double_buffer.lock() {
-                                let front = buffer.get_front_buffer();
-                                if front.width != width || front.height != height {
+                            if let Some(front_size) = viewport.swap_chain.with_front(|front| (front.width, front.height)) {
+                                if front_size != (width, height) {
                                     // Clean up current texture before resize to free memory
                                     viewport.current_texture = None;
                                     viewport.rgba_conversion_buffer.clear();
@@ -703,11 +1773,12 @@ impl<E: RenderEngine> Render for Viewport<E> {
                             // Update texture if needed
                             viewport.update_texture_if_needed(window);
 
-                            // Paint the texture
+                            // Paint the texture, GPUI scales it to the image bounds
                             if let Some(ref texture) = viewport.current_texture {
+                                let image_bounds = viewport.image_bounds(bounds);
                                 window.with_content_mask(Some(ContentMask { bounds }), |window| {
                                     let _ = window.paint_image(
-                                        bounds,
+                                        image_bounds,
                                         Corners::all(px(0.0)),
                                         texture.clone(),
                                         0,
@@ -741,14 +1812,19 @@ pub struct TestRenderEngine {
     frame_count: u64,
     color_cycle: f32,
     notify_callback: Option<Box<dyn Fn() + Send + Sync>>,
+    capture_dir: Option<PathBuf>,
 }
 
+/// Save every Nth frame when [`TestRenderEngine::with_capture_dir`] is set
+const TEST_ENGINE_CAPTURE_INTERVAL: u64 = 100;
+
 impl std::fmt::Debug for TestRenderEngine {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TestRenderEngine")
             .field("frame_count", &self.frame_count)
             .field("color_cycle", &self.color_cycle)
             .field("notify_callback", &self.notify_callback.as_ref().map(|_| "<callback>"))
+            .field("capture_dir", &self.capture_dir)
             .finish()
     }
 }
@@ -759,8 +1835,15 @@ impl TestRenderEngine {
             frame_count: 0,
             color_cycle: 0.0,
             notify_callback: None,
+            capture_dir: None,
         }
     }
+
+    /// Save every 100th frame as `frame_<N>.png` into the directory
+    pub fn with_capture_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.capture_dir = Some(dir.into());
+        self
+    }
 }
 
 impl RenderEngine for TestRenderEngine {
@@ -776,21 +1859,32 @@ impl RenderEngine for TestRenderEngine {
         framebuffer.clear([r, g, b, 255]);
 
         // Draw some animated content
-        for y in 0..framebuffer.height {
-            for x in 0..framebuffer.width {
-                let offset = ((y * framebuffer.pitch + x * 4) as usize).min(framebuffer.buffer.len().saturating_sub(4));
-                if offset + 3 < framebuffer.buffer.len() {
-                    let wave = ((x as f32 / 50.0 + self.color_cycle).sin() * 127.0 + 128.0) as u8;
-                    framebuffer.buffer[offset] = wave;
-                    framebuffer.buffer[offset + 1] = ((y as f32 / 50.0 + self.color_cycle).cos() * 127.0 + 128.0) as u8;
-                    framebuffer.buffer[offset + 2] = b;
-                    framebuffer.buffer[offset + 3] = 255;
-                }
-            }
+        let (width, height) = (framebuffer.width as i32, framebuffer.height as i32);
+        for x in (0..width).step_by(4) {
+            let wave = ((x as f32 / 50.0 + self.color_cycle).sin() * 127.0 + 128.0) as u8;
+            framebuffer.draw_rect(x, 0, 2, framebuffer.height, [wave, 255 - wave, b, 255]);
         }
 
+        let white = [255, 255, 255, 255];
+        let (center_x, center_y) = (width / 2, height / 2);
+        let orbit = (width.min(height) / 4).max(1);
+        let orbit_x = center_x + (self.color_cycle.cos() * orbit as f32) as i32;
+        let orbit_y = center_y + (self.color_cycle.sin() * orbit as f32) as i32;
+        framebuffer.draw_line(center_x, center_y, orbit_x, orbit_y, white);
+        framebuffer.draw_circle(orbit_x, orbit_y, (orbit / 4) as u32, white);
+        framebuffer.draw_text_ascii(8, 8, &format!("frame {}", self.frame_count), white);
+
         framebuffer.mark_dirty(None);
 
+        if let Some(dir) = &self.capture_dir {
+            if self.frame_count % TEST_ENGINE_CAPTURE_INTERVAL == 0 {
+                let path = dir.join(format!("frame_{}.png", self.frame_count));
+                if let Err(e) = framebuffer.save_png(&path) {
+                    eprintln!("[TEST_ENGINE] Failed to capture frame {}: {}", self.frame_count, e);
+                }
+            }
+        }
+
         // Notify GPUI that the viewport needs to be redrawn
         // This is called from the render thread after each frame is complete
         if let Some(callback) = &self.notify_callback {
@@ -822,4 +1916,181 @@ impl Default for TestRenderEngine {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use gpui::{point, px, size, Bounds};
+
+    use super::{
+        back_buffer_index, BufferingStrategy, Framebuffer, FramebufferFormat, RenderPacing, ScaleMode,
+        SwapChain,
+    };
+
+    #[test]
+    fn test_f32_framebuffer() {
+        let mut framebuffer = Framebuffer::new(2, 2, FramebufferFormat::F32Rgba);
+        assert!(framebuffer.buffer.is_empty());
+        assert_eq!(framebuffer.hdr_buffer.len(), 4);
+        assert_eq!(framebuffer.pitch, 32);
+
+        framebuffer.clear([255, 0, 0, 255]);
+        *framebuffer.pixel_mut_f32(1, 1) = [3.0, 0.0, f32::INFINITY, 0.5];
+
+        let mut rgba = Vec::new();
+        framebuffer.to_rgba8(&mut rgba);
+        assert_eq!(rgba.len(), 16);
+        // Reinhard: 1.0 / (1.0 + 1.0) = 0.5
+        assert_eq!(&rgba[0..4], &[128, 0, 0, 255]);
+        // 3.0 / (1.0 + 3.0) = 0.75
+        assert_eq!(&rgba[12..16], &[191, 0, 255, 128]);
+
+        framebuffer.resize(3, 1);
+        assert_eq!(framebuffer.hdr_buffer.len(), 3);
+        assert!(framebuffer.buffer.is_empty());
+    }
+
+    #[test]
+    fn test_blit() {
+        let mut src = Framebuffer::new(2, 2, FramebufferFormat::Rgba8);
+        src.clear([255, 0, 0, 255]);
+
+        // Cross format
+        let mut dst = Framebuffer::new(3, 3, FramebufferFormat::Bgr8);
+        dst.clear_dirty();
+        dst.blit(&src, Bounds::new(point(px(1.), px(1.)), size(px(2.), px(2.))));
+        let mut rgba = Vec::new();
+        dst.to_rgba8(&mut rgba);
+        assert_eq!(&rgba[0..4], &[0, 0, 0, 255]);
+        assert_eq!(&rgba[16..20], &[255, 0, 0, 255]);
+        assert_eq!(&rgba[32..36], &[255, 0, 0, 255]);
+        assert_eq!(
+            dst.dirty_rect(),
+            Some(Bounds::new(point(px(1.), px(1.)), size(px(2.), px(2.))))
+        );
+
+        // Same format, clipped to the destination
+        let mut dst = Framebuffer::new(3, 3, FramebufferFormat::Rgba8);
+        dst.blit(&src, Bounds::new(point(px(2.), px(-1.)), size(px(2.), px(2.))));
+        assert_eq!(&dst.buffer[8..12], &[255, 0, 0, 255]);
+        assert_eq!(&dst.buffer[4..8], &[0, 0, 0, 0]);
+        assert_eq!(&dst.buffer[20..24], &[0, 0, 0, 0]);
+
+        // Scaled into HDR
+        let mut dst = Framebuffer::new(4, 4, FramebufferFormat::F32Rgba);
+        dst.blit(&src, Bounds::new(point(px(0.), px(0.)), size(px(4.), px(4.))));
+        assert!(dst.hdr_buffer.iter().all(|pixel| *pixel == [1.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_blit_with_alpha() {
+        let mut src = Framebuffer::new(1, 1, FramebufferFormat::Rgba8);
+        src.clear([255, 0, 0, 255]);
+        let mut dst = Framebuffer::new(1, 1, FramebufferFormat::Rgb8);
+        dst.clear([0, 0, 255, 255]);
+
+        let rect = Bounds::new(point(px(0.), px(0.)), size(px(1.), px(1.)));
+        dst.blit_with_alpha(&src, rect, 0.5);
+        assert_eq!(dst.buffer, vec![128, 0, 128]);
+
+        // A transparent source leaves the destination unchanged
+        src.clear([0, 255, 0, 0]);
+        dst.blit_with_alpha(&src, rect, 1.0);
+        assert_eq!(dst.buffer, vec![128, 0, 128]);
+    }
+
+    #[test]
+    fn test_scale_mode_image_bounds() {
+        let bounds = Bounds::new(point(px(10.), px(10.)), size(px(200.), px(100.)));
+        let image = size(px(100.), px(100.));
+
+        assert_eq!(ScaleMode::Stretch.image_bounds(bounds, image), bounds);
+        assert_eq!(
+            ScaleMode::Fit.image_bounds(bounds, image),
+            Bounds::new(point(px(60.), px(10.)), size(px(100.), px(100.)))
+        );
+        assert_eq!(
+            ScaleMode::Fill.image_bounds(bounds, image),
+            Bounds::new(point(px(10.), px(-40.)), size(px(200.), px(200.)))
+        );
+        assert_eq!(ScaleMode::Fit.image_bounds(bounds, size(px(0.), px(0.))), bounds);
+    }
+
+    #[test]
+    fn test_render_pacing() {
+        let mut pacing = RenderPacing::new();
+        assert_eq!(pacing.frame_interval(), Duration::from_secs_f64(1.0 / 60.0));
+        assert_eq!(pacing.idle_duration(), None);
+
+        pacing.target_fps = 30.0;
+        assert_eq!(pacing.frame_interval(), Duration::from_secs_f64(1.0 / 30.0));
+
+        pacing.last_activity = Instant::now() - Duration::from_secs(3);
+        assert!(pacing.idle_duration().unwrap() >= Duration::from_secs(1));
+
+        pacing.idle_after = Duration::from_secs(10);
+        assert_eq!(pacing.idle_duration(), None);
+    }
+
+    #[test]
+    fn test_back_buffer_index() {
+        assert_eq!(back_buffer_index(0, [0, 0, 0]), Some(1));
+        assert_eq!(back_buffer_index(0, [1, 0, 0]), Some(1));
+        assert_eq!(back_buffer_index(0, [0, 1, 0]), Some(2));
+        assert_eq!(back_buffer_index(2, [1, 0, 0]), Some(1));
+        assert_eq!(back_buffer_index(1, [0, 0, 2]), Some(0));
+        assert_eq!(back_buffer_index(1, [1, 0, 1]), None);
+    }
+
+    #[test]
+    fn test_pack_size() {
+        assert_eq!(unpack_size(pack_size(1920, 1080)), (1920, 1080));
+        assert_eq!(unpack_size(pack_size(u32::MAX, 0)), (u32::MAX, 0));
+    }
+
+    #[test]
+    fn test_triple_buffer_readers() {
+        let buffer = TripleBuffer::new(1, 1, FramebufferFormat::Rgba8);
+        buffer.with_front(|front| {
+            // The frame rendered while the front buffer is read goes to another buffer.
+            buffer.render(|back| {
+                back.clear([255, 0, 0, 255]);
+                Ok::<_, ()>(())
+            })
+            .unwrap()
+            .unwrap();
+            assert_eq!(front.buffer, vec![0, 0, 0, 0]);
+        });
+        assert_eq!(buffer.readers.iter().map(|r| r.load(Ordering::SeqCst)).sum::<usize>(), 0);
+        assert_eq!(buffer.with_front(|front| front.buffer.clone()).unwrap(), vec![255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_swap_chain() {
+        for strategy in [BufferingStrategy::Double, BufferingStrategy::Triple] {
+            let swap_chain = SwapChain::new(strategy, 1, 1, FramebufferFormat::Rgba8);
+
+            swap_chain
+                .render(|back| {
+                    back.clear([255, 0, 0, 255]);
+                    Ok::<_, ()>(())
+                })
+                .unwrap()
+                .unwrap();
+            let red = swap_chain.with_front(|front| front.buffer.clone()).unwrap();
+            assert_eq!(red, vec![255, 0, 0, 255]);
+
+            // A failed render is not published.
+            let _ = swap_chain.render(|back| {
+                back.clear([0, 255, 0, 255]);
+                Err::<(), _>(())
+            });
+            assert_eq!(swap_chain.with_front(|front| front.buffer.clone()).unwrap(), red);
+
+            swap_chain.resize(2, 1);
+            swap_chain.render(|_| Ok::<_, ()>(())).unwrap().unwrap();
+            assert_eq!(swap_chain.with_front(|front| front.width).unwrap(), 2);
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! CPU drawing primitives of the [`Framebuffer`], to prototype render engines without a GPU.
+
+use std::ops::Range;
+
+use gpui::{point, px, size, Bounds};
+
+use super::Framebuffer;
+
+/// Width and height in pixels of the glyphs of [`Framebuffer::draw_text_ascii`].
+pub const GLYPH_SIZE: u32 = 8;
+
+/// The first character of [`FONT_8X8`], the font covers `' '..='~'`.
+const FONT_FIRST_CHAR: u8 = b' ';
+
+/// The public domain `font8x8_basic` bitmap font by Daniel Hepper, 8 bytes per glyph.
+///
+/// Each byte is a row from the top, with the leftmost pixel in the lowest bit.
+const FONT_8X8: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // space
+    0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00, // !
+    0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // "
+    0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00, // #
+    0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00, // $
+    0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00, // %
+    0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00, // &
+    0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, // '
+    0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00, // (
+    0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00, // )
+    0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00, // *
+    0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00, // +
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06, // ,
+    0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00, // -
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00, // .
+    0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00, // /
+    0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00, // 0
+    0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00, // 1
+    0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00, // 2
+    0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00, // 3
+    0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00, // 4
+    0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00, // 5
+    0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00, // 6
+    0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00, // 7
+    0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00, // 8
+    0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00, // 9
+    0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00, // :
+    0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06, // ;
+    0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00, // <
+    0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00, // =
+    0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00, // >
+    0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00, // ?
+    0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00, // @
+    0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00, // A
+    0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00, // B
+    0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00, // C
+    0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00, // D
+    0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00, // E
+    0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00, // F
+    0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00, // G
+    0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00, // H
+    0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00, // I
+    0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00, // J
+    0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00, // K
+    0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00, // L
+    0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00, // M
+    0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00, // N
+    0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00, // O
+    0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00, // P
+    0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00, // Q
+    0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00, // R
+    0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00, // S
+    0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00, // T
+    0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00, // U
+    0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00, // V
+    0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00, // W
+    0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00, // X
+    0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00, // Y
+    0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00, // Z
+    0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00, // [
+    0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00, // \
+    0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00, // ]
+    0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00, // ^
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, // _
+    0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00, // `
+    0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00, // a
+    0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00, // b
+    0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00, // c
+    0x38, 0x30, 0x30, 0x3E, 0x33, 0x33, 0x6E, 0x00, // d
+    0x00, 0x00, 0x1E, 0x33, 0x3F, 0x03, 0x1E, 0x00, // e
+    0x1C, 0x36, 0x06, 0x0F, 0x06, 0x06, 0x0F, 0x00, // f
+    0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F, // g
+    0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00, // h
+    0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00, // i
+    0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, // j
+    0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00, // k
+    0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00, // l
+    0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00, // m
+    0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00, // n
+    0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00, // o
+    0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F, // p
+    0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78, // q
+    0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00, // r
+    0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00, // s
+    0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00, // t
+    0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00, // u
+    0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00, // v
+    0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00, // w
+    0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00, // x
+    0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F, // y
+    0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00, // z
+    0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00, // {
+    0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00, // |
+    0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00, // }
+    0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // ~
+];
+
+impl Framebuffer {
+    /// Draw a 1px line from `x0`, `y0` to `x1`, `y1` (inclusive) with Bresenham's algorithm.
+    ///
+    /// The pixels outside of the framebuffer are skipped.
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: [u8; 4]) {
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+        let mut err = dx + dy;
+        let (mut x, mut y) = (x0, y0);
+        loop {
+            self.plot(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+
+        self.mark_drawn(x0.min(x1)..x0.max(x1) + 1, y0.min(y1)..y0.max(y1) + 1);
+    }
+
+    /// Fill the rectangle of `w` x `h` pixels at `x`, `y`, clipped to the framebuffer.
+    pub fn draw_rect(&mut self, x: i32, y: i32, w: u32, h: u32, color: [u8; 4]) {
+        let cols = clip(x, w, self.width);
+        let rows = clip(y, h, self.height);
+        for y in rows.clone() {
+            for x in cols.clone() {
+                self.set_pixel_rgba8(x, y, color);
+            }
+        }
+
+        self.mark_drawn(
+            cols.start as i32..cols.end as i32,
+            rows.start as i32..rows.end as i32,
+        );
+    }
+
+    /// Draw the 1px outline of the circle of radius `r` centered at `cx`, `cy` with the
+    /// midpoint circle algorithm.
+    ///
+    /// The pixels outside of the framebuffer are skipped.
+    pub fn draw_circle(&mut self, cx: i32, cy: i32, r: u32, color: [u8; 4]) {
+        let r = r.min(i32::MAX as u32 / 2) as i32;
+        let (mut x, mut y) = (r, 0);
+        let mut err = 1 - r;
+        while x >= y {
+            for (dx, dy) in [
+                (x, y),
+                (y, x),
+                (-y, x),
+                (-x, y),
+                (-x, -y),
+                (-y, -x),
+                (y, -x),
+                (x, -y),
+            ] {
+                self.plot(cx + dx, cy + dy, color);
+            }
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+
+        self.mark_drawn(cx - r..cx + r + 1, cy - r..cy + r + 1);
+    }
+
+    /// Draw the `text` with the built-in 8x8 font, the top left of the first glyph at `x`, `y`.
+    ///
+    /// A `\n` starts a new line below, the characters outside of `' '..='~'` are drawn as `?`.
+    /// The pixels outside of the framebuffer are skipped.
+    pub fn draw_text_ascii(&mut self, x: i32, y: i32, text: &str, color: [u8; 4]) {
+        let glyph_size = GLYPH_SIZE as i32;
+        let (mut right, mut bottom) = (x, y);
+        for (line_ix, line) in text.split('\n').enumerate() {
+            let top = y + line_ix as i32 * glyph_size;
+            let mut left = x;
+            for ch in line.chars() {
+                let glyph = glyph(ch);
+                for (row, bits) in glyph.iter().enumerate() {
+                    for col in 0..glyph_size {
+                        if (bits >> col) & 1 == 1 {
+                            self.plot(left + col, top + row as i32, color);
+                        }
+                    }
+                }
+                left += glyph_size;
+            }
+            right = right.max(left);
+            bottom = top + glyph_size;
+        }
+
+        self.mark_drawn(x..right, y..bottom);
+    }
+
+    /// Set the pixel at `x`, `y` if it is inside of the framebuffer.
+    fn plot(&mut self, x: i32, y: i32, color: [u8; 4]) {
+        if x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height {
+            self.set_pixel_rgba8(x as u32, y as u32, color);
+        }
+    }
+
+    /// Add the drawn pixels, clipped to the framebuffer, to the dirty rect.
+    fn mark_drawn(&mut self, cols: Range<i32>, rows: Range<i32>) {
+        let cols = cols.start.clamp(0, self.width as i32)..cols.end.clamp(0, self.width as i32);
+        let rows = rows.start.clamp(0, self.height as i32)..rows.end.clamp(0, self.height as i32);
+        if cols.is_empty() || rows.is_empty() {
+            return;
+        }
+
+        let bounds = Bounds::new(
+            point(px(cols.start as f32), px(rows.start as f32)),
+            size(px(cols.len() as f32), px(rows.len() as f32)),
+        );
+        let dirty_rect = self
+            .dirty_rect
+            .map_or(bounds, |dirty_rect| dirty_rect.union(&bounds));
+        self.mark_dirty(Some(dirty_rect));
+    }
+}
+
+/// Clip the span of `len` pixels from `start` to `0..max`.
+fn clip(start: i32, len: u32, max: u32) -> Range<u32> {
+    let end = (start as i64 + len as i64).clamp(0, max as i64) as u32;
+    let start = (start as i64).clamp(0, max as i64) as u32;
+    start..end
+}
+
+/// Returns the rows of the glyph of `ch` in [`FONT_8X8`].
+fn glyph(ch: char) -> &'static [u8] {
+    let ix = match ch {
+        ' '..='~' => ch as u8 - FONT_FIRST_CHAR,
+        _ => b'?' - FONT_FIRST_CHAR,
+    } as usize;
+    &FONT_8X8[ix * GLYPH_SIZE as usize..(ix + 1) * GLYPH_SIZE as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Bounds};
+
+    use super::{Framebuffer, GLYPH_SIZE};
+    use crate::viewport::FramebufferFormat;
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+    /// Render the framebuffer as rows of `#` for the white pixels and `.` for the others.
+    fn ascii(fb: &Framebuffer) -> Vec<String> {
+        (0..fb.height)
+            .map(|y| {
+                (0..fb.width)
+                    .map(|x| {
+                        let offset = (y * fb.pitch + x * 4) as usize;
+                        if fb.buffer[offset..offset + 4] == WHITE {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn framebuffer(width: u32, height: u32) -> Framebuffer {
+        let mut fb = Framebuffer::new(width, height, FramebufferFormat::Rgba8);
+        fb.clear([0, 0, 0, 255]);
+        fb.clear_dirty();
+        fb
+    }
+
+    #[test]
+    fn test_draw_line() {
+        let mut fb = framebuffer(5, 3);
+        fb.draw_line(0, 0, 4, 2, WHITE);
+        assert_eq!(ascii(&fb), ["#....", ".##..", "...##"]);
+
+        // Clipped to the framebuffer
+        let mut fb = framebuffer(3, 3);
+        fb.draw_line(-2, 1, 10, 1, WHITE);
+        assert_eq!(ascii(&fb), ["...", "###", "..."]);
+    }
+
+    #[test]
+    fn test_draw_rect() {
+        let mut fb = framebuffer(4, 3);
+        fb.draw_rect(-1, 1, 3, 5, WHITE);
+        assert_eq!(ascii(&fb), ["....", "##..", "##.."]);
+        assert_eq!(
+            fb.dirty_rect(),
+            Some(Bounds::new(point(px(0.), px(1.)), size(px(2.), px(2.))))
+        );
+
+        let mut fb = framebuffer(2, 2);
+        fb.draw_rect(5, 5, 3, 3, WHITE);
+        assert!(!fb.is_dirty());
+    }
+
+    #[test]
+    fn test_draw_circle() {
+        let mut fb = framebuffer(7, 7);
+        fb.draw_circle(3, 3, 3, WHITE);
+        assert_eq!(
+            ascii(&fb),
+            ["..###..", ".#...#.", "#.....#", "#.....#", "#.....#", ".#...#.", "..###..",]
+        );
+    }
+
+    #[test]
+    fn test_draw_text_ascii() {
+        let mut fb = framebuffer(GLYPH_SIZE * 2, GLYPH_SIZE);
+        fb.draw_text_ascii(0, 0, "H!", WHITE);
+        assert_eq!(
+            ascii(&fb),
+            [
+                "##..##.....##...",
+                "##..##....####..",
+                "##..##....####..",
+                "######.....##...",
+                "##..##.....##...",
+                "##..##..........",
+                "##..##.....##...",
+                "................",
+            ]
+        );
+
+        // Unknown characters are drawn as `?`
+        let mut unknown = framebuffer(GLYPH_SIZE, GLYPH_SIZE);
+        unknown.draw_text_ascii(0, 0, "\u{e9}", WHITE);
+        let mut question = framebuffer(GLYPH_SIZE, GLYPH_SIZE);
+        question.draw_text_ascii(0, 0, "?", WHITE);
+        assert_eq!(ascii(&unknown), ascii(&question));
+    }
+}
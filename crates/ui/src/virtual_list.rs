@@ -246,39 +246,57 @@ impl VirtualList {
             return scroll_offset;
         };
 
-        let mut scroll_offset = scroll_offset;
-        match scroll_to_item.strategy {
-            ScrollStrategy::Center => {
-                if self.axis.is_vertical() {
-                    scroll_offset.y = content_bounds.top() + content_bounds.size.height.half()
-                        - bounds.top()
-                        - bounds.size.height.half()
-                } else {
-                    scroll_offset.x = content_bounds.left() + content_bounds.size.width.half()
-                        - bounds.left()
-                        - bounds.size.width.half()
-                }
+        let scroll_offset = scroll_offset_to_item(
+            self.axis,
+            scroll_offset,
+            &bounds,
+            content_bounds,
+            scroll_to_item.strategy,
+        );
+        self.scroll_handle.set_offset(scroll_offset);
+        scroll_offset
+    }
+}
+
+/// Returns the scroll offset to show the item with the given bounds in the content bounds.
+fn scroll_offset_to_item(
+    axis: Axis,
+    scroll_offset: Point<Pixels>,
+    bounds: &Bounds<Pixels>,
+    content_bounds: &Bounds<Pixels>,
+    strategy: ScrollStrategy,
+) -> Point<Pixels> {
+    let mut scroll_offset = scroll_offset;
+    match strategy {
+        ScrollStrategy::Center => {
+            if axis.is_vertical() {
+                scroll_offset.y = content_bounds.top() + content_bounds.size.height.half()
+                    - bounds.top()
+                    - bounds.size.height.half()
+            } else {
+                scroll_offset.x = content_bounds.left() + content_bounds.size.width.half()
+                    - bounds.left()
+                    - bounds.size.width.half()
             }
-            _ => {
-                // Ref: https://github.com/zed-industries/zed/blob/0d145289e0867a8d5d63e5e1397a5ca69c9d49c3/crates/gpui/src/elements/div.rs#L3026
-                if self.axis.is_vertical() {
-                    if bounds.top() + scroll_offset.y < content_bounds.top() {
-                        scroll_offset.y = content_bounds.top() - bounds.top()
-                    } else if bounds.bottom() + scroll_offset.y > content_bounds.bottom() {
-                        scroll_offset.y = content_bounds.bottom() - bounds.bottom();
-                    }
-                } else {
-                    if bounds.left() + scroll_offset.x < content_bounds.left() {
-                        scroll_offset.x = content_bounds.left() - bounds.left();
-                    } else if bounds.right() + scroll_offset.x > content_bounds.right() {
-                        scroll_offset.x = content_bounds.right() - bounds.right();
-                    }
+        }
+        _ => {
+            // Ref: https://github.com/zed-industries/zed/blob/0d145289e0867a8d5d63e5e1397a5ca69c9d49c3/crates/gpui/src/elements/div.rs#L3026
+            if axis.is_vertical() {
+                if bounds.top() + scroll_offset.y < content_bounds.top() {
+                    scroll_offset.y = content_bounds.top() - bounds.top()
+                } else if bounds.bottom() + scroll_offset.y > content_bounds.bottom() {
+                    scroll_offset.y = content_bounds.bottom() - bounds.bottom();
+                }
+            } else {
+                if bounds.left() + scroll_offset.x < content_bounds.left() {
+                    scroll_offset.x = content_bounds.left() - bounds.left();
+                } else if bounds.right() + scroll_offset.x > content_bounds.right() {
+                    scroll_offset.x = content_bounds.right() - bounds.right();
                 }
             }
         }
-        self.scroll_handle.set_offset(scroll_offset);
-        scroll_offset
     }
+    scroll_offset
 }
 
 /// Frame state used by the [VirtualItem].
@@ -707,3 +725,52 @@ impl Element for VirtualList {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Axis, Bounds, ScrollStrategy};
+
+    use super::scroll_offset_to_item;
+
+    #[test]
+    fn test_scroll_offset_to_item() {
+        let content_bounds = Bounds::new(point(px(0.), px(0.)), size(px(300.), px(100.)));
+        // The 6th column of 100px width, out of the view on the right.
+        let bounds = Bounds::new(point(px(500.), px(0.)), size(px(100.), px(100.)));
+        let scroll_offset = point(px(0.), px(0.));
+
+        assert_eq!(
+            scroll_offset_to_item(
+                Axis::Horizontal,
+                scroll_offset,
+                &bounds,
+                &content_bounds,
+                ScrollStrategy::Top
+            ),
+            point(px(-300.), px(0.))
+        );
+        assert_eq!(
+            scroll_offset_to_item(
+                Axis::Horizontal,
+                scroll_offset,
+                &bounds,
+                &content_bounds,
+                ScrollStrategy::Center
+            ),
+            point(px(-400.), px(0.))
+        );
+
+        // Already visible, keep the offset.
+        let scroll_offset = point(px(-400.), px(0.));
+        assert_eq!(
+            scroll_offset_to_item(
+                Axis::Horizontal,
+                scroll_offset,
+                &bounds,
+                &content_bounds,
+                ScrollStrategy::Top
+            ),
+            scroll_offset
+        );
+    }
+}